@@ -0,0 +1,51 @@
+#!/usr/bin/env python3
+"""
+Write data/data_manifest.json for Ship Lens.
+Records the data pack version, source game patch and extraction date, plus a
+SHA-256 checksum of every bundled data file. Run after each data update.
+
+Usage: build_data_manifest.py <data_version> <game_patch> [extraction_date]
+"""
+
+import hashlib
+import json
+import sys
+from datetime import date
+from pathlib import Path
+
+DATA_DIR = Path(__file__).resolve().parent.parent / "data"
+MANIFEST_FILE = "data_manifest.json"
+
+# Same globs as the bundled resources in src-tauri/tauri.conf.json
+BUNDLED = ["*.json", "*.csv", "ships/*.json"]
+
+
+def checksums(data_dir: Path) -> dict:
+    files = {}
+    for pattern in BUNDLED:
+        for path in sorted(data_dir.glob(pattern)):
+            name = path.relative_to(data_dir).as_posix()
+            if name == MANIFEST_FILE:
+                continue
+            files[name] = hashlib.sha256(path.read_bytes()).hexdigest()
+    return dict(sorted(files.items()))
+
+
+def main():
+    if len(sys.argv) < 3:
+        print(__doc__.strip())
+        sys.exit(1)
+    manifest = {
+        "data_version": sys.argv[1],
+        "game_patch": sys.argv[2],
+        "extracted": sys.argv[3] if len(sys.argv) > 3 else date.today().isoformat(),
+        "files": checksums(DATA_DIR),
+    }
+    with open(DATA_DIR / MANIFEST_FILE, "w") as f:
+        json.dump(manifest, f, indent=2)
+        f.write("\n")
+    print(f"Wrote {MANIFEST_FILE} with {len(manifest['files'])} files")
+
+
+if __name__ == "__main__":
+    main()
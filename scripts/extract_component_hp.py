@@ -114,9 +114,9 @@ def find_ship_xml(ship_json_name: str) -> Optional[str]:
 
     return None
 
-def extract_component_hp_from_ship(ship_xml_path: str, component_hp_lookup: Dict[str, int], guid_lookup: Dict[str, str]) -> Dict[str, int]:
+def extract_component_hp_from_ship(ship_xml_path: str, component_hp_lookup: Dict[str, int], guid_lookup: Dict[str, str]) -> tuple[Dict[str, int], Dict[str, int]]:
     """
-    Extract component HP totals from a ship XML file.
+    Extract component HP totals and instance counts from a ship XML file.
 
     Args:
         ship_xml_path: Path to ship XML file
@@ -124,7 +124,7 @@ def extract_component_hp_from_ship(ship_xml_path: str, component_hp_lookup: Dict
         guid_lookup: GUID → component name lookup dictionary
 
     Returns:
-        Dict with HP totals for each component type
+        Tuple of (HP totals per component type, instance counts per component type)
     """
     hp_totals = {
         'turret_total_hp': 0,
@@ -134,6 +134,7 @@ def extract_component_hp_from_ship(ship_xml_path: str, component_hp_lookup: Dict
         'qd_total_hp': 0,
         'thruster_total_hp': 0,
     }
+    counts = {key.replace('_total_hp', '_count'): 0 for key in hp_totals}
 
     component_not_found = []
 
@@ -179,6 +180,7 @@ def extract_component_hp_from_ship(ship_xml_path: str, component_hp_lookup: Dict
                 entity_lower = entity_class.lower()
                 if entity_lower in component_hp_lookup:
                     hp_totals[component_type] += component_hp_lookup[entity_lower]
+                    counts[component_type.replace('_total_hp', '_count')] += 1
                 else:
                     component_not_found.append(f"{entity_class} (port: {port_name})")
 
@@ -188,7 +190,7 @@ def extract_component_hp_from_ship(ship_xml_path: str, component_hp_lookup: Dict
     except Exception as e:
         print(f"Error parsing ship XML {ship_xml_path}: {e}")
 
-    return hp_totals
+    return hp_totals, counts
 
 def update_ship_json_files(component_hp_lookup: Dict[str, int], guid_lookup: Dict[str, str]):
     """
@@ -215,7 +217,7 @@ def update_ship_json_files(component_hp_lookup: Dict[str, int], guid_lookup: Dic
             continue
 
         # Extract component HP from ship XML
-        hp_totals = extract_component_hp_from_ship(ship_xml_path, component_hp_lookup, guid_lookup)
+        hp_totals, counts = extract_component_hp_from_ship(ship_xml_path, component_hp_lookup, guid_lookup)
 
         # Check if any HP values were found
         if sum(hp_totals.values()) == 0:
@@ -250,6 +252,12 @@ def update_ship_json_files(component_hp_lookup: Dict[str, int], guid_lookup: Dic
                             ship_data['thrusters']['total_hp'] = value
                             changed = True
 
+            # Instance counts let the backend derive per-component HP (total / count)
+            for key, value in counts.items():
+                if value > 0 and ship_data['components'].get(key) != value:
+                    ship_data['components'][key] = value
+                    changed = True
+
             if changed:
                 # Write updated JSON
                 with open(json_path, 'w') as f:
@@ -0,0 +1,38 @@
+#!/bin/bash
+# Upload locally built Linux packages to a GitHub release with their checksums
+#
+# Usage: scripts/upload_linux_packages.sh v0.1.24 [extra package files...]
+#
+# Picks up the .rpm and .deb from the Tauri bundle directory plus any extra
+# files given (e.g. the Arch .pkg.tar.zst), writes `<asset>.sha256` next to each
+# in `sha256sum` format and uploads both. The in-app Linux updater refuses a
+# package without its .sha256.
+
+set -euo pipefail
+
+TAG="${1:?usage: $0 <tag> [extra package files...]}"
+shift
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+BUNDLE_DIR="$SCRIPT_DIR/../src-tauri/target/release/bundle"
+
+shopt -s nullglob
+PACKAGES=("$BUNDLE_DIR"/rpm/*.rpm "$BUNDLE_DIR"/deb/*.deb "$@")
+shopt -u nullglob
+
+if [ ${#PACKAGES[@]} -eq 0 ]; then
+    echo "No packages found in $BUNDLE_DIR (run npm run tauri build first)" >&2
+    exit 1
+fi
+
+ASSETS=()
+for package in "${PACKAGES[@]}"; do
+    dir="$(dirname "$package")"
+    name="$(basename "$package")"
+    # Checksum the bare file name so `sha256sum -c` works from the download directory
+    (cd "$dir" && sha256sum "$name" > "$name.sha256")
+    echo "$(cut -d' ' -f1 "$package.sha256")  $name"
+    ASSETS+=("$package" "$package.sha256")
+done
+
+gh release upload "$TAG" "${ASSETS[@]}" --clobber
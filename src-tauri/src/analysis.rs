@@ -0,0 +1,1269 @@
+//! Ship Lens Analysis Module
+//!
+//! Side-by-side comparisons and rankings built on top of the data and TTK models.
+//! Everything here is computed in Rust so every consumer gets identical math.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::data::{Cooler, GameData, OrdnanceHardpoint, PowerPlant, Shield, Ship, Weapon};
+use crate::tasks::TaskProgress;
+use crate::ttk::{
+    apply_rule_of_two, calculate_shield_damage, calculate_ttk, calculate_ttk_no_shields,
+    CombatScenario, DamageBreakdown, EquippedWeapon, RuleOfTwoOptions, WeaponTuning, ZoneModifiers,
+};
+
+/// One ship's normalized stats for side-by-side comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShipComparisonRow {
+    pub ship_name: String,
+    pub hull_hp: f64,
+    pub armor_hp: f64,
+    /// Armor HP as seen by each damage type (armor_hp / (damage_mult × resist))
+    pub armor_effective_physical: f64,
+    pub armor_effective_energy: f64,
+    pub armor_effective_distortion: f64,
+    pub shield_name: Option<String>,
+    pub shield_count: i32,
+    /// Total shield HP after Rule of Two failover
+    pub shield_hp: f64,
+    /// Regen of the active shield pair
+    pub shield_regen: f64,
+    pub pilot_weapon_count: i32,
+    pub total_weapon_count: i32,
+    pub hardpoint_summary: String,  // e.g. "4x S3 pilot, 2x S4 manned turret"
+}
+
+/// Percentage deltas of one ship against the baseline (first) ship
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ShipComparisonDelta {
+    pub ship_name: String,
+    pub hull_hp: f64,
+    pub armor_hp: f64,
+    pub armor_effective_physical: f64,
+    pub armor_effective_energy: f64,
+    pub armor_effective_distortion: f64,
+    pub shield_hp: f64,
+    pub shield_regen: f64,
+    pub total_weapon_count: f64,
+}
+
+/// Complete ship comparison result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShipComparison {
+    /// Ship stats in the requested order
+    pub rows: Vec<ShipComparisonRow>,
+    /// Percentage deltas against the first ship (first entry is always all zeros)
+    pub deltas: Vec<ShipComparisonDelta>,
+}
+
+/// Percentage change from base to value (0.0 when the base is zero)
+pub fn percent_delta(base: f64, value: f64) -> f64 {
+    if base.abs() > f64::EPSILON {
+        (value - base) / base * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Armor HP as seen by a damage type after both armor layers
+pub(crate) fn effective_armor(armor_hp: f64, damage_mult: f64, resist: f64) -> f64 {
+    let factor = damage_mult * resist;
+    if factor > 0.0 {
+        armor_hp / factor
+    } else {
+        armor_hp
+    }
+}
+
+/// Build a weapon layout string grouped by category and size
+///
+/// Sub-ports are expanded so a twin-S3 gimbal counts as two S3 weapons.
+/// Pilot weapons are listed first, then the remaining categories in order of appearance.
+pub fn hardpoint_summary(ship: &Ship) -> String {
+    let mut groups: Vec<(String, i32, i32)> = Vec::new();
+
+    for hp in &ship.weapon_hardpoints {
+        for sp in &hp.sub_ports {
+            match groups.iter_mut().find(|(cat, size, _)| *cat == hp.category && *size == sp.size) {
+                Some((_, _, count)) => *count += 1,
+                None => groups.push((hp.category.clone(), sp.size, 1)),
+            }
+        }
+    }
+
+    // Pilot first, then categories by first appearance, largest size first within a category
+    let mut category_order: Vec<String> = Vec::new();
+    for (cat, _, _) in &groups {
+        if !category_order.contains(cat) {
+            category_order.push(cat.clone());
+        }
+    }
+    groups.sort_by_key(|(cat, size, _)| {
+        let appearance = category_order.iter().position(|c| c == cat).unwrap_or(0);
+        (cat != "pilot", appearance, -size)
+    });
+
+    groups.iter()
+        .map(|(cat, size, count)| format!("{}x S{} {}", count, size, cat.replace('_', " ")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Armament summary categories, in display order
+const ARMAMENT_CATEGORIES: [&str; 5] = ["pilot", "manned_turret", "remote_turret", "pdc", "ordnance"];
+
+/// Mounts of one category with the same weapon layout (e.g. two twin-S4 manned turrets)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArmamentGroup {
+    /// One of `ARMAMENT_CATEGORIES`, or the raw hardpoint category for anything else
+    pub category: String,
+    pub mounts: i32,
+    /// Weapon sizes of each mount, largest first
+    pub sizes: Vec<i32>,
+    /// mounts × weapons per mount
+    pub weapons: i32,
+}
+
+/// Weapon layout of a ship grouped by category (returned by `get_ship_armament_summary`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArmamentSummary {
+    pub ship_name: String,
+    /// e.g. "4x S3 pilot, 2x twin-S4 manned turrets, 8x S2 PDC"
+    pub summary: String,
+    pub groups: Vec<ArmamentGroup>,
+    /// Weapon count per category
+    pub totals: BTreeMap<String, i32>,
+}
+
+/// Armament category of a hardpoint category (missiles, torpedoes and bombs are ordnance)
+fn armament_category(category: &str) -> &str {
+    match category {
+        "missile" | "torpedo" | "bomb" => "ordnance",
+        other => other,
+    }
+}
+
+/// Layout label of one group, e.g. "8x S2 PDC" or "2x twin-S4 manned turrets"
+fn armament_label(group: &ArmamentGroup) -> String {
+    let layout = match group.sizes.as_slice() {
+        [size] => format!("S{}", size),
+        sizes if sizes.iter().all(|s| *s == sizes[0]) => {
+            let multiple = match sizes.len() {
+                2 => "twin".to_string(),
+                3 => "triple".to_string(),
+                4 => "quad".to_string(),
+                n => format!("{}-gun", n),
+            };
+            format!("{}-S{}", multiple, sizes[0])
+        }
+        sizes => sizes.iter().map(|s| format!("S{}", s)).collect::<Vec<_>>().join("+"),
+    };
+    let name = match group.category.as_str() {
+        "pdc" => "PDC".to_string(),
+        category if category.ends_with("turret") && group.mounts > 1 => format!("{}s", category.replace('_', " ")),
+        category => category.replace('_', " "),
+    };
+    format!("{}x {} {}", group.mounts, layout, name)
+}
+
+/// Group a ship's weapon hardpoints by category and mount layout
+///
+/// Unlike `hardpoint_summary`, multi-weapon mounts keep their layout: two twin-S4
+/// turrets read "2x twin-S4 manned turrets" rather than "4x S4 manned turret".
+pub fn armament_summary(ship: &Ship) -> ArmamentSummary {
+    let mut groups: Vec<ArmamentGroup> = Vec::new();
+    let mut totals = BTreeMap::new();
+
+    for hp in ship.weapon_hardpoints.iter().filter(|hp| !hp.sub_ports.is_empty()) {
+        let category = armament_category(&hp.category).to_string();
+        let mut sizes: Vec<i32> = hp.sub_ports.iter().map(|sp| sp.size).collect();
+        sizes.sort_by(|a, b| b.cmp(a));
+        let weapons = sizes.len() as i32;
+        *totals.entry(category.clone()).or_insert(0) += weapons;
+        match groups.iter_mut().find(|g| g.category == category && g.sizes == sizes) {
+            Some(group) => {
+                group.mounts += 1;
+                group.weapons += weapons;
+            }
+            None => groups.push(ArmamentGroup { category, mounts: 1, sizes, weapons }),
+        }
+    }
+
+    // Known categories in display order, others after; largest weapons first within a category
+    groups.sort_by_key(|g| {
+        let order = ARMAMENT_CATEGORIES.iter().position(|c| *c == g.category).unwrap_or(ARMAMENT_CATEGORIES.len());
+        (order, g.category.clone(), -g.sizes[0], -(g.sizes.len() as i32))
+    });
+
+    ArmamentSummary {
+        ship_name: ship.display_name.clone(),
+        summary: groups.iter().map(armament_label).collect::<Vec<_>>().join(", "),
+        groups,
+        totals,
+    }
+}
+
+/// Weapon power draw of a loadout against a power plant (returned by `check_power_budget`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerBudget {
+    /// Display name of the plant checked against (None = no power plant data)
+    pub power_plant: Option<String>,
+    pub power_output: f64,
+    /// Sum of `power_consumption` × count over the loadout
+    pub power_draw: f64,
+    /// Output left after the draw (negative when power-starved)
+    pub headroom: f64,
+    pub power_starved: bool,
+    pub warning: Option<String>,
+}
+
+/// Approximate EM/IR signature of a loadout while firing (returned by `estimate_signatures`)
+///
+/// EM follows power draw and IR follows heat generated, so both are in the data's
+/// power and heat units rather than in-game signature values; compare loadouts
+/// against each other, not against detection thresholds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureEstimate {
+    /// Sustained DPS of the loadout
+    pub total_dps: f64,
+    /// Weapon power draw
+    pub em_signature: f64,
+    /// Weapon heat generated per second while firing
+    pub ir_signature: f64,
+    /// Draw as a fraction of the power plant's output (None = no power plant data)
+    pub em_load: Option<f64>,
+    /// Heat as a fraction of the cooler's rate (None = no cooler data)
+    pub ir_load: Option<f64>,
+    /// DPS per unit of signature (None when the signature is unknown)
+    pub dps_per_em: Option<f64>,
+    pub dps_per_ir: Option<f64>,
+    /// Weapons without power or heat data, left out of the signatures
+    pub unknown_weapons: Vec<String>,
+}
+
+/// Estimate the signature impact of a loadout powered by `plant` and cooled by `cooler`
+pub fn estimate_signatures(
+    plant: Option<&PowerPlant>,
+    cooler: Option<&Cooler>,
+    weapons: &[EquippedWeapon],
+) -> SignatureEstimate {
+    let mut total_dps = 0.0;
+    let mut em_signature = 0.0;
+    let mut ir_signature = 0.0;
+    let mut unknown_weapons = Vec::new();
+    for w in weapons {
+        let count = w.count as f64;
+        let heat_per_second = w.weapon.heat_per_shot * w.weapon.fire_rate / 60.0;
+        total_dps += w.weapon.sustained_dps * count;
+        em_signature += w.weapon.power_consumption * count;
+        ir_signature += heat_per_second * count;
+        if (w.weapon.power_consumption <= 0.0 || heat_per_second <= 0.0)
+            && !unknown_weapons.contains(&w.weapon.display_name)
+        {
+            unknown_weapons.push(w.weapon.display_name.clone());
+        }
+    }
+
+    SignatureEstimate {
+        total_dps,
+        em_signature,
+        ir_signature,
+        em_load: plant.map(|p| em_signature / p.power_output),
+        ir_load: cooler.map(|c| ir_signature / c.cooling_rate),
+        dps_per_em: (em_signature > 0.0).then(|| total_dps / em_signature),
+        dps_per_ir: (ir_signature > 0.0).then(|| total_dps / ir_signature),
+        unknown_weapons,
+    }
+}
+
+/// Check a loadout's weapon power draw against `plant`
+///
+/// Only weapons are counted (shields, coolers and thrusters have no draw data),
+/// so a build over budget here is starved in game too.
+pub fn power_budget(plant: Option<&PowerPlant>, weapons: &[EquippedWeapon]) -> PowerBudget {
+    let power_draw: f64 = weapons.iter().map(|w| w.weapon.power_consumption * w.count as f64).sum();
+    let Some(plant) = plant else {
+        return PowerBudget {
+            power_plant: None,
+            power_output: 0.0,
+            power_draw,
+            headroom: 0.0,
+            power_starved: false,
+            warning: Some("No power plant data; power budget not checked".to_string()),
+        };
+    };
+    let headroom = plant.power_output - power_draw;
+    let power_starved = headroom < 0.0;
+    PowerBudget {
+        power_plant: Some(plant.display_name.clone()),
+        power_output: plant.power_output,
+        power_draw,
+        headroom,
+        power_starved,
+        warning: power_starved.then(|| format!(
+            "Power-starved: weapons draw {:.0} of {:.0} from {}", power_draw, plant.power_output, plant.display_name
+        )),
+    }
+}
+
+/// Missiles of one category and size a ship carries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrdnanceLoad {
+    pub category: String,
+    pub missile_size: i32,
+    pub racks: i32,
+    pub missiles: i32,
+}
+
+/// Ordnance a ship can carry (returned by `get_ordnance_capacity`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrdnanceCapacity {
+    pub ship_name: String,
+    pub racks: Vec<OrdnanceHardpoint>,
+    /// By category, then largest missile first
+    pub loads: Vec<OrdnanceLoad>,
+    pub total_missiles: i32,
+    pub total_torpedoes: i32,
+    pub total_bombs: i32,
+}
+
+/// Total a ship's ordnance racks by category and missile size
+pub fn ordnance_capacity(ship: &Ship) -> OrdnanceCapacity {
+    let mut loads: Vec<OrdnanceLoad> = Vec::new();
+    for rack in &ship.ordnance_hardpoints {
+        match loads.iter_mut().find(|l| l.category == rack.category && l.missile_size == rack.missile_size) {
+            Some(load) => {
+                load.racks += 1;
+                load.missiles += rack.missile_count;
+            }
+            None => loads.push(OrdnanceLoad {
+                category: rack.category.clone(),
+                missile_size: rack.missile_size,
+                racks: 1,
+                missiles: rack.missile_count,
+            }),
+        }
+    }
+    loads.sort_by(|a, b| a.category.cmp(&b.category).then(b.missile_size.cmp(&a.missile_size)));
+
+    let total = |category: &str| loads.iter().filter(|l| l.category == category).map(|l| l.missiles).sum();
+    OrdnanceCapacity {
+        ship_name: ship.display_name.clone(),
+        racks: ship.ordnance_hardpoints.clone(),
+        total_missiles: total("missile"),
+        total_torpedoes: total("torpedo"),
+        total_bombs: total("bomb"),
+        loads,
+    }
+}
+
+/// Build the comparison row for a single ship
+fn ship_comparison_row(data: &GameData, ship: &Ship) -> ShipComparisonRow {
+    let shield = data.find_default_shield(ship);
+    let (shield_hp, shield_regen) = shield
+        .map(|s| {
+            let effective = apply_rule_of_two(s, ship.shield_count, &RuleOfTwoOptions::default());
+            (effective.total_hp, effective.regen)
+        })
+        .unwrap_or((0.0, 0.0));
+
+    let total_weapon_count = ship.weapon_hardpoints.iter()
+        .map(|hp| hp.sub_ports.len() as i32)
+        .sum();
+
+    ShipComparisonRow {
+        ship_name: ship.display_name.clone(),
+        hull_hp: ship.hull_hp,
+        armor_hp: ship.armor_hp,
+        armor_effective_physical: effective_armor(ship.armor_hp, ship.armor_damage_mult_physical, ship.armor_resist_physical),
+        armor_effective_energy: effective_armor(ship.armor_hp, ship.armor_damage_mult_energy, ship.armor_resist_energy),
+        armor_effective_distortion: effective_armor(ship.armor_hp, ship.armor_damage_mult_distortion, ship.armor_resist_distortion),
+        shield_name: shield.map(|s| s.display_name.clone()),
+        shield_count: ship.shield_count,
+        shield_hp,
+        shield_regen,
+        pilot_weapon_count: ship.pilot_weapon_count,
+        total_weapon_count,
+        hardpoint_summary: hardpoint_summary(ship),
+    }
+}
+
+/// Compare ships side by side
+///
+/// Rows are returned in the requested order; deltas are percentages against the first ship.
+pub fn compare_ships(data: &GameData, names: &[String]) -> Result<ShipComparison, String> {
+    if names.is_empty() {
+        return Err("No ships to compare".to_string());
+    }
+
+    let rows = names.iter()
+        .map(|name| {
+            data.ship(name)
+                .map(|ship| ship_comparison_row(data, ship))
+                .ok_or_else(|| format!("Ship '{}' not found", name))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let base = &rows[0];
+    let deltas = rows.iter()
+        .map(|row| ShipComparisonDelta {
+            ship_name: row.ship_name.clone(),
+            hull_hp: percent_delta(base.hull_hp, row.hull_hp),
+            armor_hp: percent_delta(base.armor_hp, row.armor_hp),
+            armor_effective_physical: percent_delta(base.armor_effective_physical, row.armor_effective_physical),
+            armor_effective_energy: percent_delta(base.armor_effective_energy, row.armor_effective_energy),
+            armor_effective_distortion: percent_delta(base.armor_effective_distortion, row.armor_effective_distortion),
+            shield_hp: percent_delta(base.shield_hp, row.shield_hp),
+            shield_regen: percent_delta(base.shield_regen, row.shield_regen),
+            total_weapon_count: percent_delta(base.total_weapon_count as f64, row.total_weapon_count as f64),
+        })
+        .collect();
+
+    Ok(ShipComparison { rows, deltas })
+}
+
+/// Metric used to rank weapons
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeaponMetric {
+    /// Raw sustained DPS
+    SustainedDps,
+    /// Sustained DPS per unit of power draw
+    DpsPerPower,
+    /// Sustained DPS per unit of heat generated per second
+    DpsPerHeat,
+    /// Damage of a single shot
+    AlphaDamage,
+    /// DPS that sticks to the reference shield after absorption and resistance
+    ShieldDps,
+    /// DPS that passes through the reference shield to armor/hull
+    PassthroughDps,
+    /// Sustained DPS per 1000 aUEC of purchase price
+    DpsPerKiloAuec,
+}
+
+impl WeaponMetric {
+    fn value(&self, stats: &WeaponStats) -> f64 {
+        match self {
+            WeaponMetric::SustainedDps => stats.sustained_dps,
+            WeaponMetric::DpsPerPower => stats.dps_per_power,
+            WeaponMetric::DpsPerHeat => stats.dps_per_heat,
+            WeaponMetric::AlphaDamage => stats.alpha_damage,
+            WeaponMetric::ShieldDps => stats.shield_dps,
+            WeaponMetric::PassthroughDps => stats.passthrough_dps,
+            WeaponMetric::DpsPerKiloAuec => stats.dps_per_kilo_auec,
+        }
+    }
+}
+
+/// Derived weapon stats for comparison and ranking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponStats {
+    pub weapon_name: String,
+    pub filename: String,
+    pub size: i32,
+    pub damage_type: String,
+    pub sustained_dps: f64,
+    pub dps_per_power: f64,   // 0.0 when power draw is unknown
+    pub dps_per_heat: f64,    // 0.0 when heat is unknown
+    pub alpha_damage: f64,
+    pub shield_dps: f64,      // vs reference shield
+    pub passthrough_dps: f64, // vs reference shield
+    /// Fraction of sustained DPS that damages the reference shield (0.0-1.0+)
+    pub shield_effectiveness: f64,
+    #[serde(default)]
+    pub price_auec: Option<f64>,
+    #[serde(default)]
+    pub dps_per_kilo_auec: f64,  // 0.0 when the price is unknown
+}
+
+/// Neutral reference shield used when no shield is specified
+///
+/// Uses the same absorption defaults as `GameData::load_shields` with no resistances.
+pub fn reference_shield() -> Shield {
+    Shield {
+        id: 0,
+        display_name: "Reference Shield".to_string(),
+        internal_name: "reference_shield".to_string(),
+        size: 0,
+        max_hp: 0.0,
+        regen: 0.0,
+        resist_physical: 0.0,
+        resist_energy: 0.0,
+        resist_distortion: 0.0,
+        absorb_physical: 0.225,
+        absorb_energy: 1.0,
+        absorb_distortion: 1.0,
+        damaged_regen_delay: 5.0,
+        downed_regen_delay: 10.0,
+    }
+}
+
+/// Compute derived stats for a weapon against a reference shield
+pub fn weapon_stats(weapon: &Weapon, shield: &Shield) -> WeaponStats {
+    let damage = DamageBreakdown::from_weapon_dps(weapon, weapon.sustained_dps);
+    let (shield_dps, passthrough_dps) = calculate_shield_damage(&damage, shield);
+
+    let shield_effectiveness = if weapon.sustained_dps > 0.0 {
+        shield_dps / weapon.sustained_dps
+    } else {
+        0.0
+    };
+
+    WeaponStats {
+        weapon_name: weapon.display_name.clone(),
+        filename: weapon.filename.clone(),
+        size: weapon.size,
+        damage_type: weapon.damage_type.clone(),
+        sustained_dps: weapon.sustained_dps,
+        dps_per_power: weapon.dps_per_power().unwrap_or(0.0),
+        dps_per_heat: weapon.dps_per_heat().unwrap_or(0.0),
+        alpha_damage: weapon.damage_physical + weapon.damage_energy + weapon.damage_distortion,
+        shield_dps,
+        passthrough_dps,
+        shield_effectiveness,
+        price_auec: None,
+        dps_per_kilo_auec: 0.0,
+    }
+}
+
+/// `weapon_stats` with the weapon's aUEC price from the data
+fn priced_weapon_stats(data: &GameData, weapon: &Weapon, shield: &Shield) -> WeaponStats {
+    let mut stats = weapon_stats(weapon, shield);
+    stats.price_auec = data.price_of(&weapon.filename).and_then(|p| p.auec);
+    stats.dps_per_kilo_auec = stats.price_auec
+        .filter(|&price| price > 0.0)
+        .map_or(0.0, |price| weapon.sustained_dps / (price / 1000.0));
+    stats
+}
+
+/// Compare weapons side by side against a reference shield
+///
+/// Weapons are looked up by display name first, then by filename.
+pub fn compare_weapons(data: &GameData, names: &[String], shield: &Shield) -> Result<Vec<WeaponStats>, String> {
+    names.iter()
+        .map(|name| {
+            data.weapon(name)
+                .map(|w| priced_weapon_stats(data, w, shield))
+                .ok_or_else(|| format!("Weapon '{}' not found", name))
+        })
+        .collect()
+}
+
+/// Rank all weapons of a size by a metric (best first)
+///
+/// Ties are broken by weapon name so the ordering is stable between calls.
+pub fn rank_weapons(data: &GameData, size: i32, metric: WeaponMetric, shield: &Shield) -> Vec<WeaponStats> {
+    let mut ranked: Vec<WeaponStats> = data.weapons.values()
+        .filter(|w| w.size == size && w.sustained_dps > 0.0)
+        .map(|w| priced_weapon_stats(data, w, shield))
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        metric.value(b).partial_cmp(&metric.value(a))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.weapon_name.cmp(&b.weapon_name))
+    });
+
+    ranked
+}
+
+/// Map items in parallel across scoped worker threads, preserving input order
+///
+/// Used by batch commands that evaluate many independent matchups. Finished
+/// items are counted on `progress`; workers stop early once it is cancelled.
+pub fn parallel_map<T, R, F>(items: &[T], progress: &TaskProgress, f: F) -> Result<Vec<R>, String>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    progress.start(items.len());
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(items.len().max(1));
+    let chunk_size = items.len().div_ceil(workers).max(1);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = items.chunks(chunk_size)
+            .map(|chunk| {
+                let f = &f;
+                scope.spawn(move || {
+                    let mut results = Vec::with_capacity(chunk.len());
+                    for item in chunk {
+                        if progress.is_cancelled() {
+                            break;
+                        }
+                        results.push(f(item));
+                        progress.advance();
+                    }
+                    results
+                })
+            })
+            .collect();
+
+        let results: Vec<R> = handles.into_iter()
+            .flat_map(|h| h.join().expect("batch worker panicked"))
+            .collect();
+        progress.check()?;
+        Ok(results)
+    })
+}
+
+/// Generic incoming damage profile: total DPS split by damage type fractions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreatProfile {
+    /// Total incoming DPS (before scenario modifiers)
+    pub dps: f64,
+    /// Fraction of DPS that is physical/ballistic (0.0-1.0)
+    pub physical: f64,
+    /// Fraction of DPS that is energy (0.0-1.0)
+    pub energy: f64,
+    /// Fraction of DPS that is distortion (0.0-1.0)
+    #[serde(default)]
+    pub distortion: f64,
+}
+
+impl ThreatProfile {
+    /// Build a synthetic weapon that deals this profile's damage mix
+    pub fn to_equipped_weapon(&self) -> EquippedWeapon {
+        let weapon = Weapon {
+            id: 0,
+            display_name: "Generic Threat".to_string(),
+            filename: "generic_threat".to_string(),
+            size: 0,
+            damage_type: "Mixed".to_string(),
+            sustained_dps: self.dps,
+            power_consumption: 0.0,
+            weapon_type: "gun".to_string(),
+            restricted_to: vec![],
+            ship_exclusive: false,
+            manufacturer: String::new(),
+            manufacturer_code: String::new(),
+            damage_physical: self.physical,
+            damage_energy: self.energy,
+            damage_distortion: self.distortion,
+            base_penetration_distance: 2.0,
+            near_radius: 0.1,
+            far_radius: 0.2,
+            fire_rate: 0.0,
+            damage_per_shot: 0.0,
+            speed: 0.0,
+            max_range: 0.0,
+            heat_per_shot: 0.0,
+        };
+        EquippedWeapon {
+            weapon,
+            count: 1,
+            name_with_label: "Generic Threat".to_string(),
+            tuning: WeaponTuning::default(),
+            category: String::new(),
+            group: 0,
+        }
+    }
+}
+
+/// One ship's survivability against a reference attacker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurvivabilityEntry {
+    pub rank: usize,
+    pub ship_name: String,
+    pub shield_name: Option<String>,
+    /// Time for the reference attacker to kill this ship (seconds)
+    pub time_to_die: f64,
+    pub shield_time: f64,
+    pub armor_time: f64,
+    pub hull_time: f64,
+}
+
+/// Rank every ship by how long it survives against an attacker loadout (most survivable first)
+///
+/// Each ship uses its default shield. Ships are evaluated in parallel.
+pub fn rank_ships_by_survivability(
+    data: &GameData,
+    weapons: &[EquippedWeapon],
+    scenario: &CombatScenario,
+    zone: &ZoneModifiers,
+    progress: &TaskProgress,
+) -> Result<Vec<SurvivabilityEntry>, String> {
+    let ships: Vec<&Ship> = data.ships.values().map(|s| s.as_ref()).collect();
+
+    let mut entries = parallel_map(&ships, progress, |ship| {
+        let shield = data.find_default_shield(ship);
+        let result = match shield {
+            Some(s) => calculate_ttk(weapons, ship, s, scenario, zone),
+            None => calculate_ttk_no_shields(weapons, ship, scenario),
+        };
+        SurvivabilityEntry {
+            rank: 0,
+            ship_name: ship.display_name.clone(),
+            shield_name: shield.map(|s| s.display_name.clone()),
+            time_to_die: result.total_ttk,
+            shield_time: result.shield_time,
+            armor_time: result.armor_time,
+            hull_time: result.hull_time,
+        }
+    })?;
+
+    entries.sort_by(|a, b| {
+        b.time_to_die.partial_cmp(&a.time_to_die)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.ship_name.cmp(&b.ship_name))
+    });
+    for (i, entry) in entries.iter_mut().enumerate() {
+        entry.rank = i + 1;
+    }
+
+    Ok(entries)
+}
+
+/// One shield option evaluated against a threat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShieldOption {
+    pub rank: usize,
+    pub shield_name: String,
+    pub internal_name: String,
+    pub max_hp: f64,
+    pub regen: f64,
+    /// Time the ship survives with this shield fitted (seconds)
+    pub time_to_die: f64,
+    pub shield_time: f64,
+    /// Can the threat deplete this shield at all?
+    pub shields_breakable: bool,
+}
+
+/// Rank every shield of the ship's max shield size by survival time against a threat
+///
+/// Uses the default scenario (gimballed dogfight) and center-mass zones.
+pub fn optimize_shield(
+    data: &GameData,
+    ship: &Ship,
+    threat: &ThreatProfile,
+    progress: &TaskProgress,
+) -> Result<Vec<ShieldOption>, String> {
+    if threat.dps <= 0.0 {
+        return Err("Threat DPS must be greater than zero".to_string());
+    }
+    if threat.physical + threat.energy + threat.distortion <= 0.0 {
+        return Err("Threat damage mix must contain at least one damage type".to_string());
+    }
+
+    let weapons = vec![threat.to_equipped_weapon()];
+    let scenario = CombatScenario::default();
+    let zone = ZoneModifiers::default();
+
+    let shields: Vec<&Shield> = data.shields.values()
+        .filter(|s| s.size == ship.max_shield_size)
+        .map(|s| s.as_ref())
+        .collect();
+    let mut options: Vec<ShieldOption> = parallel_map(&shields, progress, |shield| {
+        let result = calculate_ttk(&weapons, ship, shield, &scenario, &zone);
+        ShieldOption {
+            rank: 0,
+            shield_name: shield.display_name.clone(),
+            internal_name: shield.internal_name.clone(),
+            max_hp: shield.max_hp,
+            regen: shield.regen,
+            time_to_die: result.total_ttk,
+            shield_time: result.shield_time,
+            shields_breakable: result.shields_breakable,
+        }
+    })?;
+
+    if options.is_empty() {
+        return Err(format!("No size {} shields available for '{}'", ship.max_shield_size, ship.display_name));
+    }
+
+    options.sort_by(|a, b| {
+        b.time_to_die.partial_cmp(&a.time_to_die)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.shield_name.cmp(&b.shield_name))
+    });
+    for (i, option) in options.iter_mut().enumerate() {
+        option.rank = i + 1;
+    }
+
+    Ok(options)
+}
+
+/// DPS of the probe weapons `analyze_target` fires (the ratios don't depend on it)
+const PROBE_DPS: f64 = 1000.0;
+
+/// Step of the damage mix grid searched for the recommendation
+const MIX_STEP: f64 = 0.1;
+
+/// How one damage type fares against a target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DamageTypeValue {
+    pub damage_type: String,  // "physical", "energy" or "distortion"
+    /// Shield HP removed per point of damage (absorbed share after resistance)
+    pub shield_factor: f64,
+    /// Share of each point that passes through the shield to armor/hull
+    pub passthrough_factor: f64,
+    /// Armor HP removed per point of damage
+    pub armor_factor: f64,
+    /// TTK of `PROBE_DPS` of this type alone (seconds)
+    pub probe_ttk: f64,
+    /// Kill speed relative to the best type (1.0 = best)
+    pub relative_value: f64,
+}
+
+/// Damage type analysis of one target with one shield
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetAnalysis {
+    pub ship_name: String,
+    pub shield_name: Option<String>,
+    /// Physical, energy and distortion, in that order
+    pub damage_types: Vec<DamageTypeValue>,
+    /// Fractions of DPS per type that kill the target fastest
+    pub recommended_mix: DamageBreakdown,
+    /// TTK of `PROBE_DPS` in the recommended mix (seconds)
+    pub recommended_ttk: f64,
+    /// Readable comparisons, e.g. "Ballistics are 2.3x more effective vs this target's shields"
+    pub notes: Vec<String>,
+}
+
+fn damage_type_label(damage_type: &str) -> &'static str {
+    match damage_type {
+        "physical" => "Ballistics",
+        "energy" => "Energy weapons",
+        _ => "Distortion weapons",
+    }
+}
+
+/// "A is N.Nx more effective than B vs <layer>", or None if no type stands out
+fn compare_note(values: &[(&str, f64)], layer: &str) -> Option<String> {
+    let mut sorted: Vec<_> = values.iter().filter(|(_, v)| v.is_finite()).collect();
+    sorted.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let (best, runner_up) = (sorted.first()?, sorted.get(1)?);
+    if best.1 <= 0.0 {
+        return None;
+    }
+    if runner_up.1 <= 0.0 {
+        return Some(format!("Only {} damage {}", damage_type_label(best.0).to_lowercase(), layer));
+    }
+    // Within 5% is not worth mentioning
+    let ratio = best.1 / runner_up.1;
+    (ratio >= 1.05).then(|| format!(
+        "{} are {:.1}x more effective than {} vs {}",
+        damage_type_label(best.0), ratio, damage_type_label(runner_up.0).to_lowercase(), layer,
+    ))
+}
+
+/// Work out which damage types kill `ship` fastest with `shield` fitted
+///
+/// Each type is probed on its own with the default scenario, then every mix on a
+/// 10% grid is tried for the recommendation. `shield` None = the ship has no shield.
+pub fn analyze_target(ship: &Ship, shield: Option<&Shield>) -> TargetAnalysis {
+    let scenario = CombatScenario::default();
+    let zone = ZoneModifiers::default();
+    let probe = |physical: f64, energy: f64, distortion: f64| {
+        let weapons = [ThreatProfile { dps: PROBE_DPS, physical, energy, distortion }.to_equipped_weapon()];
+        match shield {
+            Some(shield) => calculate_ttk(&weapons, ship, shield, &scenario, &zone),
+            None => calculate_ttk_no_shields(&weapons, ship, &scenario),
+        }
+        .total_ttk
+    };
+
+    let types = [
+        ("physical", DamageBreakdown { physical: 1.0, energy: 0.0, distortion: 0.0 }, ship.armor_damage_mult_physical * ship.armor_resist_physical),
+        ("energy", DamageBreakdown { physical: 0.0, energy: 1.0, distortion: 0.0 }, ship.armor_damage_mult_energy * ship.armor_resist_energy),
+        ("distortion", DamageBreakdown { physical: 0.0, energy: 0.0, distortion: 1.0 }, ship.armor_damage_mult_distortion * ship.armor_resist_distortion),
+    ];
+    let mut damage_types: Vec<DamageTypeValue> = types.iter()
+        .map(|(name, unit, armor_factor)| {
+            let (shield_factor, passthrough_factor) = shield.map_or((0.0, 1.0), |s| calculate_shield_damage(unit, s));
+            DamageTypeValue {
+                damage_type: name.to_string(),
+                shield_factor,
+                passthrough_factor,
+                armor_factor: *armor_factor,
+                probe_ttk: probe(unit.physical, unit.energy, unit.distortion),
+                relative_value: 0.0,
+            }
+        })
+        .collect();
+    let best_ttk = damage_types.iter().map(|t| t.probe_ttk).fold(f64::INFINITY, f64::min);
+    for value in &mut damage_types {
+        value.relative_value = if value.probe_ttk.is_finite() { best_ttk / value.probe_ttk } else { 0.0 };
+    }
+
+    // Grid search over mixes summing to 1 (ties keep the earlier, more physical mix)
+    let steps = (1.0 / MIX_STEP).round() as usize;
+    let mut recommended_mix = DamageBreakdown::default();
+    let mut recommended_ttk = f64::INFINITY;
+    for p in 0..=steps {
+        for e in 0..=(steps - p) {
+            let (physical, energy) = (p as f64 * MIX_STEP, e as f64 * MIX_STEP);
+            let distortion = (steps - p - e) as f64 * MIX_STEP;
+            let ttk = probe(physical, energy, distortion);
+            if ttk < recommended_ttk {
+                recommended_ttk = ttk;
+                recommended_mix = DamageBreakdown { physical, energy, distortion };
+            }
+        }
+    }
+
+    let layer = |f: fn(&DamageTypeValue) -> f64| -> Vec<(&str, f64)> {
+        damage_types.iter().map(|t| (t.damage_type.as_str(), f(t))).collect()
+    };
+    let mut notes = Vec::new();
+    if shield.is_some() {
+        notes.extend(compare_note(&layer(|t| t.shield_factor), "this target's shields"));
+        notes.extend(compare_note(&layer(|t| t.passthrough_factor), "armor while the shields are up"));
+    }
+    notes.extend(compare_note(&layer(|t| t.armor_factor), "this target's armor"));
+    notes.extend(compare_note(&layer(|t| t.relative_value), "this target overall"));
+
+    TargetAnalysis {
+        ship_name: ship.display_name.clone(),
+        shield_name: shield.map(|s| s.display_name.clone()),
+        damage_types,
+        recommended_mix,
+        recommended_ttk,
+        notes,
+    }
+}
+
+/// Spread of one stat across a group of entities
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Distribution {
+    pub count: usize,
+    pub min: f64,
+    pub p25: f64,
+    pub median: f64,
+    pub p75: f64,
+    pub max: f64,
+    pub mean: f64,
+}
+
+impl Distribution {
+    /// Summarize `values` (percentiles interpolated between neighbors)
+    pub fn of(mut values: Vec<f64>) -> Self {
+        values.retain(|v| v.is_finite());
+        if values.is_empty() {
+            return Self::default();
+        }
+        values.sort_by(f64::total_cmp);
+        let percentile = |p: f64| {
+            let rank = p * (values.len() - 1) as f64;
+            let (low, high) = (values[rank.floor() as usize], values[rank.ceil() as usize]);
+            low + (high - low) * rank.fract()
+        };
+        Self {
+            count: values.len(),
+            min: values[0],
+            p25: percentile(0.25),
+            median: percentile(0.5),
+            p75: percentile(0.75),
+            max: values[values.len() - 1],
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+        }
+    }
+}
+
+/// Dataset overview for charts and import sanity checks
+#[derive(Debug, Clone, Serialize)]
+pub struct DatasetAnalytics {
+    pub ship_count: usize,
+    pub weapon_count: usize,
+    pub shield_count: usize,
+    pub missile_count: usize,
+    /// Hull HP by size class ("unknown" for ships without class metadata)
+    pub hull_hp_by_class: BTreeMap<String, Distribution>,
+    /// Sustained DPS of guns by weapon size
+    pub weapon_dps_by_size: BTreeMap<i32, Distribution>,
+    /// Shield generator HP by size
+    pub shield_hp_by_size: BTreeMap<i32, Distribution>,
+}
+
+/// Group `(key, value)` pairs into one distribution per key
+fn distributions<K: Ord>(pairs: impl Iterator<Item = (K, f64)>) -> BTreeMap<K, Distribution> {
+    let mut groups: BTreeMap<K, Vec<f64>> = BTreeMap::new();
+    for (key, value) in pairs {
+        groups.entry(key).or_default().push(value);
+    }
+    groups.into_iter().map(|(key, values)| (key, Distribution::of(values))).collect()
+}
+
+/// Counts and stat distributions over the whole dataset
+///
+/// Ships with no hull HP, weapons with no sustained DPS (missiles, utility) and
+/// shields with no HP are left out of their distributions.
+pub fn dataset_analytics(data: &GameData) -> DatasetAnalytics {
+    DatasetAnalytics {
+        ship_count: data.ships.len(),
+        weapon_count: data.weapons.len(),
+        shield_count: data.shields.len(),
+        missile_count: data.missiles.len(),
+        hull_hp_by_class: distributions(data.ships.values()
+            .filter(|s| s.hull_hp > 0.0)
+            .map(|s| (if s.size_class.is_empty() { "unknown".to_string() } else { s.size_class.clone() }, s.hull_hp))),
+        weapon_dps_by_size: distributions(data.weapons.values()
+            .filter(|w| w.sustained_dps > 0.0)
+            .map(|w| (w.size, w.sustained_dps))),
+        shield_hp_by_size: distributions(data.shields.values()
+            .filter(|s| s.max_hp > 0.0)
+            .map(|s| (s.size, s.max_hp))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{SubPort, WeaponHardpoint};
+    use crate::ttk::tests::make_test_weapon;
+
+    fn make_hardpoint(category: &str, sizes: &[i32]) -> WeaponHardpoint {
+        WeaponHardpoint {
+            slot_number: 0,
+            port_name: "hardpoint_test".to_string(),
+            max_size: sizes.iter().copied().max().unwrap_or(0),
+            gimbal_type: "Gimbal".to_string(),
+            control_type: category.to_string(),
+            category: category.to_string(),
+            mount_name: String::new(),
+            compatible_mounts: vec![],
+            sub_ports: sizes.iter()
+                .map(|&size| SubPort { size, default_weapon: None })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_percent_delta() {
+        assert!((percent_delta(1000.0, 1500.0) - 50.0).abs() < 1e-9);
+        assert!((percent_delta(1000.0, 750.0) + 25.0).abs() < 1e-9);
+        assert_eq!(percent_delta(0.0, 500.0), 0.0);
+    }
+
+    #[test]
+    fn test_effective_armor() {
+        // 3000 HP vs physical: 3000 / (0.75 × 0.85) = 4705.88
+        assert!((effective_armor(3000.0, 0.75, 0.85) - 4705.88).abs() < 0.01);
+        // Zero multiplier falls back to raw HP
+        assert_eq!(effective_armor(3000.0, 0.0, 1.0), 3000.0);
+    }
+
+    #[test]
+    fn test_hardpoint_summary_expands_sub_ports() {
+        let ship = Ship {
+            filename: "test_ship".to_string(),
+            display_name: "Test Ship".to_string(),
+            hull_hp: 5000.0,
+            armor_hp: 3000.0,
+            armor_damage_mult_physical: 0.75,
+            armor_damage_mult_energy: 0.6,
+            armor_damage_mult_distortion: 1.0,
+            armor_resist_physical: 0.85,
+            armor_resist_energy: 1.30,
+            armor_resist_distortion: 1.0,
+            thruster_main_hp: 0,
+            thruster_retro_hp: 0,
+            thruster_mav_hp: 0,
+            thruster_vtol_hp: 0,
+            thruster_total_hp: 0,
+            turret_total_hp: 0,
+            powerplant_total_hp: 0,
+            cooler_total_hp: 0,
+            shield_gen_total_hp: 0,
+            qd_total_hp: 0,
+            pilot_weapon_count: 4,
+            pilot_weapon_sizes: "3,3,3,3".to_string(),
+            max_shield_size: 2,
+            shield_count: 2,
+            default_shield_ref: String::new(),
+            weapon_hardpoints: vec![
+                make_hardpoint("manned_turret", &[4, 4]),
+                make_hardpoint("pilot", &[3, 3]),
+                make_hardpoint("pilot", &[3]),
+                make_hardpoint("pilot", &[5]),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(hardpoint_summary(&ship), "1x S5 pilot, 3x S3 pilot, 2x S4 manned turret");
+    }
+
+    #[test]
+    fn test_power_budget() {
+        let gun = |power: f64, count: i32| EquippedWeapon {
+            weapon: Weapon { power_consumption: power, ..make_test_weapon(1.0, 0.0, 0.0) },
+            count,
+            name_with_label: "Test Weapon".to_string(),
+            tuning: WeaponTuning::default(),
+            category: "pilot".to_string(),
+            group: 0,
+        };
+        let plant = PowerPlant { id: 1, name: "powr_test".to_string(), display_name: "Test Plant".to_string(), size: 1, power_output: 100.0 };
+
+        let budget = power_budget(Some(&plant), &[gun(20.0, 2), gun(10.0, 1)]);
+        assert_eq!((budget.power_draw, budget.headroom, budget.power_starved), (50.0, 50.0, false));
+        assert!(budget.warning.is_none());
+
+        let starved = power_budget(Some(&plant), &[gun(30.0, 4)]);
+        assert!(starved.power_starved && starved.headroom == -20.0);
+        assert!(starved.warning.unwrap().contains("Test Plant"));
+        assert!(!power_budget(None, &[gun(30.0, 4)]).power_starved);
+    }
+
+    #[test]
+    fn test_estimate_signatures() {
+        let gun = |name: &str, power: f64, heat: f64| EquippedWeapon {
+            weapon: Weapon {
+                display_name: name.to_string(),
+                power_consumption: power,
+                heat_per_shot: heat,
+                fire_rate: 600.0,
+                ..make_test_weapon(1.0, 0.0, 0.0)
+            },
+            count: 2,
+            name_with_label: name.to_string(),
+            tuning: WeaponTuning::default(),
+            category: "pilot".to_string(),
+            group: 0,
+        };
+        let cooler = Cooler { id: 1, name: "cool_test".to_string(), display_name: "Test Cooler".to_string(), size: 1, cooling_rate: 400.0 };
+        let weapons = [gun("Hot Gun", 10.0, 10.0), gun("Cold Gun", 5.0, 0.0)];
+
+        let estimate = estimate_signatures(None, Some(&cooler), &weapons);
+        assert_eq!(estimate.em_signature, 30.0);
+        // 10 heat × 10 shots/s × 2 guns
+        assert_eq!(estimate.ir_signature, 200.0);
+        assert_eq!(estimate.ir_load, Some(0.5));
+        assert!(estimate.em_load.is_none());
+        assert_eq!(estimate.dps_per_em, Some(estimate.total_dps / 30.0));
+        assert_eq!(estimate.unknown_weapons, ["Cold Gun"]);
+    }
+
+    #[test]
+    fn test_armament_summary_keeps_mount_layout() {
+        let mut hardpoints = vec![
+            make_hardpoint("pdc", &[2]),
+            make_hardpoint("manned_turret", &[4, 4]),
+            make_hardpoint("missile", &[3, 3, 3, 3]),
+            make_hardpoint("manned_turret", &[4, 4]),
+            make_hardpoint("remote_turret", &[3]),
+            make_hardpoint("pilot", &[]),
+        ];
+        hardpoints.extend((0..4).map(|_| make_hardpoint("pilot", &[3])));
+        hardpoints.extend((0..7).map(|_| make_hardpoint("pdc", &[2])));
+        let ship = Ship { display_name: "Test Ship".to_string(), weapon_hardpoints: hardpoints, ..Default::default() };
+
+        let summary = armament_summary(&ship);
+        assert_eq!(
+            summary.summary,
+            "4x S3 pilot, 2x twin-S4 manned turrets, 1x S3 remote turret, 8x S2 PDC, 1x quad-S3 ordnance"
+        );
+        assert_eq!(summary.groups[1], ArmamentGroup { category: "manned_turret".to_string(), mounts: 2, sizes: vec![4, 4], weapons: 4 });
+        assert_eq!(summary.totals["pdc"], 8);
+        assert_eq!(summary.totals["ordnance"], 4);
+        assert!(!summary.totals.contains_key("missile"));
+    }
+
+    #[test]
+    fn test_weapon_stats_vs_reference_shield() {
+        let shield = reference_shield();
+
+        // Ballistic: 22.5% absorbed, 77.5% passes through
+        let ballistic = weapon_stats(&make_test_weapon(1000.0, 0.0, 0.0), &shield);
+        assert!((ballistic.shield_dps - 225.0).abs() < 0.1);
+        assert!((ballistic.passthrough_dps - 775.0).abs() < 0.1);
+        assert!((ballistic.shield_effectiveness - 0.225).abs() < 1e-9);
+
+        // Energy: fully absorbed, no passthrough
+        let energy = weapon_stats(&Weapon { power_consumption: 0.0, ..make_test_weapon(0.0, 1000.0, 0.0) }, &shield);
+        assert!((energy.shield_dps - 1000.0).abs() < 0.1);
+        assert_eq!(energy.passthrough_dps, 0.0);
+
+        // Unknown power draw / heat yields no efficiency figures
+        assert_eq!(energy.dps_per_power, 0.0);
+        assert_eq!(energy.dps_per_heat, 0.0);
+
+        // 600 RPM at 5 heat per shot = 50 heat/s
+        let mut tuned = make_test_weapon(0.0, 1000.0, 0.0);
+        tuned.power_consumption = 4.0;
+        tuned.heat_per_shot = 5.0;
+        let stats = weapon_stats(&tuned, &shield);
+        assert!((stats.dps_per_power - tuned.sustained_dps / 4.0).abs() < 1e-9);
+        assert!((stats.dps_per_heat - tuned.sustained_dps / 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parallel_map_preserves_order() {
+        let items: Vec<i32> = (0..100).collect();
+        let progress = TaskProgress::new(String::new(), Default::default(), |_| {});
+        let doubled = parallel_map(&items, &progress, |x| x * 2).unwrap();
+        assert_eq!(doubled, (0..100).map(|x| x * 2).collect::<Vec<_>>());
+        assert!(parallel_map(&Vec::<i32>::new(), &progress, |x| *x).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_threat_profile_damage_mix() {
+        let threat = ThreatProfile { dps: 1000.0, physical: 0.7, energy: 0.3, distortion: 0.0 };
+        let equipped = threat.to_equipped_weapon();
+        let damage = DamageBreakdown::from_weapon_dps(&equipped.weapon, equipped.weapon.sustained_dps);
+        assert!((damage.physical - 700.0).abs() < 0.1);
+        assert!((damage.energy - 300.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_analyze_target_damage_mix() {
+        let ship = Ship {
+            display_name: "Test Ship".to_string(),
+            hull_hp: 5000.0,
+            armor_hp: 3000.0,
+            armor_damage_mult_physical: 0.75,
+            armor_damage_mult_energy: 0.6,
+            armor_damage_mult_distortion: 1.0,
+            armor_resist_physical: 0.85,
+            armor_resist_energy: 1.30,
+            armor_resist_distortion: 1.0,
+            shield_count: 2,
+            ..Default::default()
+        };
+        let shield = Shield { max_hp: 4000.0, resist_energy: -0.3, resist_distortion: 0.85, ..reference_shield() };
+
+        let analysis = analyze_target(&ship, Some(&shield));
+        let names: Vec<_> = analysis.damage_types.iter().map(|t| t.damage_type.as_str()).collect();
+        assert_eq!(names, ["physical", "energy", "distortion"]);
+        let energy = &analysis.damage_types[1];
+        assert!((energy.shield_factor - 1.3).abs() < 1e-9);
+        assert_eq!(energy.passthrough_factor, 0.0);
+        assert!(analysis.damage_types.iter().any(|t| t.relative_value == 1.0));
+
+        // The mix is a valid split and never slower than any single type
+        let mix = &analysis.recommended_mix;
+        assert!((mix.total() - 1.0).abs() < 1e-9);
+        assert!(analysis.damage_types.iter().all(|t| analysis.recommended_ttk <= t.probe_ttk));
+        assert!(analysis.notes.iter().any(|n| n.starts_with("Energy weapons are") && n.ends_with("vs this target's shields")));
+
+        // Without a shield there is nothing to say about shields
+        let unshielded = analyze_target(&ship, None);
+        assert!(unshielded.notes.iter().all(|n| !n.contains("shields")));
+    }
+
+    #[test]
+    fn test_dataset_analytics() {
+        let d = Distribution::of(vec![4.0, 1.0, 3.0, 2.0, f64::NAN]);
+        assert_eq!((d.count, d.min, d.max), (4, 1.0, 4.0));
+        assert_eq!((d.p25, d.median, d.p75, d.mean), (1.75, 2.5, 3.25, 2.5));
+        assert_eq!(Distribution::of(vec![]), Distribution::default());
+
+        let mut data = GameData::default();
+        for (name, class, hull) in [("A", "small", 1000.0), ("B", "small", 3000.0), ("C", "", 500.0), ("D", "large", 0.0)] {
+            let ship = Ship { display_name: name.to_string(), size_class: class.to_string(), hull_hp: hull, ..Default::default() };
+            data.ships.insert(name.to_string(), std::sync::Arc::new(ship));
+        }
+        data.shields.insert("s1".to_string(), std::sync::Arc::new(Shield { size: 1, max_hp: 2000.0, ..reference_shield() }));
+
+        let analytics = dataset_analytics(&data);
+        assert_eq!(analytics.ship_count, 4);
+        assert_eq!(analytics.hull_hp_by_class.keys().collect::<Vec<_>>(), ["small", "unknown"]);
+        assert_eq!(analytics.hull_hp_by_class["small"].median, 2000.0);
+        assert_eq!(analytics.shield_hp_by_size[&1].count, 1);
+        assert!(analytics.weapon_dps_by_size.is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+//! Ship Lens Data Audit
+//!
+//! Flags loaded entries that are probably extraction mistakes rather than real
+//! game values: ships without hull HP, guns without DPS or damage split, shields
+//! without absorption, and gun hardpoints whose default weapon isn't in the data.
+//! Unlike the load report this looks at what loaded, not at what failed to.
+
+use crate::data::GameData;
+use crate::ttk::GUN_CATEGORIES;
+use serde::Serialize;
+
+/// What kind of entry an issue is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEntity {
+    Ship,
+    Weapon,
+    Shield,
+    Hardpoint,
+}
+
+/// One suspicious value
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditIssue {
+    pub entity: AuditEntity,
+    /// Ship, weapon or shield name ("Ship / port" for hardpoints)
+    pub name: String,
+    pub field: String,
+    pub message: String,
+}
+
+/// Result of `audit`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AuditReport {
+    pub ships_checked: usize,
+    pub weapons_checked: usize,
+    pub shields_checked: usize,
+    pub hardpoints_checked: usize,
+    /// Sorted by entity, then name and field
+    pub issues: Vec<AuditIssue>,
+}
+
+impl AuditReport {
+    fn flag(&mut self, entity: AuditEntity, name: &str, field: &str, message: &str) {
+        self.issues.push(AuditIssue {
+            entity,
+            name: name.to_string(),
+            field: field.to_string(),
+            message: message.to_string(),
+        });
+    }
+}
+
+/// Check every loaded ship, weapon and shield
+pub fn audit(data: &GameData) -> AuditReport {
+    let mut report = AuditReport {
+        ships_checked: data.ships.len(),
+        weapons_checked: data.weapons.len(),
+        shields_checked: data.shields.len(),
+        ..Default::default()
+    };
+
+    for ship in data.ships.values() {
+        if ship.hull_hp <= 0.0 {
+            report.flag(AuditEntity::Ship, &ship.display_name, "hull_hp", "Zero hull HP");
+        }
+        for hp in ship.weapon_hardpoints.iter().filter(|hp| GUN_CATEGORIES.contains(&hp.category.as_str())) {
+            report.hardpoints_checked += 1;
+            let unresolved = hp.sub_ports.iter()
+                .filter_map(|p| p.default_weapon.as_deref())
+                .find(|w| {
+                    !w.is_empty() && !w.eq_ignore_ascii_case("empty")
+                        && data.weapon(w).is_none()
+                });
+            if let Some(weapon) = unresolved {
+                let name = format!("{} / {}", ship.display_name, hp.port_name);
+                report.flag(AuditEntity::Hardpoint, &name, "default_weapon", &format!("Default weapon '{}' not found", weapon));
+            }
+        }
+    }
+
+    for weapon in data.weapons.values() {
+        if weapon.sustained_dps <= 0.0 {
+            report.flag(AuditEntity::Weapon, &weapon.display_name, "sustained_dps", "Zero DPS");
+        }
+        if weapon.damage_physical + weapon.damage_energy + weapon.damage_distortion <= 0.0 {
+            report.flag(AuditEntity::Weapon, &weapon.display_name, "damage", "Missing damage breakdown");
+        }
+    }
+
+    for shield in data.shields.values() {
+        if shield.max_hp <= 0.0 {
+            report.flag(AuditEntity::Shield, &shield.display_name, "max_hp", "Zero shield HP");
+        }
+        if shield.absorb_physical <= 0.0 && shield.absorb_energy <= 0.0 && shield.absorb_distortion <= 0.0 {
+            report.flag(AuditEntity::Shield, &shield.display_name, "absorb", "Missing absorption values");
+        }
+    }
+
+    report.issues.sort_by(|a, b| (a.entity, &a.name, &a.field).cmp(&(b.entity, &b.name, &b.field)));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{reference_shield, ThreatProfile};
+    use crate::data::{Ship, Shield, SubPort, Weapon, WeaponHardpoint};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_audit_flags_suspicious_entries() {
+        let mut data = GameData::default();
+        let gun = ThreatProfile { dps: 500.0, physical: 1.0, energy: 0.0, distortion: 0.0 }.to_equipped_weapon().weapon;
+        let dud = ThreatProfile { dps: 0.0, physical: 0.0, energy: 0.0, distortion: 0.0 }.to_equipped_weapon().weapon;
+        data.weapons.insert("gun".to_string(), Arc::new(gun));
+        data.weapons.insert("dud".to_string(), Arc::new(Weapon { filename: "dud".to_string(), display_name: "Dud".to_string(), ..dud }));
+
+        let hardpoint = |port: &str, weapon: &str| WeaponHardpoint {
+            slot_number: 0,
+            port_name: port.to_string(),
+            max_size: 3,
+            gimbal_type: "Gimbal".to_string(),
+            control_type: "pilot".to_string(),
+            category: "pilot".to_string(),
+            mount_name: String::new(),
+            compatible_mounts: vec![],
+            sub_ports: vec![SubPort { size: 3, default_weapon: Some(weapon.to_string()) }],
+        };
+        let ship = Ship {
+            display_name: "Broken Ship".to_string(),
+            hull_hp: 0.0,
+            weapon_hardpoints: vec![hardpoint("hardpoint_left", "Generic Threat"), hardpoint("hardpoint_right", "missing_gun")],
+            ..Default::default()
+        };
+        data.ships.insert(ship.display_name.clone(), Arc::new(ship));
+        let shield = Shield { max_hp: 4000.0, absorb_physical: 0.0, absorb_energy: 0.0, absorb_distortion: 0.0, ..reference_shield() };
+        data.shields.insert(shield.internal_name.clone(), Arc::new(shield));
+
+        let report = audit(&data);
+        assert_eq!((report.ships_checked, report.weapons_checked, report.hardpoints_checked), (1, 2, 2));
+        let flagged: Vec<_> = report.issues.iter().map(|i| (i.entity, i.name.as_str(), i.field.as_str())).collect();
+        assert_eq!(flagged, [
+            (AuditEntity::Ship, "Broken Ship", "hull_hp"),
+            (AuditEntity::Weapon, "Dud", "damage"),
+            (AuditEntity::Weapon, "Dud", "sustained_dps"),
+            (AuditEntity::Shield, "Reference Shield", "absorb"),
+            (AuditEntity::Hardpoint, "Broken Ship / hardpoint_right", "default_weapon"),
+        ]);
+    }
+}
@@ -0,0 +1,163 @@
+//! Ship Lens Config Backup
+//!
+//! Packs the whole app config dir (settings, presets, recents, history, custom
+//! definitions) into a zip for moving between machines, and unpacks it again.
+//! Temp, backup and corrupt siblings left by `store` are not included.
+
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Marker entry identifying a Ship Lens config backup
+const MANIFEST: &str = "ship-lens-backup.json";
+
+/// Contents of the backup marker entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub app_version: String,
+    pub created_at: String,  // ISO 8601
+    pub files: Vec<String>,
+}
+
+/// Config files under `dir` as paths relative to it, sorted
+fn config_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        let entries = fs::read_dir(dir.join(&relative))
+            .map_err(|e| format!("Failed to read {}: {}", dir.join(&relative).display(), e))?;
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let path = relative.join(&name);
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => pending.push(path),
+                Ok(t) if t.is_file() && !store::is_artifact(&name) => files.push(path),
+                _ => {}
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Zip archive names always use `/`
+fn archive_name(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Write every config file in `config_dir` to a zip at `dest`, returning the manifest
+pub fn backup(config_dir: &Path, dest: &Path) -> Result<BackupManifest, String> {
+    let files = if config_dir.is_dir() { config_files(config_dir)? } else { Vec::new() };
+    let manifest = BackupManifest {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: store::now_iso8601(),
+        files: files.iter().map(|f| archive_name(f)).collect(),
+    };
+
+    let file = fs::File::create(dest)
+        .map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let zip_err = |e: zip::result::ZipError| format!("Failed to write backup: {}", e);
+
+    for (path, name) in files.iter().zip(&manifest.files) {
+        let bytes = fs::read(config_dir.join(path))
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        zip.start_file(name.as_str(), options).map_err(zip_err)?;
+        zip.write_all(&bytes).map_err(|e| format!("Failed to write backup: {}", e))?;
+    }
+
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize backup manifest: {}", e))?;
+    zip.start_file(MANIFEST, options).map_err(zip_err)?;
+    zip.write_all(json.as_bytes()).map_err(|e| format!("Failed to write backup: {}", e))?;
+    zip.finish().map_err(zip_err)?;
+
+    Ok(manifest)
+}
+
+/// Unpack a backup made by `backup` into `config_dir`, returning its manifest
+///
+/// Each restored file replaces the current one via `store::write_atomic`, so the
+/// version it replaces stays available as a backup. Files not in the archive are
+/// left alone.
+pub fn restore(config_dir: &Path, src: &Path) -> Result<BackupManifest, String> {
+    let file = fs::File::open(src)
+        .map_err(|e| format!("Failed to open {}: {}", src.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Not a valid backup archive: {}", e))?;
+
+    let manifest: BackupManifest = {
+        let mut entry = archive.by_name(MANIFEST)
+            .map_err(|_| "Not a Ship Lens config backup (no manifest)".to_string())?;
+        let mut json = Vec::new();
+        entry.read_to_end(&mut json)
+            .map_err(|e| format!("Failed to read backup manifest: {}", e))?;
+        serde_json::from_slice(&json)
+            .map_err(|e| format!("Failed to parse backup manifest: {}", e))?
+    };
+
+    // Read everything first so a bad entry aborts before any file is replaced
+    let mut contents = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)
+            .map_err(|e| format!("Failed to read backup: {}", e))?;
+        if entry.is_dir() || entry.name() == MANIFEST {
+            continue;
+        }
+        let Some(relative) = entry.enclosed_name() else {
+            return Err(format!("Backup entry '{}' escapes the config dir", entry.name()));
+        };
+        // Restored byte for byte; config files need not be UTF-8
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to read {} from backup: {}", entry.name(), e))?;
+        contents.push((relative, bytes));
+    }
+
+    for (relative, bytes) in contents {
+        store::write_atomic(&config_dir.join(&relative), &bytes)
+            .map_err(|e| format!("Failed to restore {}: {}", relative.display(), e))?;
+    }
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_round_trip() {
+        let root = std::env::temp_dir().join(format!("ship-lens-backup-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (config, restored) = (root.join("config"), root.join("restored"));
+        fs::create_dir_all(config.join("loadouts")).unwrap();
+        store::write_atomic(&config.join("settings.json"), "{\"theme\":\"drake\"}").unwrap();
+        store::write_atomic(&config.join("settings.json"), "{\"theme\":\"aegis\"}").unwrap();
+        fs::write(config.join("loadouts").join("gladius.json"), "[]").unwrap();
+        let binary = [0x89, b'P', b'N', b'G', 0xff, 0x00, 0xfe];
+        fs::write(config.join("avatar.png"), binary).unwrap();
+
+        let archive = root.join("backup.zip");
+        let manifest = backup(&config, &archive).unwrap();
+        assert_eq!(manifest.files, ["avatar.png", "loadouts/gladius.json", "settings.json"]);
+
+        restore(&restored, &archive).unwrap();
+        assert_eq!(fs::read_to_string(restored.join("settings.json")).unwrap(), "{\"theme\":\"aegis\"}");
+        assert_eq!(fs::read_to_string(restored.join("loadouts").join("gladius.json")).unwrap(), "[]");
+        assert!(!restored.join("settings.json.bak1").exists());
+        // Non-UTF-8 files survive the round trip
+        assert_eq!(fs::read(restored.join("avatar.png")).unwrap(), binary);
+
+        // Arbitrary zips are rejected
+        assert!(restore(&restored, &config.join("settings.json")).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,229 @@
+//! Ship Lens Community Benchmarks
+//!
+//! TTKs measured in game by the community, compared with what the model gives
+//! for the same matchup. Benchmarks are imported from a CSV with a header row
+//! naming at least `attacker,target,measured_ttk`; optional `scenario` and
+//! `zone` columns take preset ids, `shield` a shield name and `source` a note
+//! on who measured it. The import replaces `benchmarks.json` in the app config dir.
+//!
+//! Deltas are model minus measured: negative means the model kills too fast,
+//! which usually means the accuracy multipliers are too generous.
+
+use crate::store;
+use crate::ttk::{ScenarioPreset, TTKResult, ZonePreset};
+use crate::ShipTtkRequest;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const BENCHMARKS_FILE: &str = "benchmarks.json";
+
+/// Longest measured TTK accepted (seconds)
+const MAX_MEASURED_TTK: f64 = 3600.0;
+
+/// One measured matchup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Benchmark {
+    pub attacker: String,
+    pub target: String,
+    #[serde(default)]
+    pub scenario_preset: Option<ScenarioPreset>,
+    #[serde(default)]
+    pub zone_preset: Option<ZonePreset>,
+    #[serde(default)]
+    pub shield: Option<String>,
+    /// Measured time to kill (seconds)
+    pub measured_ttk: f64,
+    #[serde(default)]
+    pub source: String,
+}
+
+impl Benchmark {
+    /// Model request for this matchup
+    pub fn request(&self) -> ShipTtkRequest {
+        ShipTtkRequest {
+            attacker: self.attacker.clone(),
+            target: self.target.clone(),
+            shield_name: self.shield.clone(),
+            scenario_preset: self.scenario_preset,
+            zone_preset: self.zone_preset,
+            ..Default::default()
+        }
+    }
+}
+
+/// Preset from its id (empty = None)
+fn preset<T: DeserializeOwned>(id: &str) -> Result<Option<T>, ()> {
+    if id.is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_value(serde_json::Value::String(id.to_lowercase())).map(Some).map_err(|_| ())
+}
+
+/// Parse a benchmark CSV; bad rows are returned as warnings and skipped
+pub fn parse(contents: &str) -> Result<(Vec<Benchmark>, Vec<String>), String> {
+    let mut lines = contents.lines().enumerate();
+    let header = lines.next().map(|(_, h)| h).unwrap_or_default();
+    let columns: Vec<String> = header.split(',').map(|c| c.trim().to_lowercase()).collect();
+    let column = |name: &str| columns.iter().position(|c| c == name);
+    let (Some(attacker_col), Some(target_col), Some(ttk_col)) = (column("attacker"), column("target"), column("measured_ttk")) else {
+        return Err("Benchmark CSV needs attacker, target and measured_ttk columns".to_string());
+    };
+    let (scenario_col, zone_col, shield_col, source_col) = (column("scenario"), column("zone"), column("shield"), column("source"));
+
+    let mut benchmarks = Vec::new();
+    let mut warnings = Vec::new();
+    for (line_no, line) in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let field = |col: Option<usize>| col.and_then(|c| fields.get(c).copied()).unwrap_or_default();
+        let (attacker, target) = (field(Some(attacker_col)), field(Some(target_col)));
+        if attacker.is_empty() || target.is_empty() {
+            warnings.push(format!("Line {}: missing attacker or target", line_no + 1));
+            continue;
+        }
+        let measured_ttk = field(Some(ttk_col));
+        let Some(measured_ttk) = measured_ttk.parse::<f64>().ok().filter(|t| *t > 0.0 && *t <= MAX_MEASURED_TTK) else {
+            warnings.push(format!("Line {}: invalid measured_ttk '{}'", line_no + 1, measured_ttk));
+            continue;
+        };
+        let Ok(scenario_preset) = preset(field(scenario_col)) else {
+            warnings.push(format!("Line {}: unknown scenario '{}'", line_no + 1, field(scenario_col)));
+            continue;
+        };
+        let Ok(zone_preset) = preset(field(zone_col)) else {
+            warnings.push(format!("Line {}: unknown zone '{}'", line_no + 1, field(zone_col)));
+            continue;
+        };
+        let shield = field(shield_col);
+
+        benchmarks.push(Benchmark {
+            attacker: attacker.to_string(),
+            target: target.to_string(),
+            scenario_preset,
+            zone_preset,
+            shield: (!shield.is_empty()).then(|| shield.to_string()),
+            measured_ttk,
+            source: field(source_col).to_string(),
+        });
+    }
+    Ok((benchmarks, warnings))
+}
+
+/// Load the imported benchmarks (empty if none)
+pub fn load(config_dir: &Path) -> Result<Vec<Benchmark>, String> {
+    Ok(store::read_json(&config_dir.join(BENCHMARKS_FILE))?.unwrap_or_default())
+}
+
+/// Save benchmarks (atomically, keeping backups)
+pub fn save(config_dir: &Path, benchmarks: &[Benchmark]) -> Result<(), String> {
+    store::write_json(&config_dir.join(BENCHMARKS_FILE), benchmarks)
+}
+
+/// Model result for one benchmark
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkResult {
+    pub benchmark: Benchmark,
+    /// None when the matchup could not be calculated
+    pub model_ttk: Option<f64>,
+    /// `model_ttk - measured_ttk` (seconds)
+    pub delta: Option<f64>,
+    /// Delta as a percentage of the measured TTK
+    pub delta_pct: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// Model-vs-measured deltas over all benchmarks
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BenchmarkComparison {
+    pub results: Vec<BenchmarkResult>,
+    /// Matchups the model could calculate (with a finite TTK)
+    pub compared: usize,
+    /// Mean signed delta percentage (negative = model too fast)
+    pub mean_delta_pct: Option<f64>,
+    /// Mean absolute delta percentage
+    pub mean_abs_delta_pct: Option<f64>,
+    /// Median of model/measured: scaling the accuracy multipliers by this
+    /// would center the model on the measurements (TTK is about inversely
+    /// proportional to accuracy)
+    pub suggested_accuracy_scale: Option<f64>,
+}
+
+/// Run every benchmark through `compute` and compare with the measured TTK
+pub fn compare<F>(benchmarks: &[Benchmark], compute: F) -> BenchmarkComparison
+where
+    F: Fn(&ShipTtkRequest) -> Result<TTKResult, String>,
+{
+    let mut ratios = Vec::new();
+    let mut deltas = Vec::new();
+    let results: Vec<BenchmarkResult> = benchmarks.iter()
+        .map(|benchmark| {
+            let (model_ttk, error) = match compute(&benchmark.request()) {
+                Ok(result) if result.total_ttk.is_finite() => (Some(result.total_ttk), None),
+                Ok(_) => (None, Some("The model never kills the target".to_string())),
+                Err(e) => (None, Some(e)),
+            };
+            let delta = model_ttk.map(|ttk| ttk - benchmark.measured_ttk);
+            let delta_pct = delta.map(|d| d / benchmark.measured_ttk * 100.0);
+            if let (Some(ttk), Some(pct)) = (model_ttk, delta_pct) {
+                ratios.push(ttk / benchmark.measured_ttk);
+                deltas.push(pct);
+            }
+            BenchmarkResult { benchmark: benchmark.clone(), model_ttk, delta, delta_pct, error }
+        })
+        .collect();
+
+    let mean = |values: &[f64]| (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64);
+    ratios.sort_by(f64::total_cmp);
+    let median = match ratios.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(ratios[n / 2]),
+        n => Some((ratios[n / 2 - 1] + ratios[n / 2]) / 2.0),
+    };
+    BenchmarkComparison {
+        results,
+        compared: deltas.len(),
+        mean_delta_pct: mean(&deltas),
+        mean_abs_delta_pct: mean(&deltas.iter().map(|d| d.abs()).collect::<Vec<_>>()),
+        suggested_accuracy_scale: median,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_benchmark_comparison() {
+        let csv = "attacker,target,measured_ttk,scenario,zone,source\n\
+                   Aegis Gladius,Anvil Arrow,10,dogfight,engines,Test Squadron\n\
+                   Aegis Gladius,Drake Cutlass Black,20,jousting,,\n\
+                   Anvil Arrow,Aegis Gladius,15,,,\n\
+                   Aegis Gladius,Anvil Arrow,-3,,,\n\
+                   Aegis Gladius,Anvil Arrow,12,barrel-roll,,\n";
+        let (benchmarks, warnings) = parse(csv).unwrap();
+        assert_eq!(benchmarks.len(), 3);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(benchmarks[0].zone_preset, Some(ZonePreset::Engines));
+        assert_eq!(benchmarks[1].scenario_preset, Some(ScenarioPreset::Jousting));
+        assert!(parse("ship,ttk\n").is_err());
+
+        // Stand-in model: 8s against the Arrow, 30s against the Cutlass, Gladius target missing
+        let comparison = compare(&benchmarks, |request| match request.target.as_str() {
+            "Anvil Arrow" => Ok(TTKResult { total_ttk: 8.0, ..Default::default() }),
+            "Drake Cutlass Black" => Ok(TTKResult { total_ttk: 30.0, ..Default::default() }),
+            _ => Err("Target ship 'Aegis Gladius' not found".to_string()),
+        });
+        assert_eq!(comparison.compared, 2);
+        assert_eq!(comparison.results[0].delta, Some(-2.0));
+        assert_eq!(comparison.results[1].delta_pct, Some(50.0));
+        assert!(comparison.results[2].error.is_some());
+        assert_eq!(comparison.mean_delta_pct, Some(15.0));
+        assert_eq!(comparison.mean_abs_delta_pct, Some(35.0));
+        assert!((comparison.suggested_accuracy_scale.unwrap() - 1.15).abs() < 1e-9);
+    }
+}
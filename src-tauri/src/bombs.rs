@@ -0,0 +1,226 @@
+//! Ship Lens Bombing Runs
+//!
+//! Bombs load with the missiles (`missile_type` "bomb" in missiles.json). A run
+//! drops a stick of bombs in one pass; each lands with the given probability and
+//! its damage goes through the target's layers in order: the Rule of Two shield
+//! pool (the ballistic part partly passing through), armor, then the center-mass
+//! hull pool. Shield regen is ignored since the whole stick lands within moments.
+
+use crate::data::{Missile, Shield, Ship};
+use crate::ttk::{
+    apply_rule_of_two, armor_damage_by_type, shield_damage_by_type, DamageBreakdown, RuleOfTwoOptions,
+    ValidationErrors, ZoneModifiers,
+};
+use serde::Serialize;
+
+/// Most bombs in one run
+pub const MAX_BOMBS: i32 = 100;
+
+/// Expected outcome of one bombing run
+#[derive(Debug, Clone, Serialize)]
+pub struct BombingRun {
+    pub bomb: String,
+    pub target: String,
+    pub count: i32,
+    pub hit_probability: f64,
+    pub expected_hits: f64,
+    /// Total damage of one bomb (all types)
+    pub damage_per_bomb: f64,
+    /// Target pools before the run
+    pub shield_hp: f64,
+    pub armor_hp: f64,
+    pub hull_hp: f64,
+    /// Expected HP removed from each pool
+    pub shield_damage: f64,
+    pub armor_damage: f64,
+    pub hull_damage: f64,
+    /// Bombs that must land to destroy the target (None = more than `MAX_BOMBS`)
+    pub hits_to_kill: Option<i32>,
+    /// Bombs to drop for one expected kill at this hit probability
+    pub bombs_to_kill: Option<i32>,
+    /// Chance this run destroys the target
+    pub kill_probability: f64,
+}
+
+/// Target HP left as ordnance lands
+#[derive(Debug, Clone, Copy)]
+struct Pools {
+    shield: f64,
+    armor: f64,
+    hull: f64,
+}
+
+impl Pools {
+    /// Full Rule of Two shield pool and center-mass armor/hull pools of `target`
+    fn of(target: &Ship, shield: Option<&Shield>) -> Self {
+        let shield = shield
+            .map_or(0.0, |s| apply_rule_of_two(s, target.shield_count, &RuleOfTwoOptions::default()).total_hp);
+        let (armor, hull) = ZoneModifiers::default().pools(target);
+        Self { shield, armor, hull }
+    }
+
+    fn destroyed(&self) -> bool {
+        self.hull <= 0.0
+    }
+
+    /// Land `damage` (one bomb, or part of one for expected values)
+    fn land(&mut self, damage: &DamageBreakdown, shield: Option<&Shield>, target: &Ship) {
+        // Raw damage that gets past the shield
+        let mut unshielded = damage.clone();
+        if let Some(shield) = shield.filter(|_| self.shield > 0.0) {
+            let (absorbed, passthrough) = shield_damage_by_type(damage, shield);
+            // Share of the bomb the remaining shield soaks up; the rest arrives whole
+            let soaked = if absorbed.total() > 0.0 { (self.shield / absorbed.total()).min(1.0) } else { 1.0 };
+            self.shield = (self.shield - absorbed.total() * soaked).max(0.0);
+            let rest = 1.0 - soaked;
+            unshielded = DamageBreakdown {
+                physical: passthrough.physical * soaked + damage.physical * rest,
+                energy: passthrough.energy * soaked + damage.energy * rest,
+                distortion: passthrough.distortion * soaked + damage.distortion * rest,
+            };
+        }
+
+        // Share of that damage left once the armor is gone
+        let armor_damage = armor_damage_by_type(&unshielded, target).total();
+        let through = if self.armor <= 0.0 {
+            1.0
+        } else if armor_damage > 0.0 {
+            let used = (self.armor / armor_damage).min(1.0);
+            self.armor = (self.armor - armor_damage * used).max(0.0);
+            1.0 - used
+        } else {
+            0.0
+        };
+        self.hull = (self.hull - unshielded.total() * through).max(0.0);
+    }
+}
+
+/// Hits of `damage` needed to destroy `target`, landing one at a time (None = more than `MAX_BOMBS`)
+pub(crate) fn hits_to_kill(damage: &DamageBreakdown, target: &Ship, shield: Option<&Shield>) -> Option<i32> {
+    let mut pools = Pools::of(target, shield);
+    (1..=MAX_BOMBS).find(|_| {
+        pools.land(damage, shield, target);
+        pools.destroyed()
+    })
+}
+
+/// Chance at least `k` of `n` bombs land, each with probability `p`
+fn at_least(n: i32, k: i32, p: f64) -> f64 {
+    if k <= 0 {
+        return 1.0;
+    }
+    if k > n || p <= 0.0 {
+        return 0.0;
+    }
+    if p >= 1.0 {
+        return 1.0;
+    }
+    // Binomial terms built up from P(0 hits)
+    let mut term = (1.0 - p).powi(n);
+    let mut below = 0.0;
+    for i in 0..k {
+        below += term;
+        term *= (n - i) as f64 / (i + 1) as f64 * p / (1.0 - p);
+    }
+    (1.0 - below).clamp(0.0, 1.0)
+}
+
+/// Expected result of dropping `count` bombs on `target` (`shield` None = unshielded)
+pub fn bombing_run(
+    bomb: &Missile,
+    count: i32,
+    hit_probability: f64,
+    target: &Ship,
+    shield: Option<&Shield>,
+) -> Result<BombingRun, String> {
+    let mut errors = ValidationErrors::default();
+    if bomb.missile_type != "bomb" {
+        errors.push("bomb", format!("{} is not a bomb", bomb.display_name));
+    }
+    errors.check_range("count", count as f64, 1.0, MAX_BOMBS as f64);
+    errors.check_range("hit_probability", hit_probability, 0.0, 1.0);
+    errors.into_result().map_err(|e| e.to_string())?;
+
+    let damage = DamageBreakdown {
+        physical: bomb.damage_physical,
+        energy: bomb.damage_energy,
+        distortion: bomb.damage_distortion,
+    };
+    let start = Pools::of(target, shield);
+    let hits_to_kill = hits_to_kill(&damage, target, shield);
+
+    // Expected pools after the expected number of hits
+    let expected_hits = count as f64 * hit_probability;
+    let mut expected = start;
+    for _ in 0..expected_hits.floor() as i32 {
+        expected.land(&damage, shield, target);
+    }
+    expected.land(&damage.share(damage.total() * expected_hits.fract()), shield, target);
+
+    Ok(BombingRun {
+        bomb: bomb.display_name.clone(),
+        target: target.display_name.clone(),
+        count,
+        hit_probability,
+        expected_hits,
+        damage_per_bomb: damage.total(),
+        shield_hp: start.shield,
+        armor_hp: start.armor,
+        hull_hp: start.hull,
+        shield_damage: start.shield - expected.shield,
+        armor_damage: start.armor - expected.armor,
+        hull_damage: start.hull - expected.hull,
+        hits_to_kill,
+        bombs_to_kill: hits_to_kill
+            .filter(|_| hit_probability > 0.0)
+            .map(|hits| (hits as f64 / hit_probability).ceil() as i32),
+        kill_probability: hits_to_kill.map_or(0.0, |hits| at_least(count, hits, hit_probability)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bombing_run() {
+        let bomb = Missile {
+            id: 0,
+            name: "bomb_s05".to_string(),
+            display_name: "Test Bomb".to_string(),
+            size: 5,
+            missile_type: "bomb".to_string(),
+            tracking_type: "Unknown".to_string(),
+            damage_physical: 0.0,
+            damage_energy: 10_000.0,
+            damage_distortion: 0.0,
+            explosion_min_radius: 100.0,
+            explosion_max_radius: 100.0,
+            max_lifetime: 300.0,
+            arm_time: 3.0,
+            lock_time: 0.0,
+        };
+        // Center mass pools: 0.3 x 50000 armor, 0.6 x 50000 hull
+        let target = Ship {
+            display_name: "Test Base".to_string(),
+            hull_hp: 50_000.0,
+            armor_hp: 50_000.0,
+            armor_damage_mult_energy: 1.0,
+            armor_resist_energy: 1.0,
+            ..Default::default()
+        };
+
+        let run = bombing_run(&bomb, 6, 0.5, &target, None).unwrap();
+        assert_eq!(run.expected_hits, 3.0);
+        assert_eq!((run.armor_damage, run.hull_damage), (15_000.0, 15_000.0));
+        // 45000 HP needs 5 hits: 6 drops at 50% get there 7 times in 64
+        assert_eq!(run.hits_to_kill, Some(5));
+        assert_eq!(run.bombs_to_kill, Some(10));
+        assert!((run.kill_probability - 7.0 / 64.0).abs() < 1e-9);
+        assert_eq!(bombing_run(&bomb, 6, 1.0, &target, None).unwrap().kill_probability, 1.0);
+
+        assert!(bombing_run(&bomb, 0, 0.5, &target, None).is_err());
+        let missile = Missile { missile_type: "missile".to_string(), ..bomb };
+        assert!(bombing_run(&missile, 2, 0.5, &target, None).is_err());
+    }
+}
@@ -0,0 +1,266 @@
+//! Ship Lens Budget Optimizer
+//!
+//! Picks the guns and shield to buy for a ship within an aUEC budget, using the
+//! price data (see `data::prices`). Stock components cost nothing; a replacement
+//! costs its price times the number fitted.
+//!
+//! Each affordable gun is scored per hardpoint by its solo kill rate against the
+//! target (1 / solo TTK; rates add up across hardpoints), and the best-scoring set
+//! for the money is found with a multiple-choice knapsack. That runs once per
+//! affordable shield, and the combinations are compared on full TTK and time-to-die.
+
+use crate::data::{GameData, Ship};
+use crate::tasks::TaskProgress;
+use crate::ttk::GUN_CATEGORIES;
+use crate::{compute_ttk_for_ships, ShipTtkRequest};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Cost resolution of the knapsack (the budget is split into this many steps)
+const BUDGET_STEPS: f64 = 2000.0;
+
+/// What the optimizer maximizes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetGoal {
+    /// Kill the target fastest
+    #[default]
+    Ttk,
+    /// Survive the target's stock guns longest
+    Survivability,
+}
+
+/// A gun hardpoint to refit
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeaponPurchase {
+    pub port: String,
+    pub weapon: String,
+    /// Guns bought (one per sub-port)
+    pub count: i32,
+    pub cost: f64,
+}
+
+/// Best loadout found within the budget
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetLoadout {
+    /// Refitted hardpoints (others keep their stock guns)
+    pub weapons: Vec<WeaponPurchase>,
+    /// Shield internal name to buy (None = keep the stock shield)
+    pub shield: Option<String>,
+    pub shield_cost: f64,
+    pub total_cost: f64,
+    /// Port -> weapon overrides, as `calculate_ttk_for_ships` takes them
+    pub attacker_loadout: HashMap<String, String>,
+    pub ttk: f64,
+    pub time_to_die: f64,
+    pub stock_ttk: f64,
+    pub stock_time_to_die: f64,
+}
+
+/// One option per group (index 0 must be free); returns the chosen index per group
+///
+/// Maximizes the summed value with the summed cost within `budget`. Costs are
+/// rounded up to the knapsack resolution, so the result never exceeds the budget.
+fn choose(groups: &[Vec<(f64, f64)>], budget: f64) -> Vec<usize> {
+    let unit = (budget / BUDGET_STEPS).max(1.0);
+    let steps = (budget.max(0.0) / unit).floor() as usize;
+    let bucket = |cost: f64| (cost / unit).ceil() as usize;
+
+    let mut best = vec![0.0; steps + 1];
+    let mut picks: Vec<Vec<usize>> = Vec::with_capacity(groups.len());
+    for options in groups {
+        let mut next = vec![f64::NEG_INFINITY; steps + 1];
+        let mut pick = vec![0; steps + 1];
+        for b in 0..=steps {
+            for (i, &(cost, value)) in options.iter().enumerate() {
+                let c = bucket(cost);
+                if c <= b && best[b - c] + value > next[b] {
+                    next[b] = best[b - c] + value;
+                    pick[b] = i;
+                }
+            }
+        }
+        best = next;
+        picks.push(pick);
+    }
+
+    let mut chosen = vec![0; groups.len()];
+    let mut b = steps;
+    for (g, pick) in picks.iter().enumerate().rev() {
+        chosen[g] = pick[b];
+        b -= bucket(groups[g][chosen[g]].0);
+    }
+    chosen
+}
+
+/// Full TTK for a set of overrides (infinite when the attacker can't fire)
+fn ttk_with(data: &GameData, ship: &Ship, target: &Ship, overrides: HashMap<String, String>) -> f64 {
+    let request = ShipTtkRequest {
+        attacker: ship.display_name.clone(),
+        attacker_loadout: Some(overrides),
+        target: target.display_name.clone(),
+        ..Default::default()
+    };
+    compute_ttk_for_ships(data, &request).map_or(f64::INFINITY, |r| r.total_ttk)
+}
+
+/// Time `ship` survives the target's stock guns with a shield fitted
+fn time_to_die(data: &GameData, ship: &Ship, target: &Ship, shield: Option<&str>) -> f64 {
+    let request = ShipTtkRequest {
+        attacker: target.display_name.clone(),
+        target: ship.display_name.clone(),
+        shield_name: shield.or(ship.default_shield.as_deref()).map(str::to_string),
+        ..Default::default()
+    };
+    compute_ttk_for_ships(data, &request).map_or(f64::INFINITY, |r| r.total_ttk)
+}
+
+/// Find the loadout for `ship` within `budget` aUEC that best meets `goal` against `target`
+pub fn optimize(
+    data: &GameData,
+    ship: &Ship,
+    target: &Ship,
+    budget: f64,
+    goal: BudgetGoal,
+    progress: &TaskProgress,
+) -> Result<BudgetLoadout, String> {
+    if !budget.is_finite() || budget < 0.0 {
+        return Err("Budget must be zero or more aUEC".to_string());
+    }
+    let price = |class: &str| data.price_of(class).and_then(|p| p.auec);
+
+    // Purchasable guns per hardpoint: (filename, display name, sub-ports, cost)
+    let hardpoints: Vec<_> = ship.weapon_hardpoints.iter()
+        .filter(|hp| GUN_CATEGORIES.contains(&hp.category.as_str()) && !hp.sub_ports.is_empty())
+        .collect();
+    let mut candidates: Vec<Vec<(String, String, i32, f64)>> = Vec::new();
+    for hp in &hardpoints {
+        let fits = hp.sub_ports.iter().map(|p| p.size).min().unwrap_or(0);
+        let count = hp.sub_ports.len() as i32;
+        let mut options: Vec<_> = data.weapons.values()
+            .filter(|w| w.weapon_type == "gun" && w.sustained_dps > 0.0 && w.size <= fits && !w.ship_exclusive)
+            .filter(|w| w.restricted_to.is_empty() || w.restricted_to.contains(&ship.manufacturer_code))
+            .filter_map(|w| Some((w.filename.clone(), w.display_name.clone(), count, price(&w.filename)? * count as f64)))
+            .filter(|option| option.3 <= budget)
+            .collect();
+        options.sort_by(|a, b| a.0.cmp(&b.0));
+        candidates.push(options);
+    }
+
+    let shield_count = ship.shield_count.max(1) as f64;
+    let mut shields: Vec<(Option<String>, f64)> = vec![(None, 0.0)];
+    shields.extend(data.shields.values()
+        .filter(|s| s.size == ship.max_shield_size && Some(&s.internal_name) != ship.default_shield.as_ref())
+        .filter_map(|s| Some((Some(s.internal_name.clone()), price(&s.internal_name)? * shield_count)))
+        .filter(|(_, cost)| *cost <= budget));
+    shields.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if candidates.iter().all(Vec::is_empty) && shields.len() == 1 {
+        return Err(format!(
+            "No priced weapons or shields fit {} within {:.0} aUEC (import a prices CSV first)",
+            ship.display_name, budget
+        ));
+    }
+
+    progress.start(candidates.iter().map(|c| c.len() + 1).sum::<usize>() + shields.len() * 2);
+
+    // Solo kill rate of each option per hardpoint (the rest of the guns removed)
+    let cleared: HashMap<String, String> = hardpoints.iter()
+        .map(|hp| (hp.port_name.clone(), String::new()))
+        .collect();
+    let mut groups = Vec::with_capacity(hardpoints.len());
+    for (hp, options) in hardpoints.iter().zip(&candidates) {
+        let rate = |weapon: Option<&str>| {
+            let mut overrides = cleared.clone();
+            match weapon {
+                Some(weapon) => overrides.insert(hp.port_name.clone(), weapon.to_string()),
+                None => overrides.remove(&hp.port_name),
+            };
+            let ttk = ttk_with(data, ship, target, overrides);
+            progress.advance();
+            if ttk.is_finite() && ttk > 0.0 { 1.0 / ttk } else { 0.0 }
+        };
+        let mut group = vec![(0.0, rate(None))];
+        for (filename, _, _, cost) in options {
+            progress.check()?;
+            group.push((*cost, rate(Some(filename))));
+        }
+        groups.push(group);
+    }
+
+    let stock_ttk = ttk_with(data, ship, target, HashMap::new());
+    let stock_time_to_die = time_to_die(data, ship, target, None);
+
+    let mut best: Option<BudgetLoadout> = None;
+    for (shield, shield_cost) in shields {
+        progress.check()?;
+        let chosen = choose(&groups, budget - shield_cost);
+        let weapons: Vec<WeaponPurchase> = hardpoints.iter().zip(&candidates).zip(&chosen)
+            .filter(|(_, i)| **i > 0)
+            .map(|((hp, options), &i)| {
+                let (_, display_name, count, cost) = &options[i - 1];
+                WeaponPurchase { port: hp.port_name.clone(), weapon: display_name.clone(), count: *count, cost: *cost }
+            })
+            .collect();
+        let attacker_loadout: HashMap<String, String> = hardpoints.iter().zip(&candidates).zip(&chosen)
+            .filter(|(_, i)| **i > 0)
+            .map(|((hp, options), &i)| (hp.port_name.clone(), options[i - 1].0.clone()))
+            .collect();
+
+        let ttk = ttk_with(data, ship, target, attacker_loadout.clone());
+        progress.advance();
+        let time_to_die = time_to_die(data, ship, target, shield.as_deref());
+        progress.advance();
+
+        let candidate = BudgetLoadout {
+            total_cost: shield_cost + weapons.iter().map(|w| w.cost).sum::<f64>(),
+            weapons,
+            shield,
+            shield_cost,
+            attacker_loadout,
+            ttk,
+            time_to_die,
+            stock_ttk,
+            stock_time_to_die,
+        };
+        let better = match &best {
+            None => true,
+            Some(current) => {
+                // Greater = the candidate is better: primary goal, then the other, then cheaper
+                let faster = current.ttk.total_cmp(&candidate.ttk);
+                let tougher = candidate.time_to_die.total_cmp(&current.time_to_die);
+                let order = match goal {
+                    BudgetGoal::Ttk => faster.then(tougher),
+                    BudgetGoal::Survivability => tougher.then(faster),
+                };
+                order.then(current.total_cost.total_cmp(&candidate.total_cost)) == Ordering::Greater
+            }
+        };
+        if better {
+            best = Some(candidate);
+        }
+    }
+
+    best.ok_or_else(|| "No loadout fits the budget".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose_within_budget() {
+        // Two hardpoints: stock (free) or upgrades with (cost, kill rate)
+        let groups = vec![
+            vec![(0.0, 0.10), (30_000.0, 0.20), (60_000.0, 0.26)],
+            vec![(0.0, 0.10), (40_000.0, 0.25)],
+        ];
+        assert_eq!(choose(&groups, 0.0), [0, 0]);
+        assert_eq!(choose(&groups, 50_000.0), [0, 1]);
+        assert_eq!(choose(&groups, 70_000.0), [1, 1]);
+        assert_eq!(choose(&groups, 1_000_000.0), [2, 1]);
+        // A cost just over the budget is never chosen
+        assert_eq!(choose(&[vec![(0.0, 0.0), (50_000.01, 1.0)]], 50_000.0), [0]);
+    }
+}
@@ -0,0 +1,90 @@
+//! Ship Lens Data Cache
+//!
+//! Stores parsed `GameData` as a bincode blob in the app cache directory,
+//! keyed by a hash of every source data file. A cache hit skips JSON parsing
+//! entirely; any change to the data files (or the app version) makes it stale.
+
+use crate::data::{self, GameData, LoadReport};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+const CACHE_FILE: &str = "game_data.bin";
+
+/// On-disk cache envelope
+#[derive(Serialize, Deserialize)]
+struct CachedGameData {
+    app_version: String,
+    fingerprint: u64,
+    data: GameData,
+    report: LoadReport,
+}
+
+/// Source files that feed `GameData::load`, in a stable order
+fn source_files(data_dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(data_dir.join("ships"))
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().map_or(false, |ext| ext == "json"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+
+    for name in ["weapons.json", "shields.json", "missiles.json", "mounts.json"] {
+        files.push(data_dir.join(name));
+    }
+    for name in data::json_loader::source_files() {
+        files.push(data_dir.join(name));
+    }
+    for name in data::localization::SOURCE_FILES {
+        files.push(data_dir.join(name));
+    }
+    files.push(data_dir.join(data::ship_metadata::METADATA_FILE));
+    files.push(data_dir.join(data::ship_parts::PARTS_FILE));
+    files.push(data_dir.join(data::mounts::MOUNTS_FILE));
+    files.push(data_dir.join(data::components::POWER_PLANTS_FILE));
+    files.push(data_dir.join(data::components::COOLERS_FILE));
+    files.push(data_dir.join(data::hitbox_zones::HITBOX_ZONES_FILE));
+    files.push(data_dir.join(data::prices::PRICES_FILE));
+    files.push(data_dir.join(data::manifest::MANIFEST_FILE));
+    files
+}
+
+/// Hash of the names and contents of every source data file
+pub fn fingerprint(data_dir: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for path in source_files(data_dir) {
+        path.strip_prefix(data_dir).unwrap_or(&path).hash(&mut hasher);
+        // Missing optional files hash as empty so adding one invalidates the cache
+        std::fs::read(&path).unwrap_or_default().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Load cached game data (and the report from the load that produced it) if it matches the current data files
+pub fn load(cache_dir: &Path, fingerprint: u64) -> Option<(GameData, LoadReport)> {
+    let bytes = std::fs::read(cache_dir.join(CACHE_FILE)).ok()?;
+    let cached: CachedGameData = bincode::deserialize(&bytes).ok()?;
+
+    if cached.app_version != env!("CARGO_PKG_VERSION") || cached.fingerprint != fingerprint {
+        return None;
+    }
+    Some((cached.data, cached.report))
+}
+
+/// Write game data to the cache (best effort)
+pub fn store(cache_dir: &Path, fingerprint: u64, data: &GameData, report: &LoadReport) -> Result<(), String> {
+    let cached = CachedGameData {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        fingerprint,
+        data: data.clone(),
+        report: report.clone(),
+    };
+    let bytes = bincode::serialize(&cached).map_err(|e| e.to_string())?;
+
+    crate::store::replace(&cache_dir.join(CACHE_FILE), bytes).map_err(|e| e.to_string())
+}
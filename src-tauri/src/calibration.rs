@@ -0,0 +1,202 @@
+//! Ship Lens Scenario Calibration
+//!
+//! Fits a scenario's accuracy and time on target to engagements the user has
+//! observed, and keeps the fitted profiles by name in `calibrated_scenarios.json`
+//! in the app config dir.
+//!
+//! Both factors scale the delivered DPS the same way, so the data only pins down
+//! their product. The fit finds the product multiplier that minimizes the squared
+//! log error between model and observed TTKs (golden-section search), then spreads
+//! it evenly over the two factors, the smallest change to the base preset that fits.
+
+use crate::benchmarks::Benchmark;
+use crate::store;
+use crate::ttk::{ScenarioPreset, TTKResult};
+use crate::ShipTtkRequest;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const CALIBRATED_FILE: &str = "calibrated_scenarios.json";
+
+/// Smallest product multiplier searched
+const MIN_SCALE: f64 = 0.05;
+
+/// Golden-section iterations (the bracket shrinks to ~1e-10 of its width)
+const SEARCH_STEPS: usize = 50;
+
+/// Log error charged for a matchup the model never finishes
+const NO_KILL_ERROR: f64 = 10.0;
+
+/// Fitted scenario profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibratedScenario {
+    pub name: String,
+    /// Preset the profile refines (dogfight if None)
+    pub base: Option<ScenarioPreset>,
+    pub scenario_accuracy: f64,
+    pub time_on_target: f64,
+    /// Engagements the fit used
+    pub engagements: usize,
+    /// Model-vs-observed percentage per used engagement, after the fit
+    pub residuals_pct: Vec<f64>,
+    /// Root mean square of `residuals_pct`
+    pub rms_error_pct: f64,
+}
+
+/// Load saved profiles (empty if none)
+pub fn load(config_dir: &Path) -> Result<Vec<CalibratedScenario>, String> {
+    Ok(store::read_json(&config_dir.join(CALIBRATED_FILE))?.unwrap_or_default())
+}
+
+/// Save profiles (atomically, keeping backups)
+pub fn save(config_dir: &Path, profiles: &[CalibratedScenario]) -> Result<(), String> {
+    store::write_json(&config_dir.join(CALIBRATED_FILE), profiles)
+}
+
+/// Replace the profile with the same name, or append it
+pub fn upsert(profiles: &mut Vec<CalibratedScenario>, profile: CalibratedScenario) {
+    match profiles.iter().position(|p| p.name == profile.name) {
+        Some(pos) => profiles[pos] = profile,
+        None => profiles.push(profile),
+    }
+}
+
+/// Accuracy and time on target for a product multiplier, capped at 1.0 each
+fn split(base_accuracy: f64, base_time: f64, scale: f64) -> (f64, f64) {
+    let root = scale.sqrt();
+    let (accuracy, time) = (base_accuracy * root, base_time * root);
+    let product = base_accuracy * base_time * scale;
+    if accuracy > 1.0 {
+        (1.0, product)
+    } else if time > 1.0 {
+        (product, 1.0)
+    } else {
+        (accuracy, time)
+    }
+}
+
+/// Fit `base`'s accuracy and time on target to `observed` (each observation's
+/// own scenario preset is replaced by `base`)
+pub fn fit<F>(name: &str, base: Option<ScenarioPreset>, observed: &[Benchmark], compute: F) -> Result<CalibratedScenario, String>
+where
+    F: Fn(&ShipTtkRequest) -> Result<TTKResult, String>,
+{
+    if name.trim().is_empty() {
+        return Err("Calibrated scenario needs a name".to_string());
+    }
+    let base_scenario = base.map(ScenarioPreset::scenario).unwrap_or_default();
+    let (base_accuracy, base_time) = (base_scenario.scenario_accuracy, base_scenario.time_on_target);
+    if base_accuracy <= 0.0 || base_time <= 0.0 {
+        return Err("Base scenario has no accuracy or time on target to scale".to_string());
+    }
+
+    let run = |obs: &Benchmark, scale: f64| {
+        let (accuracy, time) = split(base_accuracy, base_time, scale);
+        compute(&ShipTtkRequest {
+            scenario_preset: base,
+            scenario_accuracy: Some(accuracy),
+            time_on_target: Some(time),
+            ..obs.request()
+        })
+    };
+    // Engagements the model can calculate at all
+    let usable: Vec<&Benchmark> = observed.iter()
+        .filter(|obs| match run(obs, 1.0) {
+            Ok(_) => true,
+            Err(e) => {
+                log::warn!("Calibration skips {} vs {}: {}", obs.attacker, obs.target, e);
+                false
+            }
+        })
+        .collect();
+    if usable.is_empty() {
+        return Err("No observed engagement could be calculated".to_string());
+    }
+
+    let log_errors = |scale: f64| -> Vec<f64> {
+        usable.iter()
+            .map(|obs| match run(obs, scale) {
+                Ok(result) if result.total_ttk.is_finite() && result.total_ttk > 0.0 => (result.total_ttk / obs.measured_ttk).ln(),
+                _ => NO_KILL_ERROR,
+            })
+            .collect()
+    };
+    let cost = |log_scale: f64| log_errors(log_scale.exp()).iter().map(|e| e * e).sum::<f64>();
+
+    // Golden-section search over log(scale), up to where both factors hit 1.0
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut lo, mut hi) = (MIN_SCALE.ln(), (1.0 / (base_accuracy * base_time)).ln());
+    let mut a = hi - ratio * (hi - lo);
+    let mut b = lo + ratio * (hi - lo);
+    let (mut cost_a, mut cost_b) = (cost(a), cost(b));
+    for _ in 0..SEARCH_STEPS {
+        if cost_a <= cost_b {
+            hi = b;
+            (b, cost_b) = (a, cost_a);
+            a = hi - ratio * (hi - lo);
+            cost_a = cost(a);
+        } else {
+            lo = a;
+            (a, cost_a) = (b, cost_b);
+            b = lo + ratio * (hi - lo);
+            cost_b = cost(b);
+        }
+    }
+    let scale = ((lo + hi) / 2.0).exp();
+
+    let (scenario_accuracy, time_on_target) = split(base_accuracy, base_time, scale);
+    let residuals_pct: Vec<f64> = log_errors(scale).iter().map(|e| (e.exp() - 1.0) * 100.0).collect();
+    let rms_error_pct = (residuals_pct.iter().map(|r| r * r).sum::<f64>() / residuals_pct.len() as f64).sqrt();
+    Ok(CalibratedScenario {
+        name: name.trim().to_string(),
+        base,
+        scenario_accuracy,
+        time_on_target,
+        engagements: usable.len(),
+        residuals_pct,
+        rms_error_pct,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observed(target: &str, measured_ttk: f64) -> Benchmark {
+        Benchmark {
+            attacker: "Aegis Gladius".to_string(),
+            target: target.to_string(),
+            scenario_preset: None,
+            zone_preset: None,
+            shield: None,
+            measured_ttk,
+            source: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_calibrate_scenario() {
+        // Stand-in model: TTK inversely proportional to accuracy × time on target;
+        // the observations fit a product of 0.39 (dogfight is 0.4875)
+        let model = |request: &ShipTtkRequest| -> Result<TTKResult, String> {
+            let factor = request.scenario_accuracy.unwrap() * request.time_on_target.unwrap();
+            match request.target.as_str() {
+                "Anvil Arrow" => Ok(TTKResult { total_ttk: 3.9 / factor, ..Default::default() }),
+                "Drake Cutlass Black" => Ok(TTKResult { total_ttk: 7.8 / factor, ..Default::default() }),
+                _ => Err("Target ship not found".to_string()),
+            }
+        };
+        let engagements = [observed("Anvil Arrow", 10.0), observed("Drake Cutlass Black", 20.0), observed("Missing", 5.0)];
+        let profile = fit("Org nights", None, &engagements, model).unwrap();
+
+        assert_eq!(profile.engagements, 2);
+        assert!((profile.scenario_accuracy * profile.time_on_target - 0.39).abs() < 1e-6);
+        // Spread evenly: both factors scaled by the same amount
+        assert!((profile.scenario_accuracy / 0.75 - profile.time_on_target / 0.65).abs() < 1e-6);
+        assert!(profile.rms_error_pct < 1e-4);
+
+        assert_eq!(split(0.95, 0.95, 1.2), (1.0, 0.95 * 0.95 * 1.2));
+        assert!(fit("", None, &engagements, model).is_err());
+        assert!(fit("Nothing", None, &engagements[2..], model).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+//! Ship Lens Update Channel
+//!
+//! Which release stream the updater follows, saved as `update_channel.json` in
+//! the app config dir. Stable users only ever see full releases; beta and PTU
+//! testers also get pre-release builds.
+
+use crate::download;
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const CHANNEL_FILE: &str = "update_channel.json";
+
+const RELEASES_URL: &str = "https://github.com/CapCeph/ship-lens/releases";
+
+const RELEASES_API: &str = "https://api.github.com/repos/CapCeph/ship-lens/releases?per_page=30";
+
+/// Release stream followed by the updater
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    /// Full releases only
+    #[default]
+    Stable,
+    /// Pre-release builds
+    Beta,
+    /// Pre-release builds, for testing against PTU balance
+    Ptu,
+}
+
+impl UpdateChannel {
+    /// Whether GitHub pre-releases count as updates
+    pub fn includes_prereleases(self) -> bool {
+        self != UpdateChannel::Stable
+    }
+
+    /// Tauri updater manifest for this channel
+    ///
+    /// Pre-release builds publish theirs under a rolling release tagged with the channel name.
+    pub fn app_manifest_url(self) -> String {
+        match self {
+            UpdateChannel::Stable => format!("{}/latest/download/latest.json", RELEASES_URL),
+            UpdateChannel::Beta => format!("{}/download/beta/latest.json", RELEASES_URL),
+            UpdateChannel::Ptu => format!("{}/download/ptu/latest.json", RELEASES_URL),
+        }
+    }
+}
+
+/// Load the saved channel (stable if none saved)
+pub fn load(config_dir: &Path) -> UpdateChannel {
+    store::read_json(&config_dir.join(CHANNEL_FILE)).ok().flatten().unwrap_or_default()
+}
+
+/// Save the channel (atomically, keeping backups)
+pub fn save(config_dir: &Path, channel: UpdateChannel) -> Result<(), String> {
+    store::write_json(&config_dir.join(CHANNEL_FILE), &channel)
+}
+
+/// Whether `candidate` is a newer version than `current` (`x.y.z` with optional `-pre` suffix)
+///
+/// A release is newer than any pre-release of the same version.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parse(version: &str) -> (Vec<u64>, Option<&str>) {
+        let version = version.trim().trim_start_matches('v');
+        let (core, pre) = match version.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (version, None),
+        };
+        (core.split('.').map(|n| n.parse().unwrap_or(0)).collect(), pre)
+    }
+
+    let (candidate_core, candidate_pre) = parse(candidate);
+    let (current_core, current_pre) = parse(current);
+    for i in 0..candidate_core.len().max(current_core.len()) {
+        let (a, b) = (candidate_core.get(i).copied().unwrap_or(0), current_core.get(i).copied().unwrap_or(0));
+        if a != b {
+            return a > b;
+        }
+    }
+    match (candidate_pre, current_pre) {
+        (None, Some(_)) => true,
+        (Some(a), Some(b)) => a > b,
+        _ => false,
+    }
+}
+
+/// Check that `version` is a plain `x.y.z` version (optional `-pre` of letters,
+/// digits, dots and dashes), safe to put into release URLs and file names
+#[cfg_attr(not(any(target_os = "linux", windows)), allow(dead_code))]
+pub fn check_version(version: &str) -> Result<(), String> {
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+    let core_ok = core.split('.').count() == 3
+        && core.split('.').all(|n| !n.is_empty() && n.len() <= 6 && n.bytes().all(|b| b.is_ascii_digit()));
+    let pre_ok = pre.map_or(true, |p| {
+        !p.is_empty() && p.len() <= 32 && p.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'.' || b == b'-')
+    });
+    if core_ok && pre_ok {
+        Ok(())
+    } else {
+        Err(format!("Invalid version '{}'", version))
+    }
+}
+
+/// Newest GitHub release on `channel` if it is newer than this build
+///
+/// Linux packages and the Windows portable zip have no Tauri updater entry, so
+/// their updaters check the releases directly.
+#[cfg_attr(not(any(target_os = "linux", windows)), allow(dead_code))]
+pub fn latest_release(channel: UpdateChannel) -> Result<Option<String>, String> {
+    let releases: Vec<serde_json::Value> = download::agent_for(RELEASES_API)?
+        .get(RELEASES_API)
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .map_err(|e| format!("Failed to check for updates: {}", e))?
+        .into_json()
+        .map_err(|e| format!("Failed to read release list: {}", e))?;
+
+    // Releases are listed newest first
+    let latest = releases.iter()
+        .filter(|r| !r["draft"].as_bool().unwrap_or(false))
+        .filter(|r| channel.includes_prereleases() || !r["prerelease"].as_bool().unwrap_or(false))
+        .filter_map(|r| r["tag_name"].as_str())
+        .map(|tag| tag.trim_start_matches('v'))
+        .next();
+
+    Ok(latest
+        .filter(|version| is_newer(version, env!("CARGO_PKG_VERSION")))
+        .map(str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("0.1.24", "0.1.23"));
+        assert!(is_newer("v0.2.0", "0.1.23"));
+        assert!(!is_newer("0.1.23", "0.1.23"));
+        assert!(check_version("0.1.24").is_ok() && check_version("0.2.0-beta.1").is_ok());
+        assert!(check_version("0.1.24/../../x").is_err() && check_version("0.1").is_err() && check_version("1.0.0-").is_err());
+        assert!(!is_newer("0.1.9", "0.1.23"));
+        assert!(is_newer("0.1.24-beta.2", "0.1.24-beta.1"));
+        assert!(is_newer("0.1.24", "0.1.24-beta.2"));
+        assert!(!is_newer("0.1.24-beta.1", "0.1.24"));
+    }
+
+    #[test]
+    fn test_channel_json() {
+        assert_eq!(serde_json::to_string(&UpdateChannel::Ptu).unwrap(), "\"ptu\"");
+        assert!(!UpdateChannel::default().includes_prereleases());
+    }
+}
@@ -0,0 +1,218 @@
+//! Ship Lens Combat Log Parser
+//!
+//! Reads Star Citizen's `Game.log` for real-world TTK validation. Three kinds of
+//! line are used:
+//! - `<Debug Hostility Events>` hits (`Fake hit FROM <attacker> TO <target>`)
+//! - `<Vehicle Destruction>` level changes (1 = disabled/soft death, 2 = destroyed)
+//! - `<Actor Death>` kills, which name the weapon and damage type
+//!
+//! A vehicle's observed TTK runs from the first hit on it to its destruction.
+//! Vehicle entity names (`ANVL_Hornet_F7C_1234567`) are matched to ships by
+//! class name so the kills can be lined up against predictions.
+
+use crate::data::GameData;
+use crate::store;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// What a log line recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CombatEventKind {
+    Hit,
+    /// Destroy level 1 (soft death)
+    Disabled,
+    /// Destroy level 2
+    Destroyed,
+    ActorDeath,
+}
+
+/// One combat line from the log
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CombatEvent {
+    pub timestamp: String,  // As logged (ISO 8601)
+    pub kind: CombatEventKind,
+    /// Entity hit, disabled or killed
+    pub target: String,
+    pub attacker: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weapon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub damage_type: Option<String>,
+    #[serde(skip)]
+    seconds: f64,
+}
+
+/// A vehicle destroyed in the log, with its observed time to kill
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ObservedKill {
+    /// Vehicle entity name
+    pub target: String,
+    /// Ship display name, when the vehicle class is in the data
+    pub ship: Option<String>,
+    pub attacker: String,
+    pub first_hit: Option<String>,
+    pub destroyed_at: String,
+    pub hits: usize,
+    /// Seconds from the first hit to the soft death
+    pub disable_time: Option<f64>,
+    /// Seconds from the first hit to destruction (None without a logged hit)
+    pub ttk: Option<f64>,
+}
+
+/// Everything `parse_combat_log` found
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CombatLog {
+    pub lines: usize,
+    pub events: Vec<CombatEvent>,
+    pub kills: Vec<ObservedKill>,
+}
+
+/// Text between the quotes following `marker`
+fn quoted_after<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = &line[line.find(marker)? + marker.len()..];
+    let rest = &rest[rest.find('\'')? + 1..];
+    Some(&rest[..rest.find('\'')?])
+}
+
+/// Token following `marker` (quoted or up to whitespace/period)
+fn word_after<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = line[line.find(marker)? + marker.len()..].trim_start();
+    let word = match rest.strip_prefix('\'') {
+        Some(quoted) => &quoted[..quoted.find('\'')?],
+        None => rest.split(|c: char| c.is_whitespace()).next()?.trim_end_matches('.'),
+    };
+    (!word.is_empty()).then_some(word)
+}
+
+/// Parse one log line into a combat event
+fn parse_line(line: &str) -> Option<CombatEvent> {
+    let timestamp = line.strip_prefix('<')?.split_once('>')?.0;
+    let seconds = store::parse_iso8601(timestamp)?;
+    let event = |kind, target: &str, attacker: &str| CombatEvent {
+        timestamp: timestamp.to_string(),
+        kind,
+        target: target.to_string(),
+        attacker: attacker.to_string(),
+        weapon: None,
+        damage_type: None,
+        seconds,
+    };
+
+    if line.contains("<Debug Hostility Events>") && line.contains("hit FROM") {
+        Some(event(CombatEventKind::Hit, word_after(line, " TO ")?, word_after(line, "hit FROM ")?))
+    } else if line.contains("<Vehicle Destruction>") {
+        let (_, to) = line.split("destroy level ").nth(1)?.split_once(" to ")?;
+        let level = to.chars().next()?.to_digit(10)?;
+        let kind = match level {
+            1 => CombatEventKind::Disabled,
+            2 => CombatEventKind::Destroyed,
+            _ => return None,
+        };
+        Some(event(kind, quoted_after(line, ": Vehicle ")?, quoted_after(line, "caused by ")?))
+    } else if line.contains("<Actor Death>") {
+        let mut death = event(CombatEventKind::ActorDeath, quoted_after(line, "Kill: ")?, quoted_after(line, "killed by ")?);
+        death.weapon = quoted_after(line, "using ").map(str::to_string);
+        death.damage_type = quoted_after(line, "damage type ").map(str::to_string);
+        Some(death)
+    } else {
+        None
+    }
+}
+
+/// Vehicle class of an entity name (`ANVL_Hornet_F7C_1234567` -> `ANVL_Hornet_F7C`)
+fn vehicle_class(entity: &str) -> &str {
+    match entity.rsplit_once('_') {
+        Some((class, id)) if !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()) => class,
+        _ => entity,
+    }
+}
+
+/// Parse `Game.log` text
+pub fn parse(data: &GameData, text: &str) -> CombatLog {
+    let mut log = CombatLog::default();
+    // Per target: (first hit index, hit count, soft death time)
+    let mut pending: HashMap<String, (usize, usize, Option<f64>)> = HashMap::new();
+
+    for line in text.lines() {
+        log.lines += 1;
+        let Some(event) = parse_line(line) else { continue };
+
+        match event.kind {
+            CombatEventKind::Hit => {
+                pending.entry(event.target.clone()).or_insert((log.events.len(), 0, None)).1 += 1;
+            }
+            CombatEventKind::Disabled => {
+                if let Some(entry) = pending.get_mut(&event.target) {
+                    entry.2.get_or_insert(event.seconds);
+                }
+            }
+            CombatEventKind::Destroyed => {
+                let (first_hit, hits, disabled_at) = pending.remove(&event.target)
+                    .map_or((None, 0, None), |(i, hits, disabled)| (Some(&log.events[i]), hits, disabled));
+                let class = vehicle_class(&event.target);
+                log.kills.push(ObservedKill {
+                    target: event.target.clone(),
+                    ship: data.ships.values()
+                        .find(|s| s.filename.eq_ignore_ascii_case(class))
+                        .map(|s| s.display_name.clone()),
+                    attacker: event.attacker.clone(),
+                    first_hit: first_hit.map(|e| e.timestamp.clone()),
+                    destroyed_at: event.timestamp.clone(),
+                    hits,
+                    disable_time: first_hit.zip(disabled_at).map(|(e, t)| t - e.seconds),
+                    ttk: first_hit.map(|e| event.seconds - e.seconds),
+                });
+            }
+            CombatEventKind::ActorDeath => {}
+        }
+        log.events.push(event);
+    }
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Ship;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_parse_combat_log() {
+        let mut data = GameData::default();
+        data.ships.insert("Anvil Hornet F7C".to_string(), Arc::new(Ship {
+            filename: "anvl_hornet_f7c".to_string(),
+            display_name: "Anvil Hornet F7C".to_string(),
+            ..Default::default()
+        }));
+
+        let text = "\
+<2025-12-31T20:31:40.000Z> [Notice] <Debug Hostility Events> [OnHandleHit] Fake hit FROM PlayerOne TO ANVL_Hornet_F7C_2040115. Being sent to child PilotTwo [Team_ActorTech][HitInfo]
+<2025-12-31T20:31:42.500Z> [Notice] <Debug Hostility Events> [OnHandleHit] Fake hit FROM PlayerOne TO ANVL_Hornet_F7C_2040115. Being sent to child PilotTwo [Team_ActorTech][HitInfo]
+<2025-12-31T20:31:47.000Z> [Notice] <Vehicle Destruction> CVehicle::OnAdvanceDestroyLevel: Vehicle 'ANVL_Hornet_F7C_2040115' [2040115] in zone 'OOC_Stanton_2b' [pos x: 1.0, y: 2.0, z: 3.0] driven by 'PilotTwo' [201] advanced from destroy level 0 to 1 caused by 'PlayerOne' [200] with 'Combat' [Team_VehicleFeatures][Vehicle]
+<2025-12-31T20:31:52.250Z> [Notice] <Vehicle Destruction> CVehicle::OnAdvanceDestroyLevel: Vehicle 'ANVL_Hornet_F7C_2040115' [2040115] in zone 'OOC_Stanton_2b' [pos x: 1.0, y: 2.0, z: 3.0] driven by 'PilotTwo' [201] advanced from destroy level 1 to 2 caused by 'PlayerOne' [200] with 'Combat' [Team_VehicleFeatures][Vehicle]
+<2025-12-31T20:31:52.300Z> [Notice] <Actor Death> CActor::Kill: 'PilotTwo' [201] in zone 'ANVL_Hornet_F7C_2040115' killed by 'PlayerOne' [200] using 'KLWE_LaserRepeater_S3_777' [Class KLWE_LaserRepeater_S3] with damage type 'VehicleDestruction' from direction x: 0, y: 0, z: 0 [Team_ActorTech][Actor]
+<2025-12-31T20:32:00.000Z> [Notice] <Vehicle Destruction> CVehicle::OnAdvanceDestroyLevel: Vehicle 'AEGS_Gladius_99' [99] in zone 'space' driven by 'unknown' [0] advanced from destroy level 0 to 2 caused by 'PlayerOne' [200] with 'Combat'
+<2025-12-31T20:32:01.000Z> [Notice] <Context Establisher Done> establisher=\"CReplicationModel\" runningTime=0.5
+";
+        let log = parse(&data, text);
+        assert_eq!(log.lines, 7);
+        assert_eq!(log.events.len(), 6);
+
+        let hornet = &log.kills[0];
+        assert_eq!(hornet.ship.as_deref(), Some("Anvil Hornet F7C"));
+        assert_eq!(hornet.attacker, "PlayerOne");
+        assert_eq!(hornet.hits, 2);
+        assert_eq!(hornet.disable_time, Some(7.0));
+        assert_eq!(hornet.ttk, Some(12.25));
+
+        // Destroyed without a logged hit: no TTK
+        assert_eq!(log.kills[1].ship, None);
+        assert_eq!(log.kills[1].ttk, None);
+
+        let death = &log.events[4];
+        assert_eq!(death.kind, CombatEventKind::ActorDeath);
+        assert_eq!(death.weapon.as_deref(), Some("KLWE_LaserRepeater_S3_777"));
+        assert_eq!(death.damage_type.as_deref(), Some("VehicleDestruction"));
+    }
+}
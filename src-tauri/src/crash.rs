@@ -0,0 +1,147 @@
+//! Ship Lens Crash Reports
+//!
+//! A panic hook writes `crash_report.json` to the app config dir with the panic
+//! message, where it happened, a backtrace, the loaded data and the last command
+//! the frontend invoked. The report survives the restart, so an intermittent
+//! calculation failure can be picked up with `get_last_crash_report` next launch.
+
+use crate::locks::MutexExt;
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const CRASH_FILE: &str = "crash_report.json";
+
+/// What the app was doing, kept up to date for the panic hook
+struct CrashContext {
+    last_command: Option<String>,
+    data_version: Option<String>,
+}
+
+static CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext { last_command: None, data_version: None });
+
+/// Remember the command the frontend just invoked
+pub fn record_command(command: &str) {
+    CONTEXT.lock_or_recover().last_command = Some(command.to_string());
+}
+
+/// Remember which data is loaded
+pub fn record_data(version: String) {
+    CONTEXT.lock_or_recover().data_version = Some(version);
+}
+
+/// One panic, as saved to disk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub timestamp: String,  // ISO 8601
+    pub app_version: String,
+    pub message: String,
+    /// `file:line:column` of the panic
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: String,
+    /// Loaded data when the panic happened (None while still loading)
+    pub data_version: Option<String>,
+    pub last_command: Option<String>,
+}
+
+impl CrashReport {
+    fn new(message: String, location: Option<String>, backtrace: String) -> Self {
+        let context = CONTEXT.lock_or_recover();
+        Self {
+            timestamp: store::now_iso8601(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            message,
+            location,
+            thread: std::thread::current().name().map(str::to_string),
+            backtrace,
+            data_version: context.data_version.clone(),
+            last_command: context.last_command.clone(),
+        }
+    }
+}
+
+/// Text of a panic payload (`panic!` gives a `&str` or a `String`)
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Unknown panic".to_string())
+}
+
+/// Save every panic as the crash report in `config_dir`, then run the default hook
+pub fn install(config_dir: PathBuf) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let report = CrashReport::new(
+            panic_message(info.payload()),
+            info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            std::backtrace::Backtrace::force_capture().to_string(),
+        );
+        log::error!("Panic: {} at {}", report.message, report.location.as_deref().unwrap_or("unknown location"));
+        if let Err(e) = save(&config_dir, &report) {
+            log::error!("Could not save crash report: {}", e);
+        }
+        default_hook(info);
+    }));
+}
+
+/// The last saved crash report (None if the app never crashed or it was cleared)
+///
+/// A single plain file rather than a `store` file with backups, so clearing it
+/// can't bring an older report back.
+pub fn load(config_dir: &Path) -> Result<Option<CrashReport>, String> {
+    let path = config_dir.join(CRASH_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", CRASH_FILE, e))?;
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", CRASH_FILE, e))
+}
+
+fn save(config_dir: &Path, report: &CrashReport) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(report)
+        .map_err(|e| format!("Failed to serialize {}: {}", CRASH_FILE, e))?;
+    store::replace(&config_dir.join(CRASH_FILE), json)
+        .map_err(|e| format!("Failed to write {}: {}", CRASH_FILE, e))
+}
+
+/// Forget the saved crash report
+pub fn clear(config_dir: &Path) -> Result<(), String> {
+    let path = config_dir.join(CRASH_FILE);
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", CRASH_FILE, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crash_report_round_trip() {
+        let payload: Box<dyn Any + Send> = Box::new(format!("index {} out of range", 3));
+        assert_eq!(panic_message(payload.as_ref()), "index 3 out of range");
+        assert_eq!(panic_message(&"static message"), "static message");
+        assert_eq!(panic_message(&42), "Unknown panic");
+
+        record_data("test data".to_string());
+        record_command("calculate_ttk_v2");
+        let report = CrashReport::new("boom".to_string(), Some("src/ttk.rs:1:1".to_string()), String::new());
+        assert_eq!(report.last_command.as_deref(), Some("calculate_ttk_v2"));
+        assert_eq!(report.data_version.as_deref(), Some("test data"));
+
+        let dir = std::env::temp_dir().join(format!("ship-lens-crash-{}", std::process::id()));
+        assert_eq!(load(&dir).unwrap(), None);
+        save(&dir, &report).unwrap();
+        assert_eq!(load(&dir).unwrap(), Some(report));
+        clear(&dir).unwrap();
+        assert_eq!(load(&dir).unwrap(), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
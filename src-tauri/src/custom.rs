@@ -0,0 +1,181 @@
+//! Ship Lens Custom Definitions
+//!
+//! Homebrew ships and weapons (concept ships, hypothetical weapons) stored as
+//! JSON in the app config dir and merged into `GameData` after each load.
+//! A custom entry with the same key as a game entry replaces it.
+
+use crate::data::{GameData, LoadReport, Ship, Weapon};
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const CUSTOM_FILE: &str = "custom_definitions.json";
+
+/// Largest hardpoint/component size accepted for custom entries
+const MAX_SIZE: i32 = 12;
+
+/// All custom definitions saved by the user
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomDefinitions {
+    #[serde(default)]
+    pub ships: Vec<Ship>,
+    #[serde(default)]
+    pub weapons: Vec<Weapon>,
+}
+
+/// Load custom definitions from the config dir (empty if none saved)
+///
+/// A corrupt file is recovered from its newest good backup (see `store`).
+pub fn load(config_dir: &Path) -> Result<CustomDefinitions, String> {
+    Ok(store::read_json(&config_dir.join(CUSTOM_FILE))?.unwrap_or_default())
+}
+
+/// Save custom definitions to the config dir (atomically, keeping backups)
+pub fn save(config_dir: &Path, defs: &CustomDefinitions) -> Result<(), String> {
+    store::write_json(&config_dir.join(CUSTOM_FILE), defs)
+}
+
+fn check_finite(name: &str, value: f64) -> Result<(), String> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(format!("{} must be a non-negative number (got {})", name, value))
+    }
+}
+
+fn check_size(name: &str, size: i32) -> Result<(), String> {
+    if (0..=MAX_SIZE).contains(&size) {
+        Ok(())
+    } else {
+        Err(format!("{} must be between 0 and {} (got {})", name, MAX_SIZE, size))
+    }
+}
+
+/// Validate a custom ship definition
+pub fn validate_ship(ship: &Ship) -> Result<(), String> {
+    if ship.display_name.trim().is_empty() {
+        return Err("Ship display_name is required".to_string());
+    }
+    if ship.filename.trim().is_empty() {
+        return Err("Ship filename is required".to_string());
+    }
+    if !(ship.hull_hp.is_finite() && ship.hull_hp > 0.0) {
+        return Err(format!("hull_hp must be greater than 0 (got {})", ship.hull_hp));
+    }
+
+    for (name, value) in [
+        ("armor_hp", ship.armor_hp),
+        ("armor_damage_mult_physical", ship.armor_damage_mult_physical),
+        ("armor_damage_mult_energy", ship.armor_damage_mult_energy),
+        ("armor_damage_mult_distortion", ship.armor_damage_mult_distortion),
+        ("armor_resist_physical", ship.armor_resist_physical),
+        ("armor_resist_energy", ship.armor_resist_energy),
+        ("armor_resist_distortion", ship.armor_resist_distortion),
+    ] {
+        check_finite(name, value)?;
+    }
+
+    if ship.shield_count < 0 {
+        return Err(format!("shield_count must not be negative (got {})", ship.shield_count));
+    }
+    check_size("max_shield_size", ship.max_shield_size)?;
+
+    for hardpoint in &ship.weapon_hardpoints {
+        check_size("Hardpoint max_size", hardpoint.max_size)?;
+    }
+    Ok(())
+}
+
+/// Validate a custom weapon definition
+pub fn validate_weapon(weapon: &Weapon) -> Result<(), String> {
+    if weapon.display_name.trim().is_empty() {
+        return Err("Weapon display_name is required".to_string());
+    }
+    if weapon.filename.trim().is_empty() {
+        return Err("Weapon filename is required".to_string());
+    }
+    if !(1..=MAX_SIZE).contains(&weapon.size) {
+        return Err(format!("Weapon size must be between 1 and {} (got {})", MAX_SIZE, weapon.size));
+    }
+
+    for (name, value) in [
+        ("sustained_dps", weapon.sustained_dps),
+        ("power_consumption", weapon.power_consumption),
+        ("damage_physical", weapon.damage_physical),
+        ("damage_energy", weapon.damage_energy),
+        ("damage_distortion", weapon.damage_distortion),
+        ("fire_rate", weapon.fire_rate),
+        ("damage_per_shot", weapon.damage_per_shot),
+        ("speed", weapon.speed),
+        ("max_range", weapon.max_range),
+        ("heat_per_shot", weapon.heat_per_shot),
+    ] {
+        check_finite(name, value)?;
+    }
+
+    // Per-shot damage types drive the DPS split; all zero would deal no damage
+    if weapon.damage_physical + weapon.damage_energy + weapon.damage_distortion <= 0.0 {
+        return Err("Weapon needs at least one non-zero damage type".to_string());
+    }
+    Ok(())
+}
+
+/// Merge custom definitions into loaded game data, skipping (and reporting) invalid entries
+pub fn merge_into(data: &mut GameData, defs: &CustomDefinitions, report: &mut LoadReport) {
+    for weapon in &defs.weapons {
+        match validate_weapon(weapon) {
+            Ok(()) => data.insert_weapon(weapon.clone()),
+            Err(e) => report.warn(CUSTOM_FILE, format!("Skipped weapon '{}': {}", weapon.display_name, e)),
+        }
+    }
+    for ship in &defs.ships {
+        match validate_ship(ship) {
+            Ok(()) => data.insert_ship(ship.clone(), report),
+            Err(e) => report.warn(CUSTOM_FILE, format!("Skipped ship '{}': {}", ship.display_name, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_test_ship() -> Ship {
+        Ship {
+            filename: "homebrew_corvette".to_string(),
+            display_name: "Homebrew Corvette".to_string(),
+            hull_hp: 50000.0,
+            armor_hp: 20000.0,
+            armor_damage_mult_physical: 0.75,
+            armor_damage_mult_energy: 0.6,
+            armor_damage_mult_distortion: 1.0,
+            armor_resist_physical: 0.85,
+            armor_resist_energy: 1.3,
+            armor_resist_distortion: 1.0,
+            max_shield_size: 3,
+            shield_count: 4,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_ship() {
+        assert!(validate_ship(&make_test_ship()).is_ok());
+
+        let mut no_hull = make_test_ship();
+        no_hull.hull_hp = 0.0;
+        assert!(validate_ship(&no_hull).is_err());
+
+        let mut bad_resist = make_test_ship();
+        bad_resist.armor_resist_energy = f64::NAN;
+        assert!(validate_ship(&bad_resist).is_err());
+
+        let mut unnamed = make_test_ship();
+        unnamed.display_name = "  ".to_string();
+        assert!(validate_ship(&unnamed).is_err());
+
+        let mut oversized = make_test_ship();
+        oversized.max_shield_size = MAX_SIZE + 1;
+        assert!(validate_ship(&oversized).is_err());
+    }
+}
@@ -2,9 +2,28 @@
 //!
 //! Contains all the data models for Star Citizen ships, weapons, and shields.
 
+use crate::cache;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+pub mod components;
+pub mod hitbox_zones;
+pub mod json_loader;
+pub mod localization;
+pub mod manifest;
+pub mod manufacturers;
+pub mod mounts;
+pub mod prices;
+pub mod ship_metadata;
+pub mod ship_parts;
+pub mod p4k;
+pub use components::{Cooler, PowerPlant};
+pub use hitbox_zones::HitboxZone;
+pub use json_loader::{detect_format, DataFormat};
+use localization::Localization;
 
 /// Individual weapon sub-port within a hardpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +33,58 @@ pub struct SubPort {
     pub default_weapon: Option<String>,
 }
 
+/// Rack size, missile count and missile size from a rack class name (0 = not named)
+///
+/// `mrck_s04_behr_quad_s02` -> (4, 4, 2); `mrck_s05_rsi_constellation` -> (5, 0, 0)
+fn rack_layout(rack_name: &str) -> (i32, i32, i32) {
+    let name = rack_name.to_lowercase();
+    let parts: Vec<&str> = name.split('_').collect();
+    let sizes: Vec<i32> = parts.iter()
+        .filter_map(|part| part.strip_prefix('s').and_then(|n| n.parse().ok()))
+        .collect();
+    let count = parts.iter()
+        .find_map(|part| match *part {
+            "single" => Some(1),
+            "dual" | "twin" => Some(2),
+            "tri" | "triple" => Some(3),
+            "quad" => Some(4),
+            _ => None,
+        })
+        .unwrap_or(0);
+    match sizes.as_slice() {
+        [] => (0, count, 0),
+        [rack] => (*rack, count, 0),
+        [rack, missile, ..] => (*rack, count, *missile),
+    }
+}
+
+/// Rack summary of an ordnance hardpoint
+///
+/// Exports list a rack's identical missiles once, so the count is the larger of
+/// the sub-ports and the rack name; unknown (0) sizes come from the rack name or
+/// the loaded missile.
+fn ordnance_hardpoint(hp: &WeaponHardpoint, missile_sizes: &HashMap<String, i32>) -> OrdnanceHardpoint {
+    let (named_rack, named_count, named_missile) = rack_layout(&hp.mount_name);
+    let default_missile = hp.sub_ports.iter()
+        .filter_map(|sp| sp.default_weapon.as_ref())
+        .find(|m| !m.is_empty() && m.as_str() != "empty")
+        .cloned();
+    let loaded_size = default_missile.as_ref()
+        .and_then(|m| missile_sizes.get(&m.to_lowercase()).copied())
+        .unwrap_or(0);
+    let port_size = hp.sub_ports.iter().map(|sp| sp.size).max().unwrap_or(0);
+    let missile_size = [port_size, named_missile, loaded_size].into_iter().find(|&s| s > 0).unwrap_or(0);
+    OrdnanceHardpoint {
+        port_name: hp.port_name.clone(),
+        category: hp.category.clone(),
+        rack_name: hp.mount_name.clone(),
+        rack_size: [hp.max_size, named_rack, missile_size].into_iter().find(|&s| s > 0).unwrap_or(0),
+        missile_size,
+        missile_count: (hp.sub_ports.len() as i32).max(named_count),
+        default_missile,
+    }
+}
+
 /// Weapon hardpoint with category information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeaponHardpoint {
@@ -32,9 +103,25 @@ pub struct WeaponHardpoint {
     pub sub_ports: Vec<SubPort>,  // individual weapon ports with size and default weapon
 }
 
+/// Missile, torpedo or bomb rack (derived from the ordnance weapon hardpoints at load)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrdnanceHardpoint {
+    pub port_name: String,
+    pub category: String,  // "missile", "torpedo", "bomb"
+    #[serde(default)]
+    pub rack_name: String,  // e.g. "mrck_s03_behr_dual_s02"
+    pub rack_size: i32,
+    pub missile_size: i32,
+    pub missile_count: i32,
+    #[serde(default)]
+    pub default_missile: Option<String>,
+}
+
 /// Ship data with survivability and loadout information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Ship {
+    #[serde(default)]
+    pub id: u32,  // Stable ID assigned at load (see `GameData::assign_ids`)
     pub filename: String,
     pub display_name: String,
     pub hull_hp: f64,
@@ -58,17 +145,90 @@ pub struct Ship {
     pub cooler_total_hp: i32,
     pub shield_gen_total_hp: i32,
     pub qd_total_hp: i32,
+    // Component instance counts (0 = unknown, totals treated as a single pool)
+    #[serde(default)]
+    pub thruster_count: i32,
+    #[serde(default)]
+    pub turret_count: i32,
+    #[serde(default)]
+    pub powerplant_count: i32,
+    #[serde(default)]
+    pub cooler_count: i32,
+    #[serde(default)]
+    pub shield_gen_count: i32,
+    #[serde(default)]
+    pub qd_count: i32,
+    // Per-instance HP (total / count), derived at load time
+    #[serde(default)]
+    pub thruster_instance_hp: f64,
+    #[serde(default)]
+    pub turret_instance_hp: f64,
+    #[serde(default)]
+    pub powerplant_instance_hp: f64,
+    #[serde(default)]
+    pub cooler_instance_hp: f64,
+    #[serde(default)]
+    pub shield_gen_instance_hp: f64,
+    #[serde(default)]
+    pub qd_instance_hp: f64,
     pub pilot_weapon_count: i32,
     pub pilot_weapon_sizes: String,
     pub max_shield_size: i32,
     pub shield_count: i32,
     pub default_shield_ref: String,
+    // Resolved shield key (internal name), validated at load time
+    #[serde(default)]
+    pub default_shield: Option<String>,
+    // Stock power plant and cooler class names (empty = unknown, see `components`)
+    #[serde(default)]
+    pub default_power_plant_ref: String,
+    #[serde(default)]
+    pub default_cooler_ref: String,
+    // Maker, matched from the filename prefix at load time
+    #[serde(default)]
+    pub manufacturer: String,
+    #[serde(default)]
+    pub manufacturer_code: String,
+    // Class metadata from ship_metadata.csv (empty/0 = unknown)
+    #[serde(default)]
+    pub size_class: String,  // "small", "medium", "large", "capital"
+    #[serde(default)]
+    pub role: String,        // e.g. "fighter", "gunship", "hauler"
+    #[serde(default)]
+    pub crew: i32,
+    #[serde(default)]
+    pub hitbox_length: f64,  // Longest hull dimension in meters (0 = unknown)
+    #[serde(default)]
+    pub quantum_spool_time: f64,  // Quantum drive spool-up in seconds (0 = unknown)
+    #[serde(default)]
+    pub sensor_range: f64,  // Radar range in meters (0 = unknown)
+    // Flight data (0 = unknown, see `ttk::evasion_factor`)
+    #[serde(default)]
+    pub mass: f64,         // kg
+    #[serde(default)]
+    pub main_thrust: f64,  // Main thruster force in newtons
+    #[serde(default)]
+    pub pitch_rate: f64,   // Degrees per second
+    #[serde(default)]
+    pub yaw_rate: f64,     // Degrees per second
+    // Critical part data from ship_parts_comprehensive.csv (0 = unknown)
+    #[serde(default)]
+    pub fuse_penetration_mult: f64,       // Damage multiplier toward the fuse (critical) parts
+    #[serde(default)]
+    pub critical_explosion_chance: f64,   // Chance a destroyed critical part detonates the ship
+    // Per-zone pool shares from hitbox_zones.csv (empty = generic zone presets)
+    #[serde(default)]
+    pub hitbox_zones: Vec<HitboxZone>,
     pub weapon_hardpoints: Vec<WeaponHardpoint>,
+    #[serde(default)]
+    pub ordnance_hardpoints: Vec<OrdnanceHardpoint>,
 }
 
 /// Weapon data with damage output and penetration info
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Weapon {
+    #[serde(default)]
+    pub id: u32,  // Stable ID assigned at load (see `GameData::assign_ids`)
     pub display_name: String,
     pub filename: String,
     pub size: i32,
@@ -80,6 +240,10 @@ pub struct Weapon {
     pub restricted_to: Vec<String>,  // Manufacturer restrictions (e.g., ["VNCL", "BANU"])
     #[serde(default)]
     pub ship_exclusive: bool,  // True if weapon can only be equipped on specific ships (not swappable)
+    #[serde(default)]
+    pub manufacturer: String,
+    #[serde(default)]
+    pub manufacturer_code: String,  // e.g. "KLWE", matched from the filename prefix
     // 4.5 damage breakdown by type
     pub damage_physical: f64,
     pub damage_energy: f64,
@@ -88,11 +252,37 @@ pub struct Weapon {
     pub base_penetration_distance: f64,
     pub near_radius: f64,
     pub far_radius: f64,
+    // Ballistics
+    #[serde(default)]
+    pub fire_rate: f64,        // Rounds per minute
+    #[serde(default)]
+    pub damage_per_shot: f64,  // Alpha damage of a single shot
+    #[serde(default)]
+    pub speed: f64,            // Projectile speed (m/s)
+    #[serde(default)]
+    pub max_range: f64,        // Maximum effective range (m)
+    #[serde(default)]
+    pub heat_per_shot: f64,    // Heat generated per shot (0 = unknown)
+}
+
+impl Weapon {
+    /// Sustained DPS per unit of power draw (None when power draw is unknown)
+    pub fn dps_per_power(&self) -> Option<f64> {
+        (self.power_consumption > 0.0).then(|| self.sustained_dps / self.power_consumption)
+    }
+
+    /// Sustained DPS per unit of heat generated per second (None when heat is unknown)
+    pub fn dps_per_heat(&self) -> Option<f64> {
+        let heat_per_second = self.heat_per_shot * self.fire_rate / 60.0;
+        (heat_per_second > 0.0).then(|| self.sustained_dps / heat_per_second)
+    }
 }
 
 /// Missile/Torpedo/Bomb data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Missile {
+    #[serde(default)]
+    pub id: u32,  // Stable ID assigned at load (see `GameData::assign_ids`)
     pub name: String,
     pub display_name: String,
     pub size: i32,
@@ -111,6 +301,8 @@ pub struct Missile {
 /// Shield data with defense and absorption values
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Shield {
+    #[serde(default)]
+    pub id: u32,  // Stable ID assigned at load (see `GameData::assign_ids`)
     pub display_name: String,
     pub internal_name: String,
     pub size: i32,
@@ -136,8 +328,18 @@ pub struct Shield {
 }
 
 /// Weapon mount data (gimbals, fixed mounts, turrets)
+/// Ship/weapon maker (see `manufacturers`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manufacturer {
+    pub code: String,       // Class name prefix, e.g. "AEGS"
+    pub name: String,       // e.g. "Aegis Dynamics"
+    pub logo_key: String,   // Frontend logo asset key, e.g. "aegs"
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Mount {
+    #[serde(default)]
+    pub id: u32,  // Stable ID assigned at load (see `GameData::assign_ids`)
     #[serde(rename = "ref")]
     pub mount_ref: String,
     pub display_name: String,
@@ -169,31 +371,628 @@ pub struct CombatScenario {
     pub accuracy_modifier: f64,
 }
 
+/// HP of a single component instance (whole pool when the count is unknown)
+pub fn instance_hp(total_hp: i32, count: i32) -> f64 {
+    if count > 0 {
+        total_hp as f64 / count as f64
+    } else {
+        total_hp as f64
+    }
+}
+
+impl Ship {
+    /// Recompute the `*_instance_hp` fields from the component totals and counts
+    pub fn derive_instance_hp(&mut self) {
+        self.thruster_instance_hp = instance_hp(self.thruster_total_hp, self.thruster_count);
+        self.turret_instance_hp = instance_hp(self.turret_total_hp, self.turret_count);
+        self.powerplant_instance_hp = instance_hp(self.powerplant_total_hp, self.powerplant_count);
+        self.cooler_instance_hp = instance_hp(self.cooler_total_hp, self.cooler_count);
+        self.shield_gen_instance_hp = instance_hp(self.shield_gen_total_hp, self.shield_gen_count);
+        self.qd_instance_hp = instance_hp(self.qd_total_hp, self.qd_count);
+    }
+}
+
+/// Manufacturer filter match: exact code or name substring, case-insensitive
+pub fn manufacturer_matches(filter: &str, code: &str, name: &str) -> bool {
+    code.eq_ignore_ascii_case(filter) || name.to_lowercase().contains(&filter.to_lowercase())
+}
+
+/// Filter for `query_weapons` (all fields optional)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeaponFilter {
+    pub size: Option<i32>,
+    pub damage_type: Option<String>,   // "Ballistic", "Energy", "Distortion" (case-insensitive)
+    pub manufacturer: Option<String>,  // Code (exact) or name (substring), case-insensitive
+    pub min_dps: Option<f64>,
+    pub max_dps: Option<f64>,
+}
+
+impl WeaponFilter {
+    pub fn matches(&self, weapon: &Weapon) -> bool {
+        self.size.map_or(true, |size| weapon.size == size)
+            && self.damage_type.as_ref()
+                .map_or(true, |t| weapon.damage_type.eq_ignore_ascii_case(t))
+            && self.manufacturer.as_ref()
+                .map_or(true, |m| manufacturer_matches(m, &weapon.manufacturer_code, &weapon.manufacturer))
+            && self.min_dps.map_or(true, |min| weapon.sustained_dps >= min)
+            && self.max_dps.map_or(true, |max| weapon.sustained_dps <= max)
+    }
+}
+
+/// Filter for `query_ships` (all fields optional; ships without metadata fail metadata filters)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShipFilter {
+    pub size_class: Option<String>,    // case-insensitive
+    pub role: Option<String>,          // case-insensitive
+    pub manufacturer: Option<String>,  // Code (exact) or name (substring), case-insensitive
+    pub min_crew: Option<i32>,
+    pub max_crew: Option<i32>,
+}
+
+impl ShipFilter {
+    pub fn matches(&self, ship: &Ship) -> bool {
+        self.size_class.as_ref()
+            .map_or(true, |c| ship.size_class.eq_ignore_ascii_case(c))
+            && self.role.as_ref()
+                .map_or(true, |r| ship.role.eq_ignore_ascii_case(r))
+            && self.manufacturer.as_ref()
+                .map_or(true, |m| manufacturer_matches(m, &ship.manufacturer_code, &ship.manufacturer))
+            && self.min_crew.map_or(true, |min| ship.crew >= min)
+            && self.max_crew.map_or(true, |max| ship.crew > 0 && ship.crew <= max)
+    }
+}
+
+/// Ship list variant filter (persisted under `variant_filter` in the settings)
+///
+/// No patterns by default, so every loaded ship stays listed until the user
+/// adds some.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VariantFilter {
+    /// Show AI/derelict/event hulls instead of hiding them
+    pub include_npc_variants: bool,
+    /// Case-insensitive filename fragments that mark a hull as an NPC variant
+    pub patterns: Vec<String>,
+}
+
+impl VariantFilter {
+    /// True if the ship is an NPC variant by these patterns
+    pub fn is_npc_variant(&self, ship: &Ship) -> bool {
+        let filename = ship.filename.to_lowercase();
+        self.patterns.iter()
+            .filter(|p| !p.is_empty())
+            .any(|p| filename.contains(&p.to_lowercase()))
+    }
+
+    pub fn allows(&self, ship: &Ship) -> bool {
+        self.include_npc_variants || !self.is_npc_variant(ship)
+    }
+}
+
+/// Sort order for `query_weapons`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeaponSortBy {
+    #[default]
+    Name,
+    Size,
+    Dps,
+    AlphaDamage,
+    /// Best DPS per power first; unknown power draw last
+    DpsPerPower,
+    /// Best DPS per heat first; unknown heat last
+    DpsPerHeat,
+}
+
+/// A weapon query result with derived efficiency metrics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponQueryRow {
+    #[serde(flatten)]
+    pub weapon: Arc<Weapon>,
+    pub dps_per_power: Option<f64>,
+    pub dps_per_heat: Option<f64>,
+}
+
+impl WeaponQueryRow {
+    fn new(weapon: &Arc<Weapon>) -> Self {
+        Self {
+            weapon: weapon.clone(),
+            dps_per_power: weapon.dps_per_power(),
+            dps_per_heat: weapon.dps_per_heat(),
+        }
+    }
+}
+
+/// One page of weapon query results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponPage {
+    pub weapons: Vec<WeaponQueryRow>,
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+/// Progress of a data load, reported as each file set finishes parsing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadProgress {
+    pub stage: String,     // File set just finished ("ships", "weapons", ...) or "cache"
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// A single problem found while loading a data file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadIssue {
+    pub file: String,
+    pub message: String,
+}
+
+/// Per-file warnings and errors collected during a data load
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoadReport {
+    pub from_cache: bool,
+    pub errors: Vec<LoadIssue>,    // File sets that could not be loaded at all
+    pub warnings: Vec<LoadIssue>,  // Skipped entries, missing optional files, unresolved references
+    /// Check against `data_manifest.json` (None if the data dir has none)
+    pub manifest: Option<manifest::ManifestCheck>,
+}
+
+impl LoadReport {
+    pub fn error(&mut self, file: &str, message: String) {
+        log::error!("{}: {}", file, message);
+        self.errors.push(LoadIssue { file: file.to_string(), message });
+    }
+
+    pub fn warn(&mut self, file: &str, message: String) {
+        log::warn!("{}: {}", file, message);
+        self.warnings.push(LoadIssue { file: file.to_string(), message });
+    }
+
+    pub fn merge(&mut self, other: LoadReport) {
+        self.errors.extend(other.errors);
+        self.warnings.extend(other.warnings);
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// ID of an `#<id>` entity reference
+fn id_ref(name: &str) -> Option<u32> {
+    name.strip_prefix('#')?.parse().ok()
+}
+
+/// First ID above every one in use
+fn next_id<T>(entities: &HashMap<String, Arc<T>>, id: fn(&T) -> u32) -> u32 {
+    entities.values().map(|e| id(e)).max().unwrap_or(0) + 1
+}
+
+/// Display names after localization, one per `(class, current, localized)` entry
+///
+/// A localized name shared with any other entity becomes "Name (class)"; names
+/// that were not localized are kept as they are. Each clash is reported.
+fn localized_names(entries: &[(&str, &str, Option<&str>)], kind: &str, report: &mut LoadReport) -> Vec<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for &(_, current, localized) in entries {
+        *counts.entry(localized.unwrap_or(current)).or_default() += 1;
+    }
+    let mut clashes: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let names = entries.iter()
+        .map(|&(class, current, localized)| match localized {
+            Some(name) if counts[name] > 1 => {
+                clashes.entry(name).or_default().push(class);
+                format!("{} ({})", name, class)
+            }
+            Some(name) => name.to_string(),
+            None => current.to_string(),
+        })
+        .collect();
+    for (name, classes) in clashes {
+        report.warn("global.ini", format!("{} {} classes localize to '{}'; renamed to 'Name (class)': {}", counts[name], kind, name, classes.join(", ")));
+    }
+    names
+}
+
+/// Deterministic default shield lookup
+///
+/// Resolution order (independent of HashMap iteration order):
+/// 1. Exact case-insensitive internal name match
+/// 2. Internal names containing the reference (shortest, then alphabetical)
+/// 3. Shields of max_shield_size (alphabetical)
+struct DefaultShieldResolver<'a> {
+    names: Vec<&'a String>,
+    by_name: Vec<&'a Shield>,
+}
+
+impl<'a> DefaultShieldResolver<'a> {
+    fn new(shields: &'a HashMap<String, Arc<Shield>>) -> Self {
+        let mut names: Vec<&String> = shields.keys().collect();
+        names.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+
+        let mut by_name: Vec<&Shield> = shields.values().map(|s| s.as_ref()).collect();
+        by_name.sort_by(|a, b| a.internal_name.cmp(&b.internal_name));
+
+        Self { names, by_name }
+    }
+
+    fn resolve(&self, ship: &Ship, report: &mut LoadReport) -> Option<String> {
+        let default_ref = ship.default_shield_ref.to_lowercase();
+        let matched = if default_ref.is_empty() {
+            None
+        } else {
+            self.names.iter()
+                .find(|n| n.to_lowercase() == default_ref)
+                .or_else(|| self.names.iter().find(|n| n.to_lowercase().contains(&default_ref)))
+                .map(|n| n.to_string())
+        };
+
+        if matched.is_none() && !default_ref.is_empty() {
+            report.warn(&format!("ships/{}.json", ship.filename), format!(
+                "Unresolved default shield '{}' (falling back to size {})",
+                ship.default_shield_ref, ship.max_shield_size));
+        }
+
+        matched.or_else(|| {
+            self.by_name.iter()
+                .find(|s| s.size == ship.max_shield_size)
+                .map(|s| s.internal_name.clone())
+        })
+    }
+}
+
 /// The main data store for all game data
-#[derive(Debug, Clone, Default)]
+///
+/// Entities are `Arc`-shared so commands can hand them out without deep copies.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GameData {
-    pub ships: HashMap<String, Ship>,
-    pub weapons: HashMap<String, Weapon>,
-    pub shields: HashMap<String, Shield>,
-    pub missiles: HashMap<String, Missile>,
-    pub mounts: HashMap<String, Mount>,
+    pub ships: HashMap<String, Arc<Ship>>,
+    pub weapons: HashMap<String, Arc<Weapon>>,
+    pub shields: HashMap<String, Arc<Shield>>,
+    pub missiles: HashMap<String, Arc<Missile>>,
+    pub mounts: HashMap<String, Arc<Mount>>,
+    #[serde(default)]
+    pub manufacturers: HashMap<String, Arc<Manufacturer>>,
+    /// aUEC price/pledge availability by lowercase class name (see `prices`)
+    #[serde(default)]
+    pub prices: HashMap<String, prices::Price>,
+    #[serde(default)]
+    pub power_plants: HashMap<String, Arc<PowerPlant>>,
+    #[serde(default)]
+    pub coolers: HashMap<String, Arc<Cooler>>,
 }
 
 impl GameData {
     /// Load all game data from JSON files in the data directory
+    ///
+    /// Fails with the first error in loader order (ships first); use
+    /// `load_with_report` to keep whatever loaded and inspect every issue.
     pub fn load(data_dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let (data, report) = Self::load_with_report(data_dir, &|_| {});
+        match report.errors.first() {
+            Some(issue) => Err(format!("{}: {}", issue.file, issue.message).into()),
+            None => Ok(data),
+        }
+    }
+
+    /// Load all game data, collecting per-file problems instead of failing
+    ///
+    /// Each file set is parsed on its own thread into a partial GameData, then merged.
+    /// A file set that fails to load is recorded as an error and left empty;
+    /// `on_progress` is called as each file set finishes.
+    /// A scunpacked export or extracted game records are detected and loaded
+    /// through `json_loader`/`p4k` instead. Names are then localized from a
+    /// `Global.ini`/`names.csv` in the data directory, if there is one.
+    pub fn load_with_report(
+        data_dir: &Path,
+        on_progress: &(dyn Fn(LoadProgress) + Sync),
+    ) -> (Self, LoadReport) {
+        let (mut data, mut report) = match detect_format(data_dir) {
+            Some(DataFormat::Scunpacked) => json_loader::load_scunpacked(data_dir, on_progress),
+            Some(DataFormat::GameRecords) => p4k::load_p4k(data_dir, on_progress),
+            _ => Self::load_files(data_dir, on_progress),
+        };
+
+        match Localization::load(data_dir) {
+            Some(Ok(localization)) => data.apply_localization(&localization, &mut report),
+            Some(Err(e)) => report.warn("global.ini", e),
+            None => {}
+        }
+        data.attach_manufacturers();
+        ship_metadata::apply(&mut data, data_dir, &mut report);
+        ship_parts::apply(&mut data, data_dir, &mut report);
+        mounts::apply(&mut data, data_dir, &mut report);
+        components::apply(&mut data, data_dir, &mut report);
+        hitbox_zones::apply(&mut data, data_dir, &mut report);
+        prices::apply(&mut data, data_dir, &mut report);
+        for ship in data.ships.values_mut() {
+            Arc::make_mut(ship).derive_instance_hp();
+        }
+        data.expand_sub_ports();
+        data.collect_ordnance();
+        manifest::verify(data_dir, &mut report);
+        data.assign_ids();
+        (data, report)
+    }
+
+    /// Load the project's own JSON layout (`ships/*.json`, `weapons.json`, ...)
+    fn load_files(
+        data_dir: &Path,
+        on_progress: &(dyn Fn(LoadProgress) + Sync),
+    ) -> (Self, LoadReport) {
+        type Loader = fn(&mut GameData, &Path, &mut LoadReport) -> Result<(), Box<dyn std::error::Error>>;
+        let loaders: [(&str, &str, Loader); 5] = [
+            ("ships", "ships/", Self::load_ships),
+            ("weapons", "weapons.json", Self::load_weapons),
+            ("shields", "shields.json", Self::load_shields),
+            ("missiles", "missiles.json", Self::load_missiles),
+            ("mounts", "mounts.json", Self::load_mounts),
+        ];
+        let completed = AtomicUsize::new(0);
+
+        let parts: Vec<(GameData, LoadReport)> = std::thread::scope(|scope| {
+            let completed = &completed;
+            let handles: Vec<_> = loaders.iter()
+                .map(|&(stage, file, load)| scope.spawn(move || {
+                    let mut part = GameData::default();
+                    let mut report = LoadReport::default();
+                    if let Err(e) = load(&mut part, data_dir, &mut report) {
+                        report.error(file, e.to_string());
+                        part = GameData::default();
+                    }
+                    on_progress(LoadProgress {
+                        stage: stage.to_string(),
+                        completed: completed.fetch_add(1, Ordering::SeqCst) + 1,
+                        total: loaders.len(),
+                    });
+                    (part, report)
+                }))
+                .collect();
+            handles.into_iter()
+                .zip(loaders.iter())
+                .map(|(h, &(_, file, _))| h.join().unwrap_or_else(|_| {
+                    let mut report = LoadReport::default();
+                    report.error(file, "Data loader thread panicked".to_string());
+                    (GameData::default(), report)
+                }))
+                .collect()
+        });
+
         let mut data = GameData::default();
+        let mut report = LoadReport::default();
+        for (part, part_report) in parts {
+            data.ships.extend(part.ships);
+            data.weapons.extend(part.weapons);
+            data.shields.extend(part.shields);
+            data.missiles.extend(part.missiles);
+            data.mounts.extend(part.mounts);
+            report.merge(part_report);
+        }
+        data.resolve_default_shields(&mut report);
+
+        (data, report)
+    }
+
+    /// Load game data, reusing the binary cache in `cache_dir` when it is still fresh
+    ///
+    /// Only clean loads (no errors) are cached, so a broken file is retried next launch.
+    pub fn load_cached(
+        data_dir: &Path,
+        cache_dir: &Path,
+        on_progress: &(dyn Fn(LoadProgress) + Sync),
+    ) -> (Self, LoadReport) {
+        // Extracted game records are thousands of files; hashing them costs as much as parsing
+        if detect_format(data_dir) == Some(DataFormat::GameRecords) {
+            return Self::load_with_report(data_dir, on_progress);
+        }
+
+        let fingerprint = cache::fingerprint(data_dir);
+        if let Some((data, mut report)) = cache::load(cache_dir, fingerprint) {
+            log::info!("Loaded game data from cache {:?}", cache_dir);
+            on_progress(LoadProgress { stage: "cache".to_string(), completed: 1, total: 1 });
+            report.from_cache = true;
+            return (data, report);
+        }
+
+        let (data, report) = Self::load_with_report(data_dir, on_progress);
+        if report.is_ok() {
+            if let Err(e) = cache::store(cache_dir, fingerprint, &data, &report) {
+                log::warn!("Could not write game data cache: {}", e);
+            }
+        }
+        (data, report)
+    }
+
+    /// Fill the manufacturer table and tag every ship/weapon with its maker
+    pub fn attach_manufacturers(&mut self) {
+        self.manufacturers = manufacturers::builtin().into_iter()
+            .map(|m| (m.code.clone(), Arc::new(m)))
+            .collect();
+
+        for ship in self.ships.values_mut() {
+            if let Some(maker) = self.manufacturers.get(&manufacturers::code_from_class(&ship.filename)) {
+                let ship = Arc::make_mut(ship);
+                ship.manufacturer_code = maker.code.clone();
+                ship.manufacturer = maker.name.clone();
+            }
+        }
+        for weapon in self.weapons.values_mut() {
+            Self::tag_weapon_maker(&self.manufacturers, Arc::make_mut(weapon));
+        }
+    }
+
+    /// Give each gun hardpoint as many sub-ports as its mount has weapon ports
+    ///
+    /// Exports list a mount's identical weapons once, so a C1 nose turret with two
+    /// S3 cannons can come through as a single sub-port. Missing sub-ports are added
+    /// with the mount's port size and the last listed weapon, so loadouts count
+    /// every gun and each one can be swapped on its own.
+    pub fn expand_sub_ports(&mut self) {
+        let mounts = &self.mounts;
+        // Mount of a gun hardpoint listing fewer sub-ports than the mount has
+        let short_mount = |hp: &WeaponHardpoint| {
+            mounts.get(&hp.mount_name)
+                .filter(|m| m.ports as usize > hp.sub_ports.len())
+                .filter(|_| !matches!(hp.category.as_str(), "missile" | "torpedo" | "bomb"))
+                .cloned()
+        };
+        for ship in self.ships.values_mut() {
+            if !ship.weapon_hardpoints.iter().any(|hp| short_mount(hp).is_some()) {
+                continue;
+            }
+
+            let ship = Arc::make_mut(ship);
+            for hp in &mut ship.weapon_hardpoints {
+                let Some(mount) = short_mount(hp) else { continue };
+                let default_weapon = hp.sub_ports.last().and_then(|sp| sp.default_weapon.clone());
+                hp.sub_ports.resize(mount.ports as usize, SubPort { size: mount.port_size, default_weapon });
+                if mount.port_size > 0 {
+                    for sub_port in &mut hp.sub_ports {
+                        sub_port.size = mount.port_size;
+                    }
+                }
+            }
+
+            let pilot_sizes: Vec<String> = ship.weapon_hardpoints.iter()
+                .filter(|hp| hp.category == "pilot")
+                .flat_map(|hp| hp.sub_ports.iter().map(|sp| sp.size.to_string()))
+                .collect();
+            ship.pilot_weapon_count = pilot_sizes.len() as i32;
+            ship.pilot_weapon_sizes = pilot_sizes.join(",");
+        }
+    }
+
+    /// Fill each ship's `ordnance_hardpoints` from its missile, torpedo and bomb hardpoints
+    ///
+    /// Size-0 ports get their size from the rack name or missile (see `ordnance_hardpoint`)
+    /// instead of dropping out of the ordnance capacity.
+    pub fn collect_ordnance(&mut self) {
+        let missile_sizes: HashMap<String, i32> = self.missiles.iter()
+            .map(|(key, m)| (key.to_lowercase(), m.size))
+            .collect();
+        for ship in self.ships.values_mut() {
+            let ordnance: Vec<OrdnanceHardpoint> = ship.weapon_hardpoints.iter()
+                .filter(|hp| matches!(hp.category.as_str(), "missile" | "torpedo" | "bomb"))
+                .map(|hp| ordnance_hardpoint(hp, &missile_sizes))
+                .collect();
+            if ordnance != ship.ordnance_hardpoints {
+                Arc::make_mut(ship).ordnance_hardpoints = ordnance;
+            }
+        }
+    }
 
-        data.load_ships(data_dir)?;
-        data.load_weapons(data_dir)?;
-        data.load_shields(data_dir)?;
-        data.load_missiles(data_dir)?;
-        data.load_mounts(data_dir)?;
+    /// Set a weapon's manufacturer code (and name, if the data had none)
+    fn tag_weapon_maker(table: &HashMap<String, Arc<Manufacturer>>, weapon: &mut Weapon) {
+        if let Some(maker) = table.get(&manufacturers::code_from_class(&weapon.filename)) {
+            weapon.manufacturer_code = maker.code.clone();
+            if weapon.manufacturer.is_empty() {
+                weapon.manufacturer = maker.name.clone();
+            }
+        }
+    }
+
+    /// Replace display names with localized strings where one exists (ships are re-keyed)
+    ///
+    /// Localized names that clash (two classes sharing one name) get the class
+    /// appended, so no ship is lost when ships are re-keyed by name.
+    pub fn apply_localization(&mut self, localization: &Localization, report: &mut LoadReport) {
+        let mut ships: Vec<Arc<Ship>> = std::mem::take(&mut self.ships).into_values().collect();
+        ships.sort_by(|a, b| a.filename.cmp(&b.filename));
+        let names: Vec<_> = ships.iter()
+            .map(|s| (s.filename.as_str(), s.display_name.as_str(), localization.ship_name(&s.filename)))
+            .collect();
+        let names = localized_names(&names, "ship", report);
+        self.ships = ships.into_iter()
+            .zip(names)
+            .map(|(mut ship, name)| {
+                if ship.display_name != name {
+                    Arc::make_mut(&mut ship).display_name = name.clone();
+                }
+                (name, ship)
+            })
+            .collect();
 
-        Ok(data)
+        let mut weapons: Vec<&mut Arc<Weapon>> = self.weapons.values_mut().collect();
+        weapons.sort_by(|a, b| a.filename.cmp(&b.filename));
+        let names: Vec<_> = weapons.iter()
+            .map(|w| (w.filename.as_str(), w.display_name.as_str(), localization.item_name(&w.filename)))
+            .collect();
+        let names = localized_names(&names, "weapon", report);
+        for (weapon, name) in weapons.into_iter().zip(names) {
+            if weapon.display_name != name {
+                Arc::make_mut(weapon).display_name = name;
+            }
+        }
+        for shield in self.shields.values_mut() {
+            if let Some(name) = localization.item_name(&shield.internal_name) {
+                Arc::make_mut(shield).display_name = name.to_string();
+            }
+        }
+        for missile in self.missiles.values_mut() {
+            if let Some(name) = localization.item_name(&missile.name) {
+                Arc::make_mut(missile).display_name = name.to_string();
+            }
+        }
     }
 
-    fn load_ships(&mut self, data_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    /// Number entities without an ID, per kind, after the highest ID in use
+    ///
+    /// A fresh load numbers each kind from 1 in key order, so IDs stay the same
+    /// across reloads of the same data.
+    pub fn assign_ids(&mut self) {
+        fn assign<T: Clone>(entities: &mut HashMap<String, Arc<T>>, id: fn(&T) -> u32, set: fn(&mut T, u32)) {
+            let first = next_id(entities, id);
+            let mut keys: Vec<String> = entities.iter()
+                .filter(|(_, e)| id(e) == 0)
+                .map(|(key, _)| key.clone())
+                .collect();
+            keys.sort();
+            for (key, new_id) in keys.iter().zip(first..) {
+                if let Some(entity) = entities.get_mut(key) {
+                    set(Arc::make_mut(entity), new_id);
+                }
+            }
+        }
+        assign(&mut self.ships, |s| s.id, |s, id| s.id = id);
+        assign(&mut self.weapons, |w| w.id, |w, id| w.id = id);
+        assign(&mut self.shields, |s| s.id, |s, id| s.id = id);
+        assign(&mut self.missiles, |m| m.id, |m, id| m.id = id);
+        assign(&mut self.mounts, |m| m.id, |m, id| m.id = id);
+        assign(&mut self.power_plants, |p| p.id, |p, id| p.id = id);
+        assign(&mut self.coolers, |c| c.id, |c, id| c.id = id);
+    }
+
+    /// Resolve each ship's default_shield_ref to a concrete shield once, at load time
+    fn resolve_default_shields(&mut self, report: &mut LoadReport) {
+        let resolver = DefaultShieldResolver::new(&self.shields);
+        for ship in self.ships.values_mut().map(Arc::make_mut) {
+            ship.default_shield = resolver.resolve(ship, report);
+        }
+    }
+
+    /// Add (or replace) a ship after load, resolving its default shield and instance HP
+    pub fn insert_ship(&mut self, mut ship: Ship, report: &mut LoadReport) {
+        ship.default_shield = DefaultShieldResolver::new(&self.shields).resolve(&ship, report);
+        ship.derive_instance_hp();
+        if let Some(maker) = self.manufacturers.get(&manufacturers::code_from_class(&ship.filename)) {
+            ship.manufacturer_code = maker.code.clone();
+            ship.manufacturer = maker.name.clone();
+        }
+        // A replaced ship keeps its ID
+        ship.id = self.ships.get(&ship.display_name).map_or_else(|| next_id(&self.ships, |s| s.id), |s| s.id);
+        self.ships.insert(ship.display_name.clone(), Arc::new(ship));
+    }
+
+    /// Add (or replace) a weapon after load
+    pub fn insert_weapon(&mut self, mut weapon: Weapon) {
+        Self::tag_weapon_maker(&self.manufacturers, &mut weapon);
+        weapon.id = self.weapons.get(&weapon.filename).map_or_else(|| next_id(&self.weapons, |w| w.id), |w| w.id);
+        self.weapons.insert(weapon.filename.clone(), Arc::new(weapon));
+    }
+
+    fn load_ships(&mut self, data_dir: &Path, report: &mut LoadReport) -> Result<(), Box<dyn std::error::Error>> {
         let ships_dir = data_dir.join("ships");
 
         if !ships_dir.exists() {
@@ -228,6 +1027,20 @@ impl GameData {
             cooler_total_hp: i32,
             shield_gen_total_hp: i32,
             qd_total_hp: i32,
+            // Instance counts (optional - older extractions only have totals; the bundled
+            // data/ships/*.json have none, ship_parts_comprehensive.csv fills them in)
+            #[serde(default)]
+            thruster_count: i32,
+            #[serde(default)]
+            turret_count: i32,
+            #[serde(default)]
+            powerplant_count: i32,
+            #[serde(default)]
+            cooler_count: i32,
+            #[serde(default)]
+            shield_gen_count: i32,
+            #[serde(default)]
+            qd_count: i32,
         }
 
         #[derive(Deserialize)]
@@ -245,6 +1058,18 @@ impl GameData {
             max_shield_size: i32,
             #[serde(default)]
             default_shield_ref: String,
+            #[serde(default)]
+            default_power_plant_ref: String,
+            #[serde(default)]
+            default_cooler_ref: String,
+            #[serde(default)]
+            mass: f64,
+            #[serde(default)]
+            main_thrust: f64,
+            #[serde(default)]
+            pitch_rate: f64,
+            #[serde(default)]
+            yaw_rate: f64,
             weapon_hardpoints: Vec<WeaponHardpoint>,
         }
 
@@ -254,11 +1079,18 @@ impl GameData {
             let path = entry.path();
 
             if path.extension().map_or(false, |ext| ext == "json") {
-                let json_content = std::fs::read_to_string(&path)?;
+                let file = format!("ships/{}", entry.file_name().to_string_lossy());
+                let json_content = match std::fs::read_to_string(&path) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        report.warn(&file, format!("Could not read file: {}", e));
+                        continue;
+                    }
+                };
                 let ship_json: ShipJson = match serde_json::from_str(&json_content) {
                     Ok(s) => s,
                     Err(e) => {
-                        eprintln!("Failed to parse {:?}: {}", path, e);
+                        report.warn(&file, format!("Failed to parse: {}", e));
                         continue;
                     }
                 };
@@ -286,6 +1118,7 @@ impl GameData {
                 }
 
                 let ship = Ship {
+                    id: 0,
                     filename: ship_json.filename,
                     display_name: display_name.clone(),
                     hull_hp: ship_json.hull_hp,
@@ -306,15 +1139,47 @@ impl GameData {
                     cooler_total_hp: ship_json.components.cooler_total_hp,
                     shield_gen_total_hp: ship_json.components.shield_gen_total_hp,
                     qd_total_hp: ship_json.components.qd_total_hp,
+                    thruster_count: ship_json.components.thruster_count,
+                    turret_count: ship_json.components.turret_count,
+                    powerplant_count: ship_json.components.powerplant_count,
+                    cooler_count: ship_json.components.cooler_count,
+                    shield_gen_count: ship_json.components.shield_gen_count,
+                    qd_count: ship_json.components.qd_count,
+                    // Derived once every data file is merged (see `Ship::derive_instance_hp`)
+                    thruster_instance_hp: 0.0,
+                    turret_instance_hp: 0.0,
+                    powerplant_instance_hp: 0.0,
+                    cooler_instance_hp: 0.0,
+                    shield_gen_instance_hp: 0.0,
+                    qd_instance_hp: 0.0,
                     pilot_weapon_count,
                     pilot_weapon_sizes,
                     max_shield_size: ship_json.max_shield_size,
                     shield_count: ship_json.shield_count,
                     default_shield_ref: ship_json.default_shield_ref,
+                    default_shield: None,
+                    default_power_plant_ref: ship_json.default_power_plant_ref,
+                    default_cooler_ref: ship_json.default_cooler_ref,
+                    manufacturer: String::new(),
+                    manufacturer_code: String::new(),
+                    size_class: String::new(),
+                    role: String::new(),
+                    crew: 0,
+                    hitbox_length: 0.0,
+                    quantum_spool_time: 0.0,
+                    sensor_range: 0.0,
+                    mass: ship_json.mass,
+                    main_thrust: ship_json.main_thrust,
+                    pitch_rate: ship_json.pitch_rate,
+                    yaw_rate: ship_json.yaw_rate,
+                    fuse_penetration_mult: 0.0,
+                    critical_explosion_chance: 0.0,
+                    hitbox_zones: Vec::new(),
                     weapon_hardpoints: hardpoints,
+                    ordnance_hardpoints: Vec::new(),
                 };
 
-                self.ships.insert(display_name, ship);
+                self.ships.insert(display_name, Arc::new(ship));
             }
         }
 
@@ -454,7 +1319,7 @@ impl GameData {
         format!("{} {}", mfr_name, model_parts.join(" "))
     }
 
-    fn load_weapons(&mut self, data_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    fn load_weapons(&mut self, data_dir: &Path, _report: &mut LoadReport) -> Result<(), Box<dyn std::error::Error>> {
         let json_path = data_dir.join("weapons.json");
 
         if !json_path.exists() {
@@ -488,13 +1353,24 @@ impl GameData {
             // Parse ship_exclusive flag (true = weapon is ship-specific, cannot be swapped to other ships)
             let ship_exclusive = weapon_data["ship_exclusive"].as_bool().unwrap_or(false);
 
+            // Ballistics (JSON uses alpha_damage/range, code uses damage_per_shot/max_range)
+            let fire_rate = weapon_data["fire_rate"].as_f64().unwrap_or(0.0);
+            let damage_per_shot = weapon_data["alpha_damage"].as_f64()
+                .or_else(|| weapon_data["damage_per_shot"].as_f64())
+                .unwrap_or(damage_physical + damage_energy + damage_distortion);
+            let speed = weapon_data["speed"].as_f64().unwrap_or(0.0);
+            let max_range = weapon_data["range"].as_f64()
+                .or_else(|| weapon_data["max_range"].as_f64())
+                .unwrap_or(0.0);
+
             let weapon = Weapon {
+                id: 0,
                 display_name: display_name.clone(),
                 filename: weapon_key.clone(),
                 size,
                 damage_type: weapon_data["damage_type"].as_str().unwrap_or("Unknown").to_string(),
                 sustained_dps,
-                power_consumption: 0.0,  // Power data now in JSON if needed
+                power_consumption: weapon_data["power_consumption"].as_f64().unwrap_or(0.0),
                 weapon_type,
                 damage_physical,
                 damage_energy,
@@ -504,15 +1380,22 @@ impl GameData {
                 far_radius: 0.2,
                 restricted_to,
                 ship_exclusive,
+                manufacturer: weapon_data["manufacturer"].as_str().unwrap_or("").to_string(),
+                manufacturer_code: String::new(),
+                fire_rate,
+                damage_per_shot,
+                speed,
+                max_range,
+                heat_per_shot: weapon_data["heat_per_shot"].as_f64().unwrap_or(0.0),
             };
 
-            self.weapons.insert(weapon_key.clone(), weapon);
+            self.weapons.insert(weapon_key.clone(), Arc::new(weapon));
         }
 
         Ok(())
     }
 
-    fn load_shields(&mut self, data_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    fn load_shields(&mut self, data_dir: &Path, _report: &mut LoadReport) -> Result<(), Box<dyn std::error::Error>> {
         let json_path = data_dir.join("shields.json");
 
         if !json_path.exists() {
@@ -534,6 +1417,7 @@ impl GameData {
             }
 
             let shield = Shield {
+                id: 0,
                 display_name: shield_data["display_name"].as_str().unwrap_or("Unknown").to_string(),
                 internal_name: internal_name.clone(),
                 size: shield_data["size"].as_i64().unwrap_or(0) as i32,
@@ -571,18 +1455,18 @@ impl GameData {
                     .unwrap_or(10.0), // Default ~10s
             };
 
-            self.shields.insert(shield.internal_name.clone(), shield);
+            self.shields.insert(shield.internal_name.clone(), Arc::new(shield));
         }
 
         Ok(())
     }
 
-    fn load_missiles(&mut self, data_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    fn load_missiles(&mut self, data_dir: &Path, report: &mut LoadReport) -> Result<(), Box<dyn std::error::Error>> {
         let json_path = data_dir.join("missiles.json");
 
         if !json_path.exists() {
             // Missiles are optional - don't fail if not found
-            eprintln!("Missiles file not found: {:?} (skipping)", json_path);
+            report.warn("missiles.json", "File not found (skipping)".to_string());
             return Ok(());
         }
 
@@ -596,6 +1480,7 @@ impl GameData {
             }
 
             let missile = Missile {
+                id: 0,
                 name: missile_key.clone(),
                 display_name: missile_data["display_name"].as_str().unwrap_or("Unknown").to_string(),
                 size,
@@ -611,18 +1496,18 @@ impl GameData {
                 lock_time: missile_data["lock_time"].as_f64().unwrap_or(0.0),
             };
 
-            self.missiles.insert(missile_key.clone(), missile);
+            self.missiles.insert(missile_key.clone(), Arc::new(missile));
         }
 
         Ok(())
     }
 
-    fn load_mounts(&mut self, data_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    fn load_mounts(&mut self, data_dir: &Path, report: &mut LoadReport) -> Result<(), Box<dyn std::error::Error>> {
         let json_path = data_dir.join("mounts.json");
 
         if !json_path.exists() {
             // Mounts are optional - don't fail if not found
-            eprintln!("Mounts file not found: {:?} (skipping)", json_path);
+            report.warn("mounts.json", "File not found (skipping)".to_string());
             return Ok(());
         }
 
@@ -630,10 +1515,10 @@ impl GameData {
         let mounts_vec: Vec<Mount> = serde_json::from_str(&json_content)?;
 
         for mount in mounts_vec {
-            self.mounts.insert(mount.mount_ref.clone(), mount);
+            self.mounts.insert(mount.mount_ref.clone(), Arc::new(mount));
         }
 
-        println!("Loaded {} mounts", self.mounts.len());
+        log::info!("Loaded {} mounts", self.mounts.len());
 
         Ok(())
     }
@@ -645,6 +1530,16 @@ impl GameData {
         names
     }
 
+    /// Get sorted list of ship names allowed by a variant filter
+    pub fn get_filtered_ships_sorted(&self, filter: &VariantFilter) -> Vec<String> {
+        let mut names: Vec<_> = self.ships.iter()
+            .filter(|(_, ship)| filter.allows(ship))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
     /// Get weapons of a specific size, sorted by DPS
     pub fn get_weapons_by_size(&self, size: i32) -> Vec<String> {
         let mut weapons: Vec<_> = self.weapons.iter()
@@ -665,13 +1560,131 @@ impl GameData {
         shields.into_iter().map(|(n, _)| n).collect()
     }
 
+    /// Ship by display name or `#<id>`
+    pub fn ship(&self, name: &str) -> Option<&Arc<Ship>> {
+        match id_ref(name) {
+            Some(id) => self.ships.values().find(|s| s.id == id),
+            None => self.ships.get(name),
+        }
+    }
+
+    /// Shield by internal name or `#<id>`
+    pub fn shield(&self, name: &str) -> Option<&Arc<Shield>> {
+        match id_ref(name) {
+            Some(id) => self.shields.values().find(|s| s.id == id),
+            None => self.shields.get(name),
+        }
+    }
+
+    /// Weapon by display name, filename or `#<id>`
+    pub fn weapon(&self, name: &str) -> Option<&Arc<Weapon>> {
+        match id_ref(name) {
+            Some(id) => self.weapons.values().find(|w| w.id == id),
+            None => self.get_weapon_by_display_name(name).or_else(|| self.get_weapon_by_filename(name)),
+        }
+    }
+
+    /// Missile by display name, key or `#<id>`
+    pub fn missile(&self, name: &str) -> Option<&Arc<Missile>> {
+        match id_ref(name) {
+            Some(id) => self.missiles.values().find(|m| m.id == id),
+            None => self.get_missile_by_display_name(name).or_else(|| self.missiles.get(name)),
+        }
+    }
+
+    /// Mount by ref or `#<id>`
+    pub fn mount(&self, mount_ref: &str) -> Option<&Arc<Mount>> {
+        match id_ref(mount_ref) {
+            Some(id) => self.mounts.values().find(|m| m.id == id),
+            None => self.mounts.get(mount_ref),
+        }
+    }
+
+    /// Power plant by display name, class name or `#<id>`
+    pub fn power_plant(&self, name: &str) -> Option<&Arc<PowerPlant>> {
+        match id_ref(name) {
+            Some(id) => self.power_plants.values().find(|p| p.id == id),
+            None => self.power_plants.get(&name.to_lowercase())
+                .or_else(|| self.power_plants.values().find(|p| p.display_name == name)),
+        }
+    }
+
+    /// Stock power plant of a ship (None if unknown or not loaded)
+    pub fn find_default_power_plant(&self, ship: &Ship) -> Option<&Arc<PowerPlant>> {
+        self.power_plants.get(&ship.default_power_plant_ref.to_lowercase())
+    }
+
+    /// Cooler by display name, class name or `#<id>`
+    pub fn cooler(&self, name: &str) -> Option<&Arc<Cooler>> {
+        match id_ref(name) {
+            Some(id) => self.coolers.values().find(|c| c.id == id),
+            None => self.coolers.get(&name.to_lowercase())
+                .or_else(|| self.coolers.values().find(|c| c.display_name == name)),
+        }
+    }
+
+    /// Stock cooler of a ship (None if unknown or not loaded)
+    pub fn find_default_cooler(&self, ship: &Ship) -> Option<&Arc<Cooler>> {
+        self.coolers.get(&ship.default_cooler_ref.to_lowercase())
+    }
+
+    /// Mount item a hardpoint carries (None for bare weapons and unknown mounts)
+    pub fn hardpoint_mount(&self, hardpoint: &WeaponHardpoint) -> Option<&Arc<Mount>> {
+        self.mounts.get(&mounts::mount_ref(&hardpoint.mount_name))
+    }
+
     /// Get weapon by display name (searches all weapons for matching display_name)
-    pub fn get_weapon_by_display_name(&self, display_name: &str) -> Option<&Weapon> {
+    pub fn get_weapon_by_display_name(&self, display_name: &str) -> Option<&Arc<Weapon>> {
         self.weapons.values().find(|w| w.display_name == display_name)
     }
 
+    /// Filter, sort and paginate weapons
+    ///
+    /// Sorting is stable: ties fall back to display name, then filename.
+    /// Pages are 0-based; a page_size of 0 returns every match.
+    pub fn query_weapons(&self, filter: &WeaponFilter, page: usize, page_size: usize, sort_by: WeaponSortBy) -> WeaponPage {
+        let mut matches: Vec<&Arc<Weapon>> = self.weapons.values()
+            .filter(|w| filter.matches(w))
+            .collect();
+
+        matches.sort_by(|a, b| {
+            let primary = match sort_by {
+                WeaponSortBy::Name => std::cmp::Ordering::Equal,
+                WeaponSortBy::Size => a.size.cmp(&b.size),
+                WeaponSortBy::Dps => b.sustained_dps.total_cmp(&a.sustained_dps),
+                WeaponSortBy::AlphaDamage => b.damage_per_shot.total_cmp(&a.damage_per_shot),
+                WeaponSortBy::DpsPerPower => b.dps_per_power().unwrap_or(f64::NEG_INFINITY)
+                    .total_cmp(&a.dps_per_power().unwrap_or(f64::NEG_INFINITY)),
+                WeaponSortBy::DpsPerHeat => b.dps_per_heat().unwrap_or(f64::NEG_INFINITY)
+                    .total_cmp(&a.dps_per_heat().unwrap_or(f64::NEG_INFINITY)),
+            };
+            primary
+                .then_with(|| a.display_name.cmp(&b.display_name))
+                .then_with(|| a.filename.cmp(&b.filename))
+        });
+
+        let total = matches.len();
+        let weapons = if page_size == 0 {
+            matches.into_iter().map(WeaponQueryRow::new).collect()
+        } else {
+            matches.into_iter().skip(page * page_size).take(page_size).map(WeaponQueryRow::new).collect()
+        };
+
+        WeaponPage { weapons, total, page, page_size }
+    }
+
+    /// Ships matching a filter, sorted by display name
+    pub fn query_ships(&self, filter: &ShipFilter) -> Vec<Arc<Ship>> {
+        let mut ships: Vec<Arc<Ship>> = self.ships.values()
+            .filter(|s| filter.matches(s))
+            .cloned()
+            .collect();
+        ships.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+        ships
+    }
+
     /// Get weapon by filename (direct HashMap lookup)
-    pub fn get_weapon_by_filename(&self, filename: &str) -> Option<&Weapon> {
+    pub fn get_weapon_by_filename(&self, filename: &str) -> Option<&Arc<Weapon>> {
         self.weapons.get(filename)
     }
 
@@ -686,9 +1699,19 @@ impl GameData {
     }
 
     /// Get missile by display name
-    pub fn get_missile_by_display_name(&self, display_name: &str) -> Option<&Missile> {
+    pub fn get_missile_by_display_name(&self, display_name: &str) -> Option<&Arc<Missile>> {
         self.missiles.values().find(|m| m.display_name == display_name)
     }
+
+    /// Price of a ship, weapon or shield by class name (filename/internal name)
+    pub fn price_of(&self, class_name: &str) -> Option<&prices::Price> {
+        self.prices.get(&class_name.to_lowercase())
+    }
+
+    /// Find a ship's default shield (resolved at load time)
+    pub fn find_default_shield(&self, ship: &Ship) -> Option<&Arc<Shield>> {
+        ship.default_shield.as_ref().and_then(|name| self.shields.get(name))
+    }
 }
 
 /// Calculate damage and TTK
@@ -751,3 +1774,148 @@ pub fn calculate_damage(
         total_hp_to_destroy: total_hp,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stable_ids() {
+        let mut data = GameData::default();
+        for name in ["Zeta", "Alpha", "Mid"] {
+            data.ships.insert(name.to_string(), Arc::new(Ship { display_name: name.to_string(), ..Default::default() }));
+        }
+        data.assign_ids();
+        let ids: Vec<_> = ["Alpha", "Mid", "Zeta"].iter().map(|n| data.ships[*n].id).collect();
+        assert_eq!(ids, [1, 2, 3]);
+
+        // Later additions go after; replacements keep their ID
+        let mut report = LoadReport::default();
+        data.insert_ship(Ship { display_name: "Beta".to_string(), ..Default::default() }, &mut report);
+        data.insert_ship(Ship { display_name: "Alpha".to_string(), hull_hp: 10.0, ..Default::default() }, &mut report);
+        data.assign_ids();
+        assert_eq!((data.ships["Beta"].id, data.ships["Alpha"].id), (4, 1));
+
+        assert_eq!(data.ship("#4").unwrap().display_name, "Beta");
+        assert_eq!(data.ship("Mid").unwrap().id, 2);
+        assert!(data.ship("#99").is_none());
+    }
+
+    #[test]
+    fn test_localization_name_clashes() {
+        let mut data = GameData::default();
+        for (name, filename) in [("Gladius", "AEGS_Gladius"), ("Gladius Pirate", "AEGS_Gladius_Pirate"), ("Arrow", "ANVL_Arrow")] {
+            data.ships.insert(name.to_string(), Arc::new(Ship { display_name: name.to_string(), filename: filename.to_string(), ..Default::default() }));
+        }
+        let localization = Localization::from_ini("vehicle_NameAEGS_Gladius=Gladius\nvehicle_NameAEGS_Gladius_Pirate=Gladius\n");
+        let mut report = LoadReport::default();
+        data.apply_localization(&localization, &mut report);
+
+        let mut names: Vec<_> = data.ships.keys().cloned().collect();
+        names.sort();
+        assert_eq!(names, ["Arrow", "Gladius (AEGS_Gladius)", "Gladius (AEGS_Gladius_Pirate)"]);
+        assert!(data.ships.iter().all(|(key, ship)| key == &ship.display_name));
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_collect_ordnance() {
+        assert_eq!(rack_layout("mrck_s04_behr_quad_s02"), (4, 4, 2));
+        assert_eq!(rack_layout("mrck_s05_rsi_constellation"), (5, 0, 0));
+        assert_eq!(rack_layout("mrck_s02_rsi_apollo_triage"), (2, 0, 0));
+
+        let rack = |port: &str, category: &str, rack: &str, max_size: i32, sub_ports: Vec<SubPort>| WeaponHardpoint {
+            slot_number: 0,
+            port_name: port.to_string(),
+            max_size,
+            gimbal_type: "Fixed".to_string(),
+            control_type: category.to_string(),
+            category: category.to_string(),
+            mount_name: rack.to_string(),
+            compatible_mounts: vec![],
+            sub_ports,
+        };
+        let port = |size: i32, missile: &str| SubPort { size, default_weapon: Some(missile.to_string()) };
+        let mut data = GameData::default();
+        data.missiles.insert("misl_s09_torpedo".to_string(), Arc::new(Missile {
+            id: 0,
+            name: "misl_s09_torpedo".to_string(),
+            display_name: "Torpedo".to_string(),
+            size: 9,
+            missile_type: "torpedo".to_string(),
+            tracking_type: "IR".to_string(),
+            damage_physical: 0.0,
+            damage_energy: 0.0,
+            damage_distortion: 0.0,
+            explosion_min_radius: 0.0,
+            explosion_max_radius: 0.0,
+            max_lifetime: 0.0,
+            arm_time: 0.0,
+            lock_time: 0.0,
+        }));
+        data.ships.insert("Test Ship".to_string(), Arc::new(Ship {
+            display_name: "Test Ship".to_string(),
+            weapon_hardpoints: vec![
+                rack("hardpoint_gun", "pilot", "", 3, vec![port(3, "gun")]),
+                // Quad rack listed with one sub-port
+                rack("hardpoint_missile_left", "missile", "mrck_s04_behr_quad_s02", 4, vec![port(2, "misl_s02_ir")]),
+                rack("hardpoint_missile_right", "missile", "mrck_s03_behr_dual_s02", 0, vec![port(0, "empty"), port(0, "empty")]),
+                rack("hardpoint_torpedo", "torpedo", "", 0, vec![port(0, "misl_s09_torpedo")]),
+            ],
+            ..Default::default()
+        }));
+        data.collect_ordnance();
+
+        let ordnance = &data.ships["Test Ship"].ordnance_hardpoints;
+        let layout: Vec<_> = ordnance.iter().map(|o| (o.rack_size, o.missile_count, o.missile_size)).collect();
+        assert_eq!(layout, [(4, 4, 2), (3, 2, 2), (9, 1, 9)]);
+        assert_eq!(ordnance[1].default_missile, None);
+        assert_eq!(ordnance[2].default_missile.as_deref(), Some("misl_s09_torpedo"));
+    }
+
+    #[test]
+    fn test_expand_sub_ports() {
+        let mut data = GameData::default();
+        data.mounts.insert("crus_spirit_nose_turret_s3".to_string(), Arc::new(Mount {
+            id: 0,
+            mount_ref: "crus_spirit_nose_turret_s3".to_string(),
+            display_name: "Turret".to_string(),
+            size: 3,
+            ports: 2,
+            port_size: 3,
+            hp: 5000,
+            mount_type: "turret".to_string(),
+        }));
+        let hardpoint = |category: &str, mount: &str, sub_ports: Vec<SubPort>| WeaponHardpoint {
+            slot_number: 0,
+            port_name: format!("hardpoint_{}", category),
+            max_size: 3,
+            gimbal_type: "Turret".to_string(),
+            control_type: category.to_string(),
+            category: category.to_string(),
+            mount_name: mount.to_string(),
+            compatible_mounts: vec![],
+            sub_ports,
+        };
+        let cannon = SubPort { size: 3, default_weapon: Some("behr_lasercannon_s3".to_string()) };
+        data.ships.insert("C1".to_string(), Arc::new(Ship {
+            display_name: "C1".to_string(),
+            pilot_weapon_count: 2,
+            pilot_weapon_sizes: "3,3".to_string(),
+            weapon_hardpoints: vec![
+                // Dual nose turret exported with one sub-port
+                hardpoint("pilot", "crus_spirit_nose_turret_s3", vec![cannon.clone()]),
+                hardpoint("pilot", "mount_gimbal_s3", vec![cannon.clone()]),
+            ],
+            ..Default::default()
+        }));
+        data.expand_sub_ports();
+
+        let ship = &data.ships["C1"];
+        let nose = &ship.weapon_hardpoints[0].sub_ports;
+        assert_eq!(nose.len(), 2);
+        assert!(nose.iter().all(|sp| sp.size == 3 && sp.default_weapon.as_deref() == Some("behr_lasercannon_s3")));
+        assert_eq!(ship.weapon_hardpoints[1].sub_ports.len(), 1);
+        assert_eq!((ship.pilot_weapon_count, ship.pilot_weapon_sizes.as_str()), (3, "3,3,3"));
+    }
+}
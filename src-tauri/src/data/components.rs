@@ -0,0 +1,134 @@
+//! Power plant and cooler items
+//!
+//! `power_plants.json` and `coolers.json` are keyed by class name like
+//! `weapons.json` (`{"display_name", "size", "power_output"}` and
+//! `{"display_name", "size", "cooling_rate"}`); both are optional. Ships name
+//! their stock parts in `default_power_plant_ref`/`default_cooler_ref`, which
+//! are checked against the loaded items here.
+
+use super::{GameData, LoadReport};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+pub const POWER_PLANTS_FILE: &str = "power_plants.json";
+pub const COOLERS_FILE: &str = "coolers.json";
+
+/// Power plant with its power output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerPlant {
+    #[serde(default)]
+    pub id: u32,  // Stable ID assigned at load (see `GameData::assign_ids`)
+    pub name: String,
+    pub display_name: String,
+    pub size: i32,
+    /// Power generated (same units as `Weapon::power_consumption`)
+    pub power_output: f64,
+}
+
+/// Cooler with its cooling capacity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cooler {
+    #[serde(default)]
+    pub id: u32,  // Stable ID assigned at load (see `GameData::assign_ids`)
+    pub name: String,
+    pub display_name: String,
+    pub size: i32,
+    /// Heat removed per second
+    pub cooling_rate: f64,
+}
+
+/// Class name -> item JSON of an optional item file (empty if absent)
+fn read_items(data_dir: &Path, file: &str, report: &mut LoadReport) -> HashMap<String, Value> {
+    let Ok(contents) = std::fs::read_to_string(data_dir.join(file)) else {
+        return HashMap::new();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        report.warn(file, format!("Failed to parse: {}", e));
+        HashMap::new()
+    })
+}
+
+/// Display name and size of an item entry
+fn item_name_size(key: &str, item: &Value) -> (String, i32) {
+    (
+        item["display_name"].as_str().unwrap_or(key).to_string(),
+        item["size"].as_i64().unwrap_or(0) as i32,
+    )
+}
+
+/// Load `power_plants.json`/`coolers.json` (if present) and check each ship's stock parts
+pub fn apply(data: &mut GameData, data_dir: &Path, report: &mut LoadReport) {
+    for (key, item) in read_items(data_dir, POWER_PLANTS_FILE, report) {
+        let Some(power_output) = item["power_output"].as_f64().filter(|p| *p > 0.0) else {
+            report.warn(POWER_PLANTS_FILE, format!("Skipped '{}': no power_output", key));
+            continue;
+        };
+        let (display_name, size) = item_name_size(&key, &item);
+        let name = key.to_lowercase();
+        data.power_plants.insert(name.clone(), Arc::new(PowerPlant { id: 0, name, display_name, size, power_output }));
+    }
+    for (key, item) in read_items(data_dir, COOLERS_FILE, report) {
+        let Some(cooling_rate) = item["cooling_rate"].as_f64().filter(|c| *c > 0.0) else {
+            report.warn(COOLERS_FILE, format!("Skipped '{}': no cooling_rate", key));
+            continue;
+        };
+        let (display_name, size) = item_name_size(&key, &item);
+        let name = key.to_lowercase();
+        data.coolers.insert(name.clone(), Arc::new(Cooler { id: 0, name, display_name, size, cooling_rate }));
+    }
+
+    // Without any items loaded every ref would be "unknown"
+    if data.power_plants.is_empty() && data.coolers.is_empty() {
+        return;
+    }
+    for ship in data.ships.values() {
+        let plant = &ship.default_power_plant_ref;
+        if !plant.is_empty() && data.find_default_power_plant(ship).is_none() {
+            report.warn(POWER_PLANTS_FILE, format!("{}: unknown default power plant '{}'", ship.display_name, plant));
+        }
+        let cooler = &ship.default_cooler_ref;
+        if !cooler.is_empty() && data.find_default_cooler(ship).is_none() {
+            report.warn(COOLERS_FILE, format!("{}: unknown default cooler '{}'", ship.display_name, cooler));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Ship;
+
+    #[test]
+    fn test_component_items() {
+        let dir = std::env::temp_dir().join(format!("ship-lens-components-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(POWER_PLANTS_FILE), r#"{
+            "POWR_JSPN_S01_JS300": {"display_name": "JS-300", "size": 1, "power_output": 8000},
+            "powr_broken": {"display_name": "Broken", "size": 1}
+        }"#).unwrap();
+        std::fs::write(dir.join(COOLERS_FILE), r#"{"cool_aegs_s01_bracer": {"display_name": "Bracer", "size": 1, "cooling_rate": 350}}"#).unwrap();
+
+        let mut data = GameData::default();
+        for (name, plant) in [("Gladius", "powr_jspn_s01_js300"), ("Arrow", "powr_missing")] {
+            data.ships.insert(name.to_string(), Arc::new(Ship {
+                display_name: name.to_string(),
+                default_power_plant_ref: plant.to_string(),
+                default_cooler_ref: "cool_aegs_s01_bracer".to_string(),
+                ..Default::default()
+            }));
+        }
+        let mut report = LoadReport::default();
+        apply(&mut data, &dir, &mut report);
+
+        assert_eq!(data.power_plants.len(), 1);
+        assert_eq!(data.find_default_power_plant(&data.ships["Gladius"]).map(|p| p.power_output), Some(8000.0));
+        assert_eq!(data.power_plant("JS-300").map(|p| p.size), Some(1));
+        assert_eq!(data.coolers["cool_aegs_s01_bracer"].cooling_rate, 350.0);
+        // The plant without output and Arrow's unknown plant
+        assert_eq!(report.warnings.len(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
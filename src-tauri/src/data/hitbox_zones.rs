@@ -0,0 +1,108 @@
+//! Per-ship hitbox zone shares
+//!
+//! `hitbox_zones.csv` rows are `filename,zone,hull,armor,thruster,component`:
+//! for one ship and one target zone (a `ttk::ZonePreset` id such as `engines`),
+//! the share of each HP pool the zone's hitboxes hold. Extracted tables replace
+//! the generic preset shares for that ship; zones without a row keep them.
+
+use super::{GameData, LoadReport};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+pub const HITBOX_ZONES_FILE: &str = "hitbox_zones.csv";
+
+/// Zone ids a row may name (the named `ttk::ZonePreset`s)
+pub const ZONE_IDS: [&str; 6] = ["center-mass", "engines", "cockpit", "components", "wings", "turrets"];
+
+/// Pool shares of one zone of a ship
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HitboxZone {
+    pub zone: String,
+    pub hull: f64,
+    pub armor: f64,
+    pub thruster: f64,
+    pub component: f64,
+}
+
+/// Rows keyed by lowercase ship filename (bad rows are reported and skipped)
+fn parse(contents: &str, report: &mut LoadReport) -> HashMap<String, Vec<HitboxZone>> {
+    let mut rows: HashMap<String, Vec<HitboxZone>> = HashMap::new();
+    for (line_no, line) in contents.lines().enumerate().skip(1) {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let [filename, zone, ref shares @ ..] = fields[..] else {
+            report.warn(HITBOX_ZONES_FILE, format!("Line {}: expected 6 columns", line_no + 1));
+            continue;
+        };
+        let zone = zone.to_lowercase();
+        if !ZONE_IDS.contains(&zone.as_str()) {
+            report.warn(HITBOX_ZONES_FILE, format!("Line {}: unknown zone '{}'", line_no + 1, zone));
+            continue;
+        }
+        let shares: Vec<f64> = shares.iter()
+            .filter_map(|s| s.parse::<f64>().ok().filter(|v| (0.0..=1.0).contains(v)))
+            .collect();
+        let [hull, armor, thruster, component] = shares[..] else {
+            report.warn(HITBOX_ZONES_FILE, format!("Line {}: expected 4 shares between 0.0 and 1.0", line_no + 1));
+            continue;
+        };
+        if hull + armor + thruster + component > 1.0 + 1e-6 {
+            report.warn(HITBOX_ZONES_FILE, format!("Line {}: shares sum to more than 1.0", line_no + 1));
+            continue;
+        }
+
+        let zones = rows.entry(filename.to_lowercase()).or_default();
+        zones.retain(|z| z.zone != zone);
+        zones.push(HitboxZone { zone, hull, armor, thruster, component });
+    }
+    rows
+}
+
+/// Apply `hitbox_zones.csv` from the data directory (no-op if the file is absent)
+pub fn apply(data: &mut GameData, data_dir: &Path, report: &mut LoadReport) {
+    let Ok(contents) = std::fs::read_to_string(data_dir.join(HITBOX_ZONES_FILE)) else {
+        return;
+    };
+    let mut rows = parse(&contents, report);
+
+    for ship in data.ships.values_mut() {
+        if let Some(zones) = rows.remove(&ship.filename.to_lowercase()) {
+            Arc::make_mut(ship).hitbox_zones = zones;
+        }
+    }
+    if !rows.is_empty() {
+        report.warn(HITBOX_ZONES_FILE, format!("{} ships match no ship filename", rows.len()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hitbox_zones() {
+        let mut report = LoadReport::default();
+        let rows = parse(
+            "filename,zone,hull,armor,thruster,component\n\
+             aegs_gladius,engines,0.1,0.1,0.75,0.05\n\
+             aegs_gladius,cockpit,0.4,0.2,0.0,0.4\n\
+             aegs_gladius,engines,0.15,0.1,0.7,0.05\n\
+             aegs_gladius,nose,0.4,0.2,0.0,0.4\n\
+             anvl_arrow,wings,0.5,0.5,0.5,0.0\n\
+             anvl_arrow,wings,0.5,0.5\n",
+            &mut report,
+        );
+        let gladius = &rows["aegs_gladius"];
+        assert_eq!(gladius.len(), 2);
+        // The later engines row wins
+        assert_eq!(gladius.iter().find(|z| z.zone == "engines").unwrap().thruster, 0.7);
+        assert!(!rows.contains_key("anvl_arrow"));
+        assert_eq!(report.warnings.len(), 3);
+    }
+}
@@ -0,0 +1,477 @@
+//! scunpacked JSON ingestion
+//!
+//! Populates `GameData` from a community scunpacked/SPViewer-style export instead of
+//! the project's own per-ship JSON layout:
+//! - `ships.json`: array of vehicles (ClassName, Name, Hull, Armor, Loadout, ...)
+//! - `ship-items.json` (or `items.json`): array of items with a `stdItem` block
+//!   describing weapons, shields and missiles
+//!
+//! Field names differ slightly between export versions, so every value is read
+//! from a list of candidate JSON pointers and falls back to a sane default.
+
+use super::{
+    Cooler, GameData, LoadProgress, LoadReport, Missile, PowerPlant, Shield, Ship, SubPort, Weapon, WeaponHardpoint,
+};
+use serde_json::Value;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Layout of a data directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    /// Project layout: `ships/*.json` + `weapons.json`/`shields.json`/...
+    ShipLens,
+    /// scunpacked/SPViewer export: `ships.json` + `ship-items.json`
+    Scunpacked,
+    /// unp4k'd game `Data` directory (see `p4k`)
+    GameRecords,
+}
+
+const SHIPS_FILE: &str = "ships.json";
+const ITEM_FILES: [&str; 2] = ["ship-items.json", "items.json"];
+
+/// Files that make up a scunpacked export (used for cache fingerprints)
+pub fn source_files() -> Vec<&'static str> {
+    let mut files = vec![SHIPS_FILE];
+    files.extend(ITEM_FILES);
+    files
+}
+
+/// Detect which data layout a directory holds (project layout wins if both are present)
+pub fn detect_format(data_dir: &Path) -> Option<DataFormat> {
+    if data_dir.join("ships").is_dir() && data_dir.join("weapons.json").exists() {
+        Some(DataFormat::ShipLens)
+    } else if data_dir.join(SHIPS_FILE).is_file() || items_path(data_dir).is_some() {
+        Some(DataFormat::Scunpacked)
+    } else if super::p4k::is_data_root(data_dir) {
+        Some(DataFormat::GameRecords)
+    } else {
+        None
+    }
+}
+
+fn items_path(data_dir: &Path) -> Option<std::path::PathBuf> {
+    ITEM_FILES.iter().map(|f| data_dir.join(f)).find(|p| p.is_file())
+}
+
+/// First numeric value found at any of the JSON pointers
+fn f64_at(value: &Value, pointers: &[&str]) -> Option<f64> {
+    pointers.iter().find_map(|p| value.pointer(p).and_then(Value::as_f64))
+}
+
+/// First string value found at any of the JSON pointers
+fn str_at<'a>(value: &'a Value, pointers: &[&str]) -> Option<&'a str> {
+    pointers.iter().find_map(|p| value.pointer(p).and_then(Value::as_str))
+}
+
+/// Physical/energy/distortion triple under a damage object
+fn damage_at(value: &Value, bases: &[&str]) -> (f64, f64, f64) {
+    for base in bases {
+        if let Some(damage) = value.pointer(base) {
+            let physical = damage["Physical"].as_f64().unwrap_or(0.0);
+            let energy = damage["Energy"].as_f64().unwrap_or(0.0);
+            let distortion = damage["Distortion"].as_f64().unwrap_or(0.0);
+            if physical + energy + distortion > 0.0 {
+                return (physical, energy, distortion);
+            }
+        }
+    }
+    (0.0, 0.0, 0.0)
+}
+
+/// Dominant damage type label, matching the project's weapon data
+pub(super) fn damage_type_label(physical: f64, energy: f64, distortion: f64) -> &'static str {
+    if physical >= energy && physical >= distortion {
+        "Ballistic"
+    } else if energy >= distortion {
+        "Energy"
+    } else {
+        "Distortion"
+    }
+}
+
+/// Item type (e.g. "WeaponGun", "Shield", "Missile") without its sub-type suffix
+fn item_type(item: &Value) -> String {
+    str_at(item, &["/type", "/stdItem/Type", "/Type"])
+        .unwrap_or("")
+        .split('.')
+        .next()
+        .unwrap_or("")
+        .to_string()
+}
+
+fn item_key(item: &Value) -> Option<String> {
+    str_at(item, &["/className", "/stdItem/ClassName", "/ClassName"]).map(|s| s.to_lowercase())
+}
+
+fn item_name<'a>(item: &'a Value, fallback: &'a str) -> &'a str {
+    str_at(item, &["/name", "/stdItem/Name", "/Name"]).unwrap_or(fallback)
+}
+
+fn item_size(item: &Value) -> i32 {
+    f64_at(item, &["/size", "/stdItem/Size", "/Size"]).unwrap_or(0.0) as i32
+}
+
+fn parse_weapon(item: &Value, key: &str) -> Option<Weapon> {
+    let (physical, energy, distortion) = damage_at(item, &[
+        "/stdItem/Weapon/Ammunition/ImpactDamage",
+        "/stdItem/Weapon/Damage",
+        "/stdItem/Weapon/Firing/0/DamagePerShot",
+    ]);
+    if physical + energy + distortion <= 0.0 {
+        return None;
+    }
+
+    let fire_rate = f64_at(item, &["/stdItem/Weapon/Firing/0/RoundsPerMinute", "/stdItem/Weapon/RateOfFire"])
+        .unwrap_or(0.0);
+    let alpha = physical + energy + distortion;
+    let (dps_physical, dps_energy, dps_distortion) =
+        damage_at(item, &["/stdItem/Weapon/Firing/0/DamagePerSecond"]);
+    let listed_dps = dps_physical + dps_energy + dps_distortion;
+    let sustained_dps = if listed_dps > 0.0 { listed_dps } else { alpha * fire_rate / 60.0 };
+
+    Some(Weapon {
+        id: 0,
+        display_name: item_name(item, key).to_string(),
+        filename: key.to_string(),
+        size: item_size(item),
+        damage_type: damage_type_label(physical, energy, distortion).to_string(),
+        sustained_dps,
+        power_consumption: f64_at(item, &["/stdItem/ResourceNetwork/Usage/Power/Maximum"]).unwrap_or(0.0),
+        weapon_type: "gun".to_string(),
+        restricted_to: Vec::new(),
+        ship_exclusive: false,
+        manufacturer: str_at(item, &["/stdItem/Manufacturer/Name", "/manufacturer"]).unwrap_or("").to_string(),
+        manufacturer_code: String::new(),
+        damage_physical: physical,
+        damage_energy: energy,
+        damage_distortion: distortion,
+        base_penetration_distance: 2.0,
+        near_radius: 0.1,
+        far_radius: 0.2,
+        fire_rate,
+        damage_per_shot: alpha,
+        speed: f64_at(item, &["/stdItem/Weapon/Ammunition/Speed"]).unwrap_or(0.0),
+        max_range: f64_at(item, &["/stdItem/Weapon/Ammunition/Range"]).unwrap_or(0.0),
+        heat_per_shot: f64_at(item, &["/stdItem/Weapon/Firing/0/HeatPerShot", "/stdItem/Weapon/HeatPerShot"]).unwrap_or(0.0),
+    })
+}
+
+fn parse_shield(item: &Value, key: &str) -> Option<Shield> {
+    let max_hp = f64_at(item, &["/stdItem/Shield/MaxShieldHealth", "/stdItem/Shield/MaxHealth"])?;
+    if max_hp <= 0.0 {
+        return None;
+    }
+
+    // Exports give min/max ranges for resistance and absorption; the max is the full-power value
+    let ranged = |field: &str, kind: &str, default: f64| {
+        f64_at(item, &[
+            &format!("/stdItem/Shield/{}/{}/Maximum", field, kind),
+            &format!("/stdItem/Shield/{}/{}", field, kind),
+        ]).unwrap_or(default)
+    };
+
+    Some(Shield {
+        id: 0,
+        display_name: item_name(item, key).to_string(),
+        internal_name: key.to_string(),
+        size: item_size(item),
+        max_hp,
+        regen: f64_at(item, &["/stdItem/Shield/MaxShieldRegen", "/stdItem/Shield/Regen"]).unwrap_or(0.0),
+        resist_physical: ranged("Resistance", "Physical", 0.0),
+        resist_energy: ranged("Resistance", "Energy", 0.0),
+        resist_distortion: ranged("Resistance", "Distortion", 0.0),
+        absorb_physical: ranged("Absorption", "Physical", 0.225),
+        absorb_energy: ranged("Absorption", "Energy", 1.0),
+        absorb_distortion: ranged("Absorption", "Distortion", 1.0),
+        damaged_regen_delay: f64_at(item, &["/stdItem/Shield/DamagedRegenDelay"]).unwrap_or(5.0),
+        downed_regen_delay: f64_at(item, &["/stdItem/Shield/DownedRegenDelay"]).unwrap_or(10.0),
+    })
+}
+
+fn parse_missile(item: &Value, key: &str, missile_type: &str) -> Option<Missile> {
+    let (physical, energy, distortion) = damage_at(item, &[
+        "/stdItem/Missile/Damage",
+        "/stdItem/Missile/Explosion/Damage",
+    ]);
+    if physical + energy + distortion <= 0.0 {
+        return None;
+    }
+
+    Some(Missile {
+        id: 0,
+        name: key.to_string(),
+        display_name: item_name(item, key).to_string(),
+        size: item_size(item),
+        missile_type: missile_type.to_string(),
+        tracking_type: str_at(item, &["/stdItem/Missile/TrackingSignalType", "/stdItem/Missile/SignalType"])
+            .unwrap_or("Unknown")
+            .to_string(),
+        damage_physical: physical,
+        damage_energy: energy,
+        damage_distortion: distortion,
+        explosion_min_radius: f64_at(item, &["/stdItem/Missile/Explosion/MinRadius"]).unwrap_or(0.0),
+        explosion_max_radius: f64_at(item, &["/stdItem/Missile/Explosion/MaxRadius"]).unwrap_or(0.0),
+        max_lifetime: f64_at(item, &["/stdItem/Missile/MaxLifetime"]).unwrap_or(0.0),
+        arm_time: f64_at(item, &["/stdItem/Missile/ArmTime"]).unwrap_or(0.0),
+        lock_time: f64_at(item, &["/stdItem/Missile/LockTime"]).unwrap_or(0.0),
+    })
+}
+
+fn parse_power_plant(item: &Value, key: &str) -> Option<PowerPlant> {
+    let power_output = f64_at(item, &[
+        "/stdItem/ResourceNetwork/Generation/Power",
+        "/stdItem/PowerPlant/PowerOutput",
+    ]).filter(|p| *p > 0.0)?;
+    Some(PowerPlant {
+        id: 0,
+        name: key.to_string(),
+        display_name: item_name(item, key).to_string(),
+        size: item_size(item),
+        power_output,
+    })
+}
+
+fn parse_cooler(item: &Value, key: &str) -> Option<Cooler> {
+    let cooling_rate = f64_at(item, &[
+        "/stdItem/ResourceNetwork/Generation/Coolant",
+        "/stdItem/Cooler/CoolingRate",
+    ]).filter(|c| *c > 0.0)?;
+    Some(Cooler {
+        id: 0,
+        name: key.to_string(),
+        display_name: item_name(item, key).to_string(),
+        size: item_size(item),
+        cooling_rate,
+    })
+}
+
+fn load_items(data: &mut GameData, data_dir: &Path, report: &mut LoadReport) -> Result<(), Box<dyn std::error::Error>> {
+    let path = items_path(data_dir).ok_or("Item file not found (ship-items.json or items.json)")?;
+    let items: Vec<Value> = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+
+    let file = path.file_name().map(|f| f.to_string_lossy().to_string()).unwrap_or_default();
+    for item in &items {
+        let Some(key) = item_key(item) else { continue };
+        if key.contains("template") {
+            continue;
+        }
+
+        match item_type(item).as_str() {
+            "WeaponGun" => match parse_weapon(item, &key) {
+                Some(weapon) => { data.weapons.insert(key, Arc::new(weapon)); }
+                None => report.warn(&file, format!("Skipped weapon '{}': no damage data", key)),
+            },
+            "Shield" => {
+                if let Some(shield) = parse_shield(item, &key) {
+                    data.shields.insert(key, Arc::new(shield));
+                }
+            }
+            kind @ ("Missile" | "Torpedo" | "Bomb") => {
+                if let Some(missile) = parse_missile(item, &key, &kind.to_lowercase()) {
+                    data.missiles.insert(key, Arc::new(missile));
+                }
+            }
+            "PowerPlant" => {
+                if let Some(plant) = parse_power_plant(item, &key) {
+                    data.power_plants.insert(key, Arc::new(plant));
+                }
+            }
+            "Cooler" => {
+                if let Some(cooler) = parse_cooler(item, &key) {
+                    data.coolers.insert(key, Arc::new(cooler));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Walk a (possibly nested) loadout, collecting shield ports, weapon hardpoints and stock power plant/cooler
+fn collect_ports(entries: &[Value], ship: &mut Ship, default_shield: &mut Option<String>) {
+    for entry in entries {
+        let port_name = str_at(entry, &["/HardpointName", "/hardpointName"]).unwrap_or("");
+        let max_size = f64_at(entry, &["/MaxSize", "/maxSize", "/Size"]).unwrap_or(0.0) as i32;
+        let item = str_at(entry, &["/ClassName", "/ItemClassName", "/className"]).map(|s| s.to_lowercase());
+        let types = str_at(entry, &["/Type", "/Types", "/type"]).unwrap_or("").to_string();
+
+        if types.starts_with("Shield") {
+            ship.shield_count += 1;
+            ship.max_shield_size = ship.max_shield_size.max(max_size);
+            if default_shield.is_none() {
+                *default_shield = item.clone();
+            }
+        } else if types.starts_with("PowerPlant") {
+            if ship.default_power_plant_ref.is_empty() {
+                ship.default_power_plant_ref = item.clone().unwrap_or_default();
+            }
+        } else if types.starts_with("Cooler") {
+            if ship.default_cooler_ref.is_empty() {
+                ship.default_cooler_ref = item.clone().unwrap_or_default();
+            }
+        } else if types.starts_with("WeaponGun") || types.starts_with("Turret") {
+            let category = if types.starts_with("Turret") { "manned_turret" } else { "pilot" };
+            ship.weapon_hardpoints.push(WeaponHardpoint {
+                slot_number: ship.weapon_hardpoints.len() as i32 + 1,
+                port_name: port_name.to_string(),
+                max_size,
+                gimbal_type: "Unknown".to_string(),
+                control_type: String::new(),
+                category: category.to_string(),
+                mount_name: String::new(),
+                compatible_mounts: Vec::new(),
+                sub_ports: vec![SubPort { size: max_size, default_weapon: item }],
+            });
+        }
+
+        if let Some(children) = entry["Loadout"].as_array() {
+            collect_ports(children, ship, default_shield);
+        }
+    }
+}
+
+fn parse_ship(vehicle: &Value) -> Option<Ship> {
+    let filename = str_at(vehicle, &["/ClassName", "/className"])?.to_lowercase();
+    let hull_hp = f64_at(vehicle, &["/Hull/StructureHealthPoints/VitalParts/Body", "/Hull/Health", "/Health"])
+        .unwrap_or(0.0);
+    if hull_hp <= 0.0 {
+        return None;
+    }
+
+    // Typical 4.5 armor values when the export omits them (see ttk::calculate_armor_damage)
+    let mut ship = Ship {
+        display_name: str_at(vehicle, &["/Name", "/name"]).unwrap_or(&filename).to_string(),
+        filename,
+        hull_hp,
+        armor_hp: f64_at(vehicle, &["/Armor/Health", "/Armor/HealthPoints"]).unwrap_or(0.0),
+        armor_damage_mult_physical: f64_at(vehicle, &["/Armor/DamageMultipliers/Physical"]).unwrap_or(0.75),
+        armor_damage_mult_energy: f64_at(vehicle, &["/Armor/DamageMultipliers/Energy"]).unwrap_or(0.6),
+        armor_damage_mult_distortion: f64_at(vehicle, &["/Armor/DamageMultipliers/Distortion"]).unwrap_or(1.0),
+        armor_resist_physical: f64_at(vehicle, &["/Armor/Resistances/Physical"]).unwrap_or(0.85),
+        armor_resist_energy: f64_at(vehicle, &["/Armor/Resistances/Energy"]).unwrap_or(1.3),
+        armor_resist_distortion: f64_at(vehicle, &["/Armor/Resistances/Distortion"]).unwrap_or(1.0),
+        hitbox_length: f64_at(vehicle, &["/Size/Length", "/Dimensions/Length"]).unwrap_or(0.0),
+        mass: f64_at(vehicle, &["/Mass", "/MassTotal"]).unwrap_or(0.0),
+        main_thrust: f64_at(vehicle, &["/FlightCharacteristics/ThrustCapacity/Main", "/Propulsion/MainThrust"]).unwrap_or(0.0),
+        pitch_rate: f64_at(vehicle, &["/FlightCharacteristics/Pitch", "/FlightCharacteristics/MaxAngularVelocity/Pitch"]).unwrap_or(0.0),
+        yaw_rate: f64_at(vehicle, &["/FlightCharacteristics/Yaw", "/FlightCharacteristics/MaxAngularVelocity/Yaw"]).unwrap_or(0.0),
+        ..Default::default()
+    };
+
+    let mut default_shield = None;
+    if let Some(loadout) = vehicle["Loadout"].as_array() {
+        collect_ports(loadout, &mut ship, &mut default_shield);
+    }
+    ship.default_shield_ref = default_shield.unwrap_or_default();
+
+    let pilot_sizes: Vec<String> = ship.weapon_hardpoints.iter()
+        .filter(|hp| hp.category == "pilot")
+        .map(|hp| hp.max_size.to_string())
+        .collect();
+    ship.pilot_weapon_count = pilot_sizes.len() as i32;
+    ship.pilot_weapon_sizes = pilot_sizes.join(",");
+
+    Some(ship)
+}
+
+fn load_ships(data: &mut GameData, data_dir: &Path, report: &mut LoadReport) -> Result<(), Box<dyn std::error::Error>> {
+    let vehicles: Vec<Value> = serde_json::from_str(&std::fs::read_to_string(data_dir.join(SHIPS_FILE))?)?;
+
+    for vehicle in &vehicles {
+        match parse_ship(vehicle) {
+            Some(ship) => { data.ships.insert(ship.display_name.clone(), Arc::new(ship)); }
+            None => {
+                let name = str_at(vehicle, &["/ClassName", "/Name"]).unwrap_or("unknown");
+                report.warn(SHIPS_FILE, format!("Skipped '{}': no hull data", name));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Load a scunpacked export into GameData (same reporting contract as `GameData::load_with_report`)
+pub fn load_scunpacked(
+    data_dir: &Path,
+    on_progress: &(dyn Fn(LoadProgress) + Sync),
+) -> (GameData, LoadReport) {
+    let mut data = GameData::default();
+    let mut report = LoadReport::default();
+
+    type Loader = fn(&mut GameData, &Path, &mut LoadReport) -> Result<(), Box<dyn std::error::Error>>;
+    let loaders: [(&str, &str, Loader); 2] = [
+        ("items", "ship-items.json", load_items),
+        ("ships", SHIPS_FILE, load_ships),
+    ];
+    for (i, (stage, file, load)) in loaders.iter().enumerate() {
+        if let Err(e) = load(&mut data, data_dir, &mut report) {
+            report.error(file, e.to_string());
+        }
+        on_progress(LoadProgress { stage: stage.to_string(), completed: i + 1, total: loaders.len() });
+    }
+
+    data.resolve_default_shields(&mut report);
+    (data, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEMS: &str = r#"[
+        {"className": "KLWE_LaserRepeater_S3", "name": "Attrition-3", "size": 3, "type": "WeaponGun.Gun",
+         "stdItem": {"Weapon": {"Ammunition": {"ImpactDamage": {"Energy": 30.0}, "Speed": 1200.0, "Range": 2400.0},
+                                "Firing": [{"RoundsPerMinute": 600.0}]}}},
+        {"className": "SHLD_GODI_S01_FR66", "name": "FR-66", "size": 1, "type": "Shield",
+         "stdItem": {"Shield": {"MaxShieldHealth": 4000.0, "MaxShieldRegen": 400.0,
+                                "Resistance": {"Physical": {"Minimum": 0.0, "Maximum": 0.1}}}}},
+        {"className": "template_gun", "type": "WeaponGun"}
+    ]"#;
+
+    const SHIPS: &str = r#"[
+        {"ClassName": "AEGS_Gladius", "Name": "Aegis Gladius", "Hull": {"Health": 5000.0}, "Armor": {"Health": 2000.0},
+         "Loadout": [
+            {"HardpointName": "hardpoint_shield_generator", "MaxSize": 1, "Type": "Shield.ShieldGenerator", "ClassName": "SHLD_GODI_S01_FR66"},
+            {"HardpointName": "hardpoint_weapon_left", "MaxSize": 3, "Type": "WeaponGun.Gun", "ClassName": "KLWE_LaserRepeater_S3"}
+         ]},
+        {"ClassName": "No_Hull", "Name": "Broken"}
+    ]"#;
+
+    #[test]
+    fn test_scunpacked_fixture() {
+        let dir = std::env::temp_dir().join(format!("ship-lens-scunpacked-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(detect_format(&dir), None);
+        std::fs::write(dir.join(SHIPS_FILE), SHIPS).unwrap();
+        std::fs::write(dir.join("ship-items.json"), ITEMS).unwrap();
+        assert_eq!(detect_format(&dir), Some(DataFormat::Scunpacked));
+
+        let (data, report) = load_scunpacked(&dir, &|_| {});
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 1);
+
+        let weapon = &data.weapons["klwe_laserrepeater_s3"];
+        assert_eq!((weapon.display_name.as_str(), weapon.size, weapon.damage_type.as_str()), ("Attrition-3", 3, "Energy"));
+        assert_eq!((weapon.sustained_dps, weapon.speed, weapon.max_range), (300.0, 1200.0, 2400.0));
+        assert!(!data.weapons.contains_key("template_gun"));
+
+        let shield = &data.shields["shld_godi_s01_fr66"];
+        assert_eq!((shield.max_hp, shield.regen, shield.resist_physical), (4000.0, 400.0, 0.1));
+        assert_eq!(shield.absorb_physical, 0.225);
+
+        let ship = &data.ships["Aegis Gladius"];
+        assert_eq!((ship.hull_hp, ship.armor_hp, ship.shield_count), (5000.0, 2000.0, 1));
+        assert_eq!(ship.default_shield.as_deref(), Some("shld_godi_s01_fr66"));
+        assert_eq!((ship.pilot_weapon_count, ship.pilot_weapon_sizes.as_str()), (1, "3"));
+        let hp = &ship.weapon_hardpoints[0];
+        assert_eq!(hp.sub_ports[0].default_weapon.as_deref(), Some("klwe_laserrepeater_s3"));
+        assert!(!data.ships.contains_key("Broken"));
+
+        // The project layout wins when both are present
+        std::fs::create_dir_all(dir.join("ships")).unwrap();
+        std::fs::write(dir.join("weapons.json"), "[]").unwrap();
+        assert_eq!(detect_format(&dir), Some(DataFormat::ShipLens));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,106 @@
+//! Localized display names
+//!
+//! Loads the game's `Global.ini` (`key=value` lines, e.g.
+//! `vehicle_NameAEGS_Gladius=Aegis Gladius`) or a bundled `names.csv`
+//! (`key,display_name`) so names come from real localized strings.
+//! `GameData::format_ship_name` remains the fallback for anything missing.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Localization files looked for in a data directory, in priority order
+pub const SOURCE_FILES: [&str; 3] = ["global.ini", "Localization/english/global.ini", "names.csv"];
+
+/// Placeholder the game uses for strings that were never filled in
+const UNINITIALIZED: &str = "<= UNINITIALIZED =>";
+
+/// Localization key -> display string (keys stored lowercase)
+#[derive(Debug, Clone, Default)]
+pub struct Localization {
+    strings: HashMap<String, String>,
+}
+
+impl Localization {
+    /// Parse `Global.ini` contents
+    pub fn from_ini(contents: &str) -> Self {
+        let mut strings = HashMap::new();
+        for line in contents.trim_start_matches('\u{feff}').lines() {
+            let Some((key, value)) = line.split_once('=') else { continue };
+            // Keys may carry a platform suffix: "item_NameFoo,P=..."
+            let key = key.split(',').next().unwrap_or(key).trim();
+            if key.is_empty() || key.starts_with(';') {
+                continue;
+            }
+            strings.insert(key.to_lowercase(), value.trim().to_string());
+        }
+        Self { strings }
+    }
+
+    /// Parse a `key,display_name` CSV (header row optional)
+    pub fn from_csv(contents: &str) -> Self {
+        let mut strings = HashMap::new();
+        for line in contents.trim_start_matches('\u{feff}').lines() {
+            let Some((key, value)) = line.split_once(',') else { continue };
+            let key = key.trim().to_lowercase();
+            if key.is_empty() || key == "key" || key == "filename" || key == "name" {
+                continue;
+            }
+            strings.insert(key, value.trim().trim_matches('"').to_string());
+        }
+        Self { strings }
+    }
+
+    /// Load the first localization file present in `dir` (None if there is none)
+    pub fn load(dir: &Path) -> Option<Result<Self, String>> {
+        let path = Self::find(dir)?;
+        Some(
+            std::fs::read_to_string(&path)
+                .map(|contents| match path.extension().and_then(|e| e.to_str()) {
+                    Some("csv") => Self::from_csv(&contents),
+                    _ => Self::from_ini(&contents),
+                })
+                .map_err(|e| format!("Failed to read {:?}: {}", path, e)),
+        )
+    }
+
+    /// First localization file present in `dir`
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        SOURCE_FILES.iter().map(|f| dir.join(f)).find(|p| p.is_file())
+    }
+
+    /// Look up a key (with or without the leading `@` used in game records)
+    pub fn get(&self, key: &str) -> Option<&str> {
+        let key = key.trim_start_matches('@').to_lowercase();
+        self.strings.get(&key)
+            .map(String::as_str)
+            .filter(|s| !s.is_empty() && *s != UNINITIALIZED)
+    }
+
+    /// Display name for a ship class (e.g. "aegs_gladius")
+    pub fn ship_name(&self, class: &str) -> Option<&str> {
+        self.get(&format!("vehicle_Name{}", class)).or_else(|| self.get(class))
+    }
+
+    /// Display name for an item class (weapon, shield, missile)
+    pub fn item_name(&self, class: &str) -> Option<&str> {
+        self.get(&format!("item_Name{}", class)).or_else(|| self.get(class))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_ini() {
+        let loc = Localization::from_ini(
+            "\u{feff}vehicle_NameAEGS_Gladius=Aegis Gladius\n\
+             item_NameKLWE_LaserRepeater_S3,P=CF-337 Panther Repeater\n\
+             item_NameSHLD_Placeholder=<= UNINITIALIZED =>\n",
+        );
+        assert_eq!(loc.ship_name("aegs_gladius"), Some("Aegis Gladius"));
+        assert_eq!(loc.item_name("klwe_laserrepeater_s3"), Some("CF-337 Panther Repeater"));
+        assert_eq!(loc.get("@vehicle_NameAEGS_Gladius"), Some("Aegis Gladius"));
+        assert_eq!(loc.item_name("shld_placeholder"), None);
+    }
+}
@@ -0,0 +1,136 @@
+//! Data pack manifest
+//!
+//! `data_manifest.json` ships with the bundled data: the data pack version, the
+//! game patch it was extracted from, the extraction date and a SHA-256 checksum
+//! of every data file (written by `scripts/build_data_manifest.py`). Data dirs
+//! without one (game records, hand-made dumps) simply have no version.
+//!
+//! Each load checks the files against the manifest, so a partial install or a
+//! hand-edited file shows up as load report warnings instead of short lists.
+
+use super::LoadReport;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub const MANIFEST_FILE: &str = "data_manifest.json";
+
+/// Contents of `data_manifest.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DataManifest {
+    pub data_version: String,
+    /// Star Citizen patch the data was extracted from (`4.5 LIVE`)
+    pub game_patch: String,
+    /// Extraction date (YYYY-MM-DD)
+    pub extracted: String,
+    /// Data file path (relative, `/`-separated) -> SHA-256 hex digest
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+impl DataManifest {
+    /// Short label for logs and reports (`2025.12.31 (4.5 LIVE)`)
+    pub fn label(&self) -> String {
+        format!("{} ({})", self.data_version, self.game_patch)
+    }
+}
+
+/// Read the manifest in `data_dir` (None if there is none)
+pub fn load(data_dir: &Path) -> Result<Option<DataManifest>, String> {
+    let path = data_dir.join(MANIFEST_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", MANIFEST_FILE, e))?;
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", MANIFEST_FILE, e))
+}
+
+/// Outcome of checking a data dir against its manifest
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManifestCheck {
+    pub data_version: String,
+    /// Files listed in the manifest
+    pub checked: usize,
+    /// Listed files that aren't there
+    pub missing: Vec<String>,
+    /// Listed files whose checksum differs
+    pub mismatched: Vec<String>,
+}
+
+/// SHA-256 of `bytes` as lowercase hex
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Compare every file listed in `manifest` with the one in `data_dir`
+pub fn check(data_dir: &Path, manifest: &DataManifest) -> ManifestCheck {
+    let mut result = ManifestCheck {
+        data_version: manifest.data_version.clone(),
+        checked: manifest.files.len(),
+        ..Default::default()
+    };
+    for (file, expected) in &manifest.files {
+        match std::fs::read(data_dir.join(file)) {
+            Ok(bytes) if sha256_hex(&bytes).eq_ignore_ascii_case(expected) => {}
+            Ok(_) => result.mismatched.push(file.clone()),
+            Err(_) => result.missing.push(file.clone()),
+        }
+    }
+    result
+}
+
+/// Check `data_dir` against its manifest (if any), warning about every bad file
+pub fn verify(data_dir: &Path, report: &mut LoadReport) {
+    let manifest = match load(data_dir) {
+        Ok(Some(manifest)) => manifest,
+        Ok(None) => return,
+        Err(e) => return report.warn(MANIFEST_FILE, e),
+    };
+    let result = check(data_dir, &manifest);
+    for file in &result.missing {
+        report.warn(file, format!("Missing (listed in {} {})", MANIFEST_FILE, manifest.data_version));
+    }
+    for file in &result.mismatched {
+        report.warn(file, format!("Contents differ from {} {}", MANIFEST_FILE, manifest.data_version));
+    }
+    report.manifest = Some(result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_check() {
+        assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+
+        let dir = std::env::temp_dir().join(format!("ship-lens-manifest-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("ships")).unwrap();
+        std::fs::write(dir.join("weapons.json"), "abc").unwrap();
+        std::fs::write(dir.join("ships/aegs_gladius.json"), "edited").unwrap();
+        let manifest = DataManifest {
+            data_version: "2025.12.31".to_string(),
+            game_patch: "4.5 LIVE".to_string(),
+            extracted: "2025-12-31".to_string(),
+            files: [
+                ("weapons.json", sha256_hex(b"abc")),
+                ("ships/aegs_gladius.json", sha256_hex(b"original")),
+                ("shields.json", sha256_hex(b"")),
+            ].into_iter().map(|(f, h)| (f.to_string(), h)).collect(),
+        };
+        std::fs::write(dir.join(MANIFEST_FILE), serde_json::to_string(&manifest).unwrap()).unwrap();
+
+        let mut report = LoadReport::default();
+        verify(&dir, &mut report);
+        let result = report.manifest.unwrap();
+        assert_eq!(result.checked, 3);
+        assert_eq!(result.missing, ["shields.json"]);
+        assert_eq!(result.mismatched, ["ships/aegs_gladius.json"]);
+        assert_eq!(report.warnings.len(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,65 @@
+//! Manufacturer table
+//!
+//! Class names start with the maker's four-letter code (`aegs_gladius`,
+//! `klwe_laserrepeater_s3`), so ships and weapons are matched to this table by
+//! their filename prefix.
+
+use super::Manufacturer;
+
+/// (code, full name) for every maker that appears in ship/weapon class names
+const MANUFACTURERS: [(&str, &str); 38] = [
+    ("AEGS", "Aegis Dynamics"),
+    ("AMRS", "Amon & Reese Co."),
+    ("ANVL", "Anvil Aerospace"),
+    ("APAR", "Apocalypse Arms"),
+    ("ARGO", "Argo Astronautics"),
+    ("ASAD", "Associated Science & Development"),
+    ("BANU", "Banu"),
+    ("BASL", "Basilisk"),
+    ("BEHR", "Behring Applied Technology"),
+    ("CNOU", "Consolidated Outland"),
+    ("CRUS", "Crusader Industries"),
+    ("DRAK", "Drake Interplanetary"),
+    ("ESPR", "Esperia"),
+    ("FSKI", "FireStorm Kinetics"),
+    ("GAMA", "Gatac Manufacture"),
+    ("GATS", "Gallenson Tactical Systems"),
+    ("GLSN", "Gallenson Tactical Systems"),
+    ("GODI", "Gorgon Defender Industries"),
+    ("GRIN", "Greycat Industrial"),
+    ("HRST", "Hurston Dynamics"),
+    ("JOKR", "Joker Engineering"),
+    ("KBAR", "Knightbridge Arms"),
+    ("KLWE", "Klaus & Werner"),
+    ("KRIG", "Kruger Intergalactic"),
+    ("KRON", "Kroneg"),
+    ("MISC", "Musashi Industrial & Starflight Concern"),
+    ("MRAI", "Mirai"),
+    ("MXOX", "MaxOx"),
+    ("NOVP", "Nova Pyrotechnica"),
+    ("ORIG", "Origin Jumpworks"),
+    ("PRAR", "Preacher Armaments"),
+    ("RSI", "Roberts Space Industries"),
+    ("SECO", "Seal Corporation"),
+    ("TALN", "Talon Weapon Systems"),
+    ("TMBL", "Tumbril Land Systems"),
+    ("TOAG", "Thermyte Concern"),
+    ("VNCL", "Vanduul"),
+    ("XIAN", "Aopoa"),
+];
+
+/// Built-in manufacturer table
+pub fn builtin() -> Vec<Manufacturer> {
+    MANUFACTURERS.iter()
+        .map(|&(code, name)| Manufacturer {
+            code: code.to_string(),
+            name: name.to_string(),
+            logo_key: code.to_lowercase(),
+        })
+        .collect()
+}
+
+/// Manufacturer code from a class name prefix ("aegs_gladius" -> "AEGS")
+pub fn code_from_class(class: &str) -> String {
+    class.split('_').next().unwrap_or("").to_uppercase()
+}
@@ -0,0 +1,170 @@
+//! Gimbal, fixed and turret mount items
+//!
+//! Mounts are items of their own: a size (the hardpoint they fit), a sub-port
+//! layout (the weapons they hold) and a type. `mounts.json` has the extracted
+//! table; `mounts.csv` rows (`ref,display_name,size,layout,type[,hp]`, layout
+//! like `2xS3` or `S4`) add mounts or correct extracted ones. Hardpoint
+//! `mount_name`s are then normalized to table refs so each links to its mount.
+
+use super::{GameData, LoadReport, Mount};
+use std::path::Path;
+use std::sync::Arc;
+
+pub const MOUNTS_FILE: &str = "mounts.csv";
+
+/// Known mount types
+pub const MOUNT_TYPES: [&str; 4] = ["fixed", "gimbal", "turret", "missile_rack"];
+
+/// Prefix some extractions leave on class names
+const CLASS_PREFIX: &str = "entityclassdefinition.";
+
+/// Table ref of a mount class name (lowercase, without the entity class prefix)
+pub fn mount_ref(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    name.strip_prefix(CLASS_PREFIX).map(str::to_string).unwrap_or(name)
+}
+
+/// Hardpoint gimbal type of a mount type (see `ttk::mount_accuracy`)
+pub fn gimbal_type(mount_type: &str) -> &'static str {
+    match mount_type {
+        "gimbal" => "Gimbal",
+        "turret" => "Turret",
+        _ => "Fixed",
+    }
+}
+
+/// (ports, port size) of a layout: `2xS3`, `S4` or `1x4`
+fn parse_layout(layout: &str) -> Option<(i32, i32)> {
+    let layout = layout.trim().to_lowercase();
+    let (ports, size) = match layout.split_once('x') {
+        Some((ports, size)) => (ports.trim().parse().ok()?, size),
+        None => (1, layout.as_str()),
+    };
+    let size: i32 = size.trim().trim_start_matches('s').parse().ok()?;
+    (ports > 0 && size > 0).then_some((ports, size))
+}
+
+fn parse(contents: &str, report: &mut LoadReport) -> Vec<Mount> {
+    let mut mounts = Vec::new();
+    let mut lines = contents.lines().enumerate();
+    let Some((_, header)) = lines.next() else {
+        return mounts;
+    };
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    let column = |name: &str| columns.iter().position(|c| *c == name);
+    let (Some(ref_col), Some(name_col), Some(size_col), Some(layout_col), Some(type_col)) =
+        (column("ref"), column("display_name"), column("size"), column("layout"), column("type"))
+    else {
+        report.warn(MOUNTS_FILE, "missing ref/display_name/size/layout/type columns".to_string());
+        return mounts;
+    };
+    let hp_col = column("hp");
+
+    for (line_no, line) in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let field = |col: usize| fields.get(col).copied();
+        let (Some(mount_ref_field), Some(display_name), Some(size), Some(layout), Some(mount_type)) =
+            (field(ref_col), field(name_col), field(size_col), field(layout_col), field(type_col))
+        else {
+            report.warn(MOUNTS_FILE, format!("Line {}: expected {} columns", line_no + 1, columns.len()));
+            continue;
+        };
+        let Ok(size) = size.parse::<i32>() else {
+            report.warn(MOUNTS_FILE, format!("Line {}: invalid size '{}'", line_no + 1, size));
+            continue;
+        };
+        let Some((ports, port_size)) = parse_layout(layout) else {
+            report.warn(MOUNTS_FILE, format!("Line {}: invalid layout '{}'", line_no + 1, layout));
+            continue;
+        };
+        let mount_type = mount_type.to_lowercase();
+        if !MOUNT_TYPES.contains(&mount_type.as_str()) {
+            report.warn(MOUNTS_FILE, format!("Line {}: unknown type '{}' (fixed, gimbal, turret or missile_rack)", line_no + 1, mount_type));
+            continue;
+        }
+
+        mounts.push(Mount {
+            id: 0,
+            mount_ref: mount_ref(mount_ref_field),
+            display_name: display_name.to_string(),
+            size,
+            ports,
+            port_size,
+            hp: hp_col.and_then(field).and_then(|hp| hp.parse().ok()).unwrap_or(0),
+            mount_type,
+        });
+    }
+    mounts
+}
+
+/// Apply `mounts.csv` (if present) and link every hardpoint's `mount_name` to the mount table
+pub fn apply(data: &mut GameData, data_dir: &Path, report: &mut LoadReport) {
+    if let Ok(contents) = std::fs::read_to_string(data_dir.join(MOUNTS_FILE)) {
+        for mount in parse(&contents, report) {
+            let id = data.mounts.get(&mount.mount_ref).map_or(0, |m| m.id);
+            data.mounts.insert(mount.mount_ref.clone(), Arc::new(Mount { id, ..mount }));
+        }
+    }
+
+    for ship in data.ships.values_mut() {
+        let unlinked = |name: &str| !name.is_empty() && mount_ref(name) != name;
+        if !ship.weapon_hardpoints.iter().any(|hp| unlinked(&hp.mount_name)) {
+            continue;
+        }
+        for hp in &mut Arc::make_mut(ship).weapon_hardpoints {
+            hp.mount_name = mount_ref(&hp.mount_name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{Ship, WeaponHardpoint};
+
+    #[test]
+    fn test_mount_table() {
+        assert_eq!(parse_layout("2xS3"), Some((2, 3)));
+        assert_eq!(parse_layout("S4"), Some((1, 4)));
+        assert_eq!(parse_layout("0xS4"), None);
+        assert_eq!(mount_ref("EntityClassDefinition.Mount_Gimbal_S3"), "mount_gimbal_s3");
+
+        let mut report = LoadReport::default();
+        let csv = "ref,display_name,size,layout,type,hp\n\
+                   mount_fixed_s4,Fixed S4 Mount,4,S4,fixed,600\n\
+                   mount_gimbal_s4,Twin S3 Gimbal,4,2xS3,gimbal,\n\
+                   mount_odd_s2,Odd,2,S2,swivel,100\n";
+        let mounts = parse(csv, &mut report);
+        assert_eq!(mounts.len(), 2);
+        assert_eq!((mounts[1].ports, mounts[1].port_size, mounts[1].hp), (2, 3, 0));
+        assert_eq!(report.warnings.len(), 1);
+
+        let dir = std::env::temp_dir().join(format!("ship-lens-mounts-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(MOUNTS_FILE), csv).unwrap();
+        let mut data = GameData::default();
+        data.ships.insert("Test Ship".to_string(), Arc::new(Ship {
+            weapon_hardpoints: vec![WeaponHardpoint {
+                slot_number: 1,
+                port_name: "hardpoint_weapon_left".to_string(),
+                max_size: 4,
+                gimbal_type: "Gimbal".to_string(),
+                control_type: "pilot".to_string(),
+                category: "pilot".to_string(),
+                mount_name: "EntityClassDefinition.mount_gimbal_s4".to_string(),
+                compatible_mounts: vec![],
+                sub_ports: vec![],
+            }],
+            ..Default::default()
+        }));
+        apply(&mut data, &dir, &mut LoadReport::default());
+        let hp = &data.ships["Test Ship"].weapon_hardpoints[0];
+        assert_eq!(data.hardpoint_mount(hp).map(|m| m.display_name.as_str()), Some("Twin S3 Gimbal"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,753 @@
+//! Game install extraction
+//!
+//! Builds `GameData` straight from the game's own records so a new patch can be
+//! used without waiting for the extraction scripts. Reads an unp4k'd `Data`
+//! directory whose DataForge records have been unforged to XML:
+//! - `libs/foundry/records/entities/spaceships/*.xml`: ship entities + default loadouts
+//! - `libs/foundry/records/entities/scitem/ships/**.xml`: weapons, shields, armor, missiles, components
+//! - `libs/foundry/records/ammoparams/vehicle/**.xml`: projectile damage/speed
+//! - `Scripts/Vehicles/Implementations/Xml/*.xml`: hull parts and item port sizes
+//!
+//! Reading `Data.p4k` directly is not supported: the archive stores the records
+//! in a binary DataForge database, which must first be unpacked with unp4k and
+//! converted with unforge. A p4k path is only accepted when an extracted `Data`
+//! directory sits next to it.
+
+use super::{
+    GameData, LoadProgress, LoadReport, Missile, Shield, Ship, SubPort, Weapon, WeaponHardpoint,
+};
+use super::json_loader::damage_type_label;
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const RECORDS_DIR: &str = "libs/foundry/records";
+const SHIPS_DIR: &str = "libs/foundry/records/entities/spaceships";
+const ITEMS_DIR: &str = "libs/foundry/records/entities/scitem/ships";
+const AMMO_DIR: &str = "libs/foundry/records/ammoparams/vehicle";
+
+/// Locate the extracted `Data` root for a user-supplied path
+///
+/// Accepts the `Data` directory itself, a directory containing it (unp4k output or
+/// the game's `LIVE`/`PTU` folder), or a `Data.p4k` with an extracted `Data` beside it.
+/// A bare `Data.p4k` is rejected (raw archives are not read).
+pub fn find_data_root(path: &Path) -> Result<PathBuf, String> {
+    let base = if path.is_file() {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+
+    for candidate in [base.to_path_buf(), base.join("Data"), base.join("data")] {
+        if candidate.join(RECORDS_DIR).is_dir() {
+            return Ok(candidate);
+        }
+    }
+
+    if path.is_file() || base.join("Data.p4k").is_file() {
+        return Err(format!(
+            "{:?} has no extracted records, and Data.p4k can't be read directly; unpack it with unp4k and convert Game.dcb with unforge first",
+            base
+        ));
+    }
+    Err(format!("No game records found in {:?}", path))
+}
+
+/// True if the directory is an extracted `Data` root
+pub fn is_data_root(path: &Path) -> bool {
+    path.join(RECORDS_DIR).is_dir()
+}
+
+/// Item classes from `scitem/ships`, keyed by lowercase class name
+#[derive(Default)]
+struct ItemIndex {
+    weapons: HashMap<String, PendingWeapon>,
+    shields: HashMap<String, Shield>,
+    missiles: HashMap<String, Missile>,
+    armor: HashMap<String, Armor>,
+    /// Item size by class name (all items, used for mounts and sub ports)
+    sizes: HashMap<String, i32>,
+    /// Health by class name (components counted into ship HP pools)
+    health: HashMap<String, f64>,
+    /// GUID -> class name, for loadout entries that only carry a reference
+    guids: HashMap<String, String>,
+}
+
+/// Weapon whose damage still lives in a separate ammo record
+struct PendingWeapon {
+    weapon: Weapon,
+    ammo_ref: String,
+}
+
+struct Armor {
+    hp: f64,
+    damage_mult: [f64; 3],
+    resist: [f64; 3],
+}
+
+/// Projectile data from `ammoparams`
+struct Ammo {
+    damage: [f64; 3],
+    speed: f64,
+    lifetime: f64,
+}
+
+fn attr_f64(node: Node, name: &str) -> Option<f64> {
+    node.attribute(name).and_then(|v| v.parse().ok())
+}
+
+fn find<'a, 'i>(node: Node<'a, 'i>, tag: &str) -> Option<Node<'a, 'i>> {
+    node.descendants().find(|n| n.has_tag_name(tag))
+}
+
+/// Physical/energy/distortion from the first `DamageInfo` under a node
+fn damage_info(node: Node) -> [f64; 3] {
+    find(node, "DamageInfo").map_or([0.0; 3], |info| {
+        [
+            attr_f64(info, "DamagePhysical").unwrap_or(0.0),
+            attr_f64(info, "DamageEnergy").unwrap_or(0.0),
+            attr_f64(info, "DamageDistortion").unwrap_or(0.0),
+        ]
+    })
+}
+
+/// Min/max pairs of a shield resistance/absorption list (physical, energy, distortion order)
+fn shield_ranges(node: Node, list: &str, entry: &str, default: [f64; 3]) -> [f64; 3] {
+    let mut values = default;
+    if let Some(list) = find(node, list) {
+        let entries = list.children().filter(|n| n.has_tag_name(entry));
+        for (value, entry) in values.iter_mut().zip(entries) {
+            *value = attr_f64(entry, "Max").unwrap_or(*value);
+        }
+    }
+    values
+}
+
+fn class_name(path: &Path) -> String {
+    path.file_stem().map(|s| s.to_string_lossy().to_lowercase()).unwrap_or_default()
+}
+
+/// All `.xml` files under a directory (recursive, sorted for stable output)
+fn xml_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("xml")) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Read and parse one XML file, recording failures against its path relative to the data root
+fn with_document<T>(
+    root: &Path,
+    path: &Path,
+    report: &mut LoadReport,
+    parse: impl FnOnce(Node) -> T,
+) -> Option<T> {
+    let file = path.strip_prefix(root).unwrap_or(path).to_string_lossy().to_string();
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => {
+            report.warn(&file, format!("Could not read file: {}", e));
+            return None;
+        }
+    };
+    match Document::parse(&text) {
+        Ok(doc) => Some(parse(doc.root_element())),
+        Err(e) => {
+            report.warn(&file, format!("Failed to parse: {}", e));
+            None
+        }
+    }
+}
+
+fn load_ammo(root: &Path, report: &mut LoadReport) -> HashMap<String, Ammo> {
+    let mut ammo = HashMap::new();
+    for path in xml_files(&root.join(AMMO_DIR)) {
+        let parsed = with_document(root, &path, report, |doc| {
+            let guid = doc.attribute("__ref")?.to_lowercase();
+            let projectile = find(doc, "projectileParams").unwrap_or(doc);
+            Some((guid, Ammo {
+                damage: damage_info(projectile),
+                speed: attr_f64(doc, "speed").unwrap_or(0.0),
+                lifetime: attr_f64(doc, "lifetime").unwrap_or(0.0),
+            }))
+        });
+        if let Some(Some((guid, entry))) = parsed {
+            ammo.insert(guid, entry);
+        }
+    }
+    ammo
+}
+
+fn index_item(index: &mut ItemIndex, class: String, doc: Node) {
+    if let Some(guid) = doc.attribute("__ref") {
+        index.guids.insert(guid.to_lowercase(), class.clone());
+    }
+    let Some(attach) = find(doc, "AttachDef") else { return };
+    let kind = attach.attribute("Type").unwrap_or("");
+    let size = attach.attribute("Size").and_then(|s| s.parse().ok()).unwrap_or(0);
+    index.sizes.insert(class.clone(), size);
+
+    if let Some(health) = find(doc, "SHealthComponentParams").and_then(|h| attr_f64(h, "Health")) {
+        index.health.insert(class.clone(), health);
+    }
+
+    match kind {
+        "WeaponGun" => {
+            let Some(params) = find(doc, "SCItemWeaponComponentParams") else { return };
+            // Rapid/single fire actions carry the rate; sequences nest them
+            let fire_rate = params.descendants()
+                .find_map(|n| if n.tag_name().name().starts_with("SWeaponActionFire") { attr_f64(n, "fireRate") } else { None })
+                .unwrap_or(0.0);
+            let heat_per_shot = params.descendants()
+                .find_map(|n| if n.tag_name().name().starts_with("SWeaponActionFire") { attr_f64(n, "heatPerShot") } else { None })
+                .unwrap_or(0.0);
+            let ammo_ref = find(doc, "SAmmoContainerComponentParams")
+                .and_then(|a| a.attribute("ammoParamsRecord"))
+                .unwrap_or("")
+                .to_lowercase();
+            let power_consumption = find(doc, "EntityComponentPowerConnection")
+                .and_then(|p| attr_f64(p, "PowerDraw"))
+                .unwrap_or(0.0);
+
+            index.weapons.insert(class.clone(), PendingWeapon {
+                weapon: Weapon {
+                    id: 0,
+                    display_name: class.clone(),
+                    filename: class,
+                    size,
+                    damage_type: String::new(),
+                    sustained_dps: 0.0,
+                    power_consumption,
+                    weapon_type: "gun".to_string(),
+                    restricted_to: Vec::new(),
+                    ship_exclusive: false,
+                    manufacturer: String::new(),
+                    manufacturer_code: String::new(),
+                    damage_physical: 0.0,
+                    damage_energy: 0.0,
+                    damage_distortion: 0.0,
+                    base_penetration_distance: 2.0,
+                    near_radius: 0.1,
+                    far_radius: 0.2,
+                    fire_rate,
+                    damage_per_shot: 0.0,
+                    speed: 0.0,
+                    max_range: 0.0,
+                    heat_per_shot,
+                },
+                ammo_ref,
+            });
+        }
+        "Shield" => {
+            let Some(params) = find(doc, "SCItemShieldGeneratorParams") else { return };
+            let resist = shield_ranges(params, "ShieldResistance", "SShieldResistance", [0.0; 3]);
+            let absorb = shield_ranges(params, "ShieldAbsorption", "SShieldAbsorption", [0.225, 1.0, 1.0]);
+            index.shields.insert(class.clone(), Shield {
+                id: 0,
+                display_name: class.clone(),
+                internal_name: class,
+                size,
+                max_hp: attr_f64(params, "MaxShieldHealth").unwrap_or(0.0),
+                regen: attr_f64(params, "MaxShieldRegen").unwrap_or(0.0),
+                resist_physical: resist[0],
+                resist_energy: resist[1],
+                resist_distortion: resist[2],
+                absorb_physical: absorb[0],
+                absorb_energy: absorb[1],
+                absorb_distortion: absorb[2],
+                damaged_regen_delay: attr_f64(params, "DamagedRegenDelay").unwrap_or(0.0),
+                downed_regen_delay: attr_f64(params, "DownedRegenDelay").unwrap_or(0.0),
+            });
+        }
+        "Armor" => {
+            let Some(params) = find(doc, "SCItemVehicleArmorParams") else { return };
+            let multiplier = find(params, "damageMultiplier").map_or([1.0; 3], damage_info);
+            let resistance = |kind: &str| {
+                find(doc, kind).and_then(|r| attr_f64(r, "Multiplier")).unwrap_or(1.0)
+            };
+            index.armor.insert(class, Armor {
+                hp: find(doc, "SHealthComponentParams").and_then(|h| attr_f64(h, "Health")).unwrap_or(0.0),
+                damage_mult: multiplier,
+                resist: [
+                    resistance("PhysicalResistance"),
+                    resistance("EnergyResistance"),
+                    resistance("DistortionResistance"),
+                ],
+            });
+        }
+        "Missile" | "Torpedo" | "Bomb" => {
+            let Some(params) = find(doc, "SCItemMissileParams") else { return };
+            // Missile damage sits in the explosion block
+            let explosion = find(params, "explosionParams");
+            let damage = explosion.map_or([0.0; 3], damage_info);
+            let targeting = find(params, "targetingParams");
+            index.missiles.insert(class.clone(), Missile {
+                id: 0,
+                name: class.clone(),
+                display_name: class,
+                size,
+                missile_type: kind.to_lowercase(),
+                tracking_type: targeting
+                    .and_then(|t| t.attribute("trackingSignalType"))
+                    .unwrap_or("Unknown")
+                    .to_string(),
+                damage_physical: damage[0],
+                damage_energy: damage[1],
+                damage_distortion: damage[2],
+                explosion_min_radius: explosion.and_then(|e| attr_f64(e, "minRadius")).unwrap_or(0.0),
+                explosion_max_radius: explosion.and_then(|e| attr_f64(e, "maxRadius")).unwrap_or(0.0),
+                max_lifetime: attr_f64(params, "maxLifetime").unwrap_or(0.0),
+                arm_time: attr_f64(params, "armTime").unwrap_or(0.0),
+                lock_time: targeting.and_then(|t| attr_f64(t, "lockTime")).unwrap_or(0.0),
+            });
+        }
+        _ => {}
+    }
+}
+
+fn load_items(root: &Path, ammo: &HashMap<String, Ammo>, report: &mut LoadReport) -> ItemIndex {
+    let mut index = ItemIndex::default();
+    for path in xml_files(&root.join(ITEMS_DIR)) {
+        let class = class_name(&path);
+        with_document(root, &path, report, |doc| index_item(&mut index, class, doc));
+    }
+
+    // Fill in projectile damage; guns without damaging ammo (mining/tractor/salvage) are dropped
+    index.weapons.retain(|_, pending| {
+        let Some(ammo) = ammo.get(&pending.ammo_ref) else { return false };
+        let weapon = &mut pending.weapon;
+        let [physical, energy, distortion] = ammo.damage;
+        weapon.damage_physical = physical;
+        weapon.damage_energy = energy;
+        weapon.damage_distortion = distortion;
+        weapon.damage_per_shot = physical + energy + distortion;
+        weapon.damage_type = damage_type_label(physical, energy, distortion).to_string();
+        weapon.sustained_dps = weapon.damage_per_shot * weapon.fire_rate / 60.0;
+        weapon.speed = ammo.speed;
+        weapon.max_range = ammo.speed * ammo.lifetime;
+        weapon.damage_per_shot > 0.0
+    });
+    index
+}
+
+/// Item port definition from a vehicle implementation XML
+struct PortDef {
+    max_size: i32,
+    /// (type, subtypes) pairs from `<Types>`
+    types: Vec<(String, String)>,
+}
+
+impl PortDef {
+    fn has_type(&self, kind: &str) -> bool {
+        self.types.iter().any(|(t, _)| t == kind)
+    }
+
+    fn has_subtype(&self, fragment: &str) -> bool {
+        self.types.iter().any(|(_, s)| s.contains(fragment))
+    }
+}
+
+/// Hull HP and item ports of a vehicle implementation
+#[derive(Default)]
+struct Implementation {
+    hull_hp: f64,
+    ports: HashMap<String, PortDef>,
+}
+
+fn load_implementation(root: &Path, definition: &str, report: &mut LoadReport) -> Option<Implementation> {
+    let path = [root.join(definition), root.join(definition.to_lowercase())]
+        .into_iter()
+        .find(|p| p.is_file())?;
+
+    with_document(root, &path, report, |doc| {
+        let mut implementation = Implementation::default();
+        // The root part is the hull body; child parts are separately damageable pieces
+        if let Some(body) = find(doc, "Parts").and_then(|parts| parts.children().find(|n| n.has_tag_name("Part"))) {
+            implementation.hull_hp = attr_f64(body, "damageMax").unwrap_or(0.0);
+        }
+
+        for part in doc.descendants().filter(|n| n.has_tag_name("Part")) {
+            let Some(port) = part.children().find(|n| n.has_tag_name("ItemPort")) else { continue };
+            let types = port.descendants()
+                .filter(|n| n.has_tag_name("Type"))
+                .map(|t| (
+                    t.attribute("type").unwrap_or("").to_string(),
+                    t.attribute("subtypes").unwrap_or("").to_string(),
+                ))
+                .collect();
+            implementation.ports.insert(part.attribute("name").unwrap_or("").to_lowercase(), PortDef {
+                max_size: port.attribute("maxSize").and_then(|s| s.parse().ok()).unwrap_or(0),
+                types,
+            });
+        }
+        implementation
+    })
+}
+
+/// One entry of a default loadout (ports nest for mounts, turrets and racks)
+struct LoadoutEntry {
+    port: String,
+    class: String,
+    children: Vec<LoadoutEntry>,
+}
+
+impl LoadoutEntry {
+    /// This entry and everything mounted below it
+    fn flatten(&self) -> Vec<&LoadoutEntry> {
+        let mut entries = vec![self];
+        for child in &self.children {
+            entries.extend(child.flatten());
+        }
+        entries
+    }
+}
+
+const LOADOUT_ENTRY: &str = "SItemPortLoadoutEntryParams";
+const NULL_GUID: &str = "00000000-0000-0000-0000-000000000000";
+
+fn collect_entries(node: Node, index: &ItemIndex, out: &mut Vec<LoadoutEntry>) {
+    for child in node.children().filter(|n| n.is_element()) {
+        if !child.has_tag_name(LOADOUT_ENTRY) {
+            collect_entries(child, index, out);
+            continue;
+        }
+
+        // Entries name their item by class, or only by GUID reference
+        let class = match child.attribute("entityClassName").filter(|c| !c.is_empty()) {
+            Some(class) => class.to_lowercase(),
+            None => child.attribute("entityClassReference")
+                .filter(|r| *r != NULL_GUID)
+                .and_then(|r| index.guids.get(&r.to_lowercase()))
+                .cloned()
+                .unwrap_or_default(),
+        };
+        let mut entry = LoadoutEntry {
+            port: child.attribute("itemPortName").unwrap_or("").to_lowercase(),
+            class,
+            children: Vec::new(),
+        };
+        collect_entries(child, index, &mut entry.children);
+        out.push(entry);
+    }
+}
+
+fn hardpoint_category(port: &str, def: Option<&PortDef>) -> Option<&'static str> {
+    let has = |kind: &str| def.is_some_and(|d| d.has_type(kind));
+    if port.contains("torpedo") {
+        Some("torpedo")
+    } else if port.contains("bomb") || has("BombLauncher") {
+        Some("bomb")
+    } else if port.contains("missile") || has("MissileLauncher") {
+        Some("missile")
+    } else if port.contains("pdc") || def.is_some_and(|d| d.has_subtype("PDC")) {
+        Some("pdc")
+    } else if has("Turret") || has("TurretBase") {
+        match def {
+            Some(d) if d.has_subtype("Manned") => Some("manned_turret"),
+            Some(d) if d.has_subtype("NoseMounted") || d.has_subtype("Gimbal") => Some("pilot"),
+            _ if port.contains("remote") => Some("remote_turret"),
+            _ => Some("manned_turret"),
+        }
+    } else if has("WeaponGun") || port.contains("weapon") || port.contains("hardpoint_gun") {
+        Some("pilot")
+    } else {
+        None
+    }
+}
+
+/// Component HP pool a loadout port feeds, by port name (same rules as the extraction scripts)
+fn component_pool(port: &str) -> Option<&'static str> {
+    if port.contains("turret") {
+        Some("turret")
+    } else if port.contains("powerplant") || port.contains("power_plant") {
+        Some("powerplant")
+    } else if port.contains("cooler") {
+        Some("cooler")
+    } else if port.contains("shield_generator") || port.contains("shieldgenerator") {
+        Some("shield_gen")
+    } else if port.contains("quantum") {
+        Some("qd")
+    } else if port.contains("thruster") {
+        Some("thruster")
+    } else {
+        None
+    }
+}
+
+fn build_ship(filename: String, hull_hp: f64, implementation: &Implementation, loadout: &[LoadoutEntry], index: &ItemIndex) -> Ship {
+    let mut ship = Ship {
+        display_name: GameData::format_ship_name(&filename),
+        filename,
+        hull_hp,
+        ..Default::default()
+    };
+
+    // (total HP, count) per component pool
+    let mut pools: HashMap<&str, (f64, i32)> = HashMap::new();
+    for entry in loadout.iter().flat_map(|e| e.flatten()) {
+        if let (Some(pool), Some(hp)) = (component_pool(&entry.port), index.health.get(&entry.class)) {
+            let totals = pools.entry(pool).or_default();
+            totals.0 += hp;
+            totals.1 += 1;
+            if pool == "thruster" {
+                let split = if entry.port.contains("retro") {
+                    &mut ship.thruster_retro_hp
+                } else if entry.port.contains("vtol") {
+                    &mut ship.thruster_vtol_hp
+                } else if entry.port.contains("mav") {
+                    &mut ship.thruster_mav_hp
+                } else {
+                    &mut ship.thruster_main_hp
+                };
+                *split += *hp as i32;
+            }
+        }
+    }
+    let pool = |name: &str| pools.get(name).map_or((0, 0), |&(hp, count)| (hp as i32, count));
+    (ship.thruster_total_hp, ship.thruster_count) = pool("thruster");
+    (ship.turret_total_hp, ship.turret_count) = pool("turret");
+    (ship.powerplant_total_hp, ship.powerplant_count) = pool("powerplant");
+    (ship.cooler_total_hp, ship.cooler_count) = pool("cooler");
+    (ship.shield_gen_total_hp, ship.shield_gen_count) = pool("shield_gen");
+    (ship.qd_total_hp, ship.qd_count) = pool("qd");
+
+    for entry in loadout {
+        let def = implementation.ports.get(&entry.port);
+
+        if let Some(armor) = index.armor.get(&entry.class) {
+            ship.armor_hp = armor.hp;
+            [ship.armor_damage_mult_physical, ship.armor_damage_mult_energy, ship.armor_damage_mult_distortion] = armor.damage_mult;
+            [ship.armor_resist_physical, ship.armor_resist_energy, ship.armor_resist_distortion] = armor.resist;
+            continue;
+        }
+
+        if let Some(shield) = index.shields.get(&entry.class) {
+            ship.shield_count += 1;
+            ship.max_shield_size = ship.max_shield_size.max(def.map_or(shield.size, |d| d.max_size.max(shield.size)));
+            if ship.default_shield_ref.is_empty() {
+                ship.default_shield_ref = entry.class.clone();
+            }
+            continue;
+        }
+
+        let Some(category) = hardpoint_category(&entry.port, def) else { continue };
+
+        let mut sub_ports: Vec<SubPort> = entry.flatten().into_iter()
+            .filter(|e| index.weapons.contains_key(&e.class) || index.missiles.contains_key(&e.class))
+            .map(|e| SubPort {
+                size: index.sizes.get(&e.class).copied().unwrap_or(0),
+                default_weapon: Some(e.class.clone()),
+            })
+            .collect();
+
+        let max_size = def.map(|d| d.max_size)
+            .filter(|&s| s > 0)
+            .or_else(|| index.sizes.get(&entry.class).copied())
+            .unwrap_or_else(|| sub_ports.iter().map(|sp| sp.size).max().unwrap_or(0));
+        if sub_ports.is_empty() {
+            sub_ports.push(SubPort { size: max_size, default_weapon: None });
+        }
+
+        // A weapon sitting directly in the port has no mount
+        let mount_name = if index.weapons.contains_key(&entry.class) { String::new() } else { entry.class.clone() };
+        let gimbal_type = if mount_name.contains("gimbal") {
+            "Gimbal"
+        } else if mount_name.contains("turret") || category.ends_with("turret") {
+            "Turret"
+        } else {
+            "Fixed"
+        };
+
+        ship.weapon_hardpoints.push(WeaponHardpoint {
+            slot_number: ship.weapon_hardpoints.len() as i32 + 1,
+            port_name: entry.port.clone(),
+            max_size,
+            gimbal_type: gimbal_type.to_string(),
+            control_type: category.to_string(),
+            category: category.to_string(),
+            mount_name,
+            compatible_mounts: Vec::new(),
+            sub_ports,
+        });
+    }
+
+    let pilot_sizes: Vec<String> = ship.weapon_hardpoints.iter()
+        .filter(|hp| hp.category == "pilot")
+        .flat_map(|hp| hp.sub_ports.iter().map(|sp| sp.size.to_string()))
+        .collect();
+    ship.pilot_weapon_count = pilot_sizes.len() as i32;
+    ship.pilot_weapon_sizes = pilot_sizes.join(",");
+    ship
+}
+
+fn load_ships(root: &Path, index: &ItemIndex, data: &mut GameData, report: &mut LoadReport) {
+    for path in xml_files(&root.join(SHIPS_DIR)) {
+        let filename = class_name(&path);
+        // Templates aren't ships; AI variants are kept and hidden by `VariantFilter`
+        if filename.contains("template") {
+            continue;
+        }
+
+        let parsed = with_document(root, &path, report, |doc| {
+            let mut loadout = Vec::new();
+            if let Some(default_loadout) = find(doc, "SEntityComponentDefaultLoadoutParams") {
+                collect_entries(default_loadout, index, &mut loadout);
+            }
+            let definition = find(doc, "VehicleComponentParams")
+                .and_then(|v| v.attribute("vehicleDefinition"))
+                .map(str::to_string);
+            (loadout, definition)
+        });
+        let Some((loadout, definition)) = parsed else { continue };
+        // Entities without a vehicle definition are props/ground items, not ships
+        let Some(definition) = definition else { continue };
+
+        let file = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().to_string();
+        let implementation = match load_implementation(root, &definition, report) {
+            Some(implementation) if implementation.hull_hp > 0.0 => implementation,
+            _ => {
+                report.warn(&file, format!("Skipped: no hull data in {}", definition));
+                continue;
+            }
+        };
+
+        let ship = build_ship(filename, implementation.hull_hp, &implementation, &loadout, index);
+        data.ships.insert(ship.display_name.clone(), Arc::new(ship));
+    }
+}
+
+/// Extract game data from an unp4k'd `Data` directory (same reporting contract as `GameData::load_with_report`)
+pub fn load_p4k(
+    data_root: &Path,
+    on_progress: &(dyn Fn(LoadProgress) + Sync),
+) -> (GameData, LoadReport) {
+    let mut data = GameData::default();
+    let mut report = LoadReport::default();
+    let progress = |stage: &str, completed: usize| {
+        on_progress(LoadProgress { stage: stage.to_string(), completed, total: 3 });
+    };
+
+    let ammo = load_ammo(data_root, &mut report);
+    progress("ammo", 1);
+
+    let index = load_items(data_root, &ammo, &mut report);
+    progress("items", 2);
+
+    load_ships(data_root, &index, &mut data, &mut report);
+    progress("ships", 3);
+
+    if data.ships.is_empty() {
+        report.error(SHIPS_DIR, "No ships could be extracted".to_string());
+    }
+
+    data.weapons = index.weapons.into_iter().map(|(k, p)| (k, Arc::new(p.weapon))).collect();
+    data.shields = index.shields.into_iter().map(|(k, s)| (k, Arc::new(s))).collect();
+    data.missiles = index.missiles.into_iter().map(|(k, m)| (k, Arc::new(m))).collect();
+
+    data.resolve_default_shields(&mut report);
+    (data, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AMMO: &str = r#"<AmmoParams __ref="AAAA-1111" speed="1200" lifetime="2">
+        <projectileParams><BulletProjectileParams><damage>
+            <DamageInfo DamagePhysical="0" DamageEnergy="30" DamageDistortion="0"/>
+        </damage></BulletProjectileParams></projectileParams>
+    </AmmoParams>"#;
+
+    const WEAPON: &str = r#"<EntityClassDefinition __ref="BBBB-2222"><Components>
+        <SAttachableComponentParams><AttachDef Type="WeaponGun" Size="3"/></SAttachableComponentParams>
+        <SCItemWeaponComponentParams><fireActions>
+            <SWeaponActionFireRapidParams fireRate="600" heatPerShot="1.5"/>
+        </fireActions></SCItemWeaponComponentParams>
+        <SAmmoContainerComponentParams ammoParamsRecord="aaaa-1111"/>
+    </Components></EntityClassDefinition>"#;
+
+    const SHIELD: &str = r#"<EntityClassDefinition __ref="CCCC-3333"><Components>
+        <SAttachableComponentParams><AttachDef Type="Shield" Size="1"/></SAttachableComponentParams>
+        <SHealthComponentParams Health="500"/>
+        <SCItemShieldGeneratorParams MaxShieldHealth="4000" MaxShieldRegen="400" DamagedRegenDelay="5" DownedRegenDelay="10">
+            <ShieldResistance>
+                <SShieldResistance Min="0" Max="0.1"/><SShieldResistance Min="0" Max="0"/><SShieldResistance Min="0" Max="0"/>
+            </ShieldResistance>
+        </SCItemShieldGeneratorParams>
+    </Components></EntityClassDefinition>"#;
+
+    const SHIP: &str = r#"<EntityClassDefinition __ref="DDDD-4444"><Components>
+        <VehicleComponentParams vehicleDefinition="Scripts/Vehicles/Implementations/Xml/AEGS_Gladius.xml"/>
+        <SEntityComponentDefaultLoadoutParams><loadout><SItemPortLoadoutManualParams><entries>
+            <SItemPortLoadoutEntryParams itemPortName="hardpoint_shield_generator" entityClassName="SHLD_GODI_S01_FR66"/>
+            <SItemPortLoadoutEntryParams itemPortName="hardpoint_weapon_left" entityClassName="" entityClassReference="BBBB-2222"/>
+        </entries></SItemPortLoadoutManualParams></loadout></SEntityComponentDefaultLoadoutParams>
+    </Components></EntityClassDefinition>"#;
+
+    const IMPLEMENTATION: &str = r#"<Vehicle name="AEGS_Gladius"><Parts>
+        <Part name="Body" damageMax="5000"><Parts>
+            <Part name="hardpoint_weapon_left">
+                <ItemPort maxSize="3"><Types><Type type="WeaponGun" subtypes="Gun"/></Types></ItemPort>
+            </Part>
+        </Parts></Part>
+    </Parts></Vehicle>"#;
+
+    fn write(root: &Path, file: &str, contents: &str) {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_game_record_fixture() {
+        let dir = std::env::temp_dir().join(format!("ship-lens-p4k-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        // A bare Data.p4k can't be read; its records must be extracted first
+        write(&dir, "Data.p4k", "");
+        let error = find_data_root(&dir.join("Data.p4k")).unwrap_err();
+        assert!(error.contains("can't be read directly") && error.contains("unforge"), "{}", error);
+        assert!(find_data_root(&dir).is_err());
+
+        let root = dir.join("Data");
+        write(&root, &format!("{}/energy_s3.xml", AMMO_DIR), AMMO);
+        write(&root, &format!("{}/weapons/klwe_laserrepeater_s3.xml", ITEMS_DIR), WEAPON);
+        write(&root, &format!("{}/shields/shld_godi_s01_fr66.xml", ITEMS_DIR), SHIELD);
+        write(&root, &format!("{}/aegs_gladius.xml", SHIPS_DIR), SHIP);
+        write(&root, "Scripts/Vehicles/Implementations/Xml/AEGS_Gladius.xml", IMPLEMENTATION);
+        assert_eq!(find_data_root(&dir.join("Data.p4k")), Ok(root.clone()));
+        assert!(is_data_root(&root));
+
+        let (data, report) = load_p4k(&root, &|_| {});
+        assert!(report.errors.is_empty() && report.warnings.is_empty(), "{:?}", report.warnings);
+
+        let weapon = &data.weapons["klwe_laserrepeater_s3"];
+        assert_eq!((weapon.size, weapon.damage_type.as_str(), weapon.damage_per_shot), (3, "Energy", 30.0));
+        assert_eq!((weapon.sustained_dps, weapon.speed, weapon.max_range), (300.0, 1200.0, 2400.0));
+
+        let shield = &data.shields["shld_godi_s01_fr66"];
+        assert_eq!((shield.size, shield.max_hp, shield.regen), (1, 4000.0, 400.0));
+        assert_eq!([shield.resist_physical, shield.absorb_physical], [0.1, 0.225]);
+
+        let ship = data.ships.values().next().unwrap();
+        assert_eq!((ship.filename.as_str(), ship.hull_hp), ("aegs_gladius", 5000.0));
+        assert_eq!((ship.shield_count, ship.default_shield.as_deref()), (1, Some("shld_godi_s01_fr66")));
+        assert_eq!((ship.shield_gen_total_hp, ship.shield_gen_count), (500, 1));
+        let hp = &ship.weapon_hardpoints[0];
+        assert_eq!((hp.port_name.as_str(), hp.category.as_str(), hp.max_size), ("hardpoint_weapon_left", "pilot", 3));
+        assert_eq!(hp.sub_ports[0].default_weapon.as_deref(), Some("klwe_laserrepeater_s3"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
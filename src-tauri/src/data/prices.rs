@@ -0,0 +1,133 @@
+//! Ship and item prices
+//!
+//! `prices.csv` rows are `class_name,auec[,pledge]`: the in-game aUEC price (empty
+//! if it can't be bought in game) and whether it can be pledged for (`yes`/`no`,
+//! empty = unknown). Class names are ship and weapon filenames or shield internal
+//! names. A `prices.csv` in the data directory is loaded with the game data; one
+//! imported into the app config dir is applied on top of it.
+
+use super::{GameData, LoadReport};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub const PRICES_FILE: &str = "prices.csv";
+
+/// Price and availability of a ship or item
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Price {
+    /// In-game purchase price (None = not sold in game)
+    pub auec: Option<f64>,
+    /// Available for pledge purchase (None = unknown)
+    pub pledge_available: Option<bool>,
+}
+
+fn parse_flag(value: &str) -> Result<Option<bool>, ()> {
+    match value.to_lowercase().as_str() {
+        "" => Ok(None),
+        "yes" | "true" | "1" => Ok(Some(true)),
+        "no" | "false" | "0" => Ok(Some(false)),
+        _ => Err(()),
+    }
+}
+
+/// Parse `prices.csv` rows keyed by lowercase class name (bad rows are reported and skipped)
+pub fn parse(contents: &str, report: &mut LoadReport) -> HashMap<String, Price> {
+    let mut rows = HashMap::new();
+    for (line_no, line) in contents.lines().enumerate().skip(1) {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let (class_name, auec, pledge) = match fields[..] {
+            [class_name, auec] => (class_name, auec, ""),
+            [class_name, auec, pledge] => (class_name, auec, pledge),
+            _ => {
+                report.warn(PRICES_FILE, format!("Line {}: expected 2 or 3 columns", line_no + 1));
+                continue;
+            }
+        };
+        let auec = if auec.is_empty() {
+            None
+        } else {
+            match auec.replace('_', "").parse::<f64>() {
+                Ok(price) if price >= 0.0 => Some(price),
+                _ => {
+                    report.warn(PRICES_FILE, format!("Line {}: invalid aUEC price '{}'", line_no + 1, auec));
+                    continue;
+                }
+            }
+        };
+        let Ok(pledge_available) = parse_flag(pledge) else {
+            report.warn(PRICES_FILE, format!("Line {}: invalid pledge flag '{}'", line_no + 1, pledge));
+            continue;
+        };
+
+        rows.insert(class_name.to_lowercase(), Price { auec, pledge_available });
+    }
+    rows
+}
+
+/// Merge parsed rows into the data, replacing earlier prices for the same class
+///
+/// Rows matching no ship, weapon or shield are kept (the item may come from a
+/// later data update) but counted in a single warning.
+pub fn merge(data: &mut GameData, rows: HashMap<String, Price>, report: &mut LoadReport) {
+    let known = |class: &str| {
+        data.ships.values().any(|s| s.filename.eq_ignore_ascii_case(class))
+            || data.weapons.values().any(|w| w.filename.eq_ignore_ascii_case(class))
+            || data.shields.values().any(|s| s.internal_name.eq_ignore_ascii_case(class))
+    };
+    let unknown = rows.keys().filter(|class| !known(class)).count();
+    if unknown > 0 {
+        report.warn(PRICES_FILE, format!("{} rows match no ship, weapon or shield", unknown));
+    }
+    data.prices.extend(rows);
+}
+
+/// Apply `prices.csv` from `dir` (no-op if the file is absent)
+pub fn apply(data: &mut GameData, dir: &Path, report: &mut LoadReport) {
+    let Ok(contents) = std::fs::read_to_string(dir.join(PRICES_FILE)) else {
+        return;
+    };
+    let rows = parse(&contents, report);
+    merge(data, rows, report);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Ship;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_parse_prices() {
+        let mut report = LoadReport::default();
+        let rows = parse(
+            "class_name,auec,pledge\n\
+             AEGS_Gladius,1_320_000,yes\n\
+             klwe_laser_repeater_s3,23000\n\
+             anvl_hornet_f7c_mk2,,no\n\
+             drak_cutlass_black,cheap\n",
+            &mut report,
+        );
+        assert_eq!(rows["aegs_gladius"], Price { auec: Some(1_320_000.0), pledge_available: Some(true) });
+        assert_eq!(rows["klwe_laser_repeater_s3"].pledge_available, None);
+        assert_eq!(rows["anvl_hornet_f7c_mk2"].auec, None);
+        assert!(!rows.contains_key("drak_cutlass_black"));
+        assert_eq!(report.warnings.len(), 1);
+
+        let mut data = GameData::default();
+        data.ships.insert("Aegis Gladius".to_string(), Arc::new(Ship {
+            filename: "aegs_gladius".to_string(),
+            display_name: "Aegis Gladius".to_string(),
+            ..Default::default()
+        }));
+        merge(&mut data, rows, &mut report);
+        assert_eq!(data.price_of("AEGS_Gladius").and_then(|p| p.auec), Some(1_320_000.0));
+        // Weapon and Hornet rows match nothing in this data
+        assert_eq!(report.warnings.last().unwrap().message, "2 rows match no ship, weapon or shield");
+    }
+}
@@ -0,0 +1,156 @@
+//! Ship class/role metadata
+//!
+//! `ship_metadata.csv` rows are `filename,size_class,role,crew[,length_m[,qt_spool_s[,radar_m]]]`.
+//! A row applies to its ship and to every variant that extends its filename
+//! (`aegs_gladius` covers `aegs_gladius_valiant`); the longest matching row wins.
+//! The optional length only fills in ships whose game data has no hitbox size.
+//! The optional quantum spool time (seconds) may follow an empty length, and
+//! the optional radar range (meters) an empty spool time.
+
+use super::{GameData, LoadReport};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+pub const METADATA_FILE: &str = "ship_metadata.csv";
+
+/// Size classes accepted in the CSV
+pub const SIZE_CLASSES: [&str; 4] = ["small", "medium", "large", "capital"];
+
+#[derive(Debug, Clone)]
+struct ShipMetadata {
+    size_class: String,
+    role: String,
+    crew: i32,
+    length: f64,
+    spool: f64,
+    radar: f64,
+}
+
+/// Optional non-negative number column (0 if empty)
+fn optional(value: &str) -> Option<f64> {
+    if value.is_empty() {
+        return Some(0.0);
+    }
+    value.parse::<f64>().ok().filter(|v| *v >= 0.0)
+}
+
+fn parse(contents: &str, report: &mut LoadReport) -> HashMap<String, ShipMetadata> {
+    let mut rows = HashMap::new();
+    for (line_no, line) in contents.lines().enumerate().skip(1) {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let (filename, size_class, role, crew, length, spool, radar) = match fields[..] {
+            [filename, size_class, role, crew] => (filename, size_class, role, crew, "", "", ""),
+            [filename, size_class, role, crew, length] => (filename, size_class, role, crew, length, "", ""),
+            [filename, size_class, role, crew, length, spool] => (filename, size_class, role, crew, length, spool, ""),
+            [filename, size_class, role, crew, length, spool, radar] => (filename, size_class, role, crew, length, spool, radar),
+            _ => {
+                report.warn(METADATA_FILE, format!("Line {}: expected 4 to 7 columns", line_no + 1));
+                continue;
+            }
+        };
+        let size_class = size_class.to_lowercase();
+        if !SIZE_CLASSES.contains(&size_class.as_str()) {
+            report.warn(METADATA_FILE, format!("Line {}: unknown size class '{}'", line_no + 1, size_class));
+            continue;
+        }
+        let Ok(crew) = crew.parse::<i32>() else {
+            report.warn(METADATA_FILE, format!("Line {}: invalid crew '{}'", line_no + 1, crew));
+            continue;
+        };
+        let Some(length) = optional(length) else {
+            report.warn(METADATA_FILE, format!("Line {}: invalid length '{}'", line_no + 1, length));
+            continue;
+        };
+        let Some(spool) = optional(spool) else {
+            report.warn(METADATA_FILE, format!("Line {}: invalid quantum spool time '{}'", line_no + 1, spool));
+            continue;
+        };
+        let Some(radar) = optional(radar) else {
+            report.warn(METADATA_FILE, format!("Line {}: invalid radar range '{}'", line_no + 1, radar));
+            continue;
+        };
+
+        rows.insert(filename.to_lowercase(), ShipMetadata {
+            size_class,
+            role: role.to_lowercase(),
+            crew,
+            length,
+            spool,
+            radar,
+        });
+    }
+    rows
+}
+
+/// Most specific row for a ship filename (exact, else longest `prefix_` match)
+fn lookup<'a>(rows: &'a HashMap<String, ShipMetadata>, filename: &str) -> Option<&'a ShipMetadata> {
+    let filename = filename.to_lowercase();
+    let mut key = filename.as_str();
+    loop {
+        if let Some(row) = rows.get(key) {
+            return Some(row);
+        }
+        key = &key[..key.rfind('_')?];
+    }
+}
+
+/// Apply `ship_metadata.csv` from the data directory (no-op if the file is absent)
+pub fn apply(data: &mut GameData, data_dir: &Path, report: &mut LoadReport) {
+    let Ok(contents) = std::fs::read_to_string(data_dir.join(METADATA_FILE)) else {
+        return;
+    };
+    let rows = parse(&contents, report);
+
+    for ship in data.ships.values_mut() {
+        if let Some(meta) = lookup(&rows, &ship.filename) {
+            let ship = Arc::make_mut(ship);
+            ship.size_class = meta.size_class.clone();
+            ship.role = meta.role.clone();
+            ship.crew = meta.crew;
+            if ship.hitbox_length <= 0.0 {
+                ship.hitbox_length = meta.length;
+            }
+            if meta.spool > 0.0 {
+                ship.quantum_spool_time = meta.spool;
+            }
+            if meta.radar > 0.0 {
+                ship.sensor_range = meta.radar;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_lookup() {
+        let mut report = LoadReport::default();
+        let rows = parse(
+            "filename,size_class,role,crew\n\
+             anvl_hornet,small,fighter,1\n\
+             anvl_hornet_f7cr,small,reconnaissance,1,22.5\n\
+             anvl_hornet_f7a,small,fighter,1,,4.5\n\
+             anvl_hornet_f7r,small,reconnaissance,1,,,15000\n\
+             drak_cutlass_black,huge,multi-role,3\n",
+            &mut report,
+        );
+        assert_eq!(lookup(&rows, "anvl_hornet_f7c_wildfire").unwrap().role, "fighter");
+        assert_eq!(lookup(&rows, "anvl_hornet_f7cr_mk2").unwrap().role, "reconnaissance");
+        assert_eq!(lookup(&rows, "anvl_hornet_f7cr_mk2").unwrap().length, 22.5);
+        assert_eq!(lookup(&rows, "anvl_hornet_f7a_mk2").unwrap().spool, 4.5);
+        assert_eq!(lookup(&rows, "anvl_hornet_f7a_mk2").unwrap().length, 0.0);
+        assert_eq!(lookup(&rows, "anvl_hornet_f7r").unwrap().radar, 15000.0);
+        assert!(lookup(&rows, "anvl_arrow").is_none());
+        // Invalid size class is reported and skipped
+        assert!(lookup(&rows, "drak_cutlass_black").is_none());
+        assert_eq!(report.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,182 @@
+//! Critical part data and component counts from the parts extraction
+//!
+//! `ship_parts_comprehensive.csv` is written by `extract_ship_parts_comprehensive.py`
+//! and has many columns; `filename`, `fuse_penetration_mult`,
+//! `critical_explosion_chance` and the `*_count` component columns are read here,
+//! located by header name. Count columns are optional (older extractions lack
+//! them). Rows match ship filenames exactly (the extraction emits one row per vehicle).
+
+use super::{GameData, LoadReport, Ship};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+pub const PARTS_FILE: &str = "ship_parts_comprehensive.csv";
+
+/// Range `fuse_penetration_mult` is clamped to
+const FUSE_MULT_RANGE: (f64, f64) = (0.1, 10.0);
+
+/// Component count columns (see `count_field`)
+const COUNT_COLUMNS: [&str; 6] = [
+    "thruster_count", "turret_count", "powerplant_count", "cooler_count", "shield_gen_count", "qd_count",
+];
+
+/// Ship field for the `COUNT_COLUMNS` entry at `index`
+fn count_field(ship: &mut Ship, index: usize) -> &mut i32 {
+    match index {
+        0 => &mut ship.thruster_count,
+        1 => &mut ship.turret_count,
+        2 => &mut ship.powerplant_count,
+        3 => &mut ship.cooler_count,
+        4 => &mut ship.shield_gen_count,
+        _ => &mut ship.qd_count,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CriticalParts {
+    fuse_penetration_mult: f64,
+    critical_explosion_chance: f64,
+}
+
+/// One CSV row (None = column absent or value invalid)
+#[derive(Debug, Clone, Copy)]
+struct PartsRow {
+    critical: Option<CriticalParts>,
+    /// In `COUNT_COLUMNS` order
+    counts: [Option<i32>; 6],
+}
+
+fn parse_critical(fuse: &str, chance: &str, line: usize, report: &mut LoadReport) -> Option<CriticalParts> {
+    let fuse_penetration_mult = match fuse.parse::<f64>() {
+        Ok(mult) if mult > 0.0 => {
+            let (min, max) = FUSE_MULT_RANGE;
+            if !(min..=max).contains(&mult) {
+                report.warn(PARTS_FILE, format!("Line {}: fuse_penetration_mult {} clamped to {}..{}", line, mult, min, max));
+            }
+            mult.clamp(min, max)
+        }
+        _ => {
+            report.warn(PARTS_FILE, format!("Line {}: invalid fuse_penetration_mult '{}'", line, fuse));
+            return None;
+        }
+    };
+    let critical_explosion_chance = match chance.parse::<f64>() {
+        Ok(chance) if (0.0..=1.0).contains(&chance) => chance,
+        _ => {
+            report.warn(PARTS_FILE, format!("Line {}: invalid critical_explosion_chance '{}'", line, chance));
+            return None;
+        }
+    };
+    Some(CriticalParts { fuse_penetration_mult, critical_explosion_chance })
+}
+
+fn parse(contents: &str, report: &mut LoadReport) -> HashMap<String, PartsRow> {
+    let mut rows = HashMap::new();
+    let mut lines = contents.lines().enumerate();
+    let Some((_, header)) = lines.next() else {
+        return rows;
+    };
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    let column = |name: &str| columns.iter().position(|c| *c == name);
+    let Some(filename_col) = column("filename") else {
+        report.warn(PARTS_FILE, "missing filename column".to_string());
+        return rows;
+    };
+    let critical_cols = column("fuse_penetration_mult").zip(column("critical_explosion_chance"));
+    let count_cols = COUNT_COLUMNS.map(column);
+    if critical_cols.is_none() && count_cols.iter().all(Option::is_none) {
+        report.warn(PARTS_FILE, "missing fuse_penetration_mult/critical_explosion_chance and component count columns".to_string());
+        return rows;
+    }
+
+    for (line_no, line) in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() < columns.len() {
+            report.warn(PARTS_FILE, format!("Line {}: expected {} columns", line_no + 1, columns.len()));
+            continue;
+        }
+        let critical = critical_cols
+            .and_then(|(fuse, chance)| parse_critical(fields[fuse], fields[chance], line_no + 1, report));
+        let counts = count_cols.map(|col| {
+            let value = fields[col?];
+            match value.parse::<i32>() {
+                Ok(count) if count >= 0 => Some(count),
+                _ if value.is_empty() => None,
+                _ => {
+                    report.warn(PARTS_FILE, format!("Line {}: invalid component count '{}'", line_no + 1, value));
+                    None
+                }
+            }
+        });
+
+        rows.insert(fields[filename_col].to_lowercase(), PartsRow { critical, counts });
+    }
+    rows
+}
+
+/// Apply `ship_parts_comprehensive.csv` from the data directory (no-op if the file is absent)
+pub fn apply(data: &mut GameData, data_dir: &Path, report: &mut LoadReport) {
+    let Ok(contents) = std::fs::read_to_string(data_dir.join(PARTS_FILE)) else {
+        return;
+    };
+    let rows = parse(&contents, report);
+
+    for ship in data.ships.values_mut() {
+        if let Some(row) = rows.get(&ship.filename.to_lowercase()) {
+            let ship = Arc::make_mut(ship);
+            if let Some(parts) = row.critical {
+                ship.fuse_penetration_mult = parts.fuse_penetration_mult;
+                ship.critical_explosion_chance = parts.critical_explosion_chance;
+            }
+            for (i, count) in row.counts.into_iter().enumerate() {
+                if let Some(count) = count {
+                    *count_field(ship, i) = count;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_component_counts() {
+        let csv = "filename,fuse_penetration_mult,critical_explosion_chance,powerplant_count,cooler_count,qd_count\n\
+                   aegs_gladius,1.5,0.2,1,2,1\n\
+                   rsi_polaris,0,0.2,3,4,x\n";
+        let mut report = LoadReport::default();
+        let rows = parse(csv, &mut report);
+        let gladius = rows["aegs_gladius"];
+        assert_eq!(gladius.critical.map(|c| c.fuse_penetration_mult), Some(1.5));
+        assert_eq!(gladius.counts, [None, None, Some(1), Some(2), None, Some(1)]);
+
+        // A bad critical value or count drops only that value
+        let polaris = rows["rsi_polaris"];
+        assert!(polaris.critical.is_none());
+        assert_eq!(polaris.counts, [None, None, Some(3), Some(4), None, None]);
+        assert_eq!(report.warnings.len(), 2);
+
+        let dir = std::env::temp_dir().join(format!("ship-lens-parts-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(PARTS_FILE), csv).unwrap();
+        let mut data = GameData::default();
+        data.ships.insert("RSI Polaris".to_string(), Arc::new(Ship {
+            filename: "rsi_polaris".to_string(),
+            powerplant_total_hp: 9000,
+            ..Default::default()
+        }));
+        apply(&mut data, &dir, &mut LoadReport::default());
+        let ship = Arc::make_mut(data.ships.get_mut("RSI Polaris").unwrap());
+        ship.derive_instance_hp();
+        assert_eq!((ship.powerplant_count, ship.powerplant_instance_hp), (3, 3000.0));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,117 @@
+//! Ship Lens Data Directory
+//!
+//! Where game data is loaded from, in order: the `--data-dir` command line flag,
+//! the `SHIP_LENS_DATA_DIR` environment variable, the `data_dir.json` setting in
+//! the app config dir, then the search in `get_data_dir` (dev tree, bundled
+//! resources, install paths). Overrides are used as given, so a wrong path shows
+//! up as a load error instead of quietly falling back to the bundled data.
+
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const DATA_DIR_FILE: &str = "data_dir.json";
+
+const DATA_DIR_FLAG: &str = "--data-dir";
+
+/// Environment variable naming the data dir
+pub const DATA_DIR_ENV: &str = "SHIP_LENS_DATA_DIR";
+
+/// Saved data dir setting
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataDirSettings {
+    /// None = detect automatically
+    pub path: Option<PathBuf>,
+}
+
+/// What chose the data dir
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataDirSource {
+    Flag,
+    Env,
+    Setting,
+    Detected,
+}
+
+/// Data dir in use (returned by `get_data_location`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DataLocation {
+    pub path: PathBuf,
+    pub source: DataDirSource,
+}
+
+impl DataLocation {
+    /// Set on the command line or in the environment, which the setting can't change
+    pub fn is_forced(&self) -> bool {
+        matches!(self.source, DataDirSource::Flag | DataDirSource::Env)
+    }
+}
+
+/// Value of `--data-dir <path>` or `--data-dir=<path>` in `args`
+fn flag_value(args: impl IntoIterator<Item = String>) -> Option<PathBuf> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let value = if arg == DATA_DIR_FLAG {
+            args.next()
+        } else {
+            arg.strip_prefix(DATA_DIR_FLAG).and_then(|v| v.strip_prefix('=')).map(str::to_string)
+        };
+        if let Some(value) = value.filter(|v| !v.trim().is_empty()) {
+            return Some(PathBuf::from(value));
+        }
+    }
+    None
+}
+
+/// Data dir forced by the command line flag or the environment variable
+pub fn forced(args: impl IntoIterator<Item = String>, env: Option<String>) -> Option<DataLocation> {
+    flag_value(args)
+        .map(|path| DataLocation { path, source: DataDirSource::Flag })
+        .or_else(|| env.filter(|v| !v.trim().is_empty())
+            .map(|v| DataLocation { path: PathBuf::from(v), source: DataDirSource::Env }))
+}
+
+/// Load the saved setting (detect automatically if none saved)
+pub fn load(config_dir: &Path) -> DataDirSettings {
+    store::read_json(&config_dir.join(DATA_DIR_FILE)).ok().flatten().unwrap_or_default()
+}
+
+/// Save the setting (atomically, keeping backups)
+pub fn save(config_dir: &Path, settings: &DataDirSettings) -> Result<(), String> {
+    store::write_json(&config_dir.join(DATA_DIR_FILE), settings)
+}
+
+/// Data dir to load: a forced one, else the saved setting, else `detect()`
+pub fn resolve(forced: Option<DataLocation>, settings: &DataDirSettings, detect: impl FnOnce() -> PathBuf) -> DataLocation {
+    forced
+        .or_else(|| settings.path.clone().map(|path| DataLocation { path, source: DataDirSource::Setting }))
+        .unwrap_or_else(|| DataLocation { path: detect(), source: DataDirSource::Detected })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_dir_resolution() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(flag_value(args(&["ship-lens", "--data-dir", "/mnt/share/data"])), Some(PathBuf::from("/mnt/share/data")));
+        assert_eq!(flag_value(args(&["ship-lens", "--data-dir=D:\\SC Data"])), Some(PathBuf::from("D:\\SC Data")));
+        assert_eq!(flag_value(args(&["ship-lens", "--data-dir"])), None);
+        assert_eq!(flag_value(args(&["ship-lens", "--data-directory=x"])), None);
+
+        // Flag beats the environment, which beats the setting, which beats detection
+        let env = Some("/env/data".to_string());
+        let setting = DataDirSettings { path: Some(PathBuf::from("/saved/data")) };
+        let detect = || PathBuf::from("../data");
+        let flag = forced(args(&["ship-lens", "--data-dir", "/flag/data"]), env.clone());
+        assert_eq!(resolve(flag, &setting, detect).source, DataDirSource::Flag);
+        let from_env = forced(args(&["ship-lens"]), env);
+        assert!(from_env.as_ref().is_some_and(DataLocation::is_forced));
+        assert_eq!(resolve(from_env, &setting, detect).path, PathBuf::from("/env/data"));
+        assert_eq!(resolve(None, &setting, detect).source, DataDirSource::Setting);
+        assert_eq!(resolve(None, &DataDirSettings::default(), detect), DataLocation { path: PathBuf::from("../data"), source: DataDirSource::Detected });
+    }
+}
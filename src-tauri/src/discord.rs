@@ -0,0 +1,154 @@
+//! Ship Lens Discord Sharing
+//!
+//! Formats a TTK result as a compact, copy-ready Discord message (matchup line
+//! plus a code block with the phase times and loadout) and posts it to a
+//! channel webhook.
+
+use crate::download;
+use crate::ttk::TTKResult;
+use crate::CalcRequest;
+
+/// Discord rejects messages longer than this
+pub const MAX_MESSAGE_LEN: usize = 2000;
+
+/// Hosts that serve Discord webhooks
+const WEBHOOK_HOSTS: [&str; 4] = ["discord.com", "discordapp.com", "ptb.discord.com", "canary.discord.com"];
+
+fn seconds(value: f64) -> String {
+    if value.is_finite() && value >= 0.0 { format!("{:.1}s", value) } else { "∞".to_string() }
+}
+
+/// Render a result as a Discord message (matchup, phase times, loadout)
+///
+/// The loadout list is cut short rather than exceeding `MAX_MESSAGE_LEN`.
+pub fn format(request: &CalcRequest, result: &TTKResult) -> String {
+    let (attacker, target, shield) = match request {
+        CalcRequest::Loadout(r) => (r.attacker_ship.as_deref(), r.target_ship.as_str(), r.shield_name.as_deref()),
+        CalcRequest::Ships(r) => (Some(r.attacker.as_str()), r.target.as_str(), r.shield_name.as_deref()),
+    };
+
+    let mut header = match attacker {
+        Some(attacker) => format!("**{}** vs **{}**", attacker, target),
+        None => format!("vs **{}**", target),
+    };
+    if let Some(shield) = shield {
+        header.push_str(&format!(" ({})", shield));
+    }
+
+    let mut block = vec![
+        format!("TTK      {}", seconds(result.total_ttk)),
+        format!("DPS      {:.0}", result.effective_dps),
+        format!("Shields  {}{}", seconds(result.shield_time), if result.shields_breakable { "" } else { " (unbreakable)" }),
+        format!("Armor    {}", seconds(result.armor_time)),
+        format!("Hull     {}", seconds(result.hull_time)),
+    ];
+    if result.expected_ttk > 0.0 && (result.expected_ttk - result.total_ttk).abs() >= 0.05 {
+        block.push(format!("Expected {} (critical hits)", seconds(result.expected_ttk)));
+    }
+
+    let loadout: Vec<String> = result.weapon_breakdown.iter()
+        .map(|w| format!("{}x {}", w.count, w.weapon_name))
+        .chain(result.missile_breakdown.iter().map(|m| format!("{}x {}", m.count, m.missile_name)))
+        .collect();
+
+    let render = |lines: &[String], omitted: usize| {
+        let mut body = block.join("\n");
+        if !lines.is_empty() {
+            body.push_str("\n\nLoadout\n");
+            body.push_str(&lines.join("\n"));
+        }
+        if omitted > 0 {
+            body.push_str(&format!("\n(+{} more)", omitted));
+        }
+        format!("{}\n```\n{}\n```", header, body)
+    };
+
+    let mut shown = loadout.len();
+    loop {
+        let message = render(&loadout[..shown], loadout.len() - shown);
+        if message.chars().count() <= MAX_MESSAGE_LEN || shown == 0 {
+            return message;
+        }
+        shown -= 1;
+    }
+}
+
+/// Whether `url` is a Discord webhook URL (https, a Discord host, `/api/webhooks/...`)
+pub fn is_webhook_url(url: &str) -> bool {
+    let Some(rest) = url.strip_prefix("https://") else { return false };
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    WEBHOOK_HOSTS.contains(&host.to_lowercase().as_str()) && path.starts_with("api/webhooks/")
+}
+
+/// Post a message to a Discord webhook
+pub fn post(webhook_url: &str, content: &str) -> Result<(), String> {
+    if !is_webhook_url(webhook_url) {
+        return Err("Not a Discord webhook URL (https://discord.com/api/webhooks/...)".to_string());
+    }
+    if content.chars().count() > MAX_MESSAGE_LEN {
+        return Err(format!("Message is longer than Discord's {} character limit", MAX_MESSAGE_LEN));
+    }
+
+    download::agent_for(webhook_url)?
+        .post(webhook_url)
+        .send_json(serde_json::json!({ "content": content, "username": "Ship Lens" }))
+        .map_err(|e| format!("Failed to post to Discord: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ttk::WeaponEffectiveness;
+    use crate::ShipTtkRequest;
+
+    #[test]
+    fn test_format_for_discord() {
+        let request = CalcRequest::Ships(ShipTtkRequest {
+            attacker: "Aegis Gladius".to_string(),
+            target: "Anvil Arrow".to_string(),
+            ..Default::default()
+        });
+        let weapon = |name: &str| WeaponEffectiveness {
+            weapon_name: name.to_string(),
+            hardpoint_label: None,
+            damage_type: "Energy".to_string(),
+            count: 2,
+            raw_dps: 0.0,
+            effective_dps: 0.0,
+            shield_dps: 0.0,
+            passthrough_dps: 0.0,
+            armor_dps: 0.0,
+            hull_dps: 0.0,
+            solo_ttk: 0.0,
+            shots_to_kill: 0.0,
+            shield_time: 0.0,
+            armor_time: 0.0,
+            hull_time: 0.0,
+            is_effective: true,
+            ineffective_reason: None,
+        };
+        let mut result = TTKResult {
+            total_ttk: 12.34,
+            shield_time: f64::INFINITY,
+            shields_breakable: false,
+            weapon_breakdown: vec![weapon("CF-337 Panther Repeater")],
+            ..Default::default()
+        };
+
+        let message = format(&request, &result);
+        assert!(message.starts_with("**Aegis Gladius** vs **Anvil Arrow**\n```\nTTK      12.3s"));
+        assert!(message.contains("Shields  ∞ (unbreakable)"));
+        assert!(message.ends_with("Loadout\n2x CF-337 Panther Repeater\n```"));
+
+        // Long loadouts are cut to fit one message
+        result.weapon_breakdown = (0..200).map(|i| weapon(&format!("Weapon {}", i))).collect();
+        let message = format(&request, &result);
+        assert!(message.chars().count() <= MAX_MESSAGE_LEN);
+        assert!(message.contains("more)"));
+
+        assert!(is_webhook_url("https://discord.com/api/webhooks/123/abc"));
+        assert!(!is_webhook_url("http://discord.com/api/webhooks/123/abc"));
+        assert!(!is_webhook_url("https://example.com/api/webhooks/123/abc"));
+    }
+}
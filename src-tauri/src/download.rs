@@ -0,0 +1,338 @@
+//! Ship Lens Resumable Downloads
+//!
+//! Streams a URL to `<dest>.part`, reporting progress, and renames it into place
+//! when complete. Dropped connections are retried with an HTTP range request so
+//! the transfer resumes where it stopped (or restarts if the server ignores the
+//! range). Only partial files this process wrote are resumed; anything else at
+//! that path is discarded. Proxies come from `HTTPS_PROXY`/`HTTP_PROXY`/`ALL_PROXY`,
+//! minus `NO_PROXY`.
+//!
+//! Packages are checked against the SHA-256 published next to them
+//! (`<asset>.sha256`) before the updaters use them. Package downloads are only
+//! used by the Linux and Windows portable updaters; `get_json` is used everywhere.
+#![cfg_attr(not(any(target_os = "linux", windows)), allow(dead_code))]
+
+use crate::locks::MutexExt;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Attempts before a download gives up (retries back off 2s, 4s, 8s, ...)
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Bytes between progress reports
+const PROGRESS_STEP: u64 = 1024 * 1024;
+
+/// Partial files written by this process (the only ones safe to resume)
+static OWN_PARTS: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+/// Progress of a download (sent to the frontend as an event)
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadProgress {
+    pub url: String,
+    pub downloaded: u64,
+    /// Full size, when the server reports it
+    pub total: Option<u64>,
+    pub percent: Option<f64>,
+    pub attempt: u32,
+}
+
+enum FetchError {
+    /// Worth another attempt (network errors, 5xx, truncated body)
+    Retry(String),
+    Fatal(String),
+}
+
+/// Lowercase host of an http(s) URL
+fn host_of(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let host = match host.strip_prefix('[') {
+        Some(v6) => v6.split(']').next()?,
+        None => host.split(':').next()?,
+    };
+    (!host.is_empty()).then(|| host.to_lowercase())
+}
+
+/// Proxy for `url` from proxy environment variables (`lookup` reads a variable)
+fn proxy_for(url: &str, lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let var = |name: &str| lookup(name)
+        .or_else(|| lookup(&name.to_lowercase()))
+        .filter(|v| !v.trim().is_empty());
+
+    let host = host_of(url)?;
+    if let Some(no_proxy) = var("NO_PROXY") {
+        let bypass = no_proxy.split(',')
+            .map(|p| p.trim().trim_start_matches('.').to_lowercase())
+            .filter(|p| !p.is_empty())
+            .any(|p| p == "*" || host == p || host.ends_with(&format!(".{}", p)));
+        if bypass {
+            return None;
+        }
+    }
+
+    let scheme_var = if url.starts_with("https://") { "HTTPS_PROXY" } else { "HTTP_PROXY" };
+    var(scheme_var).or_else(|| var("ALL_PROXY"))
+}
+
+/// HTTP agent with timeouts and the environment's proxy for `url`
+pub fn agent_for(url: &str) -> Result<ureq::Agent, String> {
+    let mut builder = ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(15))
+        .timeout_read(Duration::from_secs(30));
+    if let Some(proxy) = proxy_for(url, |name| std::env::var(name).ok()) {
+        let parsed = ureq::Proxy::new(&proxy)
+            .map_err(|e| format!("Invalid proxy '{}': {}", proxy, e))?;
+        builder = builder.proxy(parsed);
+    }
+    Ok(builder.build())
+}
+
+/// Fetch `url` and parse the body as JSON
+pub fn get_json(url: &str) -> Result<serde_json::Value, String> {
+    agent_for(url)?
+        .get(url)
+        .set("Accept", "application/json")
+        .call()
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?
+        .into_json()
+        .map_err(|e| format!("{} did not return JSON: {}", url, e))
+}
+
+/// Fetch `url` as text
+fn get_text(url: &str) -> Result<String, String> {
+    agent_for(url)?
+        .get(url)
+        .call()
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?
+        .into_string()
+        .map_err(|e| format!("Failed to read {}: {}", url, e))
+}
+
+/// SHA-256 published for the asset at `url` (`<url>.sha256`, `sha256sum` format)
+pub fn published_sha256(url: &str) -> Result<String, String> {
+    let checksum_url = format!("{}.sha256", url);
+    let text = get_text(&checksum_url).map_err(|e| format!(
+        "No checksum published for this package ({}); refusing to install it unverified: {}", checksum_url, e,
+    ))?;
+    parse_sha256(&text)
+        .ok_or_else(|| format!("{} does not hold a SHA-256 checksum", checksum_url))
+}
+
+/// Hex digest from `sha256sum` output (`<hex>  <file name>`)
+fn parse_sha256(text: &str) -> Option<String> {
+    let hex = text.split_whitespace().next()?;
+    (hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit())).then(|| hex.to_lowercase())
+}
+
+/// Lowercase hex SHA-256 of a file
+fn file_sha256(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn partial_path(dest: &Path) -> PathBuf {
+    let name = dest.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    dest.with_file_name(format!("{}.part", name))
+}
+
+/// Whether this process wrote `part` (resumable), marking it as such if `claim`
+fn own_part(part: &Path, claim: bool) -> bool {
+    let mut parts = OWN_PARTS.lock_or_recover();
+    let parts = parts.get_or_insert_with(HashSet::new);
+    if claim {
+        parts.insert(part.to_path_buf());
+    }
+    parts.contains(part)
+}
+
+/// Download `url` to `dest` and check it against the published SHA-256
+///
+/// A mismatching download is deleted.
+pub fn download_verified(url: &str, dest: &Path, on_progress: impl Fn(&DownloadProgress)) -> Result<(), String> {
+    let expected = published_sha256(url)?;
+    download(url, dest, on_progress)?;
+    let actual = file_sha256(dest)?;
+    if actual != expected {
+        let _ = fs::remove_file(dest);
+        return Err(format!("Downloaded package failed its checksum (expected {}, got {})", expected, actual));
+    }
+    Ok(())
+}
+
+/// Full size from a `Content-Range: bytes <start>-<end>/<size>` header
+fn content_range_total(header: &str) -> Option<u64> {
+    header.rsplit_once('/')?.1.trim().parse().ok()
+}
+
+/// Download `url` to `dest`, resuming a previous partial download if one exists
+pub fn download(url: &str, dest: &Path, on_progress: impl Fn(&DownloadProgress)) -> Result<(), String> {
+    let agent = agent_for(url)?;
+    let part = partial_path(dest);
+    // Someone else's partial file can't be trusted to hold the start of this package
+    if part.exists() && !own_part(&part, false) {
+        fs::remove_file(&part).map_err(|e| format!("Failed to remove stale {}: {}", part.display(), e))?;
+    }
+
+    let mut last_error = String::new();
+    for attempt in 1..=MAX_ATTEMPTS {
+        if attempt > 1 {
+            std::thread::sleep(Duration::from_secs(1 << (attempt - 1)));
+        }
+        match fetch(&agent, url, &part, attempt, &on_progress) {
+            Ok(()) => {
+                return fs::rename(&part, dest)
+                    .map_err(|e| format!("Failed to move download into place: {}", e));
+            }
+            Err(FetchError::Fatal(e)) => return Err(e),
+            Err(FetchError::Retry(e)) => {
+                log::warn!("Download attempt {} of {} failed: {}", attempt, MAX_ATTEMPTS, e);
+                last_error = e;
+            }
+        }
+    }
+    Err(format!("Failed to download package after {} attempts: {}", MAX_ATTEMPTS, last_error))
+}
+
+/// One attempt: request the rest of `part` and append it
+fn fetch(
+    agent: &ureq::Agent,
+    url: &str,
+    part: &Path,
+    attempt: u32,
+    on_progress: &impl Fn(&DownloadProgress),
+) -> Result<(), FetchError> {
+    let resume_from = fs::metadata(part).map(|m| m.len()).unwrap_or(0);
+    let mut request = agent.get(url);
+    if resume_from > 0 {
+        request = request.set("Range", &format!("bytes={}-", resume_from));
+    }
+
+    let response = match request.call() {
+        Ok(response) => response,
+        // The partial file is as long as the package or longer: its length can't
+        // be checked against the server's, so start over
+        Err(ureq::Error::Status(416, _)) if resume_from > 0 => {
+            let _ = fs::remove_file(part);
+            return Err(FetchError::Retry("server rejected the resume range; restarting".to_string()));
+        }
+        Err(ureq::Error::Status(code, _)) if code == 429 || code >= 500 => {
+            return Err(FetchError::Retry(format!("HTTP {}", code)));
+        }
+        Err(ureq::Error::Status(code, response)) => {
+            return Err(FetchError::Fatal(format!("Failed to download package: HTTP {} {}", code, response.status_text())));
+        }
+        Err(e) => return Err(FetchError::Retry(e.to_string())),
+    };
+
+    // 206 continues the partial file; 200 means the server sent the whole file again
+    let resumed = resume_from > 0 && response.status() == 206;
+    let offset = if resumed { resume_from } else { 0 };
+    let total = response.header("Content-Range").and_then(content_range_total)
+        .or_else(|| response.header("Content-Length")?.parse::<u64>().ok().map(|len| len + offset));
+
+    let file = if resumed {
+        OpenOptions::new().append(true).open(part)
+    } else {
+        own_part(part, true);
+        fs::File::create(part)
+    };
+    let mut file = file.map_err(|e| FetchError::Fatal(format!("Failed to create temp file: {}", e)))?;
+
+    let report = |downloaded: u64| on_progress(&DownloadProgress {
+        url: url.to_string(),
+        downloaded,
+        total,
+        percent: total.filter(|&t| t > 0).map(|t| downloaded as f64 / t as f64 * 100.0),
+        attempt,
+    });
+
+    let mut reader = response.into_reader();
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut downloaded = offset;
+    let mut reported = offset;
+    report(downloaded);
+    loop {
+        let n = reader.read(&mut buf).map_err(|e| FetchError::Retry(e.to_string()))?;
+        if n == 0 {
+            break;
+        }
+        file.write_all(&buf[..n])
+            .map_err(|e| FetchError::Fatal(format!("Failed to write package: {}", e)))?;
+        downloaded += n as u64;
+        if downloaded - reported >= PROGRESS_STEP {
+            report(downloaded);
+            reported = downloaded;
+        }
+    }
+    file.flush().map_err(|e| FetchError::Fatal(format!("Failed to flush file: {}", e)))?;
+    report(downloaded);
+
+    match total {
+        Some(total) if downloaded < total => Err(FetchError::Retry(
+            format!("connection closed after {} of {} bytes", downloaded, total),
+        )),
+        Some(total) if downloaded > total => {
+            let _ = fs::remove_file(part);
+            Err(FetchError::Retry(format!("received {} bytes of a {} byte package; restarting", downloaded, total)))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_proxy_from_env() {
+        let env = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            move |name: &str| vars.get(name).cloned()
+        };
+        let url = "https://github.com/CapCeph/ship-lens/releases/download/v0.1.23/pkg.deb";
+
+        assert_eq!(proxy_for(url, env(&[])), None);
+        assert_eq!(proxy_for(url, env(&[("https_proxy", "http://proxy:3128")])), Some("http://proxy:3128".to_string()));
+        assert_eq!(proxy_for(url, env(&[("HTTP_PROXY", "http://a:1"), ("ALL_PROXY", "socks5://b:2")])), Some("socks5://b:2".to_string()));
+        assert_eq!(proxy_for(url, env(&[("HTTPS_PROXY", "http://proxy:3128"), ("NO_PROXY", "localhost, .github.com")])), None);
+
+        assert_eq!(host_of("https://user:pw@Example.com:8443/x"), Some("example.com".to_string()));
+        assert_eq!(content_range_total("bytes 100-999/1000"), Some(1000));
+    }
+
+    #[test]
+    fn test_checksums_and_owned_parts() {
+        let hex = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        assert_eq!(parse_sha256(&format!("{}  pkg.deb\n", hex.to_uppercase())), Some(hex.to_string()));
+        assert_eq!(parse_sha256("not a checksum"), None);
+
+        let dir = std::env::temp_dir().join(format!("ship-lens-download-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("pkg.deb");
+        fs::write(&file, "abc").unwrap();
+        assert_eq!(file_sha256(&file).unwrap(), hex);
+
+        let part = partial_path(&file);
+        assert!(!own_part(&part, false));
+        assert!(own_part(&part, true) && own_part(&part, false));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,328 @@
+//! Ship Lens Duels
+//!
+//! A duel runs the ship-vs-ship TTK both ways: how fast I kill the opponent and
+//! how fast they kill me. Whoever needs less time wins; the margin is the
+//! difference in seconds. Batch commands use it to build threat matrices.
+//!
+//! Saved fleet presets can fight too: their per-slot weapon picks are mapped back
+//! onto hardpoints in the order the loadout panel lists them.
+
+use crate::analysis::parallel_map;
+use crate::data::{GameData, Ship};
+use crate::presets::FleetPreset;
+use crate::tasks::TaskProgress;
+use crate::ttk::{ScenarioPreset, GUN_CATEGORIES};
+use crate::{compute_ttk_for_ships, ShipTtkRequest};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// TTK differences below this are a draw (seconds)
+const DRAW_MARGIN: f64 = 0.1;
+
+/// Hardpoint categories in loadout panel order (unknown categories go last)
+const SLOT_ORDER: [&str; 9] = [
+    "pilot", "manned_turret", "remote_turret", "pdc", "missile", "torpedo", "bomb", "spinal", "specialized",
+];
+
+/// One side of a duel
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Fighter {
+    /// Ship display name
+    pub ship: String,
+    /// Port -> weapon overrides (stock guns elsewhere)
+    pub loadout: Option<HashMap<String, String>>,
+    /// Shield internal name (the ship's default if None)
+    pub shield: Option<String>,
+}
+
+impl Fighter {
+    /// A ship with its default loadout and shield
+    pub fn stock(ship: &Ship) -> Self {
+        Self { ship: ship.display_name.clone(), ..Default::default() }
+    }
+
+    /// A ship fitted as a saved preset
+    ///
+    /// `preset.weapons` holds one pick per panel slot: categories in panel order,
+    /// one slot per sub-port, camera turrets and unmounted empty hardpoints left
+    /// out. A hardpoint takes the pick of its first filled slot; guns in disabled
+    /// categories are cleared. The preset's shield is a display name.
+    pub fn from_preset(data: &GameData, ship: &Ship, preset: &FleetPreset) -> Self {
+        let order = |category: &str| SLOT_ORDER.iter().position(|c| *c == category).unwrap_or(SLOT_ORDER.len());
+        let mut hardpoints: Vec<_> = ship.weapon_hardpoints.iter().collect();
+        hardpoints.sort_by_key(|hp| order(&hp.category));
+
+        let mut loadout = HashMap::new();
+        let mut slot = 0;
+        for hp in hardpoints {
+            let has_default = hp.sub_ports.iter().any(|p| p.default_weapon.as_deref().is_some_and(|w| !w.is_empty() && w != "empty"));
+            let camera = matches!(hp.category.as_str(), "remote_turret" | "torpedo") && !has_default && hp.sub_ports.len() <= 1;
+            if camera || (!has_default && hp.mount_name.trim().is_empty()) {
+                continue;
+            }
+            let slots = hp.sub_ports.len().max(1);
+            let picks = preset.weapons.iter().skip(slot).take(slots);
+            slot += slots;
+
+            if !GUN_CATEGORIES.contains(&hp.category.as_str()) {
+                continue;
+            }
+            let enabled = preset.enabled_categories.is_empty() || preset.enabled_categories.contains(&hp.category);
+            let pick = picks.map(String::as_str).find(|w| !w.is_empty() && !w.eq_ignore_ascii_case("empty"));
+            loadout.insert(hp.port_name.clone(), if enabled { pick.unwrap_or("").to_string() } else { String::new() });
+        }
+
+        let shield = data.shields.values()
+            .find(|s| s.display_name == preset.shield || s.internal_name == preset.shield)
+            .map(|s| s.internal_name.clone());
+        Self { ship: ship.display_name.clone(), loadout: Some(loadout), shield }
+    }
+
+    /// This fighter fitted as the saved preset `loadout_id`
+    ///
+    /// An empty `ship` takes the preset's; otherwise it must be the preset's ship.
+    /// Own loadout entries override the preset's picks, and an own shield its shield.
+    pub fn with_preset(self, data: &GameData, presets: &[FleetPreset], loadout_id: &str) -> Result<Self, String> {
+        let preset = presets.iter()
+            .find(|p| p.id == loadout_id)
+            .ok_or_else(|| format!("Loadout preset '{}' not found", loadout_id))?;
+        let ship = data.ship(&preset.ship_name)
+            .ok_or_else(|| format!("Loadout preset '{}' is for unknown ship '{}'", preset.name, preset.ship_name))?;
+        if !self.ship.is_empty() && data.ship(&self.ship).map(|s| &s.display_name) != Some(&ship.display_name) {
+            return Err(format!("Loadout preset '{}' is for {}, not {}", preset.name, ship.display_name, self.ship));
+        }
+
+        let fitted = Self::from_preset(data, ship, preset);
+        let mut loadout = fitted.loadout.unwrap_or_default();
+        loadout.extend(self.loadout.unwrap_or_default());
+        Ok(Self { ship: fitted.ship, loadout: Some(loadout), shield: self.shield.or(fitted.shield) })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuelOutcome {
+    Win,
+    Loss,
+    Draw,
+    /// Neither side can kill the other
+    Stalemate,
+}
+
+/// Result of one duel, from my side
+#[derive(Debug, Clone, Serialize)]
+pub struct Matchup {
+    pub rank: usize,
+    pub opponent: String,
+    /// Time I need to kill the opponent (seconds)
+    pub my_ttk: f64,
+    /// Time the opponent needs to kill me (seconds)
+    pub their_ttk: f64,
+    /// `their_ttk - my_ttk`: positive when I win, 0 in a stalemate
+    pub margin: f64,
+    pub outcome: DuelOutcome,
+}
+
+/// How one saved preset fares against an enemy
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetMatchup {
+    pub preset_id: String,
+    pub preset_name: String,
+    pub ship: String,
+    /// `opponent` is the enemy; `rank` 1 handles it best
+    #[serde(flatten)]
+    pub matchup: Matchup,
+}
+
+impl Matchup {
+    pub fn new(opponent: String, my_ttk: f64, their_ttk: f64) -> Self {
+        let (margin, outcome) = if my_ttk.is_infinite() && their_ttk.is_infinite() {
+            (0.0, DuelOutcome::Stalemate)
+        } else {
+            let margin = their_ttk - my_ttk;
+            let outcome = if margin.abs() < DRAW_MARGIN {
+                DuelOutcome::Draw
+            } else if margin > 0.0 {
+                DuelOutcome::Win
+            } else {
+                DuelOutcome::Loss
+            };
+            (margin, outcome)
+        };
+        Self { rank: 0, opponent, my_ttk, their_ttk, margin, outcome }
+    }
+}
+
+/// Time for `attacker` to kill `defender`
+pub fn attack(data: &GameData, attacker: &Fighter, defender: &Fighter, preset: Option<ScenarioPreset>) -> Result<f64, String> {
+    let request = ShipTtkRequest {
+        attacker: attacker.ship.clone(),
+        attacker_loadout: attacker.loadout.clone(),
+        target: defender.ship.clone(),
+        shield_name: defender.shield.clone(),
+        scenario_preset: preset,
+        ..Default::default()
+    };
+    compute_ttk_for_ships(data, &request).map(|r| r.total_ttk)
+}
+
+/// Duel `me` against `opponent`
+///
+/// A side whose calculation fails (no guns, no shield data) is taken as never
+/// getting the kill.
+pub fn duel(data: &GameData, me: &Fighter, opponent: &Fighter, preset: Option<ScenarioPreset>) -> Matchup {
+    let my_ttk = attack(data, me, opponent, preset).unwrap_or(f64::INFINITY);
+    let their_ttk = attack(data, opponent, me, preset).unwrap_or(f64::INFINITY);
+    Matchup::new(opponent.ship.clone(), my_ttk, their_ttk)
+}
+
+/// Sort matchups worst first (biggest loss margin) and number them
+pub fn rank(matchups: &mut [Matchup]) {
+    matchups.sort_by(|a, b| a.margin.total_cmp(&b.margin).then_with(|| a.opponent.cmp(&b.opponent)));
+    for (i, matchup) in matchups.iter_mut().enumerate() {
+        matchup.rank = i + 1;
+    }
+}
+
+/// Duel `me` against every other ship with its default loadout, worst matchups first
+pub fn find_counters(
+    data: &GameData,
+    me: &Fighter,
+    preset: Option<ScenarioPreset>,
+    progress: &TaskProgress,
+) -> Result<Vec<Matchup>, String> {
+    // Unknown ships, ports or weapons are request errors, not lost duels
+    attack(data, me, &Fighter { ship: me.ship.clone(), ..Default::default() }, preset)?;
+
+    let opponents: Vec<&Ship> = data.ships.values()
+        .map(|s| s.as_ref())
+        .filter(|s| s.display_name != me.ship)
+        .collect();
+    let mut matchups = parallel_map(&opponents, progress, |ship| duel(data, me, &Fighter::stock(ship), preset))?;
+    rank(&mut matchups);
+    Ok(matchups)
+}
+
+/// Duel each saved preset against `enemy` on its default loadout, best build first
+///
+/// Presets for ships missing from the data are skipped.
+pub fn assess_threat(
+    data: &GameData,
+    enemy: &Ship,
+    presets: &[FleetPreset],
+    preset: Option<ScenarioPreset>,
+    progress: &TaskProgress,
+) -> Result<Vec<PresetMatchup>, String> {
+    let fighters: Vec<(&FleetPreset, Fighter)> = presets.iter()
+        .filter_map(|p| Some((p, Fighter::from_preset(data, data.ship(&p.ship_name)?, p))))
+        .collect();
+    if fighters.is_empty() {
+        return Err("No saved presets to assess (save a fleet preset first)".to_string());
+    }
+
+    let enemy = Fighter::stock(enemy);
+    let mut matchups = parallel_map(&fighters, progress, |(p, fighter)| PresetMatchup {
+        preset_id: p.id.clone(),
+        preset_name: p.name.clone(),
+        ship: p.ship_name.clone(),
+        matchup: duel(data, fighter, &enemy, preset),
+    })?;
+    matchups.sort_by(|a, b| {
+        b.matchup.margin.total_cmp(&a.matchup.margin).then_with(|| a.preset_name.cmp(&b.preset_name))
+    });
+    for (i, entry) in matchups.iter_mut().enumerate() {
+        entry.matchup.rank = i + 1;
+    }
+    Ok(matchups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::reference_shield;
+    use crate::data::{Shield, SubPort, WeaponHardpoint};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_matchup_outcomes() {
+        let mut matchups = vec![
+            Matchup::new("Arrow".to_string(), 8.0, 12.0),
+            Matchup::new("Hornet".to_string(), 15.0, 9.0),
+            Matchup::new("Gladius".to_string(), 10.0, 10.05),
+            Matchup::new("Hull A".to_string(), 30.0, f64::INFINITY),
+            Matchup::new("Idris".to_string(), f64::INFINITY, 4.0),
+            Matchup::new("Carrack".to_string(), f64::INFINITY, f64::INFINITY),
+        ];
+        let outcomes: Vec<_> = matchups.iter().map(|m| m.outcome).collect();
+        assert_eq!(outcomes, [
+            DuelOutcome::Win, DuelOutcome::Loss, DuelOutcome::Draw,
+            DuelOutcome::Win, DuelOutcome::Loss, DuelOutcome::Stalemate,
+        ]);
+
+        rank(&mut matchups);
+        let order: Vec<_> = matchups.iter().map(|m| m.opponent.as_str()).collect();
+        assert_eq!(order, ["Idris", "Hornet", "Carrack", "Gladius", "Arrow", "Hull A"]);
+        assert_eq!(matchups[0].rank, 1);
+    }
+
+    #[test]
+    fn test_preset_fighter() {
+        let hardpoint = |port: &str, category: &str, defaults: &[&str]| WeaponHardpoint {
+            slot_number: 0,
+            port_name: port.to_string(),
+            max_size: 3,
+            gimbal_type: "Gimbal".to_string(),
+            control_type: String::new(),
+            category: category.to_string(),
+            mount_name: String::new(),
+            compatible_mounts: Vec::new(),
+            sub_ports: defaults.iter().map(|d| SubPort { size: 3, default_weapon: Some(d.to_string()) }).collect(),
+        };
+        // Panel order: pilot nose, pilot wing (dual), manned turret, camera turret (skipped), missiles
+        let ship = Ship {
+            display_name: "Test Ship".to_string(),
+            weapon_hardpoints: vec![
+                hardpoint("hardpoint_turret", "manned_turret", &["gun_a"]),
+                hardpoint("hardpoint_nose", "pilot", &["gun_a"]),
+                hardpoint("hardpoint_camera", "remote_turret", &[""]),
+                hardpoint("hardpoint_missile", "missile", &["rack"]),
+                hardpoint("hardpoint_wing", "pilot", &["gun_a", "gun_a"]),
+            ],
+            ..Default::default()
+        };
+        let mut data = GameData::default();
+        data.shields.insert("shld_fr66".to_string(), Arc::new(Shield {
+            display_name: "FR-66".to_string(),
+            internal_name: "shld_fr66".to_string(),
+            ..reference_shield()
+        }));
+
+        let preset = FleetPreset {
+            ship_name: "Test Ship".to_string(),
+            weapons: ["Attrition-3", "Empty", "CF-337", "Deadbolt III", "Rack"].map(String::from).to_vec(),
+            shield: "FR-66".to_string(),
+            enabled_categories: vec!["pilot".to_string(), "missile".to_string()],
+            ..Default::default()
+        };
+        let fighter = Fighter::from_preset(&data, &ship, &preset);
+        let loadout = fighter.loadout.unwrap();
+        assert_eq!(loadout["hardpoint_nose"], "Attrition-3");
+        assert_eq!(loadout["hardpoint_wing"], "CF-337");
+        // Manned turrets are switched off in this preset
+        assert_eq!(loadout["hardpoint_turret"], "");
+        assert!(!loadout.contains_key("hardpoint_camera"));
+        assert_eq!(fighter.shield.as_deref(), Some("shld_fr66"));
+
+        // By preset ID, with own overrides on top
+        data.ships.insert("Test Ship".to_string(), Arc::new(ship));
+        let presets = [FleetPreset { id: "p1".to_string(), ..preset }];
+        let me = Fighter { loadout: Some(HashMap::from([("hardpoint_wing".to_string(), "Omnisky VI".to_string())])), ..Default::default() };
+        let fighter = me.with_preset(&data, &presets, "p1").unwrap();
+        assert_eq!(fighter.ship, "Test Ship");
+        let loadout = fighter.loadout.unwrap();
+        assert_eq!((loadout["hardpoint_nose"].as_str(), loadout["hardpoint_wing"].as_str()), ("Attrition-3", "Omnisky VI"));
+        assert!(Fighter::default().with_preset(&data, &presets, "p2").is_err());
+        assert!(Fighter { ship: "Other Ship".to_string(), ..Default::default() }.with_preset(&data, &presets, "p1").is_err());
+    }
+}
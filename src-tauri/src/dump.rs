@@ -0,0 +1,283 @@
+//! Ship Lens Dataset Dumps
+//!
+//! Ships, weapons or shields as loaded (after localization, metadata and
+//! cleanup), one row per item in ID order, with derived values such as
+//! effective armor HP per damage type. Written as CSV or JSON for community
+//! spreadsheets and tools.
+
+use crate::analysis::effective_armor;
+use crate::data::{GameData, Shield, Ship, Weapon};
+use crate::matrix::csv_field;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Item table to dump
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatasetKind {
+    Ships,
+    Weapons,
+    Shields,
+}
+
+/// Dump file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DumpFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+struct ShipRow {
+    id: u32,
+    display_name: String,
+    filename: String,
+    manufacturer: String,
+    size_class: String,
+    role: String,
+    crew: i32,
+    hull_hp: f64,
+    armor_hp: f64,
+    /// Armor HP as seen by each damage type (see `analysis::effective_armor`)
+    armor_effective_physical: f64,
+    armor_effective_energy: f64,
+    armor_effective_distortion: f64,
+    shield_count: i32,
+    max_shield_size: i32,
+    default_shield: Option<String>,
+    pilot_weapon_count: i32,
+    pilot_weapon_sizes: String,
+    /// Gun sub-ports over all hardpoints
+    weapon_count: usize,
+    ordnance_count: i32,
+    hitbox_length: f64,
+    mass: f64,
+    sensor_range: f64,
+    quantum_spool_time: f64,
+}
+
+const SHIP_COLUMNS: [&str; 23] = [
+    "id", "display_name", "filename", "manufacturer", "size_class", "role", "crew",
+    "hull_hp", "armor_hp", "armor_effective_physical", "armor_effective_energy", "armor_effective_distortion",
+    "shield_count", "max_shield_size", "default_shield", "pilot_weapon_count", "pilot_weapon_sizes",
+    "weapon_count", "ordnance_count", "hitbox_length", "mass", "sensor_range", "quantum_spool_time",
+];
+
+#[derive(Debug, Serialize)]
+struct WeaponRow {
+    id: u32,
+    display_name: String,
+    filename: String,
+    manufacturer: String,
+    size: i32,
+    weapon_type: String,
+    damage_type: String,
+    sustained_dps: f64,
+    damage_physical: f64,
+    damage_energy: f64,
+    damage_distortion: f64,
+    fire_rate: f64,
+    damage_per_shot: f64,
+    speed: f64,
+    max_range: f64,
+    power_consumption: f64,
+    heat_per_shot: f64,
+    dps_per_power: Option<f64>,
+    dps_per_heat: Option<f64>,
+    ship_exclusive: bool,
+}
+
+const WEAPON_COLUMNS: [&str; 20] = [
+    "id", "display_name", "filename", "manufacturer", "size", "weapon_type", "damage_type",
+    "sustained_dps", "damage_physical", "damage_energy", "damage_distortion",
+    "fire_rate", "damage_per_shot", "speed", "max_range",
+    "power_consumption", "heat_per_shot", "dps_per_power", "dps_per_heat", "ship_exclusive",
+];
+
+#[derive(Debug, Serialize)]
+struct ShieldRow {
+    id: u32,
+    display_name: String,
+    internal_name: String,
+    size: i32,
+    max_hp: f64,
+    regen: f64,
+    /// Seconds to regenerate from empty, not counting the downed delay
+    full_regen_time: Option<f64>,
+    resist_physical: f64,
+    resist_energy: f64,
+    resist_distortion: f64,
+    absorb_physical: f64,
+    absorb_energy: f64,
+    absorb_distortion: f64,
+    damaged_regen_delay: f64,
+    downed_regen_delay: f64,
+}
+
+const SHIELD_COLUMNS: [&str; 15] = [
+    "id", "display_name", "internal_name", "size", "max_hp", "regen", "full_regen_time",
+    "resist_physical", "resist_energy", "resist_distortion",
+    "absorb_physical", "absorb_energy", "absorb_distortion",
+    "damaged_regen_delay", "downed_regen_delay",
+];
+
+fn ship_row(data: &GameData, ship: &Ship) -> ShipRow {
+    ShipRow {
+        id: ship.id,
+        display_name: ship.display_name.clone(),
+        filename: ship.filename.clone(),
+        manufacturer: ship.manufacturer.clone(),
+        size_class: ship.size_class.clone(),
+        role: ship.role.clone(),
+        crew: ship.crew,
+        hull_hp: ship.hull_hp,
+        armor_hp: ship.armor_hp,
+        armor_effective_physical: effective_armor(ship.armor_hp, ship.armor_damage_mult_physical, ship.armor_resist_physical),
+        armor_effective_energy: effective_armor(ship.armor_hp, ship.armor_damage_mult_energy, ship.armor_resist_energy),
+        armor_effective_distortion: effective_armor(ship.armor_hp, ship.armor_damage_mult_distortion, ship.armor_resist_distortion),
+        shield_count: ship.shield_count,
+        max_shield_size: ship.max_shield_size,
+        default_shield: data.find_default_shield(ship).map(|s| s.display_name.clone()),
+        pilot_weapon_count: ship.pilot_weapon_count,
+        pilot_weapon_sizes: ship.pilot_weapon_sizes.clone(),
+        weapon_count: ship.weapon_hardpoints.iter().map(|hp| hp.sub_ports.len()).sum(),
+        ordnance_count: ship.ordnance_hardpoints.iter().map(|hp| hp.missile_count).sum(),
+        hitbox_length: ship.hitbox_length,
+        mass: ship.mass,
+        sensor_range: ship.sensor_range,
+        quantum_spool_time: ship.quantum_spool_time,
+    }
+}
+
+fn weapon_row(weapon: &Weapon) -> WeaponRow {
+    WeaponRow {
+        id: weapon.id,
+        display_name: weapon.display_name.clone(),
+        filename: weapon.filename.clone(),
+        manufacturer: weapon.manufacturer.clone(),
+        size: weapon.size,
+        weapon_type: weapon.weapon_type.clone(),
+        damage_type: weapon.damage_type.clone(),
+        sustained_dps: weapon.sustained_dps,
+        damage_physical: weapon.damage_physical,
+        damage_energy: weapon.damage_energy,
+        damage_distortion: weapon.damage_distortion,
+        fire_rate: weapon.fire_rate,
+        damage_per_shot: weapon.damage_per_shot,
+        speed: weapon.speed,
+        max_range: weapon.max_range,
+        power_consumption: weapon.power_consumption,
+        heat_per_shot: weapon.heat_per_shot,
+        dps_per_power: weapon.dps_per_power(),
+        dps_per_heat: weapon.dps_per_heat(),
+        ship_exclusive: weapon.ship_exclusive,
+    }
+}
+
+fn shield_row(shield: &Shield) -> ShieldRow {
+    ShieldRow {
+        id: shield.id,
+        display_name: shield.display_name.clone(),
+        internal_name: shield.internal_name.clone(),
+        size: shield.size,
+        max_hp: shield.max_hp,
+        regen: shield.regen,
+        full_regen_time: (shield.regen > 0.0).then(|| shield.max_hp / shield.regen),
+        resist_physical: shield.resist_physical,
+        resist_energy: shield.resist_energy,
+        resist_distortion: shield.resist_distortion,
+        absorb_physical: shield.absorb_physical,
+        absorb_energy: shield.absorb_energy,
+        absorb_distortion: shield.absorb_distortion,
+        damaged_regen_delay: shield.damaged_regen_delay,
+        downed_regen_delay: shield.downed_regen_delay,
+    }
+}
+
+/// Rows of `kind` as JSON objects, in ID order, with their CSV column order
+fn rows(data: &GameData, kind: DatasetKind) -> (Vec<Value>, &'static [&'static str]) {
+    fn to_values<T: Serialize>(rows: Vec<(u32, T)>) -> Vec<Value> {
+        let mut rows = rows;
+        rows.sort_by_key(|(id, _)| *id);
+        rows.into_iter()
+            .map(|(_, row)| serde_json::to_value(row).unwrap_or(Value::Null))
+            .collect()
+    }
+    match kind {
+        DatasetKind::Ships => (to_values(data.ships.values().map(|s| (s.id, ship_row(data, s))).collect()), &SHIP_COLUMNS),
+        DatasetKind::Weapons => (to_values(data.weapons.values().map(|w| (w.id, weapon_row(w))).collect()), &WEAPON_COLUMNS),
+        DatasetKind::Shields => (to_values(data.shields.values().map(|s| (s.id, shield_row(s))).collect()), &SHIELD_COLUMNS),
+    }
+}
+
+/// CSV text of a value (empty for null)
+fn csv_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => csv_field(s),
+        other => other.to_string(),
+    }
+}
+
+/// Dump of `kind` in `format`, with its row count
+pub fn dump(data: &GameData, kind: DatasetKind, format: DumpFormat) -> Result<(String, usize), String> {
+    let (rows, columns) = rows(data, kind);
+    let contents = match format {
+        DumpFormat::Json => serde_json::to_string_pretty(&rows)
+            .map_err(|e| format!("Failed to serialize dataset: {}", e))?,
+        DumpFormat::Csv => {
+            let mut lines = vec![columns.join(",")];
+            lines.extend(rows.iter().map(|row| {
+                columns.iter().map(|c| csv_value(&row[*c])).collect::<Vec<_>>().join(",")
+            }));
+            lines.join("\n") + "\n"
+        }
+    };
+    Ok((contents, rows.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{reference_shield, ThreatProfile};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_dump() {
+        let mut data = GameData::default();
+        for (id, name) in [(2, "Gladius"), (1, "Arrow, Mk I")] {
+            data.ships.insert(name.to_string(), Arc::new(Ship {
+                id,
+                display_name: name.to_string(),
+                armor_hp: 1000.0,
+                armor_damage_mult_physical: 0.5,
+                armor_resist_physical: 1.0,
+                ..Default::default()
+            }));
+        }
+        let shield = reference_shield();
+        data.shields.insert(shield.internal_name.clone(), Arc::new(shield));
+
+        // Every column names a row field, and every field has a column
+        for kind in [DatasetKind::Ships, DatasetKind::Shields] {
+            let (rows, columns) = rows(&data, kind);
+            let fields = rows[0].as_object().unwrap();
+            assert_eq!(fields.len(), columns.len());
+            assert!(columns.iter().all(|c| fields.contains_key(*c)));
+        }
+        assert_eq!(serde_json::to_value(weapon_row(&ThreatProfile { dps: 100.0, physical: 1.0, energy: 0.0, distortion: 0.0 }.to_equipped_weapon().weapon)).unwrap().as_object().unwrap().len(), WEAPON_COLUMNS.len());
+
+        let (csv, count) = dump(&data, DatasetKind::Ships, DumpFormat::Csv).unwrap();
+        assert_eq!(count, 2);
+        let lines: Vec<_> = csv.lines().collect();
+        assert!(lines[0].starts_with("id,display_name,filename,"));
+        // ID order, quoted names, effective armor
+        assert!(lines[1].starts_with("1,\"Arrow, Mk I\","));
+        assert!(lines[2].contains(",1000.0,2000.0,"));
+
+        let (json, _) = dump(&data, DatasetKind::Shields, DumpFormat::Json).unwrap();
+        let parsed: Vec<Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0]["display_name"], "Reference Shield");
+    }
+}
@@ -0,0 +1,254 @@
+//! Ship Lens erkul Import
+//!
+//! Reads loadouts exported from erkul.games. An export is a tree of item ports
+//! (`itemPortName`) holding components by class name (`localName`); gimbals and
+//! missile racks nest their contents in a child `loadout`. Class names are matched
+//! against weapon filenames, missile keys and shield internal names, and anything
+//! left over is reported as unmapped.
+
+use crate::data::GameData;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// A component in the export that matches nothing in the game data
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnmappedItem {
+    /// Top-level port the item sits under
+    pub port: String,
+    /// Class name as exported
+    pub item: String,
+}
+
+/// An imported loadout, in the shape the TTK commands take
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportedLoadout {
+    /// Ship display name (`attacker_ship`), when the exported ship is in the data
+    pub ship: Option<String>,
+    /// Ship class name as exported
+    pub ship_ref: Option<String>,
+    /// `port::weapon` labels with counts (`weapon_names`/`weapon_counts`)
+    pub weapon_names: Vec<String>,
+    pub weapon_counts: Vec<i32>,
+    /// Missile display names with counts (`missile_names`/`missile_counts`)
+    pub missile_names: Vec<String>,
+    pub missile_counts: Vec<i32>,
+    /// Shield internal names, one per generator (`shield_loadout`)
+    pub shield_loadout: Vec<String>,
+    /// Weapon display name per top-level port (`attacker_loadout` overrides)
+    pub attacker_loadout: BTreeMap<String, String>,
+    pub unmapped: Vec<UnmappedItem>,
+}
+
+/// Class name of the component in a port, if it holds one
+fn item_name(port: &Value) -> Option<&str> {
+    let direct = ["localName", "className", "item"].iter()
+        .find_map(|key| port.get(key)?.as_str());
+    let nested = || {
+        let item = port.get("item")?;
+        ["localName", "className"].iter().find_map(|key| item.get(key)?.as_str())
+    };
+    direct.or_else(nested).filter(|name| !name.is_empty())
+}
+
+/// Child ports of a port (on the port itself or on its item)
+fn children(port: &Value) -> &[Value] {
+    port.get("loadout").and_then(Value::as_array)
+        .or_else(|| port.get("item")?.get("loadout")?.as_array())
+        .map_or(&[], Vec::as_slice)
+}
+
+/// Add one to a named entry in parallel name/count lists
+fn bump(names: &mut Vec<String>, counts: &mut Vec<i32>, name: String) {
+    match names.iter().position(|n| *n == name) {
+        Some(i) => counts[i] += 1,
+        None => {
+            names.push(name);
+            counts.push(1);
+        }
+    }
+}
+
+struct Importer<'a> {
+    data: &'a GameData,
+    loadout: ImportedLoadout,
+}
+
+impl Importer<'_> {
+    /// Record a component; false if it matches nothing
+    fn map(&mut self, port: &str, item: &str) -> bool {
+        let data = self.data;
+        let key = item.to_lowercase();
+        let loadout = &mut self.loadout;
+
+        if let Some(weapon) = data.weapons.values().find(|w| w.filename.to_lowercase() == key) {
+            bump(&mut loadout.weapon_names, &mut loadout.weapon_counts, format!("{}::{}", port, weapon.display_name));
+            loadout.attacker_loadout.entry(port.to_string()).or_insert_with(|| weapon.display_name.clone());
+        } else if let Some(missile) = data.missiles.iter().find(|(k, _)| k.to_lowercase() == key).map(|(_, m)| m) {
+            bump(&mut loadout.missile_names, &mut loadout.missile_counts, missile.display_name.clone());
+        } else if let Some(shield) = data.shields.values().find(|s| s.internal_name.to_lowercase() == key) {
+            loadout.shield_loadout.push(shield.internal_name.clone());
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Walk a list of ports; nested ports are credited to their top-level port
+    fn walk(&mut self, ports: &[Value], top_port: Option<&str>) {
+        for port in ports {
+            let name = port.get("itemPortName").or_else(|| port.get("portName"))
+                .and_then(Value::as_str)
+                .unwrap_or("");
+            let top = top_port.unwrap_or(name);
+            let children = children(port);
+
+            match item_name(port) {
+                Some(item) if self.map(top, item) => {}
+                // Mounts and racks are containers: only what they hold matters
+                Some(_) if !children.is_empty() => self.walk(children, Some(top)),
+                Some(item) => self.loadout.unmapped.push(UnmappedItem {
+                    port: top.to_string(),
+                    item: item.to_string(),
+                }),
+                None => self.walk(children, Some(top)),
+            }
+        }
+    }
+}
+
+/// Ship class name from the export root (`ship` as a string or an object)
+fn ship_ref(root: &Value) -> Option<String> {
+    let ship = root.get("ship").or_else(|| root.get("vehicle"))?;
+    ship.as_str()
+        .or_else(|| ["localName", "className", "name"].iter().find_map(|key| ship.get(key)?.as_str()))
+        .map(str::to_string)
+}
+
+/// Map an exported erkul loadout onto the game data
+pub fn import(data: &GameData, export: &Value) -> Result<ImportedLoadout, String> {
+    // Some exports wrap the loadout in a `data` object
+    let root = match export.get("data") {
+        Some(inner) if inner.is_object() => inner,
+        _ => export,
+    };
+    let ports = match root {
+        Value::Array(ports) => ports.as_slice(),
+        _ => root.get("loadout").and_then(Value::as_array)
+            .ok_or_else(|| "Not an erkul loadout export (no loadout ports)".to_string())?,
+    };
+
+    let ship_ref = ship_ref(root);
+    let ship = ship_ref.as_ref().and_then(|r| {
+        data.ships.values()
+            .find(|s| s.filename.eq_ignore_ascii_case(r) || s.display_name.eq_ignore_ascii_case(r))
+            .map(|s| s.display_name.clone())
+    });
+
+    let mut importer = Importer {
+        data,
+        loadout: ImportedLoadout { ship, ship_ref, ..Default::default() },
+    };
+    importer.walk(ports, None);
+
+    let loadout = importer.loadout;
+    if loadout.weapon_names.is_empty() && loadout.missile_names.is_empty()
+        && loadout.shield_loadout.is_empty() && loadout.unmapped.is_empty()
+    {
+        return Err("The erkul export contains no components".to_string());
+    }
+    Ok(loadout)
+}
+
+/// Parse pasted export text
+pub fn parse(text: &str) -> Result<Value, String> {
+    serde_json::from_str(text.trim())
+        .map_err(|e| format!("Failed to parse erkul loadout: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{Shield, Weapon};
+    use std::sync::Arc;
+
+    fn make_weapon(filename: &str, display_name: &str) -> Weapon {
+        Weapon {
+            id: 0,
+            display_name: display_name.to_string(),
+            filename: filename.to_string(),
+            size: 3,
+            damage_type: "Energy".to_string(),
+            sustained_dps: 500.0,
+            power_consumption: 0.0,
+            weapon_type: "gun".to_string(),
+            restricted_to: vec![],
+            ship_exclusive: false,
+            manufacturer: String::new(),
+            manufacturer_code: String::new(),
+            damage_physical: 0.0,
+            damage_energy: 50.0,
+            damage_distortion: 0.0,
+            base_penetration_distance: 2.0,
+            near_radius: 0.1,
+            far_radius: 0.2,
+            fire_rate: 600.0,
+            damage_per_shot: 50.0,
+            speed: 1800.0,
+            max_range: 2000.0,
+            heat_per_shot: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_import_erkul_export() {
+        let mut data = GameData::default();
+        let weapon = make_weapon("klwe_laser_repeater_s3", "CF-337 Panther Repeater");
+        data.weapons.insert(weapon.filename.clone(), Arc::new(weapon));
+        data.shields.insert("shld_godi_s01_palisade_scitem".to_string(), Arc::new(Shield {
+            id: 0,
+            display_name: "Palisade".to_string(),
+            internal_name: "shld_godi_s01_palisade_scitem".to_string(),
+            size: 1,
+            max_hp: 3000.0,
+            regen: 300.0,
+            resist_physical: 0.0,
+            resist_energy: 0.0,
+            resist_distortion: 0.0,
+            absorb_physical: 0.225,
+            absorb_energy: 1.0,
+            absorb_distortion: 1.0,
+            damaged_regen_delay: 6.0,
+            downed_regen_delay: 12.0,
+        }));
+
+        let export = parse(r#"{
+            "ship": { "localName": "AEGS_Gladius" },
+            "loadout": [
+                { "itemPortName": "hardpoint_weapon_left", "localName": "MRCK_S03_AEGS_Gladius_Gimbal",
+                  "loadout": [{ "itemPortName": "hardpoint_class_2", "localName": "KLWE_LaserRepeater_S3_Unknown" }] },
+                { "itemPortName": "hardpoint_weapon_right", "localName": "klwe_laser_repeater_s3" },
+                { "itemPortName": "hardpoint_weapon_nose", "localName": "KLWE_Laser_Repeater_S3" },
+                { "itemPortName": "hardpoint_shield_generator", "localName": "SHLD_GODI_S01_Palisade_SCItem" }
+            ]
+        }"#).unwrap();
+
+        let loadout = import(&data, &export).unwrap();
+        assert_eq!(loadout.ship, None);
+        assert_eq!(loadout.ship_ref.as_deref(), Some("AEGS_Gladius"));
+        assert_eq!(loadout.weapon_names, [
+            "hardpoint_weapon_right::CF-337 Panther Repeater",
+            "hardpoint_weapon_nose::CF-337 Panther Repeater",
+        ]);
+        assert_eq!(loadout.weapon_counts, [1, 1]);
+        assert_eq!(loadout.shield_loadout, ["shld_godi_s01_palisade_scitem"]);
+        assert_eq!(loadout.attacker_loadout["hardpoint_weapon_nose"], "CF-337 Panther Repeater");
+        // The gimbal is a container; the unknown gun inside it is reported under the top-level port
+        assert_eq!(loadout.unmapped, [UnmappedItem {
+            port: "hardpoint_weapon_left".to_string(),
+            item: "KLWE_LaserRepeater_S3_Unknown".to_string(),
+        }]);
+
+        assert!(import(&data, &parse("{}").unwrap()).is_err());
+    }
+}
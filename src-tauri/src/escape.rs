@@ -0,0 +1,138 @@
+//! Ship Lens Escape Windows
+//!
+//! Whether a target under fire can spool its quantum drive and jump out before
+//! the attacker breaks its shields or kills it. Spool time comes from the ship
+//! metadata, falling back to a typical value for the ship's size class. A
+//! dampening period (interdiction, or the pilot reacting late) delays the start
+//! of the spool.
+
+use crate::data::Ship;
+use crate::ttk::{EngagementEventKind, TTKResult, ValidationErrors};
+use serde::Serialize;
+
+/// Spool times assumed per size class when the metadata has none (seconds)
+const SIZE_CLASS_SPOOL: [(&str, f64); 4] = [("small", 4.0), ("medium", 6.0), ("large", 10.0), ("capital", 15.0)];
+
+/// Spool time for ships of unknown size class (seconds)
+const DEFAULT_SPOOL: f64 = 6.0;
+
+/// Longest spool or dampening period accepted (seconds)
+const MAX_DELAY: f64 = 600.0;
+
+/// Where a spool time came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpoolSource {
+    Input,
+    Metadata,
+    SizeClass,
+    Default,
+}
+
+/// Whether the target jumps out before it fails
+#[derive(Debug, Clone, Serialize)]
+pub struct EscapeWindow {
+    pub attacker: String,
+    pub target: String,
+    pub spool_time: f64,
+    pub spool_source: SpoolSource,
+    /// Delay before the spool can start (seconds)
+    pub dampening: f64,
+    /// Dampening plus spool: when the jump happens
+    pub escape_time: f64,
+    /// When the last shield generator falls (infinite if never, 0 if unshielded)
+    pub shields_down: f64,
+    /// When the target is destroyed (infinite if never)
+    pub destroyed: f64,
+    /// Jumps out with shields still up
+    pub escapes_with_shields: bool,
+    /// Jumps out alive
+    pub escapes: bool,
+    /// `destroyed - escape_time`: seconds to spare, negative when caught
+    pub margin: f64,
+}
+
+/// Quantum spool time of `ship` and where it came from
+pub fn spool_time(ship: &Ship) -> (f64, SpoolSource) {
+    if ship.quantum_spool_time > 0.0 {
+        return (ship.quantum_spool_time, SpoolSource::Metadata);
+    }
+    SIZE_CLASS_SPOOL.iter()
+        .find(|(class, _)| ship.size_class.eq_ignore_ascii_case(class))
+        .map_or((DEFAULT_SPOOL, SpoolSource::Default), |(_, spool)| (*spool, SpoolSource::SizeClass))
+}
+
+/// Escape window of `target` under the attack that produced `result`
+///
+/// `spool` overrides the target's spool time; `dampening` delays its start.
+pub fn escape_window(
+    result: &TTKResult,
+    attacker: &str,
+    target: &Ship,
+    spool: Option<f64>,
+    dampening: f64,
+) -> Result<EscapeWindow, String> {
+    let mut errors = ValidationErrors::default();
+    if let Some(spool) = spool {
+        errors.check_range("spool_time", spool, 0.0, MAX_DELAY);
+    }
+    errors.check_range("dampening", dampening, 0.0, MAX_DELAY);
+    errors.into_result().map_err(|e| e.to_string())?;
+
+    let (spool_time, spool_source) = spool.map_or_else(|| spool_time(target), |spool| (spool, SpoolSource::Input));
+    let escape_time = dampening + spool_time;
+    let shields_down = result.events.iter()
+        .find(|e| e.kind == EngagementEventKind::ShieldsDown)
+        .map(|e| e.time)
+        .unwrap_or(if result.shield_phases.is_empty() { 0.0 } else { f64::INFINITY });
+    let destroyed = result.total_ttk;
+
+    Ok(EscapeWindow {
+        attacker: attacker.to_string(),
+        target: target.display_name.clone(),
+        spool_time,
+        spool_source,
+        dampening,
+        escape_time,
+        shields_down,
+        destroyed,
+        escapes_with_shields: escape_time < shields_down,
+        escapes: escape_time < destroyed,
+        margin: destroyed - escape_time,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ttk::{EngagementEvent, ShieldPhaseBoundary};
+
+    #[test]
+    fn test_escape_window() {
+        // Shields fall at 6s, target dies at 12s
+        let result = TTKResult {
+            total_ttk: 12.0,
+            shield_phases: vec![ShieldPhaseBoundary { phase: 1, generators: 1, hp: 6000.0, regen: 0.0, start: 0.0, end: 6.0 }],
+            events: vec![EngagementEvent { time: 6.0, kind: EngagementEventKind::ShieldsDown, phase: Some(1), instance: None, description: String::new() }],
+            ..Default::default()
+        };
+        let mut target = Ship { display_name: "Test Target".to_string(), size_class: "medium".to_string(), ..Default::default() };
+
+        // Medium class spool (6s) jumps exactly as shields fall
+        let window = escape_window(&result, "Attacker", &target, None, 0.0).unwrap();
+        assert_eq!((window.spool_time, window.spool_source), (6.0, SpoolSource::SizeClass));
+        assert!(window.escapes && !window.escapes_with_shields);
+        assert_eq!(window.margin, 6.0);
+
+        // Metadata spool, then dampened past the kill
+        target.quantum_spool_time = 4.5;
+        let window = escape_window(&result, "Attacker", &target, None, 0.0).unwrap();
+        assert!(window.escapes_with_shields);
+        assert_eq!(window.spool_source, SpoolSource::Metadata);
+        let dampened = escape_window(&result, "Attacker", &target, Some(5.0), 10.0).unwrap();
+        assert!(!dampened.escapes);
+        assert_eq!(dampened.margin, -3.0);
+
+        assert!(escape_window(&result, "Attacker", &target, Some(-1.0), 0.0).is_err());
+    }
+}
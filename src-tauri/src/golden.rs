@@ -0,0 +1,247 @@
+//! Ship Lens Model Self-Check
+//!
+//! Golden-value regression checks for the TTK model. A curated set of matchups
+//! is run and each phase time compared with a stored golden value within a
+//! relative tolerance, so model refactors and data updates that move results
+//! show up at once. Golden values are recorded from a known-good build and
+//! saved as `golden.json` in the app config dir; cases without one are reported
+//! as unrecorded rather than failed.
+
+use crate::store;
+use crate::ttk::{ScenarioPreset, TTKResult};
+use crate::ShipTtkRequest;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const GOLDEN_FILE: &str = "golden.json";
+
+/// Relative deviation allowed unless the file sets its own
+pub const DEFAULT_TOLERANCE: f64 = 0.01;
+
+/// Curated matchups: (case name, attacker, target, scenario)
+const CURATED_CASES: [(&str, &str, &str, Option<ScenarioPreset>); 6] = [
+    ("light-fighter-duel", "Aegis Gladius", "Anvil Arrow", None),
+    ("light-fighter-duel-reverse", "Anvil Arrow", "Aegis Gladius", None),
+    ("medium-vs-light", "Drake Cutlass Black", "Aegis Gladius", Some(ScenarioPreset::Dogfight)),
+    ("light-vs-medium", "Aegis Gladius", "Drake Cutlass Black", Some(ScenarioPreset::Jousting)),
+    ("turrets-vs-large", "Aegis Hammerhead", "RSI Constellation Andromeda", Some(ScenarioPreset::TurretDefense)),
+    ("synthetic-max", "Aegis Gladius", "Aegis Gladius", Some(ScenarioPreset::SyntheticMax)),
+];
+
+/// Phase times of one result (None = never, e.g. unbreakable shields)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GoldenValues {
+    pub shield_time: Option<f64>,
+    pub armor_time: Option<f64>,
+    pub hull_time: Option<f64>,
+    pub total_ttk: Option<f64>,
+}
+
+impl GoldenValues {
+    pub fn of(result: &TTKResult) -> Self {
+        let finite = |t: f64| t.is_finite().then_some(t);
+        Self {
+            shield_time: finite(result.shield_time),
+            armor_time: finite(result.armor_time),
+            hull_time: finite(result.hull_time),
+            total_ttk: finite(result.total_ttk),
+        }
+    }
+
+    fn fields(&self) -> [(&'static str, Option<f64>); 4] {
+        [
+            ("shield_time", self.shield_time),
+            ("armor_time", self.armor_time),
+            ("hull_time", self.hull_time),
+            ("total_ttk", self.total_ttk),
+        ]
+    }
+}
+
+/// One matchup and its recorded result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoldenCase {
+    pub name: String,
+    pub request: ShipTtkRequest,
+    /// None until recorded
+    #[serde(default)]
+    pub expected: Option<GoldenValues>,
+}
+
+/// Saved golden cases
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoldenFile {
+    #[serde(default = "default_tolerance")]
+    pub tolerance: f64,
+    pub cases: Vec<GoldenCase>,
+}
+
+fn default_tolerance() -> f64 {
+    DEFAULT_TOLERANCE
+}
+
+impl Default for GoldenFile {
+    /// The curated matchups, unrecorded
+    fn default() -> Self {
+        let cases = CURATED_CASES.iter()
+            .map(|(name, attacker, target, preset)| GoldenCase {
+                name: name.to_string(),
+                request: ShipTtkRequest {
+                    attacker: attacker.to_string(),
+                    target: target.to_string(),
+                    scenario_preset: *preset,
+                    ..Default::default()
+                },
+                expected: None,
+            })
+            .collect();
+        Self { tolerance: DEFAULT_TOLERANCE, cases }
+    }
+}
+
+/// Load the saved golden cases (the curated set, unrecorded, if none saved)
+pub fn load(config_dir: &Path) -> Result<GoldenFile, String> {
+    Ok(store::read_json(&config_dir.join(GOLDEN_FILE))?.unwrap_or_default())
+}
+
+/// Save golden cases (atomically, keeping backups)
+pub fn save(config_dir: &Path, golden: &GoldenFile) -> Result<(), String> {
+    store::write_json(&config_dir.join(GOLDEN_FILE), golden)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseStatus {
+    Pass,
+    Fail,
+    /// No golden value recorded yet
+    Unrecorded,
+    /// The matchup could not be calculated (e.g. a ship left the data)
+    Error,
+}
+
+/// Outcome of one golden case
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseResult {
+    pub name: String,
+    pub status: CaseStatus,
+    pub expected: Option<GoldenValues>,
+    pub actual: Option<GoldenValues>,
+    /// Fields outside the tolerance (or whose finiteness changed)
+    pub mismatches: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Result of a model self-check
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModelCheck {
+    pub passed: usize,
+    pub failed: usize,
+    pub unrecorded: usize,
+    pub errors: usize,
+    pub tolerance: f64,
+    pub cases: Vec<CaseResult>,
+}
+
+/// Fields of `actual` that differ from `expected` by more than `tolerance` (relative)
+fn mismatches(expected: &GoldenValues, actual: &GoldenValues, tolerance: f64) -> Vec<String> {
+    expected.fields().iter().zip(actual.fields())
+        .filter_map(|(&(field, expected), (_, actual))| match (expected, actual) {
+            (None, None) => None,
+            (Some(e), Some(a)) if (a - e).abs() <= tolerance * e.abs().max(1.0) => None,
+            (e, a) => Some(format!("{}: expected {}, got {}", field, show(e), show(a))),
+        })
+        .collect()
+}
+
+fn show(value: Option<f64>) -> String {
+    value.map_or("never".to_string(), |v| format!("{:.3}", v))
+}
+
+/// Run every case through `compute` and compare with its golden values
+pub fn verify<F>(golden: &GoldenFile, compute: F) -> ModelCheck
+where
+    F: Fn(&ShipTtkRequest) -> Result<TTKResult, String>,
+{
+    let mut check = ModelCheck { tolerance: golden.tolerance, ..Default::default() };
+    for case in &golden.cases {
+        let (actual, error) = match compute(&case.request) {
+            Ok(result) => (Some(GoldenValues::of(&result)), None),
+            Err(e) => (None, Some(e)),
+        };
+        let mismatches = match (&case.expected, &actual) {
+            (Some(expected), Some(actual)) => mismatches(expected, actual, golden.tolerance),
+            _ => vec![],
+        };
+        let status = if error.is_some() {
+            check.errors += 1;
+            CaseStatus::Error
+        } else if case.expected.is_none() {
+            check.unrecorded += 1;
+            CaseStatus::Unrecorded
+        } else if mismatches.is_empty() {
+            check.passed += 1;
+            CaseStatus::Pass
+        } else {
+            check.failed += 1;
+            CaseStatus::Fail
+        };
+        check.cases.push(CaseResult {
+            name: case.name.clone(),
+            status,
+            expected: case.expected,
+            actual,
+            mismatches,
+            error,
+        });
+    }
+    check
+}
+
+/// Replace every golden value with what `compute` gives now (failed cases keep theirs)
+pub fn record<F>(golden: &mut GoldenFile, compute: F)
+where
+    F: Fn(&ShipTtkRequest) -> Result<TTKResult, String>,
+{
+    for case in &mut golden.cases {
+        if let Ok(result) = compute(&case.request) {
+            case.expected = Some(GoldenValues::of(&result));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_golden_verify() {
+        let mut golden = GoldenFile::default();
+        assert_eq!(golden.cases.len(), CURATED_CASES.len());
+        assert!(golden.cases.iter().all(|c| c.expected.is_none()));
+
+        // Stand-in model: the Arrow is missing from the data, everything else takes 10s
+        let model = |total: f64| {
+            move |request: &ShipTtkRequest| -> Result<TTKResult, String> {
+                if request.attacker == "Anvil Arrow" {
+                    return Err("Attacker ship 'Anvil Arrow' not found".to_string());
+                }
+                Ok(TTKResult { shield_time: f64::INFINITY, armor_time: 4.0, hull_time: total - 4.0, total_ttk: total, ..Default::default() })
+            }
+        };
+        let check = verify(&golden, model(10.0));
+        assert_eq!((check.unrecorded, check.errors), (CURATED_CASES.len() - 1, 1));
+
+        record(&mut golden, model(10.0));
+        assert_eq!(golden.cases[0].expected.unwrap().shield_time, None);
+        assert_eq!(golden.cases[1].expected, None);
+        // Round-trips through JSON (never is null, not an error)
+        let golden: GoldenFile = serde_json::from_str(&serde_json::to_string(&golden).unwrap()).unwrap();
+
+        // Within 1% passes; a 5% drift fails on the fields that moved
+        assert_eq!(verify(&golden, model(10.05)).passed, CURATED_CASES.len() - 1);
+        let drifted = verify(&golden, model(10.5));
+        assert_eq!(drifted.failed, CURATED_CASES.len() - 1);
+        assert_eq!(drifted.cases[0].mismatches, ["hull_time: expected 6.000, got 6.500", "total_ttk: expected 10.000, got 10.500"]);
+    }
+}
@@ -0,0 +1,117 @@
+//! Ship Lens Calculation History
+//!
+//! Every completed TTK calculation (its full request plus a result summary) is
+//! appended to `history.json` in the app config dir. Only the newest
+//! `MAX_HISTORY` entries are kept; requests can be re-run against current data.
+
+use crate::store;
+use crate::ttk::TTKResult;
+use crate::CalcRequest;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const HISTORY_FILE: &str = "history.json";
+
+/// Entries kept before the oldest is dropped
+pub const MAX_HISTORY: usize = 200;
+
+/// Headline numbers of a TTK result
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResultSummary {
+    pub total_ttk: f64,
+    pub expected_ttk: f64,
+    pub effective_dps: f64,
+    pub shield_time: f64,
+    pub armor_time: f64,
+    pub hull_time: f64,
+    pub shields_breakable: bool,
+}
+
+impl ResultSummary {
+    pub fn of(result: &TTKResult) -> Self {
+        Self {
+            total_ttk: result.total_ttk,
+            expected_ttk: result.expected_ttk,
+            effective_dps: result.effective_dps,
+            shield_time: result.shield_time,
+            armor_time: result.armor_time,
+            hull_time: result.hull_time,
+            shields_breakable: result.shields_breakable,
+        }
+    }
+}
+
+/// One completed calculation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: u64,
+    pub timestamp: String,  // ISO 8601
+    pub request: CalcRequest,
+    pub summary: ResultSummary,
+}
+
+/// Load the history, oldest first (empty if none saved)
+pub fn load(config_dir: &Path) -> Result<Vec<HistoryEntry>, String> {
+    Ok(store::read_json(&config_dir.join(HISTORY_FILE))?.unwrap_or_default())
+}
+
+/// Save the history (atomically, keeping backups)
+pub fn save(config_dir: &Path, entries: &[HistoryEntry]) -> Result<(), String> {
+    store::write_json(&config_dir.join(HISTORY_FILE), entries)
+}
+
+/// Append an entry with the next id, dropping the oldest beyond `MAX_HISTORY`
+pub fn push(entries: &mut Vec<HistoryEntry>, request: CalcRequest, result: &TTKResult) -> u64 {
+    let id = entries.iter().map(|e| e.id).max().unwrap_or(0) + 1;
+    entries.push(HistoryEntry {
+        id,
+        timestamp: store::now_iso8601(),
+        request,
+        summary: ResultSummary::of(result),
+    });
+    if entries.len() > MAX_HISTORY {
+        entries.drain(..entries.len() - MAX_HISTORY);
+    }
+    id
+}
+
+/// Newest `limit` entries (all if `None`), newest first
+pub fn newest(mut entries: Vec<HistoryEntry>, limit: Option<usize>) -> Vec<HistoryEntry> {
+    entries.reverse();
+    entries.truncate(limit.unwrap_or(usize::MAX));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShipTtkRequest;
+
+    fn make_request(target: &str) -> CalcRequest {
+        CalcRequest::Ships(ShipTtkRequest {
+            attacker: "Gladius".to_string(),
+            target: target.to_string(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_rolling_history() {
+        let result = TTKResult { total_ttk: 12.5, ..Default::default() };
+        let mut entries = Vec::new();
+        for i in 0..MAX_HISTORY + 3 {
+            push(&mut entries, make_request(&format!("Target {}", i)), &result);
+        }
+
+        assert_eq!(entries.len(), MAX_HISTORY);
+        assert_eq!(entries[0].id, 4);
+        assert_eq!(entries[0].summary.total_ttk, 12.5);
+
+        let newest = newest(entries, Some(2));
+        assert_eq!(newest.iter().map(|e| e.id).collect::<Vec<_>>(), [MAX_HISTORY as u64 + 3, MAX_HISTORY as u64 + 2]);
+
+        let json = serde_json::to_string(&newest[0]).unwrap();
+        let entry: HistoryEntry = serde_json::from_str(&json).unwrap();
+        assert!(matches!(entry.request, CalcRequest::Ships(ref r) if r.target == format!("Target {}", MAX_HISTORY + 2)));
+    }
+}
@@ -0,0 +1,206 @@
+//! Ship Lens Game Install Detection
+//!
+//! Finds Star Citizen channel folders (LIVE, PTU, EPTU, ...) so the extraction
+//! subsystem can be pointed at them without the user browsing for a path.
+//! - Windows: RSI Launcher log, launcher registry entry, default install dirs
+//! - Linux: launcher log inside Wine prefixes, Lutris game configs, common prefix paths
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Release channels, in the order they are reported
+const CHANNELS: [&str; 4] = ["LIVE", "PTU", "EPTU", "TECH-PREVIEW"];
+
+/// Library folder relative to a drive root / Wine `drive_c`
+const DEFAULT_LIBRARY: &str = "Program Files/Roberts Space Industries/StarCitizen";
+
+/// A detected game channel install
+#[derive(Debug, Clone, Serialize)]
+pub struct GameInstall {
+    pub channel: String,
+    pub path: String,
+    /// `build_manifest.id` branch, e.g. "sc-alpha-4.0.2"
+    pub branch: Option<String>,
+    pub build_id: Option<String>,
+    /// Human-readable source patch label, e.g. "sc-alpha-4.0.2 (9512345)"
+    pub version: Option<String>,
+    /// True when the install has a Data.p4k (ready to unpack for extraction)
+    pub has_data_p4k: bool,
+}
+
+/// Read branch/build from a channel folder's `build_manifest.id`
+fn read_build_manifest(channel_dir: &Path) -> (Option<String>, Option<String>) {
+    let Ok(json) = std::fs::read_to_string(channel_dir.join("build_manifest.id")) else {
+        return (None, None);
+    };
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&json) else {
+        return (None, None);
+    };
+    let field = |name: &str| manifest["Data"][name].as_str().map(str::to_string);
+    (field("Branch"), field("RequestedP4ChangeNum").or_else(|| field("BuildId")))
+}
+
+fn describe(channel_dir: &Path) -> Option<GameInstall> {
+    let channel = channel_dir.file_name()?.to_string_lossy().to_uppercase();
+    let has_data_p4k = channel_dir.join("Data.p4k").is_file();
+    if !has_data_p4k && !channel_dir.join("build_manifest.id").is_file() {
+        return None;
+    }
+
+    let (branch, build_id) = read_build_manifest(channel_dir);
+    let version = match (&branch, &build_id) {
+        (Some(branch), Some(build)) => Some(format!("{} ({})", branch, build)),
+        (Some(branch), None) => Some(branch.clone()),
+        (None, Some(build)) => Some(build.clone()),
+        (None, None) => None,
+    };
+    Some(GameInstall {
+        channel,
+        path: channel_dir.to_string_lossy().to_string(),
+        branch,
+        build_id,
+        version,
+        has_data_p4k,
+    })
+}
+
+/// Game paths mentioned in an RSI Launcher log ("Launching Star Citizen LIVE from (C:\...\LIVE)")
+fn paths_from_launcher_log(log: &str) -> Vec<String> {
+    log.lines()
+        .filter(|line| line.contains("Launching Star Citizen"))
+        .filter_map(|line| {
+            let start = line.find("from (")? + "from (".len();
+            let end = line[start..].find(')')? + start;
+            Some(line[start..end].to_string())
+        })
+        .collect()
+}
+
+/// Library folders (containing LIVE/PTU/...) to probe on this platform
+#[cfg(windows)]
+fn candidate_libraries() -> Vec<PathBuf> {
+    let mut libraries = Vec::new();
+
+    if let Ok(appdata) = std::env::var("APPDATA") {
+        let log = PathBuf::from(appdata).join("rsilauncher/logs/log.log");
+        if let Ok(contents) = std::fs::read_to_string(log) {
+            for path in paths_from_launcher_log(&contents) {
+                libraries.extend(PathBuf::from(path).parent().map(Path::to_path_buf));
+            }
+        }
+    }
+
+    // The launcher's uninstall entry points at "...\Roberts Space Industries\RSI Launcher"
+    let output = std::process::Command::new("reg")
+        .args(["query", r"HKLM\SOFTWARE\81bfc699-f883-50c7-b674-2483b6baae23", "/v", "InstallLocation"])
+        .output();
+    if let Ok(output) = output {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for line in stdout.lines().filter(|l| l.contains("InstallLocation")) {
+            if let Some(location) = line.split("REG_SZ").nth(1) {
+                if let Some(rsi_dir) = Path::new(location.trim()).parent() {
+                    libraries.push(rsi_dir.join("StarCitizen"));
+                }
+            }
+        }
+    }
+
+    for drive in ["C", "D", "E", "F"] {
+        libraries.push(PathBuf::from(format!("{}:/", drive)).join(DEFAULT_LIBRARY));
+        libraries.push(PathBuf::from(format!("{}:/", drive)).join("StarCitizen"));
+    }
+    libraries
+}
+
+/// Library folders (containing LIVE/PTU/...) to probe on this platform
+#[cfg(not(windows))]
+fn candidate_libraries() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+
+    let mut prefixes = vec![
+        home.join("Games/star-citizen"),
+        home.join("Games/starcitizen"),
+        home.join(".wine"),
+    ];
+    if let Some(prefix) = std::env::var_os("WINEPREFIX") {
+        prefixes.insert(0, PathBuf::from(prefix));
+    }
+
+    // Lutris keeps one YAML per game; the Wine prefix is a `prefix:` line
+    for dir in [home.join(".config/lutris/games"), home.join(".local/share/lutris/games")] {
+        let Ok(entries) = std::fs::read_dir(dir) else { continue };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            let is_star_citizen = path.file_name()
+                .is_some_and(|n| n.to_string_lossy().to_lowercase().contains("star"));
+            if !is_star_citizen {
+                continue;
+            }
+            let Ok(config) = std::fs::read_to_string(&path) else { continue };
+            for line in config.lines() {
+                if let Some(prefix) = line.trim().strip_prefix("prefix:") {
+                    prefixes.push(PathBuf::from(prefix.trim().trim_matches(|c| c == '"' || c == '\'')));
+                }
+            }
+        }
+    }
+
+    let mut libraries = Vec::new();
+    for prefix in prefixes {
+        let drive_c = prefix.join("drive_c");
+
+        // The launcher log inside the prefix records Windows paths; map C:\ onto drive_c
+        if let Ok(users) = std::fs::read_dir(drive_c.join("users")) {
+            for user in users.filter_map(|e| e.ok()) {
+                let log = user.path().join("AppData/Roaming/rsilauncher/logs/log.log");
+                let Ok(contents) = std::fs::read_to_string(log) else { continue };
+                for path in paths_from_launcher_log(&contents) {
+                    let relative = path.trim_start_matches("C:").trim_start_matches(['\\', '/']).replace('\\', "/");
+                    libraries.extend(drive_c.join(relative).parent().map(Path::to_path_buf));
+                }
+            }
+        }
+        libraries.push(drive_c.join(DEFAULT_LIBRARY));
+    }
+    libraries
+}
+
+/// Find all Star Citizen channel installs on this machine (LIVE first)
+pub fn detect_installs() -> Vec<GameInstall> {
+    let mut seen = HashSet::new();
+    let mut installs = Vec::new();
+
+    for library in candidate_libraries() {
+        for channel in CHANNELS {
+            let dir = library.join(channel);
+            let key = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+            if !seen.insert(key) {
+                continue;
+            }
+            installs.extend(describe(&dir));
+        }
+    }
+
+    installs.sort_by_key(|install| CHANNELS.iter().position(|c| *c == install.channel).unwrap_or(CHANNELS.len()));
+    installs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_from_launcher_log() {
+        let log = "\
+[2025-01-10 18:02:11] [info] Checking for updates
+[2025-01-10 18:02:15] [Launcher::launch] Launching Star Citizen LIVE from (C:\\Program Files\\Roberts Space Industries\\StarCitizen\\LIVE)
+[2025-01-11 20:40:03] [Launcher::launch] Launching Star Citizen PTU from (D:\\StarCitizen\\PTU)
+";
+        assert_eq!(paths_from_launcher_log(log), vec![
+            r"C:\Program Files\Roberts Space Industries\StarCitizen\LIVE".to_string(),
+            r"D:\StarCitizen\PTU".to_string(),
+        ]);
+    }
+}
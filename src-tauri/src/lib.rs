@@ -2,24 +2,169 @@
 //!
 //! Rust backend for calculating combat dynamics between ships.
 
+mod analysis;
+mod audit;
+mod backup;
+mod benchmarks;
+mod bombs;
+mod budget;
+mod calibration;
+mod cache;
+mod channel;
+mod combatlog;
+mod crash;
+mod custom;
 mod data;
+mod datadir;
+mod discord;
+mod download;
+mod duel;
+mod dump;
+mod erkul;
+mod escape;
+mod golden;
+mod history;
+mod install;
+mod locks;
+mod logs;
+mod matrix;
+mod metrics;
+mod network;
+mod overlay;
+#[cfg(windows)]
+mod portable;
+mod presets;
+mod priority;
+mod recents;
+mod result_cache;
+mod sensors;
+mod series;
+mod session;
+mod store;
+mod stream;
+mod tasks;
+mod torpedoes;
 mod ttk;
-
-use data::{GameData, Missile, Mount, Ship, Shield, Weapon};
-use ttk::{CombatScenario as TTKScenario, EquippedWeapon, TTKResult, ZoneModifiers};
-use std::fs;
-use std::path::PathBuf;
-use std::sync::Mutex;
-use tauri::{Manager, State};
-
-#[cfg(target_os = "linux")]
-use std::process::Command;
 #[cfg(target_os = "linux")]
-use std::io::Write;
+mod updater;
+mod whatif;
+
+use analysis::{ArmamentSummary, DatasetAnalytics, OrdnanceCapacity, PowerBudget, ShieldOption, ShipComparison, SignatureEstimate, SurvivabilityEntry, TargetAnalysis, ThreatProfile, WeaponMetric, WeaponStats};
+use audit::AuditReport;
+use backup::BackupManifest;
+use benchmarks::{Benchmark, BenchmarkComparison};
+use bombs::BombingRun;
+use budget::{BudgetGoal, BudgetLoadout};
+use calibration::CalibratedScenario;
+use channel::UpdateChannel;
+use combatlog::CombatLog;
+use crash::CrashReport;
+use data::manifest::DataManifest;
+use data::prices::Price;
+use data::{Cooler, GameData, LoadProgress, LoadReport, Manufacturer, Missile, Mount, PowerPlant, Ship, ShipFilter, Shield, SubPort, VariantFilter, Weapon, WeaponFilter, WeaponHardpoint, WeaponPage, WeaponSortBy};
+use datadir::{DataDirSettings, DataLocation};
+use duel::{Fighter, Matchup, PresetMatchup};
+use dump::{DatasetKind, DumpFormat};
+use erkul::ImportedLoadout;
+use escape::EscapeWindow;
+use golden::ModelCheck;
+use history::{HistoryEntry, ResultSummary};
+use locks::{MutexExt, RwLockExt};
+use matrix::WeaponShieldMatrix;
+use metrics::MetricsFile;
+use network::{NetworkSettings, NetworkStatus};
+use overlay::{OverlayReadout, OverlaySettings};
+use presets::{FleetPreset, PresetFilter};
+use priority::{FleetEnemy, KillOrder};
+use recents::{ItemKind, Recents};
+use sensors::Detection;
+use series::SimulationSeries;
+use session::{Session, SessionView};
+use stream::{StreamServer, StreamSettings, StreamStatus};
+use ttk::{ArmorAblation, CombatScenario as TTKScenario, Countermeasures, DefenderBehavior, DegradedThresholds, GunnerProfiles, EquippedWeapon, FireSchedule, PassModel, RuleOfTwoOptions, ScenarioPreset, ScenarioPresetInfo, ShieldBooster, ShieldHardening, TTKResult, ValidationErrors, WeaponTuning, ZoneModifiers, ZonePreset, ZonePresetInfo};
+use result_cache::{ResultCache, RESULT_CACHE_CAPACITY};
+use tasks::{TaskProgress, TaskRegistry};
+use torpedoes::{TorpedoDefense, TorpedoStrike};
+use whatif::{TtkChange, TtkDelta};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use tauri::{Emitter, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use tauri_plugin_updater::UpdaterExt;
 
 /// Application state holding all game data
+///
+/// Readers take a cheap `Arc` snapshot and release the lock immediately;
+/// a reload swaps in a new `Arc<GameData>` under the write lock.
 pub struct AppState {
-    pub data: Mutex<GameData>,
+    pub data: RwLock<Arc<GameData>>,
+    /// Bumped on every data swap (under the `data` write lock); scopes the memoized results
+    pub data_generation: AtomicU64,
+    pub status: RwLock<DataStatus>,
+    pub report: RwLock<LoadReport>,
+    pub tasks: TaskRegistry,
+    /// Memoized `calculate_ttk_v2` results (cleared whenever the data changes)
+    pub results: Mutex<ResultCache<TTKResult>>,
+    /// Local endpoint for OBS browser sources
+    pub stream: StreamServer,
+    /// Manifest of the loaded data dir (None if it has none)
+    pub manifest: RwLock<Option<DataManifest>>,
+    /// Data dir the game data is loaded from
+    pub data_dir: RwLock<DataLocation>,
+    /// Scenario being edited, with undo/redo history
+    pub session: Mutex<Session>,
+}
+
+/// Background data load status (also sent as `data-loading`/`data-ready`/`data-failed` events)
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", content = "error", rename_all = "snake_case")]
+pub enum DataStatus {
+    Loading,
+    Ready,
+    Failed(String),
+}
+
+impl AppState {
+    /// Snapshot of the current game data
+    pub fn snapshot(&self) -> Arc<GameData> {
+        self.data.read_or_recover().clone()
+    }
+
+    /// Snapshot of the current game data with its generation
+    pub fn versioned_snapshot(&self) -> (Arc<GameData>, u64) {
+        let data = self.data.read_or_recover();
+        (data.clone(), self.data_generation.load(Ordering::SeqCst))
+    }
+
+    /// Swap in new game data and drop the results computed against the old
+    pub fn replace_data(&self, data: Arc<GameData>) {
+        {
+            let mut current = self.data.write_or_recover();
+            *current = data;
+            self.data_generation.fetch_add(1, Ordering::SeqCst);
+        }
+        self.results.lock_or_recover().clear();
+    }
+
+    /// Apply an edit to a copy of the game data and swap it in
+    ///
+    /// Issues found while editing are appended to the load report.
+    pub fn update(&self, edit: impl FnOnce(&mut GameData, &mut LoadReport)) {
+        let mut report = LoadReport::default();
+        {
+            let mut data = self.data.write_or_recover();
+            let mut updated = GameData::clone(&data);
+            edit(&mut updated, &mut report);
+            *data = Arc::new(updated);
+            self.data_generation.fetch_add(1, Ordering::SeqCst);
+        }
+        self.results.lock_or_recover().clear();
+        self.report.write_or_recover().merge(report);
+    }
 }
 
 /// Get the data directory path (for pre-Tauri initialization)
@@ -57,6 +202,12 @@ fn get_data_dir() -> PathBuf {
             if exe_data_path.exists() && has_data_files(&exe_data_path) {
                 return exe_data_path;
             }
+
+            // macOS app bundle: the executable is in Contents/MacOS, resources in Contents/Resources
+            let bundle_data_path = exe_dir.join("../Resources/data");
+            if bundle_data_path.exists() && has_data_files(&bundle_data_path) {
+                return bundle_data_path;
+            }
         }
     }
 
@@ -72,95 +223,299 @@ fn get_data_dir() -> PathBuf {
 
 /// Check if a directory has data files (not just empty)
 fn has_data_files(path: &PathBuf) -> bool {
-    // Check for actual data files used by the app (JSON format)
+    // Check for actual data files used by the app (JSON format, or a scunpacked export)
     path.join("ships").is_dir() || path.join("weapons.json").exists() || path.join("shields.json").exists()
+        || data::detect_format(path).is_some()
+}
+
+/// Sort entities by their stable ID (list commands return them in this order)
+fn by_id<T>(mut items: Vec<Arc<T>>, id: fn(&T) -> u32) -> Vec<Arc<T>> {
+    items.sort_by_key(|item| id(item));
+    items
 }
 
 /// Get all ships sorted by name
 #[tauri::command]
 fn get_ships(state: State<AppState>) -> Vec<String> {
-    let data = state.data.lock().unwrap();
+    let data = state.snapshot();
     data.get_ships_sorted()
 }
 
+/// Settings key the ship variant filter is kept under
+const VARIANT_FILTER_KEY: &str = "variant_filter";
+
+/// Load the saved ship variant filter (defaults if none saved)
+fn load_variant_filter(app: &tauri::AppHandle) -> VariantFilter {
+    load_settings(app.clone())
+        .and_then(|mut settings| serde_json::from_value(settings.get_mut(VARIANT_FILTER_KEY)?.take()).ok())
+        .unwrap_or_default()
+}
+
+/// Get sorted ship names with AI/derelict variants hidden per the saved filter
+///
+/// `include_npc_variants` overrides the saved toggle for this call.
+#[tauri::command]
+fn get_filtered_ships(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    include_npc_variants: Option<bool>,
+) -> Vec<String> {
+    let mut filter = load_variant_filter(&app);
+    if let Some(include) = include_npc_variants {
+        filter.include_npc_variants = include;
+    }
+    state.snapshot().get_filtered_ships_sorted(&filter)
+}
+
+/// Get the saved ship variant filter
+#[tauri::command]
+fn get_variant_filter(app: tauri::AppHandle) -> VariantFilter {
+    load_variant_filter(&app)
+}
+
+/// Save the ship variant filter into the settings
+#[tauri::command]
+fn save_variant_filter(app: tauri::AppHandle, filter: VariantFilter) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    let path = config_dir.join("settings.json");
+    let mut settings: serde_json::Map<String, serde_json::Value> = store::read_json(&path)?.unwrap_or_default();
+    let filter = serde_json::to_value(&filter)
+        .map_err(|e| format!("Failed to serialize variant filter: {}", e))?;
+    settings.insert(VARIANT_FILTER_KEY.to_string(), filter);
+    store::write_json(&path, &settings)
+}
+
 /// Get a specific ship by name
 #[tauri::command]
-fn get_ship(state: State<AppState>, name: String) -> Option<Ship> {
-    let data = state.data.lock().unwrap();
-    data.ships.get(&name).cloned()
+fn get_ship(state: State<AppState>, name: String) -> Option<Arc<Ship>> {
+    let data = state.snapshot();
+    data.ship(&name).cloned()
+}
+
+/// Weapon layout of a ship by category, e.g. "4x S3 pilot, 2x twin-S4 manned turrets, 8x S2 PDC"
+#[tauri::command]
+fn get_ship_armament_summary(state: State<AppState>, ship: String) -> Result<ArmamentSummary, String> {
+    let data = state.snapshot();
+    let ship = data.ship(&ship)
+        .ok_or_else(|| format!("Ship '{}' not found", ship))?;
+    Ok(analysis::armament_summary(ship))
 }
 
-/// Get all weapons
+/// Missile, torpedo and bomb racks of a ship with missile counts by size
 #[tauri::command]
-fn get_weapons(state: State<AppState>) -> Vec<Weapon> {
+fn get_ordnance_capacity(state: State<AppState>, ship: String) -> Result<OrdnanceCapacity, String> {
+    let data = state.snapshot();
+    let ship = data.ship(&ship)
+        .ok_or_else(|| format!("Ship '{}' not found", ship))?;
+    Ok(analysis::ordnance_capacity(ship))
+}
+
+/// Get all weapons, in ID order
+#[tauri::command]
+fn get_weapons(state: State<AppState>) -> Vec<Arc<Weapon>> {
     // Return all weapons - restricted filtering done on frontend if needed
-    let data = state.data.lock().unwrap();
-    data.weapons.values()
-        
-        .cloned()
-        .collect()
+    let data = state.snapshot();
+    by_id(data.weapons.values().cloned().collect(), |w| w.id)
 }
 
-/// Get weapons by size
+/// Query ships by size class, role, crew and manufacturer (sorted by name)
 #[tauri::command]
-fn get_weapons_by_size(state: State<AppState>, size: i32) -> Vec<Weapon> {
-    let data = state.data.lock().unwrap();
-    data.weapons.values()
-        
-        .filter(|w| w.size == size)
-        .cloned()
-        .collect()
+fn query_ships(state: State<AppState>, filter: Option<ShipFilter>) -> Vec<Arc<Ship>> {
+    state.snapshot().query_ships(&filter.unwrap_or_default())
+}
+
+/// Get the target zone preset catalog, with `ship`'s own hitbox zone shares when given
+#[tauri::command]
+fn get_zone_presets(state: State<AppState>, ship: Option<String>) -> Result<Vec<ZonePresetInfo>, String> {
+    let data = state.snapshot();
+    let ship = match ship {
+        Some(name) => Some(data.ship(&name).ok_or_else(|| format!("Ship '{}' not found", name))?),
+        None => None,
+    };
+    Ok(ttk::zone_presets(ship.map(|s| s.as_ref())))
 }
 
-/// Get all shields
+/// Get the combat scenario preset catalog
 #[tauri::command]
-fn get_shields(state: State<AppState>) -> Vec<Shield> {
-    let data = state.data.lock().unwrap();
-    data.shields.values().cloned().collect()
+fn get_scenario_presets() -> Vec<ScenarioPresetInfo> {
+    ttk::scenario_presets()
 }
 
-/// Get shields by size
+/// Get manufacturers that make at least one loaded ship or weapon, sorted by name
 #[tauri::command]
-fn get_shields_by_size(state: State<AppState>, size: i32) -> Vec<Shield> {
-    let data = state.data.lock().unwrap();
-    data.shields.values()
-        .filter(|s| s.size == size)
+fn get_manufacturers(state: State<AppState>) -> Vec<Arc<Manufacturer>> {
+    let data = state.snapshot();
+    let mut manufacturers: Vec<Arc<Manufacturer>> = data.manufacturers.values()
+        .filter(|m| {
+            data.ships.values().any(|s| s.manufacturer_code == m.code)
+                || data.weapons.values().any(|w| w.manufacturer_code == m.code)
+        })
         .cloned()
-        .collect()
+        .collect();
+    manufacturers.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.code.cmp(&b.code)));
+    manufacturers
 }
 
-/// Get all missiles
+/// Query weapons with filtering, sorting and pagination (0-based pages)
 #[tauri::command]
-fn get_missiles(state: State<AppState>) -> Vec<Missile> {
-    let data = state.data.lock().unwrap();
-    data.missiles.values().cloned().collect()
+fn query_weapons(
+    state: State<AppState>,
+    filter: Option<WeaponFilter>,
+    page: Option<usize>,
+    page_size: Option<usize>,
+    sort_by: Option<WeaponSortBy>,
+) -> WeaponPage {
+    let data = state.snapshot();
+    data.query_weapons(
+        &filter.unwrap_or_default(),
+        page.unwrap_or(0),
+        page_size.unwrap_or(0),
+        sort_by.unwrap_or_default(),
+    )
 }
 
-/// Get missiles by size
+/// Get weapons by size, in ID order
 #[tauri::command]
-fn get_missiles_by_size(state: State<AppState>, size: i32) -> Vec<Missile> {
-    let data = state.data.lock().unwrap();
-    data.missiles.values()
-        .filter(|m| m.size == size)
-        .cloned()
-        .collect()
+fn get_weapons_by_size(state: State<AppState>, size: i32) -> Vec<Arc<Weapon>> {
+    let data = state.snapshot();
+    by_id(data.weapons.values().filter(|w| w.size == size).cloned().collect(), |w| w.id)
+}
+
+/// Get all shields, in ID order
+#[tauri::command]
+fn get_shields(state: State<AppState>) -> Vec<Arc<Shield>> {
+    let data = state.snapshot();
+    by_id(data.shields.values().cloned().collect(), |s| s.id)
+}
+
+/// Get shields by size, in ID order
+#[tauri::command]
+fn get_shields_by_size(state: State<AppState>, size: i32) -> Vec<Arc<Shield>> {
+    let data = state.snapshot();
+    by_id(data.shields.values().filter(|s| s.size == size).cloned().collect(), |s| s.id)
+}
+
+/// Get all missiles, in ID order
+#[tauri::command]
+fn get_missiles(state: State<AppState>) -> Vec<Arc<Missile>> {
+    let data = state.snapshot();
+    by_id(data.missiles.values().cloned().collect(), |m| m.id)
+}
+
+/// Get missiles by size, in ID order
+#[tauri::command]
+fn get_missiles_by_size(state: State<AppState>, size: i32) -> Vec<Arc<Missile>> {
+    let data = state.snapshot();
+    by_id(data.missiles.values().filter(|m| m.size == size).cloned().collect(), |m| m.id)
 }
 
 /// Get a missile by name
 #[tauri::command]
-fn get_missile(state: State<AppState>, name: String) -> Option<Missile> {
-    let data = state.data.lock().unwrap();
-    data.get_missile_by_display_name(&name).cloned()
+fn get_missile(state: State<AppState>, name: String) -> Option<Arc<Missile>> {
+    let data = state.snapshot();
+    data.missile(&name).cloned()
+}
+
+/// Get all bombs, by size then name
+#[tauri::command]
+fn get_bombs(state: State<AppState>) -> Vec<Arc<Missile>> {
+    let data = state.snapshot();
+    let mut bombs: Vec<_> = data.missiles.values()
+        .filter(|m| m.missile_type == "bomb")
+        .cloned()
+        .collect();
+    bombs.sort_by(|a, b| a.size.cmp(&b.size).then_with(|| a.display_name.cmp(&b.display_name)));
+    bombs
+}
+
+/// Expected result of a bombing run against a target
+///
+/// `bomb` is a display name or missile key; `count` bombs (1-100) are dropped
+/// and each lands with `hit_probability`. `shield_name` is an internal name (the
+/// target's default shield if omitted).
+#[tauri::command]
+fn calculate_bombing_run(
+    state: State<AppState>,
+    bomb: String,
+    count: i32,
+    hit_probability: f64,
+    target: String,
+    shield_name: Option<String>,
+) -> Result<BombingRun, String> {
+    let data = state.snapshot();
+    let ordnance = data.missile(&bomb)
+        .ok_or_else(|| format!("Bomb '{}' not found", bomb))?;
+    let target_ship = data.ship(&target)
+        .ok_or_else(|| format!("Target ship '{}' not found", target))?;
+    let shield = match shield_name {
+        Some(name) => Some(data.shield(&name).ok_or_else(|| format!("Shield '{}' not found", name))?),
+        None => data.find_default_shield(target_ship),
+    };
+    bombs::bombing_run(ordnance, count, hit_probability, target_ship, shield.map(|s| s.as_ref()))
+}
+
+/// Time to impact and expected survivors of a torpedo volley
+///
+/// `torpedo` is a display name or missile key; `count` torpedoes (1-100) are
+/// fired from `range` meters at `speed` m/s (a typical torpedo speed if
+/// omitted). `defense` sets the target's PDCs and countermeasures (PDCs counted
+/// from the target's hardpoints by default). `shield_name` is an internal name
+/// (the target's default shield if omitted).
+#[tauri::command]
+fn calculate_torpedo_strike(
+    state: State<AppState>,
+    torpedo: String,
+    count: i32,
+    range: f64,
+    target: String,
+    shield_name: Option<String>,
+    speed: Option<f64>,
+    defense: Option<TorpedoDefense>,
+) -> Result<TorpedoStrike, String> {
+    let data = state.snapshot();
+    let ordnance = data.missile(&torpedo)
+        .ok_or_else(|| format!("Torpedo '{}' not found", torpedo))?;
+    let target_ship = data.ship(&target)
+        .ok_or_else(|| format!("Target ship '{}' not found", target))?;
+    let shield = match shield_name {
+        Some(name) => Some(data.shield(&name).ok_or_else(|| format!("Shield '{}' not found", name))?),
+        None => data.find_default_shield(target_ship),
+    };
+    torpedoes::torpedo_strike(
+        ordnance,
+        count,
+        range,
+        speed.unwrap_or(torpedoes::DEFAULT_TORPEDO_SPEED),
+        target_ship,
+        shield.map(|s| s.as_ref()),
+        &defense.unwrap_or_default(),
+    )
+}
+
+/// Get all power plants, in ID order
+#[tauri::command]
+fn get_power_plants(state: State<AppState>) -> Vec<Arc<PowerPlant>> {
+    let data = state.snapshot();
+    by_id(data.power_plants.values().cloned().collect(), |p| p.id)
+}
+
+/// Get all coolers, in ID order
+#[tauri::command]
+fn get_coolers(state: State<AppState>) -> Vec<Arc<Cooler>> {
+    let data = state.snapshot();
+    by_id(data.coolers.values().cloned().collect(), |c| c.id)
 }
 
-/// Get all mounts
+/// Get all mounts, in ID order
 #[tauri::command]
-fn get_mounts(state: State<AppState>) -> Vec<Mount> {
-    let data = state.data.lock().unwrap();
-    data.mounts.values().cloned().collect()
+fn get_mounts(state: State<AppState>) -> Vec<Arc<Mount>> {
+    let data = state.snapshot();
+    by_id(data.mounts.values().cloned().collect(), |m| m.id)
 }
 
-/// Get mounts by max size (returns mounts that fit in a hardpoint of given size)
+/// Get mounts by max size (returns mounts that fit in a hardpoint of given size), in ID order
 /// Filters to show only compatible mounts:
 /// - If compatible_mounts list is provided, return only those exact mounts
 /// - Otherwise, filter by ship_ref (generic mounts + ship-specific mounts)
@@ -170,10 +525,10 @@ fn get_mounts_by_max_size(
     max_size: i32,
     ship_ref: Option<String>,
     compatible_mounts: Option<Vec<String>>
-) -> Vec<Mount> {
-    let data = state.data.lock().unwrap();
+) -> Vec<Arc<Mount>> {
+    let data = state.snapshot();
 
-    data.mounts.values()
+    let mounts = data.mounts.values()
         .filter(|m| {
             // Size check
             if m.size > max_size {
@@ -225,14 +580,15 @@ fn get_mounts_by_max_size(
             }
         })
         .cloned()
-        .collect()
+        .collect();
+    by_id(mounts, |m| m.id)
 }
 
 /// Get a mount by ref
 #[tauri::command]
-fn get_mount(state: State<AppState>, mount_ref: String) -> Option<Mount> {
-    let data = state.data.lock().unwrap();
-    data.mounts.get(&mount_ref).cloned()
+fn get_mount(state: State<AppState>, mount_ref: String) -> Option<Arc<Mount>> {
+    let data = state.snapshot();
+    data.mount(&mount_ref).cloned()
 }
 
 /// Calculate TTK between ships (legacy - kept for backwards compatibility)
@@ -245,10 +601,10 @@ fn calculate_ttk(
     mount_type: String,
     accuracy_modifier: f64,
 ) -> Option<data::DamageResult> {
-    let data = state.data.lock().unwrap();
+    let data = state.snapshot();
 
-    let _attacker = data.ships.get(&attacker_ship)?;
-    let target = data.ships.get(&target_ship)?;
+    let _attacker = data.ship(&attacker_ship)?;
+    let target = data.ship(&target_ship)?;
 
     // Get attacker weapons (simplified - uses first available of each size)
     let weapon_sizes: Vec<i32> = _attacker.pilot_weapon_sizes
@@ -262,6 +618,7 @@ fn calculate_ttk(
                 
         .filter(|w| w.size == size)
                 .max_by(|a, b| a.sustained_dps.partial_cmp(&b.sustained_dps).unwrap())
+                .map(|w| w.as_ref())
         })
         .collect();
 
@@ -269,7 +626,7 @@ fn calculate_ttk(
         return None;
     }
 
-    let shield = shield_name.and_then(|n| data.shields.get(&n));
+    let shield = shield_name.and_then(|n| data.shield(&n)).map(|s| s.as_ref());
 
     let scenario = data::CombatScenario {
         scenario_type: "Dogfight".to_string(),
@@ -282,6 +639,108 @@ fn calculate_ttk(
     Some(data::calculate_damage(&weapons, target, shield, &scenario))
 }
 
+/// Build an equipped weapon list from parallel name/count/tuning arrays
+///
+/// Names are display names and may carry a hardpoint label ("HARDPOINT::weapon_name").
+/// Entries with a count of zero or less are skipped; missing tuning means stock
+/// and a missing group means group 0.
+fn build_equipped_weapons(
+    data: &GameData,
+    weapon_names: &[String],
+    weapon_counts: &[i32],
+    weapon_tuning: &[WeaponTuning],
+    weapon_categories: &[String],
+    weapon_groups: &[u32],
+    attacker: Option<&Ship>,
+) -> Result<Vec<EquippedWeapon>, String> {
+    let mut equipped_weapons = Vec::new();
+    for (i, name) in weapon_names.iter().enumerate() {
+        let count = weapon_counts.get(i).copied().unwrap_or(1);
+        if count <= 0 {
+            continue;
+        }
+
+        // Parse the actual weapon name (may contain hardpoint label like "HARDPOINT::weapon_name")
+        let actual_name = if name.contains("::") {
+            name.splitn(2, "::").nth(1).unwrap_or(name)
+        } else {
+            name
+        };
+
+        // Hardpoint category: given explicitly, else looked up from the attacker's hardpoint label
+        let category = weapon_categories.get(i).filter(|c| !c.is_empty()).cloned()
+            .or_else(|| {
+                let label = name.split_once("::")?.0;
+                attacker?.weapon_hardpoints.iter()
+                    .find(|hp| hp.mount_name == label || hp.port_name == label)
+                    .map(|hp| hp.category.clone())
+            })
+            .unwrap_or_default();
+
+        // Try lookup by display_name (frontend passes display names)
+        if let Some(weapon) = data.weapon(actual_name) {
+            equipped_weapons.push(EquippedWeapon {
+                weapon: Weapon::clone(weapon),
+                count,
+                name_with_label: name.clone(),  // Preserve original name with hardpoint label
+                tuning: weapon_tuning.get(i).copied().unwrap_or_default(),
+                category,
+                group: weapon_groups.get(i).copied().unwrap_or_default(),
+            });
+        } else {
+            return Err(format!("Weapon '{}' not found", actual_name));
+        }
+    }
+
+    Ok(equipped_weapons)
+}
+
+/// Arguments of `calculate_ttk_v2` (see there), as kept in the calculation history
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TtkRequest {
+    pub weapon_names: Vec<String>,
+    pub weapon_counts: Vec<i32>,
+    pub missile_names: Vec<String>,
+    pub missile_counts: Vec<i32>,
+    pub target_ship: String,
+    pub shield_name: Option<String>,
+    pub mount_accuracy: Option<f64>,
+    pub scenario_accuracy: Option<f64>,
+    pub time_on_target: Option<f64>,
+    pub fire_mode: Option<f64>,
+    pub power_multiplier: Option<f64>,
+    pub zone_hull: f64,
+    pub zone_armor: f64,
+    pub zone_thruster: f64,
+    pub zone_component: f64,
+    pub engagement_range: Option<f64>,
+    pub shield_loadout: Option<Vec<String>>,
+    pub zone_preset: Option<ZonePreset>,
+    pub scenario_preset: Option<ScenarioPreset>,
+    pub attacker_ship: Option<String>,
+    pub rule_of_two: Option<RuleOfTwoOptions>,
+    pub shield_hardening: Option<ShieldHardening>,
+    pub weapon_tuning: Option<Vec<WeaponTuning>>,
+    pub passes: Option<PassModel>,
+    pub target_profile_factor: Option<f64>,
+    pub evasion_factor: Option<f64>,
+    pub armor_ablation: Option<ArmorAblation>,
+    pub impact_angle: Option<f64>,
+    pub countermeasures: Option<Countermeasures>,
+    pub weapon_categories: Option<Vec<String>>,
+    pub gunner_profiles: Option<GunnerProfiles>,
+    pub weapon_groups: Option<Vec<u32>>,
+    pub fire_schedule: Option<FireSchedule>,
+    pub defender: Option<DefenderBehavior>,
+    pub shield_booster: Option<ShieldBooster>,
+    pub degraded_thresholds: Option<DegradedThresholds>,
+    pub loadout_id: Option<String>,
+    /// The `loadout_id` preset as saved when the request was made (filled in by
+    /// `attach_loadout_preset`, so cached and replayed results follow that build)
+    pub loadout_preset: Option<FleetPreset>,
+}
+
 /// Calculate TTK with full 4.5 damage model
 ///
 /// Parameters:
@@ -293,8 +752,30 @@ fn calculate_ttk(
 /// - shield_name: Internal name of shield to use (or null for target's default)
 /// - scenario: Combat scenario configuration
 /// - zone: Target zone modifiers (hull, armor, thruster, component percentages)
+/// - engagement_range: Optional range in meters for projectile lead/max range modeling
+/// - zone_preset: Optional named zone; overrides the zone_* shares unless "custom"
+/// - scenario_preset: Optional named profile; the scenario fields above override it
+/// - attacker_ship: Optional attacker whose hardpoints the weapons must fit
+/// - rule_of_two: Optional standby efficiency / activation delay for shield failover
+/// - shield_hardening: Optional defender hardening (uptime, damage reduction) during the shield phase
+/// - weapon_tuning: Optional per-weapon tuning, parallel to weapon_names (stock if omitted)
+/// - passes: Optional jousting pass model (fire window / time between passes); replaces time_on_target
+/// - target_profile_factor: Optional scenario_accuracy multiplier (derived from the target's size if omitted)
+/// - evasion_factor: Optional time_on_target multiplier (derived from the target's agility if omitted)
+/// - armor_ablation: Optional curve for armor losing its damage reduction as it is stripped
+/// - impact_angle: Optional ballistic impact angle on armor in degrees from the surface normal (glance past 60°, penetrating under 25°)
+/// - countermeasures: Optional defender decoy/noise stock and evasion (every missile lands if omitted)
+/// - weapon_categories: Optional hardpoint category per weapon, parallel to weapon_names (looked up from attacker_ship if omitted)
+/// - gunner_profiles: Optional gunner accuracy per turret category (AI PDC, average, expert)
+/// - weapon_groups: Optional weapon group per weapon, parallel to weapon_names (all group 0 if omitted)
+/// - fire_schedule: Optional staggered fire across the weapon groups (burst / rest / alternate); replaces fire_mode
+/// - defender: Optional defender shield behavior (passive, face balancing, regen items); passive if omitted
+/// - shield_booster: Optional defender booster consumables (charges, HP restored per use, cooldown)
+/// - degraded_thresholds: Optional health fraction per component below which it runs degraded (0.5 each if omitted)
+/// - loadout_id: Optional saved fleet preset whose guns and ship stand in for weapon_names and attacker_ship
 #[tauri::command]
 fn calculate_ttk_v2(
+    app: tauri::AppHandle,
     state: State<AppState>,
     weapon_names: Vec<String>,
     weapon_counts: Vec<i32>,
@@ -302,99 +783,242 @@ fn calculate_ttk_v2(
     missile_counts: Vec<i32>,
     target_ship: String,
     shield_name: Option<String>,
-    mount_accuracy: f64,
-    scenario_accuracy: f64,
-    time_on_target: f64,
-    fire_mode: f64,
-    power_multiplier: f64,
+    mount_accuracy: Option<f64>,
+    scenario_accuracy: Option<f64>,
+    time_on_target: Option<f64>,
+    fire_mode: Option<f64>,
+    power_multiplier: Option<f64>,
     zone_hull: f64,
     zone_armor: f64,
     zone_thruster: f64,
     zone_component: f64,
+    engagement_range: Option<f64>,
+    shield_loadout: Option<Vec<String>>,
+    zone_preset: Option<ZonePreset>,
+    scenario_preset: Option<ScenarioPreset>,
+    attacker_ship: Option<String>,
+    rule_of_two: Option<RuleOfTwoOptions>,
+    shield_hardening: Option<ShieldHardening>,
+    weapon_tuning: Option<Vec<WeaponTuning>>,
+    passes: Option<PassModel>,
+    target_profile_factor: Option<f64>,
+    evasion_factor: Option<f64>,
+    armor_ablation: Option<ArmorAblation>,
+    impact_angle: Option<f64>,
+    countermeasures: Option<Countermeasures>,
+    weapon_categories: Option<Vec<String>>,
+    gunner_profiles: Option<GunnerProfiles>,
+    weapon_groups: Option<Vec<u32>>,
+    fire_schedule: Option<FireSchedule>,
+    defender: Option<DefenderBehavior>,
+    shield_booster: Option<ShieldBooster>,
+    degraded_thresholds: Option<DegradedThresholds>,
+    loadout_id: Option<String>,
 ) -> Result<TTKResult, String> {
-    let data = state.data.lock().unwrap();
+    let mut request = TtkRequest {
+        weapon_names,
+        weapon_counts,
+        missile_names,
+        missile_counts,
+        target_ship,
+        shield_name,
+        mount_accuracy,
+        scenario_accuracy,
+        time_on_target,
+        fire_mode,
+        power_multiplier,
+        zone_hull,
+        zone_armor,
+        zone_thruster,
+        zone_component,
+        engagement_range,
+        shield_loadout,
+        zone_preset,
+        scenario_preset,
+        attacker_ship,
+        rule_of_two,
+        shield_hardening,
+        weapon_tuning,
+        passes,
+        target_profile_factor,
+        evasion_factor,
+        armor_ablation,
+        impact_angle,
+        countermeasures,
+        weapon_categories,
+        gunner_profiles,
+        weapon_groups,
+        fire_schedule,
+        defender,
+        shield_booster,
+        degraded_thresholds,
+        loadout_id,
+        loadout_preset: None,
+    };
+    attach_loadout_preset(&app, &mut request)?;
+    run_calculation(&app, &state, CalcRequest::Loadout(request))
+}
 
-    // Get target ship
-    let target = data.ships.get(&target_ship)
-        .ok_or_else(|| format!("Target ship '{}' not found", target_ship))?;
+/// Look up the saved preset a request's `loadout_id` names, if any
+fn attach_loadout_preset(app: &tauri::AppHandle, request: &mut TtkRequest) -> Result<(), String> {
+    let Some(ref loadout_id) = request.loadout_id else {
+        return Ok(());
+    };
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    request.loadout_preset = presets::load(&config_dir)?.into_iter().find(|p| &p.id == loadout_id);
+    Ok(())
+}
 
-    // Build equipped weapons list
-    let mut equipped_weapons = Vec::new();
-    for (i, name) in weapon_names.iter().enumerate() {
-        let count = weapon_counts.get(i).copied().unwrap_or(1);
-        if count <= 0 {
-            continue;
-        }
+/// `request` with its `loadout_id` preset resolved into weapons and attacker
+///
+/// The preset fits its ship as in duels (see `Fighter::with_preset`); an
+/// `attacker_ship` must be that ship. Explicit `weapon_names` are rejected since
+/// the preset replaces them.
+fn resolve_loadout<'a>(data: &GameData, request: &'a TtkRequest) -> Result<Cow<'a, TtkRequest>, String> {
+    let Some(ref loadout_id) = request.loadout_id else {
+        return Ok(Cow::Borrowed(request));
+    };
+    if !request.weapon_names.is_empty() {
+        return Err("Give either weapon_names or loadout_id, not both".to_string());
+    }
+    let attacker = Fighter { ship: request.attacker_ship.clone().unwrap_or_default(), ..Default::default() };
+    let fitted = attacker.with_preset(data, request.loadout_preset.as_slice(), loadout_id)?;
+    let ship = data.ship(&fitted.ship)
+        .ok_or_else(|| format!("Attacker ship '{}' not found", fitted.ship))?;
+
+    let mut errors = ValidationErrors::default();
+    let loadout = build_ship_loadout(data, ship, &fitted.loadout.unwrap_or_default(), &HashMap::new(), &mut errors);
+    errors.into_result().map_err(|e| e.to_string())?;
+    Ok(Cow::Owned(TtkRequest {
+        weapon_names: loadout.weapons.iter().map(|w| w.name_with_label.clone()).collect(),
+        weapon_counts: loadout.weapons.iter().map(|w| w.count).collect(),
+        weapon_categories: Some(loadout.weapons.iter().map(|w| w.category.clone()).collect()),
+        attacker_ship: Some(ship.display_name.clone()),
+        ..request.clone()
+    }))
+}
 
-        // Parse the actual weapon name (may contain hardpoint label like "HARDPOINT::weapon_name")
-        let actual_name = if name.contains("::") {
-            name.splitn(2, "::").nth(1).unwrap_or(name)
-        } else {
-            name
-        };
+/// Compute a `calculate_ttk_v2` request against a data snapshot
+fn compute_ttk_v2(data: &GameData, request: &TtkRequest) -> Result<TTKResult, String> {
+    let request = &*resolve_loadout(data, request)?;
+    let mut errors = ValidationErrors::default();
+    errors.check_counts("weapon_counts", &request.weapon_counts);
+    errors.check_counts("missile_counts", &request.missile_counts);
 
-        // Try lookup by display_name (frontend passes display names)
-        if let Some(weapon) = data.get_weapon_by_display_name(actual_name) {
-            equipped_weapons.push(EquippedWeapon {
-                weapon: weapon.clone(),
-                count,
-                name_with_label: name.clone(),  // Preserve original name with hardpoint label
-            });
-        } else {
-            return Err(format!("Weapon '{}' not found", actual_name));
-        }
+    // Get target ship
+    let target = data.ship(&request.target_ship)
+        .ok_or_else(|| format!("Target ship '{}' not found", request.target_ship))?;
+
+    // Build equipped weapons list
+    let weapon_tuning = request.weapon_tuning.as_deref().unwrap_or_default();
+    for (i, tuning) in weapon_tuning.iter().enumerate() {
+        tuning.validate(&format!("weapon_tuning[{}]", i), &mut errors);
     }
+    let attacker = request.attacker_ship.as_ref().and_then(|name| data.ship(name));
+    let equipped_weapons = build_equipped_weapons(
+        data,
+        &request.weapon_names,
+        &request.weapon_counts,
+        weapon_tuning,
+        request.weapon_categories.as_deref().unwrap_or_default(),
+        request.weapon_groups.as_deref().unwrap_or_default(),
+        attacker.map(|a| &**a),
+    )?;
 
     if equipped_weapons.is_empty() {
         return Err("No weapons equipped".to_string());
     }
 
-    // Get shield (use specified, or look up target's default)
-    let shield = if let Some(ref name) = shield_name {
-        data.shields.get(name)
+    // Build scenario (named preset or default, with per-field overrides)
+    let base = request.scenario_preset.map(ScenarioPreset::scenario).unwrap_or_default();
+    let scenario = TTKScenario {
+        mount_accuracy: request.mount_accuracy.unwrap_or(base.mount_accuracy),
+        scenario_accuracy: request.scenario_accuracy.unwrap_or(base.scenario_accuracy),
+        time_on_target: request.time_on_target.unwrap_or(base.time_on_target),
+        fire_mode: request.fire_mode.unwrap_or(base.fire_mode),
+        power_multiplier: request.power_multiplier.unwrap_or(base.power_multiplier),
+        engagement_range: request.engagement_range.unwrap_or(base.engagement_range),
+        rule_of_two: request.rule_of_two.unwrap_or(base.rule_of_two),
+        shield_hardening: request.shield_hardening.unwrap_or(base.shield_hardening),
+        passes: request.passes.or(base.passes),
+        target_profile_factor: request.target_profile_factor.or(base.target_profile_factor),
+        evasion_factor: request.evasion_factor.or(base.evasion_factor),
+        armor_ablation: request.armor_ablation.or(base.armor_ablation),
+        impact_angle: request.impact_angle.or(base.impact_angle),
+        gunner_profiles: request.gunner_profiles.unwrap_or(base.gunner_profiles),
+        fire_schedule: request.fire_schedule.or(base.fire_schedule),
+        defender: request.defender.unwrap_or(base.defender),
+        shield_booster: request.shield_booster.or(base.shield_booster),
+        degraded_thresholds: request.degraded_thresholds.unwrap_or(base.degraded_thresholds),
+    };
+    scenario.validate(&mut errors);
+
+    // Mixed loadout: one shield per generator slot (overrides shield_name)
+    let mixed_shields = request.shield_loadout.as_deref().unwrap_or_default()
+        .iter()
+        .map(|name| data.shield(name).map(|s| Shield::clone(s))
+            .ok_or_else(|| format!("Shield '{}' not found", name)))
+        .collect::<Result<Vec<Shield>, String>>()?;
+    if !mixed_shields.is_empty() && mixed_shields.len() != target.shield_count.max(0) as usize {
+        errors.push("shield_loadout", format!(
+            "{} has {} shield generators, got {} shields", target.display_name, target.shield_count, mixed_shields.len(),
+        ));
+    }
+
+    // Get shield (mixed composite, specified, or target's default)
+    let shield = if let Some(composite) = ttk::composite_shield(&mixed_shields, &scenario.rule_of_two) {
+        composite
+    } else if let Some(ref name) = request.shield_name {
+        data.shield(name)
+            .map(|s| Shield::clone(s))
             .ok_or_else(|| format!("Shield '{}' not found", name))?
     } else {
-        // Try to find default shield by internal name reference
-        let default_ref = &target.default_shield_ref;
-        if !default_ref.is_empty() {
-            data.shields.values()
-                .find(|s| s.internal_name.to_lowercase().contains(&default_ref.to_lowercase()))
-                .ok_or_else(|| "Could not find default shield".to_string())?
-        } else {
-            // Fall back to first shield of matching size
-            data.shields.values()
-                .find(|s| s.size == target.max_shield_size)
-                .ok_or_else(|| "No compatible shield found".to_string())?
-        }
+        data.find_default_shield(target)
+            .map(|s| Shield::clone(s))
+            .ok_or_else(|| "Could not find default shield".to_string())?
     };
 
-    // Build scenario
-    let scenario = TTKScenario {
-        mount_accuracy,
-        scenario_accuracy,
-        time_on_target,
-        fire_mode,
-        power_multiplier,
+    let mut countermeasures = request.countermeasures.unwrap_or_default();
+    countermeasures.validate(&mut errors);
+
+    // Build zone modifiers (named preset, or validated custom shares)
+    let zone = match request.zone_preset.and_then(|preset| preset.modifiers_for(target)) {
+        Some(zone) => zone,
+        None => ZoneModifiers {
+            hull: request.zone_hull,
+            armor: request.zone_armor,
+            thruster: request.zone_thruster,
+            component: request.zone_component,
+        },
     };
+    if let Err(e) = zone.validate() {
+        errors.push("zone", e);
+    }
 
-    // Build zone modifiers
-    let zone = ZoneModifiers {
-        hull: zone_hull,
-        armor: zone_armor,
-        thruster: zone_thruster,
-        component: zone_component,
-    };
+    // Weapons must fit the attacker's hardpoints when an attacker is given
+    if let Some(ref name) = request.attacker_ship {
+        match attacker {
+            Some(attacker) => errors.check_hardpoint_fit(attacker, &equipped_weapons),
+            None => errors.push("attacker_ship", format!("Ship '{}' not found", name)),
+        }
+    }
+    errors.into_result().map_err(|e| e.to_string())?;
 
     // Calculate TTK using new model
-    let mut result = ttk::calculate_ttk(&equipped_weapons, target, shield, &scenario, &zone);
+    let mut result = if mixed_shields.is_empty() {
+        ttk::calculate_ttk(&equipped_weapons, target, &shield, &scenario, &zone)
+    } else {
+        ttk::calculate_ttk_mixed_shields(&equipped_weapons, target, &mixed_shields, &scenario, &zone)
+    };
 
     // Calculate missile effectiveness if missiles are equipped
-    if !missile_names.is_empty() {
-        use std::collections::HashMap;
-        let mut missile_groups: HashMap<String, i32> = HashMap::new();
+    if !request.missile_names.is_empty() {
+        // Ordered so countermeasure stock is spent deterministically
+        let mut missile_groups: BTreeMap<String, i32> = BTreeMap::new();
 
-        for (i, name) in missile_names.iter().enumerate() {
-            let count = missile_counts.get(i).copied().unwrap_or(1);
+        for (i, name) in request.missile_names.iter().enumerate() {
+            let count = request.missile_counts.get(i).copied().unwrap_or(1);
             if count <= 0 {
                 continue;
             }
@@ -411,13 +1035,14 @@ fn calculate_ttk_v2(
                 &name
             };
 
-            if let Some(missile) = data.get_missile_by_display_name(actual_name) {
+            if let Some(missile) = data.missile(actual_name) {
                 let effectiveness = ttk::calculate_missile_effectiveness(
                     missile,
                     &name,  // Pass full name with label
                     count,
                     target,
-                    shield,
+                    &shield,
+                    &mut countermeasures,
                 );
                 missile_breakdown.push(effectiveness);
             }
@@ -434,122 +1059,1349 @@ fn calculate_ttk_v2(
     Ok(result)
 }
 
-/// Get a weapon by name (searches by display_name)
-#[tauri::command]
-fn get_weapon(state: State<AppState>, name: String) -> Option<Weapon> {
-    let data = state.data.lock().unwrap();
-    data.get_weapon_by_display_name(&name).cloned()
-}
-
-/// Get a shield by name
-#[tauri::command]
-fn get_shield(state: State<AppState>, name: String) -> Option<Shield> {
-    let data = state.data.lock().unwrap();
-    data.shields.get(&name).cloned()
-}
-
-/// Get statistics summary
-#[tauri::command]
-fn get_stats(state: State<AppState>) -> serde_json::Value {
-    let data = state.data.lock().unwrap();
-    serde_json::json!({
-        "ship_count": data.ships.len(),
-        "weapon_count": data.weapons.len(),
-        "shield_count": data.shields.len(),
-    })
+/// A TTK calculation request, replayable from the history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "request", rename_all = "snake_case")]
+pub enum CalcRequest {
+    /// `calculate_ttk_v2`
+    Loadout(TtkRequest),
+    /// `calculate_ttk_for_ships`
+    Ships(ShipTtkRequest),
 }
 
-/// Save settings to file
-#[tauri::command]
-fn save_settings(app: tauri::AppHandle, settings: serde_json::Value) -> Result<(), String> {
-    let config_dir = app.path().app_config_dir()
-        .map_err(|e| format!("Failed to get config dir: {}", e))?;
-
-    // Create directory if it doesn't exist
-    fs::create_dir_all(&config_dir)
-        .map_err(|e| format!("Failed to create config dir: {}", e))?;
-
-    let settings_path = config_dir.join("settings.json");
-    let json = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+impl CalcRequest {
+    /// Display name of the target ship
+    fn target(&self) -> &str {
+        match self {
+            CalcRequest::Loadout(r) => &r.target_ship,
+            CalcRequest::Ships(r) => &r.target,
+        }
+    }
 
-    fs::write(&settings_path, json)
-        .map_err(|e| format!("Failed to write settings: {}", e))?;
+    /// Zone shares the request is computed with against `target` (named preset, else custom or center mass)
+    fn zone(&self, target: &Ship) -> ZoneModifiers {
+        match self {
+            CalcRequest::Loadout(r) => r.zone_preset.and_then(|preset| preset.modifiers_for(target)).unwrap_or(ZoneModifiers {
+                hull: r.zone_hull,
+                armor: r.zone_armor,
+                thruster: r.zone_thruster,
+                component: r.zone_component,
+            }),
+            CalcRequest::Ships(r) => r.zone_preset.and_then(|preset| preset.modifiers_for(target)).unwrap_or_default(),
+        }
+    }
 
-    Ok(())
+    /// Ships and shields picked explicitly, for the recents lists
+    fn picks(&self) -> Vec<(ItemKind, String)> {
+        let (ships, shields): (Vec<&String>, Vec<&String>) = match self {
+            CalcRequest::Loadout(r) => (
+                std::iter::once(&r.target_ship).chain(&r.attacker_ship).collect(),
+                r.shield_name.iter().chain(r.shield_loadout.iter().flatten()).collect(),
+            ),
+            CalcRequest::Ships(r) => (vec![&r.target, &r.attacker], r.shield_name.iter().collect()),
+        };
+        ships.into_iter().map(|name| (ItemKind::Ship, name.clone()))
+            .chain(shields.into_iter().map(|name| (ItemKind::Shield, name.clone())))
+            .collect()
+    }
 }
 
-/// Load settings from file
-#[tauri::command]
-fn load_settings(app: tauri::AppHandle) -> Option<serde_json::Value> {
-    let config_dir = app.path().app_config_dir().ok()?;
-    let settings_path = config_dir.join("settings.json");
+/// Run a TTK request against the current data
+///
+/// Answers from the memoized results when possible. Successful calculations
+/// update the recents lists, are appended to the history and are pushed to the
+/// stream endpoint.
+fn run_calculation(app: &tauri::AppHandle, state: &AppState, request: CalcRequest) -> Result<TTKResult, String> {
+    let result = cached_calculation(state, &request)?;
+    state.stream.publish(&OverlayReadout::new(&request, ResultSummary::of(&result)));
+
+    let mut picks = request.picks();
+    picks.extend(result.weapon_breakdown.iter().map(|w| (ItemKind::Weapon, w.weapon_name.clone())));
+    record_recents(app, &picks);
+    record_history(app, request, &result);
+    Ok(result)
+}
 
-    if !settings_path.exists() {
-        return None;
+/// Compute a TTK request, answering from the memoized results when possible
+fn cached_calculation(state: &AppState, request: &CalcRequest) -> Result<TTKResult, String> {
+    let (data, generation) = state.versioned_snapshot();
+
+    // Same request against the same data generation: answer from the cache
+    let key = result_cache::request_key(generation, request);
+    let cached = key.and_then(|key| state.results.lock_or_recover().get(key));
+    match cached {
+        Some(result) => {
+            log::debug!("TTK request {:016x?} answered from cache", key);
+            metrics::record_calculation();
+            Ok(result)
+        }
+        None => {
+            let computed = match request {
+                CalcRequest::Loadout(r) => compute_ttk_v2(&data, r),
+                CalcRequest::Ships(r) => compute_ttk_for_ships(&data, r),
+            };
+            let result = computed.map_err(|e| {
+                log::warn!("TTK calculation failed: {}", e);
+                e
+            })?;
+            log::debug!("TTK request {:016x?} computed: total {:.2}s", key, result.total_ttk);
+            if let Some(key) = key {
+                // The data may have been swapped (and the cache cleared) meanwhile
+                let mut results = state.results.lock_or_recover();
+                if state.data_generation.load(Ordering::SeqCst) == generation {
+                    results.insert(key, result.clone());
+                }
+            }
+            metrics::record_calculation();
+            Ok(result)
+        }
     }
-
-    let json = fs::read_to_string(&settings_path).ok()?;
-    serde_json::from_str(&json).ok()
 }
 
-/// Save a fleet preset
+/// Drop every memoized TTK result
 #[tauri::command]
-fn save_fleet_preset(app: tauri::AppHandle, preset: serde_json::Value) -> Result<(), String> {
-    let config_dir = app.path().app_config_dir()
-        .map_err(|e| format!("Failed to get config dir: {}", e))?;
-
-    fs::create_dir_all(&config_dir)
-        .map_err(|e| format!("Failed to create config dir: {}", e))?;
+fn clear_cache(state: State<AppState>) {
+    state.results.lock_or_recover().clear();
+}
 
-    let presets_path = config_dir.join("fleet_presets.json");
+/// Gun loadout derived from an attacker's weapon hardpoints
+struct ShipLoadout {
+    weapons: Vec<EquippedWeapon>,
+    /// Mount accuracy averaged over the loadout, weighted by DPS
+    mount_accuracy: Option<f64>,
+}
 
-    // Load existing presets or create empty array
-    let mut presets: Vec<serde_json::Value> = if presets_path.exists() {
-        let json = fs::read_to_string(&presets_path)
-            .map_err(|e| format!("Failed to read presets: {}", e))?;
-        serde_json::from_str(&json).unwrap_or_default()
-    } else {
-        Vec::new()
-    };
+/// Build the attacker's gun loadout from its `weapon_hardpoints`
+///
+/// Every sub-port holds one weapon, so dual sub-mounts count twice. `overrides`
+/// maps port names to a weapon display name or filename ("" or "empty" clears the
+/// port); `port#N` sets only the Nth (1-based) sub-port of a multi-weapon mount.
+/// Other ports keep their default weapon. `mounts` swaps the mount on a port
+/// (port name -> mount ref): its sub-ports follow the new mount's layout, keeping
+/// the default weapons that still fit. Weapons larger than their sub-port,
+/// mounts larger than their hardpoint and unknown ports/weapons/mounts are
+/// reported in `errors`.
+fn build_ship_loadout(
+    data: &GameData,
+    attacker: &Ship,
+    overrides: &HashMap<String, String>,
+    mounts: &HashMap<String, String>,
+    errors: &mut ValidationErrors,
+) -> ShipLoadout {
+    let mut hardpoints: Vec<Cow<WeaponHardpoint>> = attacker.weapon_hardpoints.iter().map(Cow::Borrowed).collect();
+    for (port, mount_name) in mounts {
+        let field = format!("attacker_mounts[{}]", port);
+        let Some(hp) = hardpoints.iter_mut().find(|hp| &hp.port_name == port) else {
+            errors.push(field, format!("{} has no hardpoint '{}'", attacker.display_name, port));
+            continue;
+        };
+        let Some(mount) = data.mount(mount_name).or_else(|| data.mounts.get(&data::mounts::mount_ref(mount_name))) else {
+            errors.push(field, format!("Mount '{}' not found", mount_name));
+            continue;
+        };
+        if mount.size > hp.max_size {
+            errors.push(field, format!("S{} {} does not fit an S{} hardpoint", mount.size, mount.display_name, hp.max_size));
+            continue;
+        }
+        let hp = hp.to_mut();
+        hp.sub_ports = (0..mount.ports as usize)
+            .map(|i| SubPort {
+                size: mount.port_size,
+                default_weapon: hp.sub_ports.get(i)
+                    .and_then(|sp| sp.default_weapon.clone())
+                    .filter(|name| data.weapon(name).is_some_and(|w| w.size <= mount.port_size)),
+            })
+            .collect();
+        hp.mount_name = mount.mount_ref.clone();
+        hp.gimbal_type = data::mounts::gimbal_type(&mount.mount_type).to_string();
+    }
 
-    // Check if preset with same ID exists and update, otherwise add
-    let preset_id = preset.get("id").and_then(|v| v.as_str()).unwrap_or("");
-    if let Some(pos) = presets.iter().position(|p| {
-        p.get("id").and_then(|v| v.as_str()).unwrap_or("") == preset_id
-    }) {
-        presets[pos] = preset;
-    } else {
-        presets.push(preset);
+    for key in overrides.keys() {
+        let (port, sub_slot) = match key.rsplit_once('#') {
+            Some((port, n)) => (port, n.parse::<usize>().ok().filter(|&n| n > 0).ok_or(())),
+            None => (key.as_str(), Ok(1)),
+        };
+        let found = hardpoints.iter().find(|hp| hp.port_name == port);
+        match (found, sub_slot) {
+            (None, _) => errors.push(
+                format!("attacker_loadout[{}]", key),
+                format!("{} has no hardpoint '{}'", attacker.display_name, port),
+            ),
+            (Some(hp), Ok(n)) if n <= hp.sub_ports.len().max(1) => {}
+            (Some(hp), _) => errors.push(
+                format!("attacker_loadout[{}]", key),
+                format!("'{}' has {} weapon slots", port, hp.sub_ports.len()),
+            ),
+        }
     }
 
-    let json = serde_json::to_string_pretty(&presets)
-        .map_err(|e| format!("Failed to serialize presets: {}", e))?;
+    let mut weapons = Vec::new();
+    let mut weighted_accuracy = 0.0;
+    let mut total_dps = 0.0;
+
+    let gun_hardpoints = hardpoints.iter()
+        .filter(|hp| ttk::GUN_CATEGORIES.contains(&hp.category.as_str()));
+    for hp in gun_hardpoints {
+        // Weapon per sub-port, in order, so identical weapons on a dual mount group together
+        let mut groups: Vec<(Arc<Weapon>, i32)> = Vec::new();
+        for (i, sub_port) in hp.sub_ports.iter().enumerate() {
+            let slot_key = format!("{}#{}", hp.port_name, i + 1);
+            let override_key = [&slot_key, &hp.port_name].into_iter().find(|key| overrides.contains_key(*key));
+            let name = match override_key {
+                Some(key) => overrides[key].as_str(),
+                None => sub_port.default_weapon.as_deref().unwrap_or(""),
+            };
+            if name.is_empty() || name.eq_ignore_ascii_case("empty") {
+                continue;
+            }
 
-    fs::write(&presets_path, json)
-        .map_err(|e| format!("Failed to write presets: {}", e))?;
+            let Some(weapon) = data.weapon(name)
+            else {
+                // Unknown default weapons are data gaps, not request errors
+                if let Some(key) = override_key {
+                    errors.push(format!("attacker_loadout[{}]", key), format!("Weapon '{}' not found", name));
+                }
+                continue;
+            };
+            if weapon.size > sub_port.size {
+                errors.push(
+                    format!("attacker_loadout[{}]", override_key.unwrap_or(&hp.port_name)),
+                    format!("S{} {} does not fit an S{} port", weapon.size, weapon.display_name, sub_port.size),
+                );
+                continue;
+            }
 
-    Ok(())
+            match groups.iter_mut().find(|(w, _)| Arc::ptr_eq(w, weapon)) {
+                Some((_, count)) => *count += 1,
+                None => groups.push((weapon.clone(), 1)),
+            }
+        }
+
+        for (weapon, count) in groups {
+            if let Some(accuracy) = ttk::mount_accuracy(&hp.gimbal_type) {
+                let dps = weapon.sustained_dps * count as f64;
+                weighted_accuracy += accuracy * dps;
+                total_dps += dps;
+            }
+            weapons.push(EquippedWeapon {
+                name_with_label: format!("{}::{}", hp.port_name, weapon.display_name),
+                weapon: Weapon::clone(&weapon),
+                count,
+                tuning: WeaponTuning::default(),
+                category: hp.category.clone(),
+                group: 0,
+            });
+        }
+    }
+
+    ShipLoadout {
+        weapons,
+        mount_accuracy: (total_dps > 0.0).then(|| weighted_accuracy / total_dps),
+    }
+}
+
+/// Check a ship loadout's weapon power draw against a power plant
+///
+/// The loadout is built as in `calculate_ttk_for_ships` (`attacker_loadout`,
+/// `attacker_mounts`); `power_plant` replaces the ship's stock plant.
+#[tauri::command]
+fn check_power_budget(
+    state: State<AppState>,
+    ship: String,
+    attacker_loadout: Option<HashMap<String, String>>,
+    attacker_mounts: Option<HashMap<String, String>>,
+    power_plant: Option<String>,
+) -> Result<PowerBudget, String> {
+    let data = state.snapshot();
+    let ship = data.ship(&ship)
+        .ok_or_else(|| format!("Ship '{}' not found", ship))?;
+    let plant = match power_plant {
+        Some(name) => Some(data.power_plant(&name).ok_or_else(|| format!("Power plant '{}' not found", name))?),
+        None => data.find_default_power_plant(ship),
+    };
+
+    let loadout = build_checked_loadout(&data, ship, attacker_loadout, attacker_mounts)?;
+    Ok(analysis::power_budget(plant.map(|p| p.as_ref()), &loadout.weapons))
+}
+
+/// Estimate the EM/IR signature of a ship loadout while firing
+///
+/// The loadout is built as in `check_power_budget`; `power_plant` and `cooler`
+/// replace the ship's stock parts for the load fractions.
+#[tauri::command]
+fn estimate_signatures(
+    state: State<AppState>,
+    ship: String,
+    attacker_loadout: Option<HashMap<String, String>>,
+    attacker_mounts: Option<HashMap<String, String>>,
+    power_plant: Option<String>,
+    cooler: Option<String>,
+) -> Result<SignatureEstimate, String> {
+    let data = state.snapshot();
+    let ship = data.ship(&ship)
+        .ok_or_else(|| format!("Ship '{}' not found", ship))?;
+    loadout_signatures(&data, ship, attacker_loadout, attacker_mounts, power_plant, cooler)
+}
+
+/// Signature estimate of a ship loadout (see `estimate_signatures`)
+fn loadout_signatures(
+    data: &GameData,
+    ship: &Ship,
+    overrides: Option<HashMap<String, String>>,
+    mounts: Option<HashMap<String, String>>,
+    power_plant: Option<String>,
+    cooler: Option<String>,
+) -> Result<SignatureEstimate, String> {
+    let plant = match power_plant {
+        Some(name) => Some(data.power_plant(&name).ok_or_else(|| format!("Power plant '{}' not found", name))?),
+        None => data.find_default_power_plant(ship),
+    };
+    let cooler = match cooler {
+        Some(name) => Some(data.cooler(&name).ok_or_else(|| format!("Cooler '{}' not found", name))?),
+        None => data.find_default_cooler(ship),
+    };
+
+    let loadout = build_checked_loadout(data, ship, overrides, mounts)?;
+    Ok(analysis::estimate_signatures(
+        plant.map(|p| p.as_ref()),
+        cooler.map(|c| c.as_ref()),
+        &loadout.weapons,
+    ))
+}
+
+/// How far out an attacker's radar detects a target flying a loadout
+///
+/// The target's loadout is built as in `estimate_signatures` (`target_loadout`,
+/// `target_mounts`, `power_plant`, `cooler`). `distance` (m) and `closing_speed`
+/// (m/s) give the time until detection.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn detection_range(
+    state: State<AppState>,
+    attacker: String,
+    target: String,
+    target_loadout: Option<HashMap<String, String>>,
+    target_mounts: Option<HashMap<String, String>>,
+    power_plant: Option<String>,
+    cooler: Option<String>,
+    distance: Option<f64>,
+    closing_speed: Option<f64>,
+) -> Result<Detection, String> {
+    let data = state.snapshot();
+    let attacker_ship = data.ship(&attacker)
+        .ok_or_else(|| format!("Attacker ship '{}' not found", attacker))?;
+    let target_ship = data.ship(&target)
+        .ok_or_else(|| format!("Target ship '{}' not found", target))?;
+    let signatures = loadout_signatures(&data, target_ship, target_loadout, target_mounts, power_plant, cooler)?;
+    sensors::detection(attacker_ship, target_ship, &signatures, distance, closing_speed)
+}
+
+/// `build_ship_loadout` with optional overrides, failing on any validation error
+fn build_checked_loadout(
+    data: &GameData,
+    ship: &Ship,
+    overrides: Option<HashMap<String, String>>,
+    mounts: Option<HashMap<String, String>>,
+) -> Result<ShipLoadout, String> {
+    let mut errors = ValidationErrors::default();
+    let loadout = build_ship_loadout(data, ship, &overrides.unwrap_or_default(), &mounts.unwrap_or_default(), &mut errors);
+    errors.into_result().map_err(|e| e.to_string())?;
+    Ok(loadout)
+}
+
+/// Arguments of `calculate_ttk_for_ships` (see there), as kept in the calculation history
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShipTtkRequest {
+    pub attacker: String,
+    pub attacker_loadout: Option<HashMap<String, String>>,
+    pub attacker_mounts: Option<HashMap<String, String>>,
+    pub target: String,
+    pub shield_name: Option<String>,
+    pub scenario_preset: Option<ScenarioPreset>,
+    pub mount_accuracy: Option<f64>,
+    pub scenario_accuracy: Option<f64>,
+    pub time_on_target: Option<f64>,
+    pub zone_preset: Option<ZonePreset>,
+    pub engagement_range: Option<f64>,
+    pub shield_hardening: Option<ShieldHardening>,
+    pub gunner_profiles: Option<GunnerProfiles>,
+    pub weapon_groups: Option<HashMap<String, u32>>,
+    pub fire_schedule: Option<FireSchedule>,
+    pub defender: Option<DefenderBehavior>,
+    pub shield_booster: Option<ShieldBooster>,
+    pub degraded_thresholds: Option<DegradedThresholds>,
+}
+
+/// Calculate TTK from an attacker ship's actual hardpoints
+///
+/// Unlike `calculate_ttk_v2`, weapon counts and mount types come from the
+/// attacker's `weapon_hardpoints` (pilot and turret guns; missiles are not
+/// included). `attacker_loadout` optionally replaces the weapon on individual
+/// ports (port name, or `port#N` for one weapon of a multi-weapon mount ->
+/// weapon display name); `attacker_mounts` swaps mounts (port name -> mount
+/// ref, e.g. a fixed S4 in place of a twin-S3 gimbal). The mount accuracy is derived from
+/// the hardpoint gimbal types unless `mount_accuracy` is given; `gunner_profiles`
+/// overrides it for turret guns by hardpoint category. `scenario_accuracy` and
+/// `time_on_target` override the preset's (e.g. a `calibrate_scenario` profile). `weapon_groups` assigns
+/// ports to weapon groups (port name -> group, others in group 0) for a
+/// staggered `fire_schedule`; `defender` sets how the target manages its shields
+/// and `shield_booster` the booster consumables it carries; `degraded_thresholds`
+/// sets the health fraction below which each target component runs degraded. `loadout_id` fits
+/// the attacker as a saved fleet preset (`attacker` may then be empty), with
+/// `attacker_loadout` entries on top.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn calculate_ttk_for_ships(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    attacker: String,
+    loadout_id: Option<String>,
+    attacker_loadout: Option<HashMap<String, String>>,
+    attacker_mounts: Option<HashMap<String, String>>,
+    target: String,
+    shield_name: Option<String>,
+    scenario_preset: Option<ScenarioPreset>,
+    mount_accuracy: Option<f64>,
+    scenario_accuracy: Option<f64>,
+    time_on_target: Option<f64>,
+    zone_preset: Option<ZonePreset>,
+    engagement_range: Option<f64>,
+    shield_hardening: Option<ShieldHardening>,
+    gunner_profiles: Option<GunnerProfiles>,
+    weapon_groups: Option<HashMap<String, u32>>,
+    fire_schedule: Option<FireSchedule>,
+    defender: Option<DefenderBehavior>,
+    shield_booster: Option<ShieldBooster>,
+    degraded_thresholds: Option<DegradedThresholds>,
+) -> Result<TTKResult, String> {
+    let Fighter { ship: attacker, loadout: attacker_loadout, .. } =
+        saved_fighter(&app, &state, Fighter { ship: attacker, loadout: attacker_loadout, shield: None }, loadout_id)?;
+    run_calculation(&app, &state, CalcRequest::Ships(ShipTtkRequest {
+        attacker,
+        attacker_loadout,
+        attacker_mounts,
+        target,
+        shield_name,
+        scenario_preset,
+        mount_accuracy,
+        scenario_accuracy,
+        time_on_target,
+        zone_preset,
+        engagement_range,
+        shield_hardening,
+        gunner_profiles,
+        weapon_groups,
+        fire_schedule,
+        defender,
+        shield_booster,
+        degraded_thresholds,
+    }))
+}
+
+/// How much a small change moves a `calculate_ttk_for_ships` result
+///
+/// `changes` swaps weapons by port, the target shield, zone or scenario on
+/// top of `base_request`. `loadout_id` fits the attacker as a saved fleet
+/// preset exactly as `calculate_ttk_for_ships` does, so the base resolves to
+/// the request that call already memoized and only the changed side is
+/// computed. Neither result is added to the history.
+#[tauri::command]
+fn ttk_delta(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    base_request: ShipTtkRequest,
+    loadout_id: Option<String>,
+    changes: TtkChange,
+) -> Result<TtkDelta, String> {
+    let fighter = Fighter { ship: base_request.attacker.clone(), loadout: base_request.attacker_loadout.clone(), shield: None };
+    let Fighter { ship: attacker, loadout: attacker_loadout, .. } = saved_fighter(&app, &state, fighter, loadout_id)?;
+    let base_request = ShipTtkRequest { attacker, attacker_loadout, ..base_request };
+    let changed_request = changes.apply(&base_request)?;
+    let base = cached_calculation(&state, &CalcRequest::Ships(base_request))?;
+    let changed = cached_calculation(&state, &CalcRequest::Ships(changed_request))?;
+    Ok(whatif::delta(base, changed))
+}
+
+/// `fighter` fitted as the saved fleet preset `loadout_id`, if given
+fn saved_fighter(app: &tauri::AppHandle, state: &AppState, fighter: Fighter, loadout_id: Option<String>) -> Result<Fighter, String> {
+    let Some(loadout_id) = loadout_id else {
+        return Ok(fighter);
+    };
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    fighter.with_preset(&state.snapshot(), &presets::load(&config_dir)?, &loadout_id)
+}
+
+/// Compute a `calculate_ttk_for_ships` request against a data snapshot
+fn compute_ttk_for_ships(data: &GameData, request: &ShipTtkRequest) -> Result<TTKResult, String> {
+    let attacker_ship = data.ship(&request.attacker)
+        .ok_or_else(|| format!("Attacker ship '{}' not found", request.attacker))?;
+    let target_ship = data.ship(&request.target)
+        .ok_or_else(|| format!("Target ship '{}' not found", request.target))?;
+
+    let mut errors = ValidationErrors::default();
+    let no_overrides = HashMap::new();
+    let overrides = request.attacker_loadout.as_ref().unwrap_or(&no_overrides);
+    let mounts = request.attacker_mounts.as_ref().unwrap_or(&no_overrides);
+    let mut loadout = build_ship_loadout(data, attacker_ship, overrides, mounts, &mut errors);
+
+    // Weapon groups by port (labels are "PORT::weapon")
+    for (port, group) in request.weapon_groups.iter().flatten() {
+        if !attacker_ship.weapon_hardpoints.iter().any(|hp| &hp.port_name == port) {
+            errors.push(
+                format!("weapon_groups[{}]", port),
+                format!("{} has no hardpoint '{}'", attacker_ship.display_name, port),
+            );
+        }
+        for weapon in loadout.weapons.iter_mut().filter(|w| w.name_with_label.split("::").next() == Some(port)) {
+            weapon.group = *group;
+        }
+    }
+
+    let base = request.scenario_preset.map(ScenarioPreset::scenario).unwrap_or_default();
+    let scenario = TTKScenario {
+        mount_accuracy: request.mount_accuracy.or(loadout.mount_accuracy).unwrap_or(base.mount_accuracy),
+        scenario_accuracy: request.scenario_accuracy.unwrap_or(base.scenario_accuracy),
+        time_on_target: request.time_on_target.unwrap_or(base.time_on_target),
+        engagement_range: request.engagement_range.unwrap_or(base.engagement_range),
+        shield_hardening: request.shield_hardening.unwrap_or(base.shield_hardening),
+        gunner_profiles: request.gunner_profiles.unwrap_or(base.gunner_profiles),
+        fire_schedule: request.fire_schedule.or(base.fire_schedule),
+        defender: request.defender.unwrap_or(base.defender),
+        shield_booster: request.shield_booster.or(base.shield_booster),
+        degraded_thresholds: request.degraded_thresholds.unwrap_or(base.degraded_thresholds),
+        ..base
+    };
+    scenario.validate(&mut errors);
+    errors.into_result().map_err(|e| e.to_string())?;
+
+    if loadout.weapons.is_empty() {
+        return Err(format!("{} has no guns equipped", attacker_ship.display_name));
+    }
+    let power = analysis::power_budget(data.find_default_power_plant(attacker_ship).map(|p| p.as_ref()), &loadout.weapons);
+    if power.power_starved {
+        log::warn!("{}: {}", attacker_ship.display_name, power.warning.unwrap_or_default());
+    }
+
+    let shield = match request.shield_name {
+        Some(ref name) => data.shield(name)
+            .map(|s| Shield::clone(s))
+            .ok_or_else(|| format!("Shield '{}' not found", name))?,
+        None => data.find_default_shield(target_ship)
+            .map(|s| Shield::clone(s))
+            .ok_or_else(|| "Could not find default shield".to_string())?,
+    };
+    let zone = request.zone_preset.and_then(|preset| preset.modifiers_for(target_ship)).unwrap_or_default();
+
+    Ok(ttk::calculate_ttk(&loadout.weapons, target_ship, &shield, &scenario, &zone))
+}
+
+/// Push picks onto their kind's most-recently-used list (best effort)
+///
+/// The first pick of each kind ends up newest.
+fn record_recents(app: &tauri::AppHandle, picks: &[(ItemKind, String)]) {
+    let Ok(config_dir) = app.path().app_config_dir() else { return };
+    let result = recents::load(&config_dir).and_then(|mut recents| {
+        for kind in [ItemKind::Ship, ItemKind::Weapon, ItemKind::Shield] {
+            recents.touch(kind, picks.iter().filter(|(k, _)| *k == kind).map(|(_, name)| name.as_str()));
+        }
+        recents::save(&config_dir, &recents)
+    });
+    if let Err(e) = result {
+        log::warn!("Could not update recents: {}", e);
+    }
+}
+
+/// Append a completed calculation to the history (best effort)
+fn record_history(app: &tauri::AppHandle, request: CalcRequest, result: &TTKResult) {
+    let Ok(config_dir) = app.path().app_config_dir() else { return };
+    let result = history::load(&config_dir).and_then(|mut entries| {
+        history::push(&mut entries, request, result);
+        history::save(&config_dir, &entries)
+    });
+    if let Err(e) = result {
+        log::warn!("Could not update calculation history: {}", e);
+    }
+}
+
+/// Get past calculations, newest first (the newest `limit` if given)
+#[tauri::command]
+fn get_history(app: tauri::AppHandle, limit: Option<usize>) -> Vec<HistoryEntry> {
+    let entries = app.path().app_config_dir().ok()
+        .and_then(|dir| history::load(&dir).ok())
+        .unwrap_or_default();
+    history::newest(entries, limit)
+}
+
+/// Re-run a past calculation against the current data (recorded as a new entry)
+#[tauri::command]
+fn rerun_history_entry(app: tauri::AppHandle, state: State<AppState>, id: u64) -> Result<TTKResult, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    let entry = history::load(&config_dir)?.into_iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| format!("History entry {} not found", id))?;
+    run_calculation(&app, &state, entry.request)
+}
+
+/// Forget all past calculations
+#[tauri::command]
+fn clear_history(app: tauri::AppHandle) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    history::save(&config_dir, &[])
+}
+
+/// Get the current session scenario and what undo/redo would do
+#[tauri::command]
+fn get_session_state(state: State<AppState>) -> SessionView {
+    state.session.lock_or_recover().view()
+}
+
+/// Record a scenario or loadout change as the new session state
+///
+/// `label` names the change for undo/redo menus. A state equal to the current
+/// one is not recorded; any change discards what could be redone.
+#[tauri::command]
+fn set_session_state(state: State<AppState>, request: ShipTtkRequest, label: Option<String>) -> SessionView {
+    let mut session = state.session.lock_or_recover();
+    session.record(label.unwrap_or_default(), request);
+    session.view()
+}
+
+/// Step the session back one change (no-op if there is none)
+#[tauri::command]
+fn session_undo(state: State<AppState>) -> SessionView {
+    let mut session = state.session.lock_or_recover();
+    session.undo();
+    session.view()
+}
+
+/// Reapply the last undone session change (no-op if there is none)
+#[tauri::command]
+fn session_redo(state: State<AppState>) -> SessionView {
+    let mut session = state.session.lock_or_recover();
+    session.redo();
+    session.view()
+}
+
+/// Mark (or with `favorite: false`, unmark) a ship, weapon or shield as a favorite
+#[tauri::command]
+fn mark_favorite(app: tauri::AppHandle, kind: ItemKind, name: String, favorite: Option<bool>) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    let mut recents = recents::load(&config_dir)?;
+    recents.set_favorite(kind, &name, favorite.unwrap_or(true));
+    recents::save(&config_dir, &recents)
+}
+
+/// Get favorites per kind (in the order they were marked)
+#[tauri::command]
+fn get_favorites(app: tauri::AppHandle) -> BTreeMap<ItemKind, Vec<String>> {
+    load_recents(&app).favorites
+}
+
+/// Get recently used names of one kind, newest first
+#[tauri::command]
+fn get_recents(app: tauri::AppHandle, kind: ItemKind) -> Vec<String> {
+    load_recents(&app).recent.remove(&kind).unwrap_or_default()
+}
+
+/// Load the saved recents and favorites (empty if none saved)
+fn load_recents(app: &tauri::AppHandle) -> Recents {
+    app.path().app_config_dir().ok()
+        .and_then(|dir| recents::load(&dir).ok())
+        .unwrap_or_default()
+}
+
+/// Compare ships side by side
+///
+/// Returns normalized stats per ship (hull, effective armor per damage type,
+/// Rule of Two shield capacity, hardpoint summary) plus percentage deltas
+/// against the first ship in the list.
+#[tauri::command]
+fn compare_ships(state: State<AppState>, names: Vec<String>) -> Result<ShipComparison, String> {
+    let data = state.snapshot();
+    analysis::compare_ships(&data, &names)
+}
+
+/// Resolve an optional reference shield by internal name (neutral reference shield if none)
+fn resolve_reference_shield(data: &GameData, shield_name: Option<String>) -> Result<Shield, String> {
+    match shield_name {
+        Some(name) => data.shield(&name)
+            .map(|s| Shield::clone(s))
+            .ok_or_else(|| format!("Shield '{}' not found", name)),
+        None => Ok(analysis::reference_shield()),
+    }
+}
+
+/// Compare weapons side by side (DPS, DPS/power, alpha, shield vs passthrough split)
+#[tauri::command]
+fn compare_weapons(
+    state: State<AppState>,
+    names: Vec<String>,
+    reference_shield: Option<String>,
+) -> Result<Vec<WeaponStats>, String> {
+    let data = state.snapshot();
+    let shield = resolve_reference_shield(&data, reference_shield)?;
+    analysis::compare_weapons(&data, &names, &shield)
+}
+
+/// Rank all weapons of a size by a metric (best first)
+///
+/// Metrics: "sustained_dps", "dps_per_power", "dps_per_heat", "alpha_damage", "shield_dps", "passthrough_dps",
+/// "dps_per_kilo_auec" (needs price data; unpriced weapons rank last)
+#[tauri::command]
+fn rank_weapons(
+    state: State<AppState>,
+    size: i32,
+    metric: WeaponMetric,
+    reference_shield: Option<String>,
+) -> Result<Vec<WeaponStats>, String> {
+    let data = state.snapshot();
+    let shield = resolve_reference_shield(&data, reference_shield)?;
+    Ok(analysis::rank_weapons(&data, size, metric, &shield))
+}
+
+/// Effective shield DPS of every weapon matching `filter` against every shield (of `shield_size` if given)
+#[tauri::command]
+fn get_weapon_shield_matrix(
+    state: State<AppState>,
+    filter: Option<WeaponFilter>,
+    shield_size: Option<i32>,
+) -> WeaponShieldMatrix {
+    matrix::build(&state.snapshot(), &filter.unwrap_or_default(), shield_size)
+}
+
+/// Write the weapon/shield matrix to `path` as CSV, returning the number of weapon rows
+#[tauri::command]
+fn export_weapon_shield_matrix(
+    state: State<AppState>,
+    path: String,
+    filter: Option<WeaponFilter>,
+    shield_size: Option<i32>,
+) -> Result<usize, String> {
+    let table = matrix::build(&state.snapshot(), &filter.unwrap_or_default(), shield_size);
+    std::fs::write(&path, table.to_csv())
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(table.weapons.len())
+}
+
+/// Write the loaded ships, weapons or shields to `path` as CSV or JSON, returning the row count
+///
+/// Rows carry the cleaned-up data plus derived values (effective armor HP,
+/// DPS per power/heat, shield regen time) for community spreadsheets.
+#[tauri::command]
+fn export_dataset(state: State<AppState>, kind: DatasetKind, format: DumpFormat, path: String) -> Result<usize, String> {
+    let (contents, rows) = dump::dump(&state.snapshot(), kind, format)?;
+    std::fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(rows)
+}
+
+/// Recommend a damage type mix against a target
+///
+/// `shield` is an internal name (the ship's default if omitted). Returns each
+/// type's shield/armor value, the mix that kills fastest, and readable notes.
+#[tauri::command]
+fn analyze_target(state: State<AppState>, ship: String, shield: Option<String>) -> Result<TargetAnalysis, String> {
+    let data = state.snapshot();
+    let target = data.ship(&ship)
+        .ok_or_else(|| format!("Ship '{}' not found", ship))?;
+    let shield = match shield {
+        Some(name) => Some(data.shield(&name).ok_or_else(|| format!("Shield '{}' not found", name))?),
+        None => data.find_default_shield(target),
+    };
+    Ok(analysis::analyze_target(target, shield.map(|s| s.as_ref())))
+}
+
+/// Whether a target can spool its quantum drive and escape an attacker
+///
+/// The attacker is fitted as in `calculate_ttk_for_ships` (`attacker_loadout`
+/// overrides ports; `shield_name` is the target's shield, default if omitted).
+/// `spool_time` overrides the target's spool time from the ship metadata and
+/// `dampening` delays the spool start (seconds, default 0).
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn calculate_escape_window(
+    state: State<AppState>,
+    target: String,
+    attacker: String,
+    attacker_loadout: Option<HashMap<String, String>>,
+    shield_name: Option<String>,
+    scenario_preset: Option<ScenarioPreset>,
+    spool_time: Option<f64>,
+    dampening: Option<f64>,
+) -> Result<EscapeWindow, String> {
+    let data = state.snapshot();
+    let request = ShipTtkRequest {
+        attacker: attacker.clone(),
+        attacker_loadout,
+        target: target.clone(),
+        shield_name,
+        scenario_preset,
+        ..Default::default()
+    };
+    let result = compute_ttk_for_ships(&data, &request)?;
+    let target_ship = data.ship(&target)
+        .ok_or_else(|| format!("Target ship '{}' not found", target))?;
+    escape::escape_window(&result, &attacker, target_ship, spool_time, dampening.unwrap_or(0.0))
+}
+
+/// Run a batch calculation on a blocking worker thread
+///
+/// Progress is emitted as `calc-progress` events tagged with `task_id` (generated
+/// if omitted); `cancel_calculation` with the same id stops it early.
+async fn run_task<R, F>(app: &tauri::AppHandle, state: &AppState, task_id: Option<String>, work: F) -> Result<R, String>
+where
+    R: Send + 'static,
+    F: FnOnce(&TaskProgress) -> Result<R, String> + Send + 'static,
+{
+    let task_id = task_id.unwrap_or_else(|| state.tasks.next_id());
+    let cancelled = state.tasks.register(&task_id);
+    let handle = app.clone();
+    let progress = TaskProgress::new(task_id.clone(), cancelled.clone(), move |progress| {
+        let _ = handle.emit("calc-progress", progress);
+    });
+
+    let result = tauri::async_runtime::spawn_blocking(move || work(&progress)).await;
+    state.tasks.finish(&task_id, &cancelled);
+    result.map_err(|e| format!("Calculation failed: {}", e))?
+}
+
+/// Run blocking work (network requests, installs) on a worker thread
+///
+/// Sync commands run on the main thread and would freeze the UI meanwhile.
+async fn run_blocking<R, F>(work: F) -> Result<R, String>
+where
+    R: Send + 'static,
+    F: FnOnce() -> Result<R, String> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(work).await
+        .map_err(|e| format!("Background work failed: {}", e))?
+}
+
+/// Cancel a running batch calculation by task id (false if it isn't running)
+#[tauri::command]
+fn cancel_calculation(state: State<AppState>, task_id: String) -> bool {
+    state.tasks.cancel(&task_id)
+}
+
+/// Rank every ship by time-to-die against a reference attacker
+///
+/// The attacker is either a weapon loadout (parallel name/count arrays, as in
+/// calculate_ttk_v2) or a generic threat profile (DPS plus damage-type mix).
+/// Uses the default scenario (gimballed dogfight) and center-mass zones.
+/// Runs as a cancellable task (see `run_task`).
+#[tauri::command]
+async fn rank_ships_by_survivability(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    weapon_names: Vec<String>,
+    weapon_counts: Vec<i32>,
+    threat: Option<ThreatProfile>,
+    task_id: Option<String>,
+) -> Result<Vec<SurvivabilityEntry>, String> {
+    let data = state.snapshot();
+
+    let weapons = match threat {
+        Some(threat) => vec![threat.to_equipped_weapon()],
+        None => build_equipped_weapons(&data, &weapon_names, &weapon_counts, &[], &[], &[], None)?,
+    };
+
+    if weapons.is_empty() {
+        return Err("No weapons equipped".to_string());
+    }
+
+    run_task(&app, &state, task_id, move |progress| {
+        analysis::rank_ships_by_survivability(
+            &data,
+            &weapons,
+            &TTKScenario::default(),
+            &ZoneModifiers::default(),
+            progress,
+        )
+    }).await
+}
+
+/// Rank all shields of a ship's max shield size by survival time against a threat profile
+///
+/// Threat example: { dps: 2000, physical: 0.7, energy: 0.3, distortion: 0.0 }
+/// Runs as a cancellable task (see `run_task`).
+#[tauri::command]
+async fn optimize_shield(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    ship: String,
+    threat: ThreatProfile,
+    task_id: Option<String>,
+) -> Result<Vec<ShieldOption>, String> {
+    let data = state.snapshot();
+    let target = data.ship(&ship).cloned()
+        .ok_or_else(|| format!("Ship '{}' not found", ship))?;
+    run_task(&app, &state, task_id, move |progress| {
+        analysis::optimize_shield(&data, &target, &threat, progress)
+    }).await
+}
+
+/// Find the guns and shield to buy for `ship` within `budget` aUEC
+///
+/// `goal` is "ttk" (kill `target` fastest, the default) or "survivability"
+/// (survive `target`'s stock guns longest). Needs price data (see `import_prices`).
+/// Runs as a cancellable task (see `run_task`).
+#[tauri::command]
+async fn optimize_budget_loadout(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    ship: String,
+    target: String,
+    budget: f64,
+    goal: Option<BudgetGoal>,
+    task_id: Option<String>,
+) -> Result<BudgetLoadout, String> {
+    let data = state.snapshot();
+    let attacker = data.ship(&ship).cloned()
+        .ok_or_else(|| format!("Ship '{}' not found", ship))?;
+    let target = data.ship(&target).cloned()
+        .ok_or_else(|| format!("Target ship '{}' not found", target))?;
+    run_task(&app, &state, task_id, move |progress| {
+        budget::optimize(&data, &attacker, &target, budget, goal.unwrap_or_default(), progress)
+    }).await
+}
+
+/// Duel my ship against every other ship (default loadouts), worst matchups first
+///
+/// `my_loadout` replaces guns by port (as in `calculate_ttk_for_ships`) and
+/// `my_shield` the default shield. Each matchup gives both kill times and the
+/// margin (their TTK minus mine). `my_loadout_id` starts from a saved fleet
+/// preset instead of the default loadout. Runs as a cancellable task (see `run_task`).
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn find_counters(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    my_ship: String,
+    my_loadout_id: Option<String>,
+    my_loadout: Option<HashMap<String, String>>,
+    my_shield: Option<String>,
+    scenario_preset: Option<ScenarioPreset>,
+    task_id: Option<String>,
+) -> Result<Vec<Matchup>, String> {
+    let me = saved_fighter(&app, &state, Fighter { ship: my_ship, loadout: my_loadout, shield: my_shield }, my_loadout_id)?;
+    let data = state.snapshot();
+    run_task(&app, &state, task_id, move |progress| {
+        duel::find_counters(&data, &me, scenario_preset, progress)
+    }).await
+}
+
+/// Rank the ships of an enemy fleet by which to focus first
+///
+/// Each enemy duels me one on one; the order kills the most dangerous ships per
+/// second of my time first, and plays out how much of my HP the rest of the fleet
+/// takes meanwhile. `attacker` is fitted as in `find_counters` (`attacker_loadout_id`
+/// picks a saved preset); fleet entries may name a preset by `loadout_id` and a
+/// `count`. Runs as a cancellable task (see `run_task`).
+#[tauri::command]
+async fn recommend_target_order(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    attacker: Fighter,
+    attacker_loadout_id: Option<String>,
+    fleet: Vec<FleetEnemy>,
+    scenario_preset: Option<ScenarioPreset>,
+    task_id: Option<String>,
+) -> Result<KillOrder, String> {
+    let me = saved_fighter(&app, &state, attacker, attacker_loadout_id)?;
+    let data = state.snapshot();
+    let saved = if fleet.iter().any(|e| e.loadout_id.is_some()) {
+        let config_dir = app.path().app_config_dir()
+            .map_err(|e| format!("Failed to get config dir: {}", e))?;
+        presets::load(&config_dir)?
+    } else {
+        Vec::new()
+    };
+    let mut enemies = Vec::new();
+    for entry in &fleet {
+        enemies.extend(entry.fighters(&data, &saved)?);
+    }
+    run_task(&app, &state, task_id, move |progress| {
+        priority::recommend_target_order(&data, &me, &enemies, scenario_preset, progress)
+    }).await
+}
+
+/// Rank the saved fleet presets against an enemy ship on its default loadout
+///
+/// Each preset duels the enemy both ways; the builds that kill it fastest while
+/// surviving longest come first. Runs as a cancellable task (see `run_task`).
+#[tauri::command]
+async fn assess_threat(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    enemy_ship: String,
+    scenario_preset: Option<ScenarioPreset>,
+    task_id: Option<String>,
+) -> Result<Vec<PresetMatchup>, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    let fleet = presets::load(&config_dir)?;
+    let data = state.snapshot();
+    let enemy = data.ship(&enemy_ship).cloned()
+        .ok_or_else(|| format!("Ship '{}' not found", enemy_ship))?;
+    run_task(&app, &state, task_id, move |progress| {
+        duel::assess_threat(&data, &enemy, &fleet, scenario_preset, progress)
+    }).await
+}
+
+/// One target of `calculate_ttk_multi_target`
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetTtk {
+    pub target: String,
+    pub result: Option<TTKResult>,
+    /// Why this target couldn't be calculated (e.g. not found)
+    pub error: Option<String>,
+}
+
+/// Run one `calculate_ttk_v2` loadout against several targets in parallel
+///
+/// `loadout` takes the `calculate_ttk_v2` arguments; its `target_ship`,
+/// `shield_name` and `shield_loadout` are ignored and each target flies its
+/// default shields. Results come back in `targets` order, a failed target
+/// carrying its error instead of failing the call. Runs as a cancellable task
+/// (see `run_task`).
+#[tauri::command]
+async fn calculate_ttk_multi_target(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    mut loadout: TtkRequest,
+    targets: Vec<String>,
+    task_id: Option<String>,
+) -> Result<Vec<TargetTtk>, String> {
+    if targets.is_empty() {
+        return Err("No targets given".to_string());
+    }
+    attach_loadout_preset(&app, &mut loadout)?;
+    let data = state.snapshot();
+    run_task(&app, &state, task_id, move |progress| {
+        analysis::parallel_map(&targets, progress, |target| {
+            let request = TtkRequest {
+                target_ship: target.clone(),
+                shield_name: None,
+                shield_loadout: None,
+                ..loadout.clone()
+            };
+            let (result, error) = match compute_ttk_v2(&data, &request) {
+                Ok(result) => (Some(result), None),
+                Err(e) => (None, Some(e)),
+            };
+            TargetTtk { target: target.clone(), result, error }
+        })
+    }).await
+}
+
+/// Get a weapon by name (searches by display_name)
+#[tauri::command]
+fn get_weapon(state: State<AppState>, name: String) -> Option<Arc<Weapon>> {
+    let data = state.snapshot();
+    data.weapon(&name).cloned()
+}
+
+/// Get a shield by name
+#[tauri::command]
+fn get_shield(state: State<AppState>, name: String) -> Option<Arc<Shield>> {
+    let data = state.snapshot();
+    data.shield(&name).cloned()
+}
+
+/// Get a ship's default shield (resolved at load time)
+#[tauri::command]
+fn get_default_shield(state: State<AppState>, ship: String) -> Option<Arc<Shield>> {
+    let data = state.snapshot();
+    let ship = data.ship(&ship)?;
+    data.find_default_shield(ship).cloned()
+}
+
+/// Get statistics summary
+#[tauri::command]
+fn get_stats(state: State<AppState>) -> serde_json::Value {
+    data_stats(&state.snapshot())
+}
+
+/// Get stat distributions over the whole dataset (hull HP by class, gun DPS and
+/// shield HP by size) for overview charts
+#[tauri::command]
+fn get_dataset_analytics(state: State<AppState>) -> DatasetAnalytics {
+    analysis::dataset_analytics(&state.snapshot())
+}
+
+/// Flag loaded entries that look like extraction mistakes (zero hull HP, guns
+/// without DPS or damage split, shields without absorption, unresolved default weapons)
+#[tauri::command]
+fn audit_data(state: State<AppState>) -> AuditReport {
+    audit::audit(&state.snapshot())
+}
+
+/// Run the TTK model self-check against the saved golden values
+///
+/// With `record`, the current results become the new golden values (saved as
+/// `golden.json` in the app config dir) before checking.
+#[tauri::command]
+fn verify_model(app: tauri::AppHandle, state: State<AppState>, record: Option<bool>) -> Result<ModelCheck, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    let data = state.snapshot();
+    let compute = |request: &ShipTtkRequest| compute_ttk_for_ships(&data, request);
+    let mut golden_file = golden::load(&config_dir)?;
+    if record.unwrap_or(false) {
+        golden::record(&mut golden_file, compute);
+        golden::save(&config_dir, &golden_file)?;
+    }
+    Ok(golden::verify(&golden_file, compute))
+}
+
+/// Import community-measured TTKs from a CSV (see `benchmarks`), replacing earlier ones
+///
+/// Returns the number of benchmarks imported; skipped rows are logged.
+#[tauri::command]
+fn import_benchmarks(app: tauri::AppHandle, path: String) -> Result<usize, String> {
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let (benchmarks, warnings) = benchmarks::parse(&contents)?;
+    for warning in &warnings {
+        log::warn!("{}: {}", path, warning);
+    }
+    if benchmarks.is_empty() {
+        return Err("No valid benchmark rows found (expected attacker,target,measured_ttk)".to_string());
+    }
+
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    benchmarks::save(&config_dir, &benchmarks)?;
+    Ok(benchmarks.len())
+}
+
+/// Model-vs-measured TTK deltas for every imported benchmark
+#[tauri::command]
+fn compare_benchmarks(app: tauri::AppHandle, state: State<AppState>) -> Result<BenchmarkComparison, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    let benchmarks = benchmarks::load(&config_dir)?;
+    let data = state.snapshot();
+    Ok(benchmarks::compare(&benchmarks, |request| compute_ttk_for_ships(&data, request)))
+}
+
+/// Fit a scenario's accuracy and time on target to observed engagements
+///
+/// `observed_ttks` are matchups with their measured TTK (as in the benchmark
+/// import; their scenario is replaced by `base_preset`, dogfight if omitted).
+/// The fitted profile is saved under `name`, replacing one of the same name;
+/// pass its factors as `scenario_accuracy`/`time_on_target` to `calculate_ttk_for_ships`.
+#[tauri::command]
+fn calibrate_scenario(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    name: String,
+    base_preset: Option<ScenarioPreset>,
+    observed_ttks: Vec<Benchmark>,
+) -> Result<CalibratedScenario, String> {
+    let data = state.snapshot();
+    let profile = calibration::fit(&name, base_preset, &observed_ttks, |request| compute_ttk_for_ships(&data, request))?;
+
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    let mut profiles = calibration::load(&config_dir)?;
+    calibration::upsert(&mut profiles, profile.clone());
+    calibration::save(&config_dir, &profiles)?;
+    Ok(profile)
+}
+
+/// Get the saved calibrated scenario profiles
+#[tauri::command]
+fn get_calibrated_scenarios(app: tauri::AppHandle) -> Result<Vec<CalibratedScenario>, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    calibration::load(&config_dir)
+}
+
+/// Delete a calibrated scenario profile by name
+#[tauri::command]
+fn delete_calibrated_scenario(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    let mut profiles = calibration::load(&config_dir)?;
+    let count = profiles.len();
+    profiles.retain(|p| p.name != name);
+    if profiles.len() == count {
+        return Err(format!("Calibrated scenario '{}' not found", name));
+    }
+    calibration::save(&config_dir, &profiles)
+}
+
+/// Entity counts shown in the status bar
+fn data_stats(data: &GameData) -> serde_json::Value {
+    serde_json::json!({
+        "ship_count": data.ships.len(),
+        "weapon_count": data.weapons.len(),
+        "shield_count": data.shields.len(),
+    })
+}
+
+/// Get the background data load status
+#[tauri::command]
+fn get_data_status(state: State<AppState>) -> DataStatus {
+    state.status.read_or_recover().clone()
+}
+
+/// App and data versions (`get_version_info`)
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionInfo {
+    pub app_version: String,
+    /// Data pack version, source game patch and extraction date (None without a manifest)
+    pub data_version: Option<String>,
+    pub game_patch: Option<String>,
+    pub extracted: Option<String>,
+    /// Data file -> SHA-256 checksum, as listed in the manifest
+    pub files: BTreeMap<String, String>,
+}
+
+/// Get the app version and which data pack (and game patch) is loaded
+#[tauri::command]
+fn get_version_info(state: State<AppState>) -> VersionInfo {
+    let manifest = state.manifest.read_or_recover().clone().unwrap_or_default();
+    let present = |value: String| (!value.is_empty()).then_some(value);
+    VersionInfo {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        data_version: present(manifest.data_version),
+        game_patch: present(manifest.game_patch),
+        extracted: present(manifest.extracted),
+        files: manifest.files,
+    }
+}
+
+/// Get per-file warnings and errors from the last data load
+#[tauri::command]
+fn get_load_report(state: State<AppState>) -> LoadReport {
+    state.report.read_or_recover().clone()
+}
+
+/// Find Star Citizen installs (LIVE/PTU/...) with their build versions
+#[tauri::command]
+fn detect_game_install() -> Vec<install::GameInstall> {
+    install::detect_installs()
+}
+
+/// Re-extract game data from a game install or unp4k output directory
+///
+/// The records must already be unpacked and unforged to XML; a bare `Data.p4k`
+/// is rejected with instructions (see `data::p4k`).
+/// Runs in the background like the startup load; progress and completion arrive as
+/// `data-loading`/`data-ready`/`data-failed` events. Custom definitions are re-merged.
+#[tauri::command]
+fn import_game_data(app: tauri::AppHandle, state: State<AppState>, path: String) -> Result<(), String> {
+    let data_root = data::p4k::find_data_root(&PathBuf::from(path))?;
+
+    *state.status.write_or_recover() = DataStatus::Loading;
+    let config_dir = app.path().app_config_dir().ok();
+    std::thread::spawn(move || load_game_data(app, data_root, None, config_dir));
+    Ok(())
+}
+
+/// Get the data dir in use and what chose it (flag, environment, setting or detection)
+#[tauri::command]
+fn get_data_location(state: State<AppState>) -> DataLocation {
+    state.data_dir.read_or_recover().clone()
+}
+
+/// Load game data from `path` from now on (None = detect automatically), reloading now
+///
+/// Fails without saving when `path` holds no game data, or when `--data-dir` or
+/// `SHIP_LENS_DATA_DIR` fixes the data dir. The reload runs in the background
+/// like the startup load (`data-loading`/`data-ready`/`data-failed` events).
+#[tauri::command]
+fn set_data_dir(app: tauri::AppHandle, state: State<AppState>, path: Option<String>) -> Result<DataLocation, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    if state.data_dir.read_or_recover().is_forced() {
+        return Err(format!("The data dir is set by --data-dir or {}", datadir::DATA_DIR_ENV));
+    }
+    let path = path.filter(|p| !p.trim().is_empty()).map(PathBuf::from);
+    if let Some(ref path) = path {
+        if !path.is_dir() || !has_data_files(path) {
+            return Err(format!("No game data found in {}", path.display()));
+        }
+    }
+
+    let settings = DataDirSettings { path };
+    datadir::save(&config_dir, &settings)?;
+    let location = datadir::resolve(None, &settings, get_data_dir);
+    *state.data_dir.write_or_recover() = location.clone();
+
+    *state.status.write_or_recover() = DataStatus::Loading;
+    let data_dir = location.path.clone();
+    let cache_dir = app.path().app_cache_dir().ok();
+    std::thread::spawn(move || load_game_data(app, data_dir, cache_dir, Some(config_dir)));
+    Ok(location)
+}
+
+/// Save settings to file (atomically, keeping backups; see `store`)
+#[tauri::command]
+fn save_settings(app: tauri::AppHandle, mut settings: serde_json::Value) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    // The frontend doesn't know the variant filter; keep the saved one
+    let path = config_dir.join("settings.json");
+    if let Some(settings) = settings.as_object_mut().filter(|s| !s.contains_key(VARIANT_FILTER_KEY)) {
+        let saved: Option<serde_json::Value> = store::read_json(&path).ok().flatten();
+        if let Some(filter) = saved.as_ref().and_then(|s| s.get(VARIANT_FILTER_KEY)) {
+            settings.insert(VARIANT_FILTER_KEY.to_string(), filter.clone());
+        }
+    }
+    store::write_json(&path, &settings)
+}
+
+/// Load settings from file (recovering from a backup if it is corrupt)
+#[tauri::command]
+fn load_settings(app: tauri::AppHandle) -> Option<serde_json::Value> {
+    let config_dir = app.path().app_config_dir().ok()?;
+    store::read_json(&config_dir.join("settings.json")).ok().flatten()
 }
 
-/// Load all fleet presets
+/// Save a fleet preset (replacing the one with the same id, else appending it)
 #[tauri::command]
-fn load_fleet_presets(app: tauri::AppHandle) -> Vec<serde_json::Value> {
+fn save_fleet_preset(app: tauri::AppHandle, preset: FleetPreset) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    let mut presets = presets::load(&config_dir)?;
+    presets::upsert(&mut presets, preset);
+    presets::save(&config_dir, &presets)
+}
+
+/// Load all fleet presets in saved order
+#[tauri::command]
+fn load_fleet_presets(app: tauri::AppHandle) -> Vec<FleetPreset> {
     let config_dir = match app.path().app_config_dir() {
         Ok(dir) => dir,
         Err(_) => return Vec::new(),
     };
 
-    let presets_path = config_dir.join("fleet_presets.json");
+    presets::load(&config_dir).unwrap_or_default()
+}
 
-    if !presets_path.exists() {
-        return Vec::new();
-    }
+/// Query fleet presets by tag, category and name (in saved order)
+#[tauri::command]
+fn query_presets(app: tauri::AppHandle, filter: Option<PresetFilter>) -> Result<Vec<FleetPreset>, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
 
-    match fs::read_to_string(&presets_path) {
-        Ok(json) => serde_json::from_str(&json).unwrap_or_default(),
-        Err(_) => Vec::new(),
-    }
+    let filter = filter.unwrap_or_default();
+    let mut presets = presets::load(&config_dir)?;
+    presets.retain(|p| filter.matches(p));
+    Ok(presets)
 }
 
 /// Delete a fleet preset by ID
@@ -558,137 +2410,498 @@ fn delete_fleet_preset(app: tauri::AppHandle, preset_id: String) -> Result<(), S
     let config_dir = app.path().app_config_dir()
         .map_err(|e| format!("Failed to get config dir: {}", e))?;
 
-    let presets_path = config_dir.join("fleet_presets.json");
-
-    if !presets_path.exists() {
+    let mut presets = presets::load(&config_dir)?;
+    let before = presets.len();
+    presets.retain(|p| p.id != preset_id);
+    if presets.len() == before {
         return Ok(());
     }
 
-    let json = fs::read_to_string(&presets_path)
-        .map_err(|e| format!("Failed to read presets: {}", e))?;
+    presets::save(&config_dir, &presets)
+}
+
+/// Load the presets, apply an edit and save them
+fn edit_presets<R>(
+    app: &tauri::AppHandle,
+    edit: impl FnOnce(&mut Vec<FleetPreset>) -> Result<R, String>,
+) -> Result<R, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    let mut presets = presets::load(&config_dir)?;
+    let result = edit(&mut presets)?;
+    presets::save(&config_dir, &presets)?;
+    Ok(result)
+}
 
-    let mut presets: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap_or_default();
+/// Rename a fleet preset
+#[tauri::command]
+fn rename_preset(app: tauri::AppHandle, preset_id: String, name: String) -> Result<(), String> {
+    edit_presets(&app, |presets| presets::rename(presets, &preset_id, &name))
+}
 
-    // Remove preset with matching ID
-    presets.retain(|p| {
-        p.get("id").and_then(|v| v.as_str()).unwrap_or("") != preset_id
-    });
+/// Copy a fleet preset (named `name`, else "<original> (copy)"), returning the copy
+#[tauri::command]
+fn duplicate_preset(app: tauri::AppHandle, preset_id: String, name: Option<String>) -> Result<FleetPreset, String> {
+    edit_presets(&app, |presets| presets::duplicate(presets, &preset_id, name.as_deref()))
+}
+
+/// Move the listed presets to the front in the given order (the rest follow unchanged)
+#[tauri::command]
+fn reorder_presets(app: tauri::AppHandle, preset_ids: Vec<String>) -> Result<(), String> {
+    edit_presets(&app, |presets| presets::reorder(presets, &preset_ids))
+}
+
+/// Replace a fleet preset's tags and category
+#[tauri::command]
+fn set_preset_tags(
+    app: tauri::AppHandle,
+    preset_id: String,
+    tags: Vec<String>,
+    category: Option<String>,
+) -> Result<(), String> {
+    edit_presets(&app, |presets| presets::set_tags(presets, &preset_id, &tags, category))
+}
+
+/// Zip the whole config dir (settings, presets, recents, history, custom data) to `path`
+#[tauri::command]
+fn backup_config(app: tauri::AppHandle, path: String) -> Result<BackupManifest, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    backup::backup(&config_dir, &PathBuf::from(path))
+}
+
+/// Zip the backend log files to `path` for bug reports, returning the file count
+#[tauri::command]
+fn export_logs(app: tauri::AppHandle, path: String) -> Result<usize, String> {
+    let data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Failed to get data dir: {}", e))?;
+
+    logs::export(&logs::log_dir(&data_dir), &PathBuf::from(path))
+}
+
+/// The crash report saved by the last panic (None if there was none)
+#[tauri::command]
+fn get_last_crash_report(app: tauri::AppHandle) -> Result<Option<CrashReport>, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    crash::load(&config_dir)
+}
+
+/// Forget the saved crash report once it has been shown or sent
+#[tauri::command]
+fn clear_crash_report(app: tauri::AppHandle) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    crash::clear(&config_dir)
+}
+
+/// Restore the config dir from a `backup_config` zip
+///
+/// Restored custom definitions are merged into the live data; custom entries
+/// missing from the backup stay loaded until the next start.
+#[tauri::command]
+fn restore_config(app: tauri::AppHandle, state: State<AppState>, path: String) -> Result<BackupManifest, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    let manifest = backup::restore(&config_dir, &PathBuf::from(path))?;
+    let defs = custom::load(&config_dir)?;
+    state.update(|data, report| custom::merge_into(data, &defs, report));
+    Ok(manifest)
+}
+
+/// Import a prices CSV (`class_name,auec[,pledge]`) into the app config dir
+///
+/// The imported prices are applied over the bundled ones now and on every start.
+/// Returns the number of priced items.
+#[tauri::command]
+fn import_prices(app: tauri::AppHandle, state: State<AppState>, path: String) -> Result<usize, String> {
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let rows = data::prices::parse(&contents, &mut LoadReport::default());
+    if rows.is_empty() {
+        return Err("No valid price rows found (expected class_name,auec[,pledge])".to_string());
+    }
 
-    let json = serde_json::to_string_pretty(&presets)
-        .map_err(|e| format!("Failed to serialize presets: {}", e))?;
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    store::write_atomic(&config_dir.join(data::prices::PRICES_FILE), &contents)
+        .map_err(|e| format!("Failed to save prices: {}", e))?;
+
+    let count = rows.len();
+    state.update(|data, report| data::prices::merge(data, rows, report));
+    Ok(count)
+}
+
+/// Get every known price by lowercase class name
+#[tauri::command]
+fn get_prices(state: State<AppState>) -> HashMap<String, Price> {
+    state.snapshot().prices.clone()
+}
+
+/// Add or replace a custom (homebrew) ship
+///
+/// The ship is validated, saved to the config dir and merged into the live data.
+/// A custom ship with the same display name as a game ship replaces it.
+#[tauri::command]
+fn add_custom_ship(app: tauri::AppHandle, state: State<AppState>, ship: Ship) -> Result<(), String> {
+    custom::validate_ship(&ship)?;
 
-    fs::write(&presets_path, json)
-        .map_err(|e| format!("Failed to write presets: {}", e))?;
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    let mut defs = custom::load(&config_dir)?;
+    defs.ships.retain(|s| s.display_name != ship.display_name);
+    defs.ships.push(ship.clone());
+    custom::save(&config_dir, &defs)?;
 
+    state.update(|data, report| data.insert_ship(ship, report));
     Ok(())
 }
 
-/// Detect Linux package manager type
-#[cfg(target_os = "linux")]
-fn detect_package_manager() -> Option<&'static str> {
-    // Check for DNF (Fedora, RHEL 8+)
-    if PathBuf::from("/usr/bin/dnf").exists() {
-        return Some("dnf");
+/// Add or replace a custom (homebrew) weapon
+///
+/// The weapon is validated, saved to the config dir and merged into the live data.
+/// A custom weapon with the same filename as a game weapon replaces it.
+#[tauri::command]
+fn add_custom_weapon(app: tauri::AppHandle, state: State<AppState>, weapon: Weapon) -> Result<(), String> {
+    custom::validate_weapon(&weapon)?;
+
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+    let mut defs = custom::load(&config_dir)?;
+    defs.weapons.retain(|w| w.filename != weapon.filename);
+    defs.weapons.push(weapon.clone());
+    custom::save(&config_dir, &defs)?;
+
+    state.update(|data, _| data.insert_weapon(weapon));
+    Ok(())
+}
+
+/// Format a calculation as a copy-ready Discord message (matchup, phase times, loadout)
+///
+/// Takes the request rather than the result so infinite times survive the round
+/// trip; the numbers come from the result cache when the request was just run.
+#[tauri::command]
+fn format_for_discord(state: State<AppState>, request: CalcRequest) -> Result<String, String> {
+    let result = cached_calculation(&state, &request)?;
+    Ok(discord::format(&request, &result))
+}
+
+/// Engagement timeline of a calculation as plottable arrays
+///
+/// Samples every `resolution` seconds (about 200 samples if omitted) of time,
+/// shield/armor/hull HP and attacker heat. Not recorded in the history or recents.
+#[tauri::command]
+fn get_simulation_series(state: State<AppState>, request: CalcRequest, resolution: Option<f64>) -> Result<SimulationSeries, String> {
+    let result = cached_calculation(&state, &request)?;
+    let data = state.snapshot();
+    let target = data.ship(request.target())
+        .ok_or_else(|| format!("Target ship '{}' not found", request.target()))?;
+    let (armor_hp, hull_hp) = request.zone(target).pools(target);
+
+    let heat_per_second = result.weapon_breakdown.iter()
+        .filter_map(|w| {
+            let weapon = data.weapon(&w.weapon_name)?;
+            Some(weapon.heat_per_shot * weapon.fire_rate / 60.0 * w.count as f64)
+        })
+        .sum();
+    Ok(series::build(&result, armor_hp, hull_hp, heat_per_second, resolution))
+}
+
+/// Post a message (usually from `format_for_discord`) to a Discord channel webhook
+#[tauri::command]
+async fn post_to_discord(app: tauri::AppHandle, webhook_url: String, content: String) -> Result<(), String> {
+    network_status(&app).require_online()?;
+    run_blocking(move || discord::post(webhook_url.trim(), &content)).await
+}
+
+/// Load the saved overlay settings
+fn load_overlay_settings(app: &tauri::AppHandle) -> OverlaySettings {
+    app.path().app_config_dir().ok()
+        .map(|dir| overlay::load(&dir))
+        .unwrap_or_default()
+}
+
+/// Show or hide the always-on-top overlay; returns whether it is now visible
+#[tauri::command]
+fn toggle_overlay(app: tauri::AppHandle) -> Result<bool, String> {
+    overlay::toggle(&app).map_err(|e| format!("Failed to toggle overlay: {}", e))
+}
+
+/// Pin a matchup to the overlay (`None` clears it)
+///
+/// Emits `overlay-pin-changed` so an open overlay refreshes, and shows the
+/// matchup on the stream endpoint until the next calculation.
+#[tauri::command]
+fn pin_matchup(app: tauri::AppHandle, state: State<AppState>, request: Option<CalcRequest>) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    if let Some(request) = &request {
+        if let Ok(result) = cached_calculation(&state, request) {
+            state.stream.publish(&OverlayReadout::new(request, ResultSummary::of(&result)));
+        }
     }
-    // Check for APT (Debian, Ubuntu)
-    if PathBuf::from("/usr/bin/apt").exists() {
-        return Some("apt");
+    let mut settings = overlay::load(&config_dir);
+    settings.pinned = request;
+    overlay::save(&config_dir, &settings)?;
+    let _ = app.emit("overlay-pin-changed", ());
+    Ok(())
+}
+
+/// TTK readout for the pinned matchup (`None` if nothing is pinned)
+///
+/// Not recorded in the history or recents; the overlay re-reads it whenever the
+/// pin or the data changes.
+#[tauri::command]
+fn get_overlay_readout(app: tauri::AppHandle, state: State<AppState>) -> Result<Option<OverlayReadout>, String> {
+    let Some(request) = load_overlay_settings(&app).pinned else { return Ok(None) };
+    let result = cached_calculation(&state, &request)?;
+    Ok(Some(OverlayReadout::new(&request, ResultSummary::of(&result))))
+}
+
+/// Change the global shortcut that toggles the overlay (`None` restores the default)
+#[tauri::command]
+fn set_overlay_shortcut(app: tauri::AppHandle, shortcut: Option<String>) -> Result<String, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    let mut settings = overlay::load(&config_dir);
+    let previous = settings.shortcut().to_string();
+    settings.shortcut = shortcut;
+    let shortcuts = app.global_shortcut();
+    shortcuts.register(settings.shortcut())
+        .map_err(|e| format!("Failed to register shortcut '{}': {}", settings.shortcut(), e))?;
+    if previous != settings.shortcut() {
+        let _ = shortcuts.unregister(previous.as_str());
     }
-    // Fallback: check for rpm vs dpkg
-    if PathBuf::from("/usr/bin/rpm").exists() {
-        return Some("rpm");
+    overlay::save(&config_dir, &settings)?;
+    Ok(settings.shortcut().to_string())
+}
+
+/// Whether the stream endpoint is running, and its browser source URL
+#[tauri::command]
+fn get_stream_status(state: State<AppState>) -> StreamStatus {
+    state.stream.status()
+}
+
+/// Start or stop the stream endpoint for OBS browser sources (`port` defaults to the saved one)
+///
+/// The choice is saved so the endpoint comes back at the next launch.
+#[tauri::command]
+fn set_stream_enabled(app: tauri::AppHandle, state: State<AppState>, enabled: bool, port: Option<u16>) -> Result<StreamStatus, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    let mut settings = stream::load(&config_dir);
+    if port.is_some() {
+        settings.port = port;
     }
-    if PathBuf::from("/usr/bin/dpkg").exists() {
-        return Some("dpkg");
+    if enabled {
+        state.stream.start(settings.port())?;
+    } else {
+        state.stream.stop();
     }
-    None
+    settings.enabled = enabled;
+    stream::save(&config_dir, &settings)?;
+    Ok(state.stream.status())
 }
 
-/// Install a Linux update using pkexec for privilege elevation
-/// Downloads the appropriate package and installs via system package manager
-#[cfg(target_os = "linux")]
+/// Current network policy (offline setting plus environment override)
+fn network_status(app: &tauri::AppHandle) -> NetworkStatus {
+    let settings = app.path().app_config_dir().ok()
+        .map(|dir| network::load(&dir))
+        .unwrap_or_default();
+    network::status(settings)
+}
+
+/// Get whether offline mode is on (network commands fail immediately while it is)
 #[tauri::command]
-fn install_linux_update(version: String) -> Result<String, String> {
-    let pkg_manager = detect_package_manager()
-        .ok_or_else(|| "Could not detect package manager".to_string())?;
-
-    // Determine package type and URL based on package manager
-    let (pkg_type, pkg_url) = match pkg_manager {
-        "dnf" | "rpm" => {
-            let filename = format!("Ship.Lens-{}-1.x86_64.rpm", version);
-            let url = format!(
-                "https://github.com/CapCeph/ship-lens/releases/download/v{}/{}",
-                version, filename
-            );
-            ("rpm", url)
-        },
-        "apt" | "dpkg" => {
-            let filename = format!("Ship.Lens_{}_amd64.deb", version);
-            let url = format!(
-                "https://github.com/CapCeph/ship-lens/releases/download/v{}/{}",
-                version, filename
-            );
-            ("deb", url)
-        },
-        _ => return Err(format!("Unsupported package manager: {}", pkg_manager)),
-    };
+fn get_network_status(app: tauri::AppHandle) -> NetworkStatus {
+    network_status(&app)
+}
+
+/// Turn offline mode on or off
+#[tauri::command]
+fn set_offline_mode(app: tauri::AppHandle, offline: bool) -> Result<NetworkStatus, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
 
-    // Download package to temp directory
-    let temp_dir = std::env::temp_dir();
-    let pkg_filename = if pkg_type == "rpm" {
-        format!("ship-lens-{}.rpm", version)
+    network::save(&config_dir, NetworkSettings { offline })?;
+    Ok(network_status(&app))
+}
+
+/// Usage metrics consent and everything queued so far
+#[tauri::command]
+fn get_metrics(app: tauri::AppHandle) -> Result<MetricsFile, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    metrics::flush()?;
+    Ok(metrics::load(&config_dir))
+}
+
+/// Opt in to or out of usage metrics (opting out deletes the queue)
+#[tauri::command]
+fn set_metrics_consent(app: tauri::AppHandle, consent: bool) -> Result<MetricsFile, String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    metrics::set_consent(&config_dir, consent)?;
+    Ok(metrics::load(&config_dir))
+}
+
+/// Drop the queued usage metrics
+#[tauri::command]
+fn clear_metrics_queue(app: tauri::AppHandle) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
+
+    metrics::clear_queue(&config_dir)
+}
+
+/// Read Star Citizen's Game.log and extract combat events and observed TTKs
+///
+/// Each destroyed vehicle carries its matched ship name, so observed times can be
+/// compared against `calculate_ttk_for_ships` predictions.
+#[tauri::command]
+fn parse_combat_log(state: State<AppState>, path: String) -> Result<CombatLog, String> {
+    // The log isn't guaranteed to be valid UTF-8 (player and item names)
+    let bytes = std::fs::read(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    Ok(combatlog::parse(&state.snapshot(), &String::from_utf8_lossy(&bytes)))
+}
+
+/// Import a loadout exported from erkul.games (pasted JSON, or a URL serving it)
+///
+/// The result carries the TTK request fields (`weapon_names`/`weapon_counts`,
+/// `shield_loadout`, `attacker_loadout`, ...) plus the components that matched
+/// nothing in the game data.
+#[tauri::command]
+async fn import_loadout(app: tauri::AppHandle, state: State<'_, AppState>, url_or_json: String) -> Result<ImportedLoadout, String> {
+    let input = url_or_json.trim().to_string();
+    let export = if input.starts_with("http://") || input.starts_with("https://") {
+        network_status(&app).require_online()?;
+        run_blocking(move || download::get_json(&input)).await.map_err(|e| format!(
+            "{} (if this is an erkul page link, export the loadout as JSON and paste it instead)", e
+        ))?
     } else {
-        format!("ship-lens-{}.deb", version)
+        erkul::parse(&input)?
     };
-    let pkg_path = temp_dir.join(&pkg_filename);
+    erkul::import(&state.snapshot(), &export)
+}
 
-    eprintln!("Downloading {} to {:?}", pkg_url, pkg_path);
+/// Load the saved update channel (stable if none saved)
+fn load_update_channel(app: &tauri::AppHandle) -> UpdateChannel {
+    app.path().app_config_dir().ok()
+        .map(|dir| channel::load(&dir))
+        .unwrap_or_default()
+}
 
-    // Download the package using ureq
-    let response = ureq::get(&pkg_url)
-        .call()
-        .map_err(|e| format!("Failed to download package: {}", e))?;
+/// Get the update channel (stable, beta or ptu)
+#[tauri::command]
+fn get_update_channel(app: tauri::AppHandle) -> UpdateChannel {
+    load_update_channel(&app)
+}
 
-    let mut file = fs::File::create(&pkg_path)
-        .map_err(|e| format!("Failed to create temp file: {}", e))?;
+/// Choose the update channel used by the app updater
+#[tauri::command]
+fn set_update_channel(app: tauri::AppHandle, channel: UpdateChannel) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir()
+        .map_err(|e| format!("Failed to get config dir: {}", e))?;
 
-    std::io::copy(&mut response.into_reader(), &mut file)
-        .map_err(|e| format!("Failed to write package: {}", e))?;
+    channel::save(&config_dir, channel)
+}
 
-    file.flush()
-        .map_err(|e| format!("Failed to flush file: {}", e))?;
+/// An app update offered by the Tauri updater
+#[derive(Debug, Clone, Serialize)]
+pub struct AppUpdate {
+    pub version: String,
+    pub current_version: String,
+    pub notes: Option<String>,
+    pub channel: UpdateChannel,
+}
 
-    drop(file); // Close file before installing
+/// Tauri updater pointed at the saved channel's manifest
+fn channel_updater(app: &tauri::AppHandle) -> Result<tauri_plugin_updater::Updater, String> {
+    let url = load_update_channel(app).app_manifest_url();
+    let url = tauri::Url::parse(&url).map_err(|e| format!("Invalid update URL '{}': {}", url, e))?;
+    app.updater_builder()
+        .endpoints(vec![url])
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("Failed to set up updater: {}", e))
+}
 
-    eprintln!("Downloaded package to {:?}, installing via pkexec...", pkg_path);
+/// Check the saved channel for an app update (None when up to date)
+#[tauri::command]
+async fn check_app_update(app: tauri::AppHandle) -> Result<Option<AppUpdate>, String> {
+    network_status(&app).require_online()?;
+    let update = channel_updater(&app)?.check().await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+    Ok(update.map(|update| AppUpdate {
+        version: update.version,
+        current_version: update.current_version,
+        notes: update.body,
+        channel: load_update_channel(&app),
+    }))
+}
 
-    // Build the install command based on package manager
-    let install_cmd = match pkg_manager {
-        "dnf" => format!("dnf install -y '{}'", pkg_path.display()),
-        "apt" => format!("apt install -y '{}'", pkg_path.display()),
-        "rpm" => format!("rpm -U '{}'", pkg_path.display()),
-        "dpkg" => format!("dpkg -i '{}'", pkg_path.display()),
-        _ => return Err("Unknown package manager".to_string()),
-    };
+/// Download and install the saved channel's app update
+///
+/// Download progress arrives as `update-download-progress` events.
+#[tauri::command]
+async fn install_app_update(app: tauri::AppHandle) -> Result<(), String> {
+    network_status(&app).require_online()?;
+    let update = channel_updater(&app)?.check().await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?
+        .ok_or_else(|| "No update available".to_string())?;
+
+    let mut downloaded = 0u64;
+    update.download_and_install(
+        |chunk, total| {
+            downloaded += chunk as u64;
+            let _ = app.emit("update-download-progress", serde_json::json!({
+                "downloaded": downloaded,
+                "total": total,
+            }));
+        },
+        || {},
+    ).await.map_err(|e| format!("Failed to install update: {}", e))
+}
 
-    // Run via pkexec for privilege elevation
-    let output = Command::new("pkexec")
-        .arg("sh")
-        .arg("-c")
-        .arg(&install_cmd)
-        .output()
-        .map_err(|e| format!("Failed to run pkexec: {}", e))?;
+/// Newest GitHub release on the saved channel, if newer than this build (Linux packages)
+#[cfg(target_os = "linux")]
+#[tauri::command]
+async fn check_linux_update(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    network_status(&app).require_online()?;
+    let update_channel = load_update_channel(&app);
+    run_blocking(move || channel::latest_release(update_channel)).await
+}
 
-    // Clean up temp file
-    let _ = fs::remove_file(&pkg_path);
+/// Stub for non-Linux platforms
+#[cfg(not(target_os = "linux"))]
+#[tauri::command]
+fn check_linux_update() -> Result<Option<String>, String> {
+    Ok(None)
+}
 
-    if output.status.success() {
-        Ok("Update installed successfully. Please restart the application.".to_string())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        Err(format!("Install failed:\n{}\n{}", stdout, stderr))
-    }
+/// Install a Linux update (Flatpak: `flatpak update` on the host; native: package via pkexec)
+///
+/// Package download progress arrives as `update-download-progress` events.
+#[cfg(target_os = "linux")]
+#[tauri::command]
+async fn install_linux_update(app: tauri::AppHandle, version: String) -> Result<String, String> {
+    network_status(&app).require_online()?;
+    let download_dir = app.path().app_cache_dir()
+        .map_err(|e| format!("Failed to get cache dir: {}", e))?
+        .join("updates");
+    run_blocking(move || updater::install_update(&version, &download_dir, |progress| {
+        let _ = app.emit("update-download-progress", progress);
+    })).await
 }
 
 /// Stub for non-Linux platforms
@@ -698,67 +2911,327 @@ fn install_linux_update(_version: String) -> Result<String, String> {
     Err("Linux update only available on Linux".to_string())
 }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    // Load game data
-    let data_dir = get_data_dir();
-    eprintln!("Looking for data in: {:?}", data_dir);
-    let game_data = GameData::load(&data_dir).unwrap_or_else(|e| {
-        eprintln!("Warning: Could not load game data from {:?}: {}", data_dir, e);
-        GameData::default()
+/// Newest GitHub release on the saved channel, if newer than this build (Windows portable zip)
+///
+/// None when not running from the portable zip; installs use the Tauri updater.
+#[cfg(windows)]
+#[tauri::command]
+async fn check_portable_update(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    if portable::portable_dir().is_none() {
+        return Ok(None);
+    }
+    network_status(&app).require_online()?;
+    let update_channel = load_update_channel(&app);
+    run_blocking(move || channel::latest_release(update_channel)).await
+}
+
+/// Stub for non-Windows platforms
+#[cfg(not(windows))]
+#[tauri::command]
+fn check_portable_update() -> Result<Option<String>, String> {
+    Ok(None)
+}
+
+/// Download and stage a portable update, applied by a helper once the app exits
+///
+/// Download progress arrives as `update-download-progress` events.
+#[cfg(windows)]
+#[tauri::command]
+async fn install_portable_update(app: tauri::AppHandle, version: String) -> Result<String, String> {
+    network_status(&app).require_online()?;
+    let download_dir = app.path().app_cache_dir()
+        .map_err(|e| format!("Failed to get cache dir: {}", e))?
+        .join("updates");
+    run_blocking(move || portable::install_update(&version, &download_dir, |progress| {
+        let _ = app.emit("update-download-progress", progress);
+    })).await
+}
+
+/// Stub for non-Windows platforms
+#[cfg(not(windows))]
+#[tauri::command]
+fn install_portable_update(_version: String) -> Result<String, String> {
+    Err("Portable update only available on Windows".to_string())
+}
+
+/// Load game data in the background, swapping it into AppState and emitting progress events
+///
+/// Events: `data-loading` (LoadProgress), `data-ready` (stats), `data-failed` (error message)
+fn load_game_data(
+    app: tauri::AppHandle,
+    data_dir: PathBuf,
+    cache_dir: Option<PathBuf>,
+    config_dir: Option<PathBuf>,
+) {
+    log::info!("Looking for data in: {:?}", data_dir);
+    let on_progress = |progress: LoadProgress| {
+        let _ = app.emit("data-loading", progress);
+    };
+    on_progress(LoadProgress { stage: "starting".to_string(), completed: 0, total: 0 });
+
+    let (mut game_data, mut report) = match cache_dir {
+        Some(ref cache_dir) => GameData::load_cached(&data_dir, cache_dir, &on_progress),
+        None => GameData::load_with_report(&data_dir, &on_progress),
+    };
+
+    // Homebrew ships/weapons are merged after the cache so they never end up in it
+    if let Some(ref config_dir) = config_dir {
+        match custom::load(config_dir) {
+            Ok(defs) => custom::merge_into(&mut game_data, &defs, &mut report),
+            Err(e) => report.warn("custom_definitions.json", e),
+        }
+        // Imported prices override the bundled ones
+        data::prices::apply(&mut game_data, config_dir, &mut report);
+    }
+
+    // Read fresh every load: the manifest isn't part of the data cache
+    let manifest = data::manifest::load(&data_dir).unwrap_or_else(|e| {
+        report.warn(data::manifest::MANIFEST_FILE, e);
+        None
     });
+    let version = manifest.as_ref().map_or("unversioned".to_string(), DataManifest::label);
 
-    eprintln!("Loaded {} ships, {} weapons, {} shields, {} missiles, {} mounts",
+    log::info!("Loaded data {}: {} ships, {} weapons, {} shields, {} missiles, {} mounts ({} errors, {} warnings)",
+        version,
         game_data.ships.len(),
         game_data.weapons.len(),
         game_data.shields.len(),
         game_data.missiles.len(),
-        game_data.mounts.len()
+        game_data.mounts.len(),
+        report.errors.len(),
+        report.warnings.len()
     );
 
-    let app_state = AppState {
-        data: Mutex::new(game_data),
+    // Partial data is still usable; only fail outright when there are no ships to pick
+    let failure = match report.errors.first() {
+        Some(issue) if game_data.ships.is_empty() => Some(format!(
+            "Could not load game data from {:?}: {}: {}", data_dir, issue.file, issue.message)),
+        _ => None,
     };
 
+    let state = app.state::<AppState>();
+    let stats = data_stats(&game_data);
+    crash::record_data(format!("{} from {:?}: {} ships, {} weapons, {} shields{}",
+        version, data_dir, game_data.ships.len(), game_data.weapons.len(), game_data.shields.len(),
+        if report.from_cache { " (cached)" } else { "" }));
+    metrics::record_data(match &manifest {
+        Some(manifest) => manifest.label(),
+        None => format!("{} ships, {} weapons, {} shields",
+            game_data.ships.len(), game_data.weapons.len(), game_data.shields.len()),
+    });
+    *state.manifest.write_or_recover() = manifest;
+    state.replace_data(Arc::new(game_data));
+    *state.report.write_or_recover() = report;
+
+    match failure {
+        None => {
+            *state.status.write_or_recover() = DataStatus::Ready;
+            let _ = app.emit("data-ready", stats);
+        }
+        Some(message) => {
+            *state.status.write_or_recover() = DataStatus::Failed(message.clone());
+            let _ = app.emit("data-failed", message);
+        }
+    }
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    let forced_data_dir = datadir::forced(std::env::args().skip(1), std::env::var(datadir::DATA_DIR_ENV).ok());
+
+    let handler: fn(tauri::ipc::Invoke) -> bool = tauri::generate_handler![
+        get_ships,
+        get_ship,
+        get_ship_armament_summary,
+        get_ordnance_capacity,
+        get_power_plants,
+        get_coolers,
+        check_power_budget,
+        estimate_signatures,
+        detection_range,
+        get_filtered_ships,
+        get_variant_filter,
+        save_variant_filter,
+        query_ships,
+        get_weapons,
+        query_weapons,
+        get_manufacturers,
+        get_weapons_by_size,
+        get_weapon,
+        get_shields,
+        get_shields_by_size,
+        get_shield,
+        get_default_shield,
+        get_missiles,
+        get_missiles_by_size,
+        get_missile,
+        get_bombs,
+        calculate_bombing_run,
+        calculate_torpedo_strike,
+        get_mounts,
+        get_mounts_by_max_size,
+        get_mount,
+        calculate_ttk,
+        calculate_ttk_v2,
+        calculate_ttk_for_ships,
+        ttk_delta,
+        get_zone_presets,
+        get_scenario_presets,
+        compare_ships,
+        compare_weapons,
+        rank_weapons,
+        get_weapon_shield_matrix,
+        export_weapon_shield_matrix,
+        export_dataset,
+        analyze_target,
+        calculate_escape_window,
+        rank_ships_by_survivability,
+        optimize_shield,
+        optimize_budget_loadout,
+        find_counters,
+        assess_threat,
+        recommend_target_order,
+        calculate_ttk_multi_target,
+        cancel_calculation,
+        clear_cache,
+        get_stats,
+        get_dataset_analytics,
+        audit_data,
+        verify_model,
+        import_benchmarks,
+        compare_benchmarks,
+        calibrate_scenario,
+        get_calibrated_scenarios,
+        delete_calibrated_scenario,
+        get_data_status,
+        get_load_report,
+        detect_game_install,
+        import_game_data,
+        save_settings,
+        load_settings,
+        save_fleet_preset,
+        load_fleet_presets,
+        delete_fleet_preset,
+        query_presets,
+        rename_preset,
+        duplicate_preset,
+        reorder_presets,
+        set_preset_tags,
+        mark_favorite,
+        get_favorites,
+        get_recents,
+        get_history,
+        rerun_history_entry,
+        clear_history,
+        get_session_state,
+        set_session_state,
+        session_undo,
+        session_redo,
+        add_custom_ship,
+        add_custom_weapon,
+        backup_config,
+        restore_config,
+        export_logs,
+        import_prices,
+        get_prices,
+        install_linux_update,
+        check_linux_update,
+        check_portable_update,
+        install_portable_update,
+        get_update_channel,
+        set_update_channel,
+        check_app_update,
+        install_app_update,
+        get_network_status,
+        set_offline_mode,
+        import_loadout,
+        parse_combat_log,
+        format_for_discord,
+        get_simulation_series,
+        post_to_discord,
+        toggle_overlay,
+        pin_matchup,
+        get_overlay_readout,
+        set_overlay_shortcut,
+        get_stream_status,
+        set_stream_enabled,
+        get_last_crash_report,
+        clear_crash_report,
+        get_metrics,
+        set_metrics_consent,
+        clear_metrics_queue,
+        get_version_info,
+        get_data_location,
+        set_data_dir,
+    ];
+
     tauri::Builder::default()
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
-        .manage(app_state)
-        .setup(|app| {
-            if cfg!(debug_assertions) {
-                app.handle().plugin(
-                    tauri_plugin_log::Builder::default()
-                        .level(log::LevelFilter::Info)
-                        .build(),
-                )?;
+        .plugin(
+            // The only global shortcut is the overlay toggle
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, _shortcut, event| {
+                    if event.state() == ShortcutState::Pressed {
+                        if let Err(e) = overlay::toggle(app) {
+                            log::warn!("Could not toggle overlay: {}", e);
+                        }
+                    }
+                })
+                .build(),
+        )
+        .setup(move |app| {
+            // Log files live with the app data so `export_logs` can bundle them
+            let data_path = app.path().app_data_dir()?;
+            app.handle().plugin(logs::plugin(logs::log_dir(&data_path)))?;
+            if let Ok(config_dir) = app.path().app_config_dir() {
+                crash::install(config_dir.clone());
+                metrics::start(config_dir);
+            }
+
+            let config_dir = app.path().app_config_dir().ok();
+            let settings = config_dir.as_deref().map(datadir::load).unwrap_or_default();
+            let location = datadir::resolve(forced_data_dir, &settings, get_data_dir);
+            let data_dir = location.path.clone();
+
+            // Start with empty data so the window appears immediately; load in the background
+            app.manage(AppState {
+                data: RwLock::new(Arc::new(GameData::default())),
+                data_generation: AtomicU64::new(0),
+                status: RwLock::new(DataStatus::Loading),
+                report: RwLock::new(LoadReport::default()),
+                tasks: TaskRegistry::default(),
+                results: Mutex::new(ResultCache::new(RESULT_CACHE_CAPACITY)),
+                stream: StreamServer::default(),
+                manifest: RwLock::new(None),
+                data_dir: RwLock::new(location),
+                session: Mutex::new(Session::default()),
+            });
+            let cache_dir = app.path().app_cache_dir().ok();
+            // Another app may already own the shortcut; the overlay stays reachable by command
+            let shortcut = load_overlay_settings(app.handle()).shortcut().to_string();
+            if let Err(e) = app.global_shortcut().register(shortcut.as_str()) {
+                log::warn!("Could not register overlay shortcut '{}': {}", shortcut, e);
             }
+
+            // A busy port only disables the endpoint; it can be moved from settings
+            let stream_settings: StreamSettings = config_dir.as_deref().map(stream::load).unwrap_or_default();
+            if stream_settings.enabled {
+                if let Err(e) = app.state::<AppState>().stream.start(stream_settings.port()) {
+                    log::warn!("Could not start stream endpoint: {}", e);
+                }
+            }
+
+            let handle = app.handle().clone();
+            std::thread::spawn(move || load_game_data(handle, data_dir, cache_dir, config_dir));
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            get_ships,
-            get_ship,
-            get_weapons,
-            get_weapons_by_size,
-            get_weapon,
-            get_shields,
-            get_shields_by_size,
-            get_shield,
-            get_missiles,
-            get_missiles_by_size,
-            get_missile,
-            get_mounts,
-            get_mounts_by_max_size,
-            get_mount,
-            calculate_ttk,
-            calculate_ttk_v2,
-            get_stats,
-            save_settings,
-            load_settings,
-            save_fleet_preset,
-            load_fleet_presets,
-            delete_fleet_preset,
-            install_linux_update,
-        ])
+        .invoke_handler(move |invoke| {
+            // Crash reports name the last command the frontend invoked
+            crash::record_command(invoke.message.command());
+            metrics::record_command(invoke.message.command());
+            handler(invoke)
+        })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,84 @@
+//! Ship Lens Lock Recovery
+//!
+//! A panic while a lock is held poisons it, and unwrapping every later lock
+//! would then take down each command that touches it until restart. Backend
+//! locks are taken through these helpers instead: a poisoned lock is logged,
+//! cleared and used as-is. That is safe here because every guarded value is
+//! replaced or updated in a single step (data snapshots are edited on a copy
+//! and swapped in), so a panic can't leave one half-written.
+
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Log a poisoned lock and keep its data
+fn recover<G>(what: &str, error: PoisonError<G>) -> G {
+    log::warn!("Recovered {} poisoned by a panic", what);
+    error.into_inner()
+}
+
+pub trait MutexExt<T> {
+    /// Lock, recovering the data if a panic poisoned the mutex
+    fn lock_or_recover(&self) -> MutexGuard<'_, T>;
+}
+
+impl<T> MutexExt<T> for Mutex<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(|e| {
+            let guard = recover("mutex", e);
+            self.clear_poison();
+            guard
+        })
+    }
+}
+
+pub trait RwLockExt<T> {
+    /// Read-lock, recovering the data if a panic poisoned the lock
+    fn read_or_recover(&self) -> RwLockReadGuard<'_, T>;
+    /// Write-lock, recovering the data if a panic poisoned the lock
+    fn write_or_recover(&self) -> RwLockWriteGuard<'_, T>;
+}
+
+impl<T> RwLockExt<T> for RwLock<T> {
+    fn read_or_recover(&self) -> RwLockReadGuard<'_, T> {
+        self.read().unwrap_or_else(|e| {
+            let guard = recover("read lock", e);
+            self.clear_poison();
+            guard
+        })
+    }
+
+    fn write_or_recover(&self) -> RwLockWriteGuard<'_, T> {
+        self.write().unwrap_or_else(|e| {
+            let guard = recover("write lock", e);
+            self.clear_poison();
+            guard
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_poisoned_locks_recover() {
+        let counter = Arc::new(Mutex::new(1));
+        let data = Arc::new(RwLock::new(vec![1, 2]));
+        let (c, d) = (counter.clone(), data.clone());
+        let _ = std::thread::spawn(move || {
+            let _held = c.lock().unwrap();
+            let _written = d.write().unwrap();
+            panic!("calculation failed");
+        }).join();
+        assert!(counter.is_poisoned() && data.is_poisoned());
+
+        *counter.lock_or_recover() += 1;
+        assert_eq!(*counter.lock_or_recover(), 2);
+        assert!(!counter.is_poisoned());
+
+        assert_eq!(*data.read_or_recover(), [1, 2]);
+        data.write_or_recover().push(3);
+        assert!(!data.is_poisoned());
+        assert_eq!(data.read().unwrap().len(), 3);
+    }
+}
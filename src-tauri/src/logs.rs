@@ -0,0 +1,106 @@
+//! Ship Lens Logging
+//!
+//! Backend messages go through the `log` crate. They are written to stdout and
+//! to rotating files in `logs/` under the app data dir, so bug reports can come
+//! with the log of the session that went wrong; `export` zips those files up.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::plugin::TauriPlugin;
+use tauri::Runtime;
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+
+/// Log file name (without extension) inside the log dir
+const LOG_FILE: &str = "ship-lens";
+
+/// Size at which a log file is rotated (bytes)
+const MAX_LOG_SIZE: u128 = 1_000_000;
+
+/// Rotated log files kept besides the current one
+const KEPT_LOG_FILES: usize = 5;
+
+/// Where log files are written
+pub fn log_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("logs")
+}
+
+/// Logger writing to stdout and the rotating files in `log_dir`
+///
+/// Debug builds also log debug messages (per-calculation details).
+pub fn plugin<R: Runtime>(log_dir: PathBuf) -> TauriPlugin<R> {
+    tauri_plugin_log::Builder::new()
+        .clear_targets()
+        .targets([
+            Target::new(TargetKind::Stdout),
+            Target::new(TargetKind::Folder { path: log_dir, file_name: Some(LOG_FILE.to_string()) }),
+        ])
+        .level(if cfg!(debug_assertions) { log::LevelFilter::Debug } else { log::LevelFilter::Info })
+        .max_file_size(MAX_LOG_SIZE)
+        .rotation_strategy(RotationStrategy::KeepSome(KEPT_LOG_FILES))
+        .build()
+}
+
+/// Log files in `log_dir`, oldest name first
+fn log_files(log_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    if !log_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(log_dir)
+        .map_err(|e| format!("Failed to read {}: {}", log_dir.display(), e))?;
+    let mut files: Vec<PathBuf> = entries.flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "log"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Zip every log file in `log_dir` to `dest`, returning the number of files
+pub fn export(log_dir: &Path, dest: &Path) -> Result<usize, String> {
+    let files = log_files(log_dir)?;
+    let file = fs::File::create(dest)
+        .map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let zip_err = |e: zip::result::ZipError| format!("Failed to write log archive: {}", e);
+
+    for path in &files {
+        let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let bytes = fs::read(path)
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        zip.start_file(name.as_str(), options).map_err(zip_err)?;
+        zip.write_all(&bytes).map_err(|e| format!("Failed to write log archive: {}", e))?;
+    }
+    zip.finish().map_err(zip_err)?;
+    Ok(files.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_export_logs() {
+        let dir = std::env::temp_dir().join(format!("ship-lens-logs-{}", std::process::id()));
+        let log_dir = log_dir(&dir);
+        fs::create_dir_all(&log_dir).unwrap();
+        fs::write(log_dir.join("ship-lens.log"), "current").unwrap();
+        fs::write(log_dir.join("ship-lens_2026-01-01_10-00-00.log"), "rotated").unwrap();
+        fs::write(log_dir.join("notes.txt"), "not a log").unwrap();
+
+        let dest = dir.join("logs.zip");
+        assert_eq!(export(&log_dir, &dest).unwrap(), 2);
+        let mut archive = zip::ZipArchive::new(fs::File::open(&dest).unwrap()).unwrap();
+        let mut text = String::new();
+        archive.by_name("ship-lens.log").unwrap().read_to_string(&mut text).unwrap();
+        assert_eq!(text, "current");
+        assert!(archive.by_name("notes.txt").is_err());
+
+        // No logs yet: an empty archive
+        assert_eq!(export(&dir.join("missing"), &dest).unwrap(), 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
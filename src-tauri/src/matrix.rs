@@ -0,0 +1,140 @@
+//! Ship Lens Weapon/Shield Matrix
+//!
+//! Effective shield DPS of every weapon (or a filtered set) against every shield:
+//! sustained DPS split by damage type, through the shield's absorption and
+//! resistances as in the TTK model. Returned as a table and exportable as CSV,
+//! for "which gun melts which shield" charts each patch.
+
+use crate::data::{GameData, WeaponFilter};
+use crate::ttk::{calculate_shield_damage, DamageBreakdown};
+use serde::Serialize;
+
+/// Row of the matrix
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixWeapon {
+    pub name: String,
+    pub size: i32,
+    pub damage_type: String,
+    pub sustained_dps: f64,
+}
+
+/// Column of the matrix
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixShield {
+    pub name: String,
+    pub internal_name: String,
+    pub size: i32,
+}
+
+/// Effective shield DPS of each weapon against each shield
+#[derive(Debug, Clone, Serialize)]
+pub struct WeaponShieldMatrix {
+    /// Sorted by size, then name
+    pub weapons: Vec<MatrixWeapon>,
+    /// Sorted by size, then name
+    pub shields: Vec<MatrixShield>,
+    /// `shield_dps[weapon][shield]`
+    pub shield_dps: Vec<Vec<f64>>,
+}
+
+/// Matrix of the weapons matching `filter` against the shields of `shield_size` (all if None)
+///
+/// Weapons without DPS are left out.
+pub fn build(data: &GameData, filter: &WeaponFilter, shield_size: Option<i32>) -> WeaponShieldMatrix {
+    let mut weapons: Vec<_> = data.weapons.values()
+        .filter(|w| w.sustained_dps > 0.0 && filter.matches(w))
+        .collect();
+    weapons.sort_by(|a, b| (a.size, &a.display_name).cmp(&(b.size, &b.display_name)));
+    let mut shields: Vec<_> = data.shields.values()
+        .filter(|s| shield_size.map_or(true, |size| s.size == size))
+        .collect();
+    shields.sort_by(|a, b| (a.size, &a.display_name).cmp(&(b.size, &b.display_name)));
+
+    let shield_dps = weapons.iter()
+        .map(|weapon| {
+            let damage = DamageBreakdown::from_weapon_dps(weapon, weapon.sustained_dps);
+            shields.iter().map(|shield| calculate_shield_damage(&damage, shield).0).collect()
+        })
+        .collect();
+
+    WeaponShieldMatrix {
+        weapons: weapons.iter()
+            .map(|w| MatrixWeapon {
+                name: w.display_name.clone(),
+                size: w.size,
+                damage_type: w.damage_type.clone(),
+                sustained_dps: w.sustained_dps,
+            })
+            .collect(),
+        shields: shields.iter()
+            .map(|s| MatrixShield { name: s.display_name.clone(), internal_name: s.internal_name.clone(), size: s.size })
+            .collect(),
+        shield_dps,
+    }
+}
+
+/// CSV field, quoted when it holds a comma, quote or line break
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl WeaponShieldMatrix {
+    /// One row per weapon: name, size, type, sustained DPS, then shield DPS per shield
+    pub fn to_csv(&self) -> String {
+        let mut header = vec!["weapon".to_string(), "size".to_string(), "damage_type".to_string(), "sustained_dps".to_string()];
+        header.extend(self.shields.iter().map(|s| csv_field(&format!("S{} {}", s.size, s.name))));
+        let mut lines = vec![header.join(",")];
+        for (weapon, row) in self.weapons.iter().zip(&self.shield_dps) {
+            let mut fields = vec![
+                csv_field(&weapon.name),
+                weapon.size.to_string(),
+                csv_field(&weapon.damage_type),
+                format!("{:.1}", weapon.sustained_dps),
+            ];
+            fields.extend(row.iter().map(|dps| format!("{:.1}", dps)));
+            lines.push(fields.join(","));
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{reference_shield, ThreatProfile};
+    use crate::data::{Shield, Weapon};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_weapon_shield_matrix() {
+        let mut data = GameData::default();
+        let gun = |name: &str, physical: f64, energy: f64| Weapon {
+            display_name: name.to_string(),
+            size: 3,
+            ..ThreatProfile { dps: 1000.0, physical, energy, distortion: 0.0 }.to_equipped_weapon().weapon
+        };
+        data.weapons.insert("laser".to_string(), Arc::new(gun("Laser, Mk II", 0.0, 1.0)));
+        data.weapons.insert("cannon".to_string(), Arc::new(gun("Cannon", 1.0, 0.0)));
+        data.weapons.insert("dud".to_string(), Arc::new(Weapon { sustained_dps: 0.0, ..gun("Dud", 1.0, 0.0) }));
+        let shield = Shield { size: 2, resist_energy: -0.2, ..reference_shield() };
+        data.shields.insert(shield.internal_name.clone(), Arc::new(shield));
+
+        let matrix = build(&data, &WeaponFilter::default(), None);
+        let names: Vec<_> = matrix.weapons.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, ["Cannon", "Laser, Mk II"]);
+        // Physical: 22.5% absorbed; energy: fully absorbed plus the 20% weakness
+        assert!((matrix.shield_dps[0][0] - 225.0).abs() < 1e-9);
+        assert!((matrix.shield_dps[1][0] - 1200.0).abs() < 1e-9);
+        assert!(build(&data, &WeaponFilter::default(), Some(3)).shields.is_empty());
+
+        let csv = matrix.to_csv();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "weapon,size,damage_type,sustained_dps,S2 Reference Shield");
+        assert!(lines[2].starts_with("\"Laser, Mk II\",3,"));
+        assert!(lines[2].ends_with(",1200.0"));
+    }
+}
@@ -0,0 +1,223 @@
+//! Ship Lens Usage Metrics
+//!
+//! Opt-in, anonymous usage counts: which commands are used, how many TTK
+//! calculations run, and which data is loaded (entity counts only, never paths
+//! or ship picks). Nothing is recorded until the user consents. Counts are
+//! aggregated per day and queued in `metrics.json` in the app config dir, where
+//! the user can inspect exactly what would be shared; withdrawing consent
+//! deletes the queue. Nothing is sent from here.
+
+use crate::locks::MutexExt;
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const METRICS_FILE: &str = "metrics.json";
+
+/// Daily batches kept before the oldest is dropped
+pub const MAX_BATCHES: usize = 30;
+
+/// Events recorded in memory before they are written to the queue
+const FLUSH_EVERY: u64 = 25;
+
+/// Aggregate counts for one day
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricsBatch {
+    pub day: String,  // YYYY-MM-DD (UTC)
+    pub app_version: String,
+    pub data_version: Option<String>,
+    /// Invocations per command name
+    pub commands: BTreeMap<String, u64>,
+    pub calculations: u64,
+}
+
+impl MetricsBatch {
+    fn same_period(&self, other: &MetricsBatch) -> bool {
+        self.day == other.day && self.app_version == other.app_version && self.data_version == other.data_version
+    }
+
+    fn merge(&mut self, other: MetricsBatch) {
+        for (command, count) in other.commands {
+            *self.commands.entry(command).or_default() += count;
+        }
+        self.calculations += other.calculations;
+    }
+}
+
+/// Saved consent and queued batches (returned by `get_metrics`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsFile {
+    pub consent: bool,
+    /// Oldest first
+    pub queue: Vec<MetricsBatch>,
+}
+
+/// Add `batch` to the queue, merging it into the batch of the same day and versions
+fn enqueue(queue: &mut Vec<MetricsBatch>, batch: MetricsBatch) {
+    match queue.iter_mut().find(|b| b.same_period(&batch)) {
+        Some(existing) => existing.merge(batch),
+        None => queue.push(batch),
+    }
+    if queue.len() > MAX_BATCHES {
+        queue.drain(..queue.len() - MAX_BATCHES);
+    }
+}
+
+/// Load consent and the queue (no consent if none saved)
+pub fn load(config_dir: &Path) -> MetricsFile {
+    store::read_json(&config_dir.join(METRICS_FILE)).ok().flatten().unwrap_or_default()
+}
+
+fn save(config_dir: &Path, metrics: &MetricsFile) -> Result<(), String> {
+    store::write_json(&config_dir.join(METRICS_FILE), metrics)
+}
+
+/// Counts not yet written to the queue
+#[derive(Debug, Default)]
+struct Recorder {
+    config_dir: PathBuf,
+    consent: bool,
+    data_version: Option<String>,
+    commands: BTreeMap<String, u64>,
+    calculations: u64,
+    events: u64,
+}
+
+impl Recorder {
+    /// Count one event; true when it is time to flush
+    fn count(&mut self, event: impl FnOnce(&mut Self)) -> bool {
+        if !self.consent {
+            return false;
+        }
+        event(self);
+        self.events += 1;
+        self.events >= FLUSH_EVERY
+    }
+
+    /// Pending counts as a batch for today (None if there are none)
+    fn take_batch(&mut self) -> Option<MetricsBatch> {
+        self.events = 0;
+        if self.commands.is_empty() && self.calculations == 0 {
+            return None;
+        }
+        Some(MetricsBatch {
+            day: store::now_iso8601()[..10].to_string(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            data_version: self.data_version.clone(),
+            commands: std::mem::take(&mut self.commands),
+            calculations: std::mem::take(&mut self.calculations),
+        })
+    }
+}
+
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+/// Start recording into `config_dir` if the user has consented
+pub fn start(config_dir: PathBuf) {
+    let consent = load(&config_dir).consent;
+    let mut recorder = RECORDER.lock_or_recover();
+    let data_version = recorder.take().and_then(|r| r.data_version);
+    *recorder = Some(Recorder { config_dir, consent, data_version, ..Default::default() });
+}
+
+fn count(event: impl FnOnce(&mut Recorder)) {
+    let due = RECORDER.lock_or_recover().as_mut().is_some_and(|r| r.count(event));
+    if due {
+        if let Err(e) = flush() {
+            log::warn!("Could not queue usage metrics: {}", e);
+        }
+    }
+}
+
+/// Count an invoked command
+pub fn record_command(command: &str) {
+    count(|r| *r.commands.entry(command.to_string()).or_default() += 1);
+}
+
+/// Count a completed TTK calculation
+pub fn record_calculation() {
+    count(|r| r.calculations += 1);
+}
+
+/// Remember which data is loaded (entity counts, no paths)
+pub fn record_data(version: String) {
+    let mut recorder = RECORDER.lock_or_recover();
+    recorder.get_or_insert_with(Recorder::default).data_version = Some(version);
+}
+
+/// Write pending counts to the queue
+pub fn flush() -> Result<(), String> {
+    let (config_dir, batch) = {
+        let mut recorder = RECORDER.lock_or_recover();
+        let Some(recorder) = recorder.as_mut() else { return Ok(()) };
+        match recorder.take_batch() {
+            Some(batch) => (recorder.config_dir.clone(), batch),
+            None => return Ok(()),
+        }
+    };
+    let mut metrics = load(&config_dir);
+    enqueue(&mut metrics.queue, batch);
+    save(&config_dir, &metrics)
+}
+
+/// Give or withdraw consent; withdrawing drops everything recorded so far
+pub fn set_consent(config_dir: &Path, consent: bool) -> Result<(), String> {
+    if let Some(recorder) = RECORDER.lock_or_recover().as_mut() {
+        recorder.consent = consent;
+        if !consent {
+            recorder.take_batch();
+        }
+    }
+    let mut metrics = load(config_dir);
+    metrics.consent = consent;
+    if !consent {
+        metrics.queue.clear();
+    }
+    save(config_dir, &metrics)
+}
+
+/// Drop the queued batches, keeping consent as is
+pub fn clear_queue(config_dir: &Path) -> Result<(), String> {
+    let mut metrics = load(config_dir);
+    metrics.queue.clear();
+    save(config_dir, &metrics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_need_consent_and_aggregate() {
+        let mut recorder = Recorder::default();
+        assert!(!recorder.count(|r| r.calculations += 1));
+        assert_eq!(recorder.take_batch(), None);
+
+        recorder.consent = true;
+        recorder.data_version = Some("200 ships".to_string());
+        for i in 1..FLUSH_EVERY {
+            assert!(!recorder.count(|r| *r.commands.entry("get_ships".to_string()).or_default() += 1), "{}", i);
+        }
+        assert!(recorder.count(|r| r.calculations += 1));
+        let batch = recorder.take_batch().unwrap();
+        assert_eq!((batch.commands["get_ships"], batch.calculations), (FLUSH_EVERY - 1, 1));
+        assert_eq!(recorder.take_batch(), None);
+
+        // Same day and versions merge; a data update starts a new batch
+        let mut queue = vec![];
+        enqueue(&mut queue, batch.clone());
+        enqueue(&mut queue, batch.clone());
+        enqueue(&mut queue, MetricsBatch { data_version: Some("201 ships".to_string()), ..batch.clone() });
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue[0].commands["get_ships"], 2 * (FLUSH_EVERY - 1));
+
+        for day in 0..MAX_BATCHES {
+            enqueue(&mut queue, MetricsBatch { day: format!("day {}", day), ..batch.clone() });
+        }
+        assert_eq!(queue.len(), MAX_BATCHES);
+        assert_eq!(queue[0].day, "day 0");
+    }
+}
@@ -0,0 +1,86 @@
+//! Ship Lens Network Policy
+//!
+//! An explicit offline mode, saved as `network.json` in the app config dir (or
+//! forced with `SHIP_LENS_OFFLINE=1`). While offline, every command that would
+//! touch the network (update checks, package downloads) fails immediately
+//! instead of waiting on a timeout.
+
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const NETWORK_FILE: &str = "network.json";
+
+/// Environment variable that forces offline mode regardless of the setting
+const OFFLINE_ENV: &str = "SHIP_LENS_OFFLINE";
+
+/// Error returned by network commands while offline
+pub const OFFLINE_ERROR: &str = "Offline mode is enabled; network access is disabled";
+
+/// Saved network settings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkSettings {
+    pub offline: bool,
+}
+
+/// Effective network policy (returned by `get_network_status`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct NetworkStatus {
+    /// No network calls are made
+    pub offline: bool,
+    /// Offline is forced by the environment, so the setting can't turn it off
+    pub forced_by_env: bool,
+}
+
+impl NetworkStatus {
+    /// `Err(OFFLINE_ERROR)` while offline
+    pub fn require_online(self) -> Result<(), String> {
+        if self.offline {
+            Err(OFFLINE_ERROR.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Whether an environment value switches offline mode on ("1", "true", "yes")
+fn env_forces_offline(value: Option<&str>) -> bool {
+    value.map(|v| v.trim().to_lowercase())
+        .is_some_and(|v| matches!(v.as_str(), "1" | "true" | "yes" | "on"))
+}
+
+/// Load the saved settings (online if none saved)
+pub fn load(config_dir: &Path) -> NetworkSettings {
+    store::read_json(&config_dir.join(NETWORK_FILE)).ok().flatten().unwrap_or_default()
+}
+
+/// Save the settings (atomically, keeping backups)
+pub fn save(config_dir: &Path, settings: NetworkSettings) -> Result<(), String> {
+    store::write_json(&config_dir.join(NETWORK_FILE), &settings)
+}
+
+/// Effective policy from the saved settings and the environment
+pub fn status(settings: NetworkSettings) -> NetworkStatus {
+    let forced_by_env = env_forces_offline(std::env::var(OFFLINE_ENV).ok().as_deref());
+    NetworkStatus {
+        offline: settings.offline || forced_by_env,
+        forced_by_env,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_policy() {
+        assert!(env_forces_offline(Some(" TRUE ")));
+        assert!(!env_forces_offline(Some("0")));
+        assert!(!env_forces_offline(None));
+
+        let offline = NetworkStatus { offline: true, forced_by_env: false };
+        assert_eq!(offline.require_online(), Err(OFFLINE_ERROR.to_string()));
+        assert!(NetworkStatus { offline: false, forced_by_env: false }.require_online().is_ok());
+    }
+}
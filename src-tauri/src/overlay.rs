@@ -0,0 +1,118 @@
+//! Ship Lens Overlay
+//!
+//! A compact, frameless, always-on-top window (`overlay.html`) showing the TTK of
+//! a pinned matchup, so reference numbers stay visible over a borderless game.
+//! It is toggled by a command or a global shortcut. The pinned request and the
+//! shortcut are saved as `overlay.json` in the app config dir.
+
+use crate::history::ResultSummary;
+use crate::store;
+use crate::CalcRequest;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+const OVERLAY_FILE: &str = "overlay.json";
+
+/// Window label of the overlay
+pub const OVERLAY_LABEL: &str = "overlay";
+
+/// Shortcut toggling the overlay unless another is saved
+pub const DEFAULT_SHORTCUT: &str = "CommandOrControl+Shift+O";
+
+/// Saved overlay settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverlaySettings {
+    /// Matchup shown in the overlay
+    pub pinned: Option<CalcRequest>,
+    /// Global shortcut (`DEFAULT_SHORTCUT` if unset)
+    pub shortcut: Option<String>,
+}
+
+impl OverlaySettings {
+    pub fn shortcut(&self) -> &str {
+        self.shortcut.as_deref().unwrap_or(DEFAULT_SHORTCUT)
+    }
+}
+
+/// What the overlay displays for the pinned matchup
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlayReadout {
+    pub attacker: Option<String>,
+    pub target: String,
+    pub shield: Option<String>,
+    pub summary: ResultSummary,
+}
+
+impl OverlayReadout {
+    pub fn new(request: &CalcRequest, summary: ResultSummary) -> Self {
+        let (attacker, target, shield) = match request {
+            CalcRequest::Loadout(r) => (r.attacker_ship.clone(), r.target_ship.clone(), r.shield_name.clone()),
+            CalcRequest::Ships(r) => (Some(r.attacker.clone()), r.target.clone(), r.shield_name.clone()),
+        };
+        Self { attacker, target, shield, summary }
+    }
+}
+
+/// Load the saved settings (nothing pinned if none saved)
+pub fn load(config_dir: &Path) -> OverlaySettings {
+    store::read_json(&config_dir.join(OVERLAY_FILE)).ok().flatten().unwrap_or_default()
+}
+
+/// Save the settings (atomically, keeping backups)
+pub fn save(config_dir: &Path, settings: &OverlaySettings) -> Result<(), String> {
+    store::write_json(&config_dir.join(OVERLAY_FILE), settings)
+}
+
+/// Show the overlay (creating it on first use) or hide it; returns whether it is now visible
+///
+/// The overlay is shown without taking focus so the game keeps its input.
+pub fn toggle(app: &AppHandle) -> tauri::Result<bool> {
+    if let Some(window) = app.get_webview_window(OVERLAY_LABEL) {
+        let visible = window.is_visible()?;
+        if visible {
+            window.hide()?;
+        } else {
+            window.show()?;
+        }
+        return Ok(!visible);
+    }
+
+    WebviewWindowBuilder::new(app, OVERLAY_LABEL, WebviewUrl::App("overlay.html".into()))
+        .title("Ship Lens Overlay")
+        .inner_size(320.0, 150.0)
+        .resizable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .focused(false)
+        .build()?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShipTtkRequest;
+
+    #[test]
+    fn test_overlay_settings() {
+        let settings: OverlaySettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings.shortcut(), DEFAULT_SHORTCUT);
+
+        let request = CalcRequest::Ships(ShipTtkRequest {
+            attacker: "Aegis Gladius".to_string(),
+            target: "Anvil Arrow".to_string(),
+            ..Default::default()
+        });
+        let settings = OverlaySettings { pinned: Some(request), shortcut: Some("Alt+F9".to_string()) };
+        let json = serde_json::to_string(&settings).unwrap();
+        let settings: OverlaySettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(settings.shortcut(), "Alt+F9");
+
+        let readout = OverlayReadout::new(settings.pinned.as_ref().unwrap(), ResultSummary::default());
+        assert_eq!(readout.attacker.as_deref(), Some("Aegis Gladius"));
+        assert_eq!(readout.target, "Anvil Arrow");
+    }
+}
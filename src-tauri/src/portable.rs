@@ -0,0 +1,165 @@
+//! Ship Lens Windows Portable Updater
+//!
+//! The portable zip has no Tauri updater entry (only the MSI/NSIS installers
+//! do), so it updates here, mirroring the Linux package flow: the release zip is
+//! downloaded (resumably) into the app's cache dir, checked against its published
+//! SHA-256 and extracted to a staging folder in the app folder,
+//! and a small batch helper waits for Ship Lens to exit, copies the staged files
+//! over the app folder and relaunches it. A running executable can't be
+//! replaced on Windows, hence the helper.
+
+use crate::channel;
+use crate::download::{self, DownloadProgress};
+use std::fs;
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const RELEASES_URL: &str = "https://github.com/CapCeph/ship-lens/releases/download";
+
+/// Empty file shipped in the portable zip next to the executable
+const PORTABLE_MARKER: &str = "portable";
+
+/// Folder (inside the app folder) the update is extracted to
+const STAGING_DIR: &str = "update-staging";
+
+const HELPER_FILE: &str = "apply-update.cmd";
+
+/// `CREATE_NO_WINDOW`: run the helper without a console window
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Release asset of the portable build
+fn portable_asset(version: &str) -> String {
+    format!("Ship.Lens_{}_x64_portable.zip", version)
+}
+
+/// App folder when running from the portable zip
+pub fn portable_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let dir = exe.parent()?;
+    dir.join(PORTABLE_MARKER).is_file().then(|| dir.to_path_buf())
+}
+
+/// Extract `archive` into a fresh `dest`, returning the number of files
+fn extract(archive: &Path, dest: &Path) -> Result<usize, String> {
+    if dest.exists() {
+        fs::remove_dir_all(dest).map_err(|e| format!("Failed to clear {}: {}", dest.display(), e))?;
+    }
+    let file = fs::File::open(archive)
+        .map_err(|e| format!("Failed to open {}: {}", archive.display(), e))?;
+    let mut zip = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Not a valid update archive: {}", e))?;
+
+    let mut files = 0;
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)
+            .map_err(|e| format!("Failed to read update archive: {}", e))?;
+        let Some(relative) = entry.enclosed_name() else {
+            return Err(format!("Update entry '{}' escapes the app folder", entry.name()));
+        };
+        let path = dest.join(relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let mut out = fs::File::create(&path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        std::io::copy(&mut entry, &mut out)
+            .map_err(|e| format!("Failed to extract {}: {}", path.display(), e))?;
+        files += 1;
+    }
+    Ok(files)
+}
+
+/// Batch helper: wait for process `pid` to exit, move `staging` over `app_dir`, relaunch `exe`
+fn helper_script(pid: u32, app_dir: &Path, staging: &Path, exe: &Path) -> String {
+    [
+        "@echo off".to_string(),
+        ":wait".to_string(),
+        format!("tasklist /FI \"PID eq {0}\" | find \" {0} \" >nul && (timeout /t 1 /nobreak >nul & goto wait)", pid),
+        // robocopy exit codes below 8 all mean success
+        format!("robocopy \"{}\" \"{}\" /E /MOVE /NFL /NDL /NJH /NJS >nul", staging.display(), app_dir.display()),
+        format!("rmdir /S /Q \"{}\" 2>nul", staging.display()),
+        format!("start \"\" \"{}\"", exe.display()),
+        "del \"%~f0\"".to_string(),
+    ].join("\r\n")
+}
+
+/// Download `version`'s portable zip, stage it and start the helper that applies it on exit
+///
+/// The zip downloads into `download_dir` (private to this user); `on_progress`
+/// is called meanwhile (see `download`).
+pub fn install_update(version: &str, download_dir: &Path, on_progress: impl Fn(&DownloadProgress)) -> Result<String, String> {
+    channel::check_version(version)?;
+    let app_dir = portable_dir()
+        .ok_or_else(|| "Ship Lens was installed with the installer; update it from the app updater".to_string())?;
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to locate Ship Lens: {}", e))?;
+    let exe_name = exe.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+
+    let url = format!("{}/v{}/{}", RELEASES_URL, version, portable_asset(version));
+    fs::create_dir_all(download_dir)
+        .map_err(|e| format!("Failed to create {}: {}", download_dir.display(), e))?;
+    let archive = download_dir.join(format!("ship-lens-{}-portable.zip", version));
+    log::info!("Downloading {} to {:?}", url, archive);
+    download::download_verified(&url, &archive, on_progress)?;
+
+    let staging = app_dir.join(STAGING_DIR);
+    let files = extract(&archive, &staging);
+    let _ = fs::remove_file(&archive);
+    let files = files?;
+    // Without the executable there would be nothing to relaunch
+    if !staging.join(&exe_name).is_file() {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!("Update archive does not contain {}", exe_name.to_string_lossy()));
+    }
+
+    let helper = app_dir.join(HELPER_FILE);
+    fs::write(&helper, helper_script(std::process::id(), &app_dir, &staging, &exe))
+        .map_err(|e| format!("Failed to write {}: {}", HELPER_FILE, e))?;
+    Command::new("cmd")
+        .arg("/C")
+        .arg(&helper)
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()
+        .map_err(|e| format!("Failed to start update helper: {}", e))?;
+
+    log::info!("Staged {} files in {:?}; applying when Ship Lens exits", files, staging);
+    Ok("Update downloaded. Restart Ship Lens to finish installing it.".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_extract_update() {
+        assert_eq!(portable_asset("0.1.24"), "Ship.Lens_0.1.24_x64_portable.zip");
+
+        let dir = std::env::temp_dir().join(format!("ship-lens-portable-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let archive = dir.join("update.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        for (name, contents) in [("ship-lens.exe", "exe"), ("portable", ""), ("data/ships.csv", "ships")] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let staging = dir.join(STAGING_DIR);
+        fs::create_dir_all(&staging).unwrap();
+        fs::write(staging.join("stale.txt"), "left over").unwrap();
+        assert_eq!(extract(&archive, &staging).unwrap(), 3);
+        assert_eq!(fs::read_to_string(staging.join("data/ships.csv")).unwrap(), "ships");
+        assert!(!staging.join("stale.txt").exists());
+
+        let script = helper_script(42, &dir, &staging, &dir.join("ship-lens.exe"));
+        assert!(script.contains("PID eq 42") && script.contains("robocopy"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,200 @@
+//! Ship Lens Fleet Presets
+//!
+//! Saved attacker/target builds, kept in user order in `fleet_presets.json` in the
+//! app config dir. Presets carry free-form tags and an optional category so large
+//! org fleets can be filtered.
+
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const PRESETS_FILE: &str = "fleet_presets.json";
+
+/// A saved ship build (field names match the frontend's camelCase JSON)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FleetPreset {
+    pub id: String,
+    pub name: String,
+    pub ship_name: String,
+    pub weapons: Vec<String>,          // Selected weapon per slot
+    pub shield: String,
+    pub enabled_categories: Vec<String>,
+    pub created_at: String,            // ISO 8601 timestamp
+    pub tags: Vec<String>,
+    pub category: Option<String>,
+    /// Fields written by newer frontends, kept so a save never drops them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Filter for `query_presets` (all fields optional, case-insensitive)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PresetFilter {
+    pub tag: Option<String>,       // Preset has this tag
+    pub category: Option<String>,
+    pub search: Option<String>,    // Substring of the preset or ship name
+}
+
+impl PresetFilter {
+    pub fn matches(&self, preset: &FleetPreset) -> bool {
+        self.tag.as_ref()
+            .map_or(true, |t| preset.tags.iter().any(|tag| tag.eq_ignore_ascii_case(t)))
+            && self.category.as_ref()
+                .map_or(true, |c| preset.category.as_ref().is_some_and(|pc| pc.eq_ignore_ascii_case(c)))
+            && self.search.as_ref().map_or(true, |s| {
+                let s = s.to_lowercase();
+                preset.name.to_lowercase().contains(&s) || preset.ship_name.to_lowercase().contains(&s)
+            })
+    }
+}
+
+/// Load presets in saved order (empty if none saved)
+///
+/// Errors only when the file and every backup are unreadable, so callers never
+/// overwrite presets they failed to read.
+pub fn load(config_dir: &Path) -> Result<Vec<FleetPreset>, String> {
+    Ok(store::read_json(&config_dir.join(PRESETS_FILE))?.unwrap_or_default())
+}
+
+/// Save presets in order (atomically, keeping backups)
+pub fn save(config_dir: &Path, presets: &[FleetPreset]) -> Result<(), String> {
+    store::write_json(&config_dir.join(PRESETS_FILE), presets)
+}
+
+fn find_mut<'a>(presets: &'a mut [FleetPreset], id: &str) -> Result<&'a mut FleetPreset, String> {
+    presets.iter_mut().find(|p| p.id == id)
+        .ok_or_else(|| format!("Preset '{}' not found", id))
+}
+
+/// Replace the preset with the same id in place, or append it
+pub fn upsert(presets: &mut Vec<FleetPreset>, preset: FleetPreset) {
+    match presets.iter().position(|p| p.id == preset.id) {
+        Some(pos) => presets[pos] = preset,
+        None => presets.push(preset),
+    }
+}
+
+/// Rename a preset
+pub fn rename(presets: &mut [FleetPreset], id: &str, name: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Preset name is required".to_string());
+    }
+    find_mut(presets, id)?.name = name.to_string();
+    Ok(())
+}
+
+/// Copy a preset to a new id, inserted right after the original
+///
+/// The copy is named `name` if given, else "<original> (copy)".
+pub fn duplicate(presets: &mut Vec<FleetPreset>, id: &str, name: Option<&str>) -> Result<FleetPreset, String> {
+    let pos = presets.iter().position(|p| p.id == id)
+        .ok_or_else(|| format!("Preset '{}' not found", id))?;
+
+    let mut copy = presets[pos].clone();
+    copy.name = match name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => name.to_string(),
+        None => format!("{} (copy)", copy.name),
+    };
+    copy.created_at = store::now_iso8601();
+
+    // Same id scheme as the frontend, bumped until unique
+    let mut stamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or(0);
+    copy.id = loop {
+        let candidate = format!("preset_{}", stamp);
+        if !presets.iter().any(|p| p.id == candidate) {
+            break candidate;
+        }
+        stamp += 1;
+    };
+
+    presets.insert(pos + 1, copy.clone());
+    Ok(copy)
+}
+
+/// Reorder presets so `ids` come first, in that order
+///
+/// Presets not listed keep their relative order after the listed ones.
+pub fn reorder(presets: &mut Vec<FleetPreset>, ids: &[String]) -> Result<(), String> {
+    for (i, id) in ids.iter().enumerate() {
+        if !presets.iter().any(|p| &p.id == id) {
+            return Err(format!("Preset '{}' not found", id));
+        }
+        if ids[..i].contains(id) {
+            return Err(format!("Preset '{}' listed twice", id));
+        }
+    }
+
+    let mut remaining = std::mem::take(presets);
+    for id in ids {
+        let pos = remaining.iter().position(|p| &p.id == id).unwrap();
+        presets.push(remaining.remove(pos));
+    }
+    presets.append(&mut remaining);
+    Ok(())
+}
+
+/// Replace a preset's tags (trimmed, deduplicated case-insensitively) and category
+pub fn set_tags(presets: &mut [FleetPreset], id: &str, tags: &[String], category: Option<String>) -> Result<(), String> {
+    let preset = find_mut(presets, id)?;
+    preset.tags.clear();
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !preset.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            preset.tags.push(tag.to_string());
+        }
+    }
+    preset.category = category.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_preset(id: &str, name: &str) -> FleetPreset {
+        FleetPreset {
+            id: id.to_string(),
+            name: name.to_string(),
+            ship_name: "Gladius".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn ids(presets: &[FleetPreset]) -> Vec<&str> {
+        presets.iter().map(|p| p.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_preset_management() {
+        let mut presets = vec![make_preset("a", "Alpha"), make_preset("b", "Bravo"), make_preset("c", "Charlie")];
+
+        rename(&mut presets, "b", " Bravo Two ").unwrap();
+        assert_eq!(presets[1].name, "Bravo Two");
+        assert!(rename(&mut presets, "missing", "X").is_err());
+
+        let copy = duplicate(&mut presets, "a", None).unwrap();
+        assert_eq!(copy.name, "Alpha (copy)");
+        assert_eq!(presets[1].id, copy.id);
+
+        reorder(&mut presets, &["c".to_string(), "b".to_string()]).unwrap();
+        assert_eq!(ids(&presets), ["c", "b", "a", copy.id.as_str()]);
+        assert!(reorder(&mut presets, &["c".to_string(), "c".to_string()]).is_err());
+
+        set_tags(&mut presets, "a", &["Org".to_string(), "org".to_string(), " PvP ".to_string()], Some("Fighters".to_string())).unwrap();
+        assert_eq!(presets[2].tags, ["Org", "PvP"]);
+
+        let filter = PresetFilter { tag: Some("pvp".to_string()), category: Some("fighters".to_string()), search: None };
+        assert_eq!(presets.iter().filter(|p| filter.matches(p)).count(), 1);
+    }
+
+    #[test]
+    fn test_unknown_fields_round_trip() {
+        let json = r#"{"id":"preset_1","name":"Alpha","shipName":"Gladius","notes":"keep me"}"#;
+        let preset: FleetPreset = serde_json::from_str(json).unwrap();
+        assert_eq!(preset.extra["notes"], "keep me");
+        assert_eq!(serde_json::to_value(&preset).unwrap()["notes"], "keep me");
+    }
+}
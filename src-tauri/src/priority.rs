@@ -0,0 +1,175 @@
+//! Ship Lens Target Priority
+//!
+//! Kill order against a mixed enemy fleet. Each enemy is dueled one on one
+//! (see `duel`): my TTK on it is how long it takes to remove, and its TTK on
+//! me how fast it wears me down (1 / their TTK of my HP per second). Focusing
+//! enemies by threat per second of my time (threat / my TTK, highest first)
+//! minimizes the HP lost before the fleet is gone. Incoming damage from every
+//! ship still alive adds up linearly; I only ever shoot one target.
+
+use crate::analysis::parallel_map;
+use crate::data::GameData;
+use crate::duel::{attack, duel, Fighter, Matchup};
+use crate::presets::FleetPreset;
+use crate::tasks::TaskProgress;
+use crate::ttk::ScenarioPreset;
+use serde::{Deserialize, Serialize};
+
+/// Largest number of ships of one fleet entry
+const MAX_COUNT: u32 = 50;
+
+/// One entry of an enemy fleet
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FleetEnemy {
+    #[serde(flatten)]
+    pub fighter: Fighter,
+    /// Saved fleet preset the enemy is fitted as (see `Fighter::with_preset`)
+    pub loadout_id: Option<String>,
+    /// Ships of this build (1 if 0)
+    pub count: u32,
+}
+
+impl FleetEnemy {
+    /// One fighter per ship of this entry
+    pub fn fighters(&self, data: &GameData, presets: &[FleetPreset]) -> Result<Vec<Fighter>, String> {
+        if self.count > MAX_COUNT {
+            return Err(format!("At most {} ships per fleet entry ({} has {})", MAX_COUNT, self.fighter.ship, self.count));
+        }
+        let fighter = match &self.loadout_id {
+            Some(id) => self.fighter.clone().with_preset(data, presets, id)?,
+            None => self.fighter.clone(),
+        };
+        Ok(vec![fighter; self.count.max(1) as usize])
+    }
+}
+
+/// One enemy in kill order
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetPriority {
+    /// 1 = focus first
+    pub rank: usize,
+    pub enemy: String,
+    /// Time I need to kill it (seconds)
+    pub my_ttk: f64,
+    /// Time it needs to kill me alone (seconds)
+    pub their_ttk: f64,
+    /// Share of my HP it removes per second
+    pub threat: f64,
+    /// Seconds from the start until it dies (None if I can't kill it)
+    pub kill_at: Option<f64>,
+    /// Share of my HP lost to the whole fleet by then
+    pub hp_lost: Option<f64>,
+}
+
+/// Kill-order sheet for a fleet
+#[derive(Debug, Clone, Serialize)]
+pub struct KillOrder {
+    pub attacker: String,
+    pub targets: Vec<TargetPriority>,
+    /// Seconds to kill the whole fleet (None if some enemy can't be killed)
+    pub total_time: Option<f64>,
+    /// Share of my HP lost until the last kill (1.0 or more: I die first)
+    pub hp_lost: f64,
+    /// Whether I kill every enemy before the fleet kills me
+    pub survives: bool,
+}
+
+/// Order duel results by focus priority and play the fight out
+pub fn kill_order(attacker: String, matchups: Vec<Matchup>) -> KillOrder {
+    let threat = |m: &Matchup| if m.their_ttk.is_finite() && m.their_ttk > 0.0 { 1.0 / m.their_ttk } else { 0.0 };
+    let (mut killable, mut unkillable): (Vec<Matchup>, Vec<Matchup>) = matchups.into_iter().partition(|m| m.my_ttk.is_finite());
+    killable.sort_by(|a, b| {
+        (threat(b) / b.my_ttk).total_cmp(&(threat(a) / a.my_ttk))
+            .then_with(|| a.my_ttk.total_cmp(&b.my_ttk))
+            .then_with(|| a.opponent.cmp(&b.opponent))
+    });
+    // Enemies I can't kill go last, most dangerous first
+    unkillable.sort_by(|a, b| threat(b).total_cmp(&threat(a)).then_with(|| a.opponent.cmp(&b.opponent)));
+
+    let mut incoming: f64 = killable.iter().chain(&unkillable).map(threat).sum();
+    let (mut time, mut hp_lost) = (0.0, 0.0);
+    let mut targets = Vec::new();
+    for m in &killable {
+        time += m.my_ttk;
+        hp_lost += m.my_ttk * incoming;
+        incoming -= threat(m);
+        targets.push((m, Some(time), Some(hp_lost)));
+    }
+    targets.extend(unkillable.iter().map(|m| (m, None, None)));
+
+    let all_killed = unkillable.is_empty();
+    KillOrder {
+        attacker,
+        targets: targets.into_iter()
+            .enumerate()
+            .map(|(i, (m, kill_at, lost))| TargetPriority {
+                rank: i + 1,
+                enemy: m.opponent.clone(),
+                my_ttk: m.my_ttk,
+                their_ttk: m.their_ttk,
+                threat: threat(m),
+                kill_at,
+                hp_lost: lost,
+            })
+            .collect(),
+        total_time: all_killed.then_some(time),
+        hp_lost,
+        survives: all_killed && hp_lost < 1.0,
+    }
+}
+
+/// Duel `me` against every enemy of `fleet` and rank them by focus priority
+pub fn recommend_target_order(
+    data: &GameData,
+    me: &Fighter,
+    fleet: &[Fighter],
+    preset: Option<ScenarioPreset>,
+    progress: &TaskProgress,
+) -> Result<KillOrder, String> {
+    if fleet.is_empty() {
+        return Err("Enemy fleet is empty".to_string());
+    }
+    // Unknown ships, ports or weapons are request errors, not lost duels
+    attack(data, me, &Fighter { ship: me.ship.clone(), ..Default::default() }, preset)?;
+    for enemy in fleet {
+        data.ship(&enemy.ship).ok_or_else(|| format!("Enemy ship '{}' not found", enemy.ship))?;
+    }
+
+    let matchups = parallel_map(fleet, progress, |enemy| duel(data, me, enemy, preset))?;
+    Ok(kill_order(me.ship.clone(), matchups))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kill_order() {
+        let matchups = vec![
+            // Slow to kill but harmless
+            Matchup::new("Cargo".to_string(), 20.0, f64::INFINITY),
+            // Threat 0.05/s, 10s to kill
+            Matchup::new("Gunship".to_string(), 10.0, 20.0),
+            // Threat 0.025/s, 2s to kill: best threat per second of my time
+            Matchup::new("Fighter".to_string(), 2.0, 40.0),
+            Matchup::new("Capital".to_string(), f64::INFINITY, 100.0),
+        ];
+        let order = kill_order("Me".to_string(), matchups);
+        let names: Vec<_> = order.targets.iter().map(|t| t.enemy.as_str()).collect();
+        assert_eq!(names, ["Fighter", "Gunship", "Cargo", "Capital"]);
+
+        // Incoming 0.085/s for 2s, then 0.06/s for 10s, then 0.01/s for 20s
+        let fighter = &order.targets[0];
+        assert_eq!(fighter.kill_at, Some(2.0));
+        assert!((fighter.hp_lost.unwrap() - 0.17).abs() < 1e-9);
+        assert!((order.targets[2].hp_lost.unwrap() - 0.97).abs() < 1e-9);
+        assert_eq!(order.targets[3].kill_at, None);
+        assert_eq!(order.total_time, None);
+        assert!(!order.survives);
+
+        let enemy = FleetEnemy { fighter: Fighter { ship: "Fighter".to_string(), ..Default::default() }, count: 3, ..Default::default() };
+        assert_eq!(enemy.fighters(&GameData::default(), &[]).unwrap().len(), 3);
+        assert!(FleetEnemy { count: MAX_COUNT + 1, ..enemy }.fighters(&GameData::default(), &[]).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+//! Ship Lens Recents and Favorites
+//!
+//! Per-kind favorite and most-recently-used name lists, saved as
+//! `recents.json` in the app config dir so pickers can surface frequent picks.
+//! Ships and weapons are tracked by display name, shields by internal name.
+
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const RECENTS_FILE: &str = "recents.json";
+
+/// Names kept per most-recently-used list
+pub const MAX_RECENTS: usize = 20;
+
+/// Kind of item a picker selects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Ship,
+    Weapon,
+    Shield,
+}
+
+/// Favorites (in the order they were marked) and recents (newest first) per kind
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Recents {
+    pub favorites: BTreeMap<ItemKind, Vec<String>>,
+    pub recent: BTreeMap<ItemKind, Vec<String>>,
+}
+
+impl Recents {
+    /// Mark or unmark a favorite (no-op if already in that state)
+    pub fn set_favorite(&mut self, kind: ItemKind, name: &str, favorite: bool) {
+        let list = self.favorites.entry(kind).or_default();
+        let existing = list.iter().position(|n| n == name);
+        match (favorite, existing) {
+            (true, None) => list.push(name.to_string()),
+            (false, Some(pos)) => {
+                list.remove(pos);
+            }
+            _ => {}
+        }
+    }
+
+    /// Move names to the front of a kind's recent list (first name ends up newest)
+    pub fn touch<'a>(&mut self, kind: ItemKind, names: impl IntoIterator<Item = &'a str>) {
+        let names: Vec<&str> = names.into_iter().filter(|n| !n.is_empty()).collect();
+        let list = self.recent.entry(kind).or_default();
+        for name in names.into_iter().rev() {
+            list.retain(|n| n != name);
+            list.insert(0, name.to_string());
+        }
+        list.truncate(MAX_RECENTS);
+    }
+}
+
+/// Load recents and favorites (empty if none saved)
+pub fn load(config_dir: &Path) -> Result<Recents, String> {
+    Ok(store::read_json(&config_dir.join(RECENTS_FILE))?.unwrap_or_default())
+}
+
+/// Save recents and favorites (atomically, keeping backups)
+pub fn save(config_dir: &Path, recents: &Recents) -> Result<(), String> {
+    store::write_json(&config_dir.join(RECENTS_FILE), recents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recents_and_favorites() {
+        let mut recents = Recents::default();
+
+        recents.touch(ItemKind::Ship, ["Gladius", "Arrow"]);
+        recents.touch(ItemKind::Ship, ["Hornet F7C", "Gladius"]);
+        assert_eq!(recents.recent[&ItemKind::Ship], ["Hornet F7C", "Gladius", "Arrow"]);
+
+        let weapons: Vec<String> = (0..MAX_RECENTS + 5).map(|i| format!("W{}", i)).collect();
+        recents.touch(ItemKind::Weapon, weapons.iter().map(String::as_str));
+        assert_eq!(recents.recent[&ItemKind::Weapon].len(), MAX_RECENTS);
+        assert_eq!(recents.recent[&ItemKind::Weapon][0], "W0");
+
+        recents.set_favorite(ItemKind::Shield, "SHLD_GODI_S01_FR66", true);
+        recents.set_favorite(ItemKind::Shield, "SHLD_GODI_S01_FR66", true);
+        assert_eq!(recents.favorites[&ItemKind::Shield].len(), 1);
+        recents.set_favorite(ItemKind::Shield, "SHLD_GODI_S01_FR66", false);
+        assert!(recents.favorites[&ItemKind::Shield].is_empty());
+
+        let json = serde_json::to_string(&recents).unwrap();
+        assert!(json.contains("\"ship\""));
+        assert_eq!(serde_json::from_str::<Recents>(&json).unwrap(), recents);
+    }
+}
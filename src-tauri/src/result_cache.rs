@@ -0,0 +1,98 @@
+//! In-memory memoization of calculation results
+//!
+//! Requests are keyed by a hash of their arguments as canonical JSON (object keys
+//! sorted, so map fields hash the same whatever their iteration order) plus the
+//! generation of the game data they ran against, so nudging a slider back to a previous
+//! value answers instantly. Least recently used entries are evicted first.
+
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Results kept before the least recently used one is evicted
+pub const RESULT_CACHE_CAPACITY: usize = 256;
+
+/// Hash of a request's arguments within `scope` (e.g. the data generation)
+///
+/// None if the request doesn't serialize; such requests aren't cached.
+pub fn request_key<T: Serialize>(scope: u64, request: &T) -> Option<u64> {
+    // `Value` objects are sorted maps, unlike the request's own `HashMap`s
+    let canonical = serde_json::to_value(request).ok()?.to_string();
+    let mut hasher = DefaultHasher::new();
+    scope.hash(&mut hasher);
+    canonical.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Least-recently-used cache of results by request key
+pub struct ResultCache<V> {
+    capacity: usize,
+    clock: u64,
+    entries: HashMap<u64, (V, u64)>,
+}
+
+impl<V: Clone> ResultCache<V> {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, clock: 0, entries: HashMap::new() }
+    }
+
+    /// Cached result for `key`, marking it most recently used
+    pub fn get(&mut self, key: u64) -> Option<V> {
+        self.clock += 1;
+        let (value, used) = self.entries.get_mut(&key)?;
+        *used = self.clock;
+        Some(value.clone())
+    }
+
+    /// Store a result, evicting the least recently used one when full
+    pub fn insert(&mut self, key: u64, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            if let Some(&oldest) = self.entries.iter().min_by_key(|(_, (_, used))| *used).map(|(k, _)| k) {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.clock += 1;
+        self.entries.insert(key, (value, self.clock));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut cache = ResultCache::new(2);
+        let key = |scope: u64, request: (&str, f64)| request_key(scope, &request).unwrap();
+        let (a, b, c) = (key(0, ("a", 1.0)), key(0, ("b", 1.0)), key(0, ("a", 1.5)));
+        assert_ne!(a, c);
+        assert_ne!(a, key(1, ("a", 1.0)));
+
+        // Map iteration order doesn't change the key
+        let ports: Vec<(String, String)> = (0..16).map(|i| (format!("hardpoint_{}", i), "CF-337".to_string())).collect();
+        let forward: HashMap<String, String> = ports.iter().cloned().collect();
+        let backward: HashMap<String, String> = ports.into_iter().rev().collect();
+        assert_eq!(request_key(0, &forward), request_key(0, &backward));
+        assert!(request_key(0, &forward).is_some());
+
+        cache.insert(a, 1);
+        cache.insert(b, 2);
+        assert_eq!(cache.get(a), Some(1));
+        // b is now least recently used
+        cache.insert(c, 3);
+        assert_eq!(cache.get(b), None);
+        assert_eq!(cache.get(a), Some(1));
+        assert_eq!(cache.get(c), Some(3));
+
+        cache.clear();
+        assert_eq!(cache.get(a), None);
+    }
+}
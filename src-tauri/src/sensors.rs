@@ -0,0 +1,152 @@
+//! Ship Lens Sensor Model
+//!
+//! How far out an attacker's radar picks up a target, for planning ambushes
+//! next to TTK. The target's emission grows with its size class and with its
+//! loadout's EM/IR load (see `analysis::estimate_signatures`): a ship firing
+//! at full power plant output emits twice its idle signature. Detection range
+//! follows the square root of the emission, as for any inverse-square falloff.
+//! Radar range comes from the ship metadata, falling back to a typical value
+//! for the attacker's size class.
+
+use crate::analysis::SignatureEstimate;
+use crate::data::Ship;
+use crate::ttk::ValidationErrors;
+use serde::Serialize;
+
+/// Radar ranges assumed per size class when the metadata has none (meters)
+const SIZE_CLASS_RADAR: [(&str, f64); 4] = [("small", 8000.0), ("medium", 12000.0), ("large", 18000.0), ("capital", 25000.0)];
+
+/// Radar range for ships of unknown size class (meters)
+const DEFAULT_RADAR: f64 = 10000.0;
+
+/// Idle emission per size class, relative to a small ship
+const SIZE_CLASS_EMISSION: [(&str, f64); 4] = [("small", 1.0), ("medium", 2.0), ("large", 4.0), ("capital", 8.0)];
+
+/// Longest approach distance accepted (meters)
+const MAX_DISTANCE: f64 = 1_000_000.0;
+
+/// Where a radar range came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RadarSource {
+    Metadata,
+    SizeClass,
+    Default,
+}
+
+/// How far out and how soon the attacker detects the target
+#[derive(Debug, Clone, Serialize)]
+pub struct Detection {
+    pub attacker: String,
+    pub target: String,
+    pub radar_range: f64,
+    pub radar_source: RadarSource,
+    /// Target emission relative to an idle small ship
+    pub emission: f64,
+    /// Whether the loadout's EM or IR load drives the emission
+    pub dominant_signature: String,
+    /// Range at which the target shows up on the attacker's radar (meters)
+    pub detection_range: f64,
+    /// Seconds until detection when closing from `distance` at `closing_speed`
+    /// (0 if already detected, None without both inputs or if never reached)
+    pub time_to_detect: Option<f64>,
+}
+
+/// Radar range of `ship` and where it came from
+pub fn radar_range(ship: &Ship) -> (f64, RadarSource) {
+    if ship.sensor_range > 0.0 {
+        return (ship.sensor_range, RadarSource::Metadata);
+    }
+    SIZE_CLASS_RADAR.iter()
+        .find(|(class, _)| ship.size_class.eq_ignore_ascii_case(class))
+        .map_or((DEFAULT_RADAR, RadarSource::Default), |(_, range)| (*range, RadarSource::SizeClass))
+}
+
+/// Idle emission of `ship` relative to a small ship (unknown classes count as small)
+fn idle_emission(ship: &Ship) -> f64 {
+    SIZE_CLASS_EMISSION.iter()
+        .find(|(class, _)| ship.size_class.eq_ignore_ascii_case(class))
+        .map_or(1.0, |(_, emission)| *emission)
+}
+
+/// Detection of `target` flying `signatures` by `attacker`
+///
+/// `distance` and `closing_speed` (m/s) give the time until detection.
+pub fn detection(
+    attacker: &Ship,
+    target: &Ship,
+    signatures: &SignatureEstimate,
+    distance: Option<f64>,
+    closing_speed: Option<f64>,
+) -> Result<Detection, String> {
+    let mut errors = ValidationErrors::default();
+    if let Some(distance) = distance {
+        errors.check_range("distance", distance, 0.0, MAX_DISTANCE);
+    }
+    if let Some(speed) = closing_speed {
+        errors.check_range("closing_speed", speed, 0.0, MAX_DISTANCE);
+    }
+    errors.into_result().map_err(|e| e.to_string())?;
+
+    let (radar_range, radar_source) = radar_range(attacker);
+    let em_load = signatures.em_load.unwrap_or(0.0);
+    let ir_load = signatures.ir_load.unwrap_or(0.0);
+    let (load, dominant_signature) = if ir_load > em_load { (ir_load, "ir") } else { (em_load, "em") };
+    let emission = idle_emission(target) * (1.0 + load);
+    let detection_range = radar_range * emission.sqrt();
+
+    let time_to_detect = match (distance, closing_speed) {
+        (Some(distance), _) if distance <= detection_range => Some(0.0),
+        (Some(distance), Some(speed)) if speed > 0.0 => Some((distance - detection_range) / speed),
+        _ => None,
+    };
+
+    Ok(Detection {
+        attacker: attacker.display_name.clone(),
+        target: target.display_name.clone(),
+        radar_range,
+        radar_source,
+        emission,
+        dominant_signature: dominant_signature.to_string(),
+        detection_range,
+        time_to_detect,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detection() {
+        let attacker = Ship { display_name: "Hunter".to_string(), size_class: "small".to_string(), ..Default::default() };
+        let target = Ship { display_name: "Prey".to_string(), size_class: "large".to_string(), ..Default::default() };
+        let idle = SignatureEstimate {
+            total_dps: 0.0,
+            em_signature: 0.0,
+            ir_signature: 0.0,
+            em_load: None,
+            ir_load: None,
+            dps_per_em: None,
+            dps_per_ir: None,
+            unknown_weapons: vec![],
+        };
+
+        // Large target (4x emission) doubles the small class radar range
+        let idle_detection = detection(&attacker, &target, &idle, Some(20000.0), Some(100.0)).unwrap();
+        assert_eq!((idle_detection.radar_range, idle_detection.radar_source), (8000.0, RadarSource::SizeClass));
+        assert_eq!(idle_detection.detection_range, 16000.0);
+        assert_eq!(idle_detection.time_to_detect, Some(40.0));
+
+        // Cooler running at 125%: IR drives the emission up to 9x
+        let hot = SignatureEstimate { em_load: Some(0.5), ir_load: Some(1.25), ..idle.clone() };
+        let hot_detection = detection(&attacker, &target, &hot, Some(20000.0), None).unwrap();
+        assert_eq!(hot_detection.dominant_signature, "ir");
+        assert_eq!(hot_detection.detection_range, 24000.0);
+        assert_eq!(hot_detection.time_to_detect, Some(0.0));
+
+        let scout = Ship { sensor_range: 15000.0, ..attacker.clone() };
+        assert_eq!(radar_range(&scout), (15000.0, RadarSource::Metadata));
+        assert!(detection(&attacker, &target, &idle, Some(-1.0), None).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! Ship Lens Simulation Series
+//!
+//! Samples a TTK result into plain arrays (time, shield/armor/hull HP, attacker
+//! heat) for charting. Curves are piecewise linear between the result's phase
+//! boundaries and timeline events, so they follow the same model as the result
+//! without re-running it. The data has no magazine sizes, so ammo is not tracked.
+
+use crate::ttk::{EngagementEventKind, TTKResult};
+use serde::Serialize;
+
+/// Most samples in one series
+pub const MAX_SAMPLES: usize = 5000;
+
+/// Samples used when no resolution is given
+const DEFAULT_SAMPLES: f64 = 200.0;
+
+/// Time span plotted when the target is never destroyed (seconds)
+const OPEN_ENDED_SPAN: f64 = 60.0;
+
+/// Parallel arrays, one entry per sample
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SimulationSeries {
+    /// Seconds from the first shot
+    pub t: Vec<f64>,
+    /// Remaining HP of all shield generators not yet broken
+    pub shield_hp: Vec<f64>,
+    pub armor_hp: Vec<f64>,
+    /// Hull pool including the targeted thrusters and components
+    pub hull_hp: Vec<f64>,
+    /// Heat the attacker's guns have generated (no cooling modeled)
+    pub attacker_heat: Vec<f64>,
+}
+
+/// Linear interpolation through `(time, value)` points, holding the end values
+fn interpolate(points: &[(f64, f64)], t: f64) -> f64 {
+    let Some(&(first_t, first_value)) = points.first() else { return 0.0 };
+    if t <= first_t {
+        return first_value;
+    }
+    for pair in points.windows(2) {
+        let ((t0, v0), (t1, v1)) = (pair[0], pair[1]);
+        if t <= t1 {
+            return if t1 > t0 { v0 + (v1 - v0) * (t - t0) / (t1 - t0) } else { v1 };
+        }
+    }
+    points.last().map_or(0.0, |&(_, value)| value)
+}
+
+/// Sample `result` every `resolution` seconds (about 200 samples if None)
+///
+/// `armor_hp` and `hull_hp` are the zone-scaled pools the result was computed
+/// against; `heat_per_second` is the attacker's heat output while firing.
+pub fn build(result: &TTKResult, armor_hp: f64, hull_hp: f64, heat_per_second: f64, resolution: Option<f64>) -> SimulationSeries {
+    let event = |kind: EngagementEventKind| result.events.iter().find(|e| e.kind == kind).map(|e| e.time);
+    let shields_down = event(EngagementEventKind::ShieldsDown);
+    let armor_depleted = event(EngagementEventKind::ArmorDepleted);
+    let destroyed = result.total_ttk.is_finite().then_some(result.total_ttk);
+
+    let span = destroyed
+        .or_else(|| result.events.iter().map(|e| e.time).reduce(f64::max))
+        .filter(|span| *span > 0.0)
+        .unwrap_or(OPEN_ENDED_SPAN);
+    let step = resolution.filter(|r| r.is_finite() && *r > 0.0)
+        .unwrap_or(span / DEFAULT_SAMPLES)
+        .max(span / MAX_SAMPLES as f64);
+
+    // Armor takes passthrough while shields are up, then the full rate until depleted
+    let mut armor = vec![(0.0, armor_hp)];
+    if let Some(down) = shields_down.filter(|down| armor_depleted.map_or(true, |depleted| depleted > *down)) {
+        armor.push((down, (armor_hp - result.armor_damage_during_shields).max(0.0)));
+    }
+    if let Some(depleted) = armor_depleted {
+        armor.push((depleted, 0.0));
+    }
+
+    // Hull drops once the armor (or, without armor, the shields) is gone
+    let mut hull = vec![(0.0, hull_hp)];
+    if let Some(end) = destroyed {
+        let start = armor_depleted.or(shields_down).unwrap_or(0.0).min(end);
+        hull.push((start, hull_hp));
+        hull.push((end, 0.0));
+    }
+
+    let shield_at = |t: f64| -> f64 {
+        result.shield_phases.iter()
+            .map(|phase| {
+                if t <= phase.start || phase.end.is_infinite() {
+                    phase.hp
+                } else if t >= phase.end {
+                    0.0
+                } else {
+                    phase.hp * (phase.end - t) / (phase.end - phase.start)
+                }
+            })
+            .sum()
+    };
+
+    let mut series = SimulationSeries::default();
+    let samples = ((span / step).ceil() as usize).min(MAX_SAMPLES);
+    for i in 0..=samples {
+        let t = (i as f64 * step).min(span);
+        series.t.push(t);
+        series.shield_hp.push(shield_at(t));
+        series.armor_hp.push(interpolate(&armor, t));
+        series.hull_hp.push(interpolate(&hull, t));
+        series.attacker_heat.push(heat_per_second * t.min(destroyed.unwrap_or(f64::INFINITY)));
+    }
+    series
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ttk::{EngagementEvent, ShieldPhaseBoundary};
+
+    #[test]
+    fn test_simulation_series() {
+        let event = |time: f64, kind: EngagementEventKind| EngagementEvent { time, kind, phase: None, instance: None, description: String::new() };
+        // Shields 0-10s, armor (half already taken by passthrough) 10-15s, hull 15-20s
+        let result = TTKResult {
+            total_ttk: 20.0,
+            armor_damage_during_shields: 500.0,
+            shield_phases: vec![ShieldPhaseBoundary { phase: 1, generators: 2, hp: 8000.0, regen: 0.0, start: 0.0, end: 10.0 }],
+            events: vec![
+                event(10.0, EngagementEventKind::ShieldsDown),
+                event(15.0, EngagementEventKind::ArmorDepleted),
+                event(20.0, EngagementEventKind::TargetDestroyed),
+            ],
+            ..Default::default()
+        };
+
+        let series = build(&result, 1000.0, 4000.0, 2.0, Some(5.0));
+        assert_eq!(series.t, [0.0, 5.0, 10.0, 15.0, 20.0]);
+        assert_eq!(series.shield_hp, [8000.0, 4000.0, 0.0, 0.0, 0.0]);
+        assert_eq!(series.armor_hp, [1000.0, 750.0, 500.0, 0.0, 0.0]);
+        assert_eq!(series.hull_hp, [4000.0, 4000.0, 4000.0, 4000.0, 0.0]);
+        assert_eq!(series.attacker_heat, [0.0, 10.0, 20.0, 30.0, 40.0]);
+
+        // Unbreakable shields: flat curves over the default span
+        let stalemate = TTKResult {
+            total_ttk: f64::INFINITY,
+            shield_phases: vec![ShieldPhaseBoundary { phase: 1, generators: 2, hp: 8000.0, regen: 900.0, start: 0.0, end: f64::INFINITY }],
+            ..Default::default()
+        };
+        let series = build(&stalemate, 1000.0, 4000.0, 0.0, None);
+        assert_eq!(*series.t.last().unwrap(), OPEN_ENDED_SPAN);
+        assert!(series.shield_hp.iter().all(|hp| *hp == 8000.0));
+        assert!(series.hull_hp.iter().all(|hp| *hp == 4000.0));
+    }
+}
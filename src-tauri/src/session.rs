@@ -0,0 +1,130 @@
+//! Ship Lens Scenario Session
+//!
+//! The scenario being worked on (attacker, loadout, target and scenario
+//! settings, as a `calculate_ttk_for_ships` request) with undo/redo history, so
+//! comparison workflows can step back and forth between edits. Kept in memory
+//! only; a restart starts a fresh session.
+
+use crate::ShipTtkRequest;
+use serde::Serialize;
+
+/// Changes kept for undo
+pub const MAX_UNDO: usize = 100;
+
+/// One recorded scenario state
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SessionStep {
+    /// What the change did, e.g. "Swap nose gun" (may be empty)
+    pub label: String,
+    pub request: ShipTtkRequest,
+}
+
+/// Current scenario with undo and redo stacks (most recent last)
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    current: SessionStep,
+    undo: Vec<SessionStep>,
+    redo: Vec<SessionStep>,
+}
+
+/// Session as shown to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionView {
+    pub state: ShipTtkRequest,
+    pub label: String,
+    /// Label of the change `session_undo` reverts (None if nothing to undo)
+    pub undo_label: Option<String>,
+    /// Label of the change `session_redo` reapplies (None if nothing to redo)
+    pub redo_label: Option<String>,
+    pub undo_depth: usize,
+    pub redo_depth: usize,
+}
+
+impl Session {
+    /// Make `request` the current state; returns false if nothing changed
+    ///
+    /// A new change discards the redo stack.
+    pub fn record(&mut self, label: String, request: ShipTtkRequest) -> bool {
+        let unchanged = serde_json::to_value(&request).ok() == serde_json::to_value(&self.current.request).ok();
+        if unchanged {
+            return false;
+        }
+        let previous = std::mem::replace(&mut self.current, SessionStep { label, request });
+        self.undo.push(previous);
+        if self.undo.len() > MAX_UNDO {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+        true
+    }
+
+    /// Step back one change; returns false if there is none
+    pub fn undo(&mut self) -> bool {
+        let Some(previous) = self.undo.pop() else {
+            return false;
+        };
+        self.redo.push(std::mem::replace(&mut self.current, previous));
+        true
+    }
+
+    /// Reapply the last undone change; returns false if there is none
+    pub fn redo(&mut self) -> bool {
+        let Some(next) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push(std::mem::replace(&mut self.current, next));
+        true
+    }
+
+    pub fn view(&self) -> SessionView {
+        SessionView {
+            state: self.current.request.clone(),
+            label: self.current.label.clone(),
+            // Undo reverts the current step, redo reapplies the top of the redo stack
+            undo_label: (!self.undo.is_empty()).then(|| self.current.label.clone()),
+            redo_label: self.redo.last().map(|s| s.label.clone()),
+            undo_depth: self.undo.len(),
+            redo_depth: self.redo.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(attacker: &str, target: &str) -> ShipTtkRequest {
+        ShipTtkRequest { attacker: attacker.to_string(), target: target.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn test_session_undo_redo() {
+        let mut session = Session::default();
+        assert!(!session.undo());
+
+        assert!(session.record("Pick attacker".to_string(), request("Aegis Gladius", "")));
+        assert!(session.record("Pick target".to_string(), request("Aegis Gladius", "Anvil Arrow")));
+        assert!(!session.record("Same again".to_string(), request("Aegis Gladius", "Anvil Arrow")));
+        assert!(session.record("Swap target".to_string(), request("Aegis Gladius", "Drake Buccaneer")));
+
+        assert!(session.undo());
+        let view = session.view();
+        assert_eq!(view.state.target, "Anvil Arrow");
+        assert_eq!(view.undo_label.as_deref(), Some("Pick target"));
+        assert_eq!(view.redo_label.as_deref(), Some("Swap target"));
+
+        assert!(session.redo());
+        assert_eq!(session.view().state.target, "Drake Buccaneer");
+        assert!(!session.redo());
+
+        // A new change after undoing drops the redo stack
+        session.undo();
+        session.record("Other target".to_string(), request("Aegis Gladius", "Anvil Hornet F7C"));
+        assert_eq!((session.view().undo_depth, session.view().redo_depth), (3, 0));
+
+        for i in 0..MAX_UNDO + 5 {
+            session.record(String::new(), request("Aegis Gladius", &i.to_string()));
+        }
+        assert_eq!(session.view().undo_depth, MAX_UNDO);
+    }
+}
@@ -0,0 +1,203 @@
+//! Crash-safe storage for user config files
+//!
+//! Writes go to `<name>.tmp`, are synced, and renamed over the target, so a crash
+//! leaves either the old or the new file, never half of one. The version being
+//! replaced is kept as `<name>.bak1` (newest) .. `<name>.bak{BACKUP_COUNT}`.
+//! Reads that fail to parse fall back to the newest backup that does, move the
+//! broken file aside as `<name>.corrupt` and restore the backup in its place.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Previous versions kept per file
+pub const BACKUP_COUNT: usize = 3;
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    path.with_file_name(format!("{}.{}", name, suffix))
+}
+
+fn backup_path(path: &Path, generation: usize) -> PathBuf {
+    sibling(path, &format!("bak{}", generation))
+}
+
+/// Whether a file name is one of the temp/backup/corrupt siblings this module leaves
+pub fn is_artifact(name: &str) -> bool {
+    let Some((_, suffix)) = name.rsplit_once('.') else { return false };
+    suffix == "tmp" || suffix == "corrupt"
+        || suffix.strip_prefix("bak").is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Write via a synced temp file renamed over `path` (no backup rotation)
+pub fn replace(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = sibling(path, "tmp");
+    {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp, path)
+}
+
+/// Atomically replace `path`, keeping the current version as the newest backup
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    if path.exists() {
+        for generation in (1..BACKUP_COUNT).rev() {
+            let older = backup_path(path, generation);
+            if older.exists() {
+                fs::rename(&older, backup_path(path, generation + 1))?;
+            }
+        }
+        // Copy rather than move so `path` exists until the rename below
+        fs::copy(path, backup_path(path, 1))?;
+    }
+    replace(path, contents)
+}
+
+/// Read and parse `path`, recovering from the newest parseable backup
+///
+/// `Ok(None)` when neither the file nor any backup exists; `Err` with the
+/// first failure when nothing parses.
+pub fn read_recovering<T>(path: &Path, parse: impl Fn(&str) -> Result<T, String>) -> Result<Option<T>, String> {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut first_error = None;
+
+    for generation in 0..=BACKUP_COUNT {
+        let candidate = if generation == 0 { path.to_path_buf() } else { backup_path(path, generation) };
+        let contents = match fs::read_to_string(&candidate) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                first_error.get_or_insert(format!("Failed to read {}: {}", name, e));
+                continue;
+            }
+        };
+        match parse(&contents) {
+            Ok(value) => {
+                if generation > 0 {
+                    log::warn!("{} was unreadable; restored backup {}", name, generation);
+                    if path.exists() {
+                        let _ = fs::rename(path, sibling(path, "corrupt"));
+                    }
+                    let _ = replace(path, &contents);
+                }
+                return Ok(Some(value));
+            }
+            Err(e) => {
+                first_error.get_or_insert(format!("Failed to parse {}: {}", name, e));
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(None),
+    }
+}
+
+/// `read_recovering` for a JSON file
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, String> {
+    read_recovering(path, |json| serde_json::from_str(json).map_err(|e| e.to_string()))
+}
+
+/// Serialize `value` as pretty JSON and `write_atomic` it
+pub fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<(), String> {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+    write_atomic(path, &json).map_err(|e| format!("Failed to write {}: {}", name, e))
+}
+
+/// Current time as an ISO 8601 UTC timestamp (`2025-01-13T18:04:05Z`)
+pub fn now_iso8601() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    iso8601(secs)
+}
+
+/// Format seconds since the Unix epoch as an ISO 8601 UTC timestamp
+fn iso8601(secs: u64) -> String {
+    // Civil-from-days (Howard Hinnant), valid for any date after 1970
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, rem / 3600, rem % 3600 / 60, rem % 60)
+}
+
+/// Seconds since the Unix epoch from an ISO 8601 UTC timestamp (`2025-12-31T20:31:45.123Z`)
+pub fn parse_iso8601(timestamp: &str) -> Option<f64> {
+    let (date, time) = timestamp.trim().trim_end_matches('Z').split_once('T')?;
+    let mut date_parts = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (date_parts.next()??, date_parts.next()??, date_parts.next()??);
+    let mut time_parts = time.splitn(3, ':');
+    let hours: i64 = time_parts.next()?.parse().ok()?;
+    let minutes: i64 = time_parts.next()?.parse().ok()?;
+    let seconds: f64 = time_parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Days-from-civil (Howard Hinnant), the inverse of `iso8601`
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Some((days * 86_400 + hours * 3600 + minutes * 60) as f64 + seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_rotation_and_recovery() {
+        let dir = std::env::temp_dir().join(format!("ship-lens-store-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("presets.json");
+        let parse = |s: &str| s.trim().parse::<i32>().map_err(|e| e.to_string());
+
+        assert_eq!(read_recovering(&path, parse), Ok(None));
+        for version in 1..=5 {
+            write_atomic(&path, &version.to_string()).unwrap();
+        }
+        assert_eq!(fs::read_to_string(backup_path(&path, 1)).unwrap(), "4");
+        assert_eq!(fs::read_to_string(backup_path(&path, BACKUP_COUNT)).unwrap(), "2");
+        assert!(!sibling(&path, "tmp").exists());
+        assert!(is_artifact("presets.json.bak2") && is_artifact("presets.json.tmp"));
+        assert!(!is_artifact("presets.json") && !is_artifact("notes.bak"));
+
+        // A torn write is recovered from the newest backup and restored in place
+        fs::write(&path, "{trunc").unwrap();
+        assert_eq!(read_recovering(&path, parse), Ok(Some(4)));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "4");
+        assert_eq!(fs::read_to_string(sibling(&path, "corrupt")).unwrap(), "{trunc");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_iso8601() {
+        assert_eq!(iso8601(0), "1970-01-01T00:00:00Z");
+        assert_eq!(iso8601(1_736_791_445), "2025-01-13T18:04:05Z");
+        assert_eq!(parse_iso8601("2025-01-13T18:04:05Z"), Some(1_736_791_445.0));
+        assert_eq!(parse_iso8601("2024-02-29T00:00:00.250Z"), Some(1_709_164_800.25));
+        assert_eq!(parse_iso8601("not a date"), None);
+    }
+}
@@ -0,0 +1,274 @@
+//! Ship Lens Stream Endpoint
+//!
+//! A tiny local HTTP server for OBS browser sources. It serves a transparent
+//! TTK readout page (`/`), the current readout as JSON (`/readout.json`) and a
+//! Server-Sent Events stream (`/events`) that pushes every new readout, so the
+//! page updates live as calculations run. It listens on localhost only and is
+//! off unless enabled; the setting is saved as `stream.json` in the app config dir.
+
+use crate::locks::MutexExt;
+use crate::overlay::OverlayReadout;
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const STREAM_FILE: &str = "stream.json";
+
+/// Port used unless another is saved
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// Idle time before a keep-alive comment is sent on an event stream
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// How often the accept loop checks for a stop request
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time a client gets to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="UTF-8">
+<style>
+  body { margin: 0; background: transparent; color: #e8ecf0; font-family: 'Rajdhani', sans-serif; text-shadow: 0 2px 4px #000; }
+  #matchup { font-size: 22px; }
+  #ttk { font-family: 'Orbitron', sans-serif; font-size: 56px; font-weight: 700; color: #4a9eff; }
+  #phases { font-size: 20px; color: #9ca3af; }
+</style>
+</head>
+<body>
+<div id="matchup">Waiting for a calculation...</div>
+<div><span id="ttk">--</span> <span>SEC</span></div>
+<div id="phases"></div>
+<script>
+  const s = (t) => (t === null || !isFinite(t) || t < 0) ? "∞" : t.toFixed(1);
+  new EventSource("/events").onmessage = (e) => {
+    const r = JSON.parse(e.data);
+    document.getElementById("matchup").textContent = (r.attacker ? r.attacker + " → " : "TTK vs ") + r.target;
+    document.getElementById("ttk").textContent = s(r.summary.total_ttk);
+    document.getElementById("phases").textContent =
+      "SHD " + s(r.summary.shield_time) + "s · ARM " + s(r.summary.armor_time) + "s · HULL " + s(r.summary.hull_time) + "s";
+  };
+</script>
+</body>
+</html>
+"#;
+
+/// Saved endpoint settings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StreamSettings {
+    /// Start the server at launch
+    pub enabled: bool,
+    /// Listening port (`DEFAULT_PORT` if unset)
+    pub port: Option<u16>,
+}
+
+impl StreamSettings {
+    pub fn port(&self) -> u16 {
+        self.port.unwrap_or(DEFAULT_PORT)
+    }
+}
+
+/// Load the saved settings (disabled if none saved)
+pub fn load(config_dir: &Path) -> StreamSettings {
+    store::read_json(&config_dir.join(STREAM_FILE)).ok().flatten().unwrap_or_default()
+}
+
+/// Save the settings (atomically, keeping backups)
+pub fn save(config_dir: &Path, settings: &StreamSettings) -> Result<(), String> {
+    store::write_json(&config_dir.join(STREAM_FILE), settings)
+}
+
+/// Whether the server is running, and where
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StreamStatus {
+    pub running: bool,
+    pub port: Option<u16>,
+    /// Browser source URL
+    pub url: Option<String>,
+}
+
+/// Latest readout and the event streams waiting for the next one
+#[derive(Default)]
+struct Hub {
+    latest: Mutex<Option<String>>,
+    subscribers: Mutex<Vec<Sender<String>>>,
+}
+
+impl Hub {
+    fn publish(&self, json: String) {
+        *self.latest.lock_or_recover() = Some(json.clone());
+        // Streams whose connection closed have dropped their receiver
+        self.subscribers.lock_or_recover().retain(|tx| tx.send(json.clone()).is_ok());
+    }
+
+    fn subscribe(&self) -> (Option<String>, Receiver<String>) {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers.lock_or_recover().push(tx);
+        (self.latest.lock_or_recover().clone(), rx)
+    }
+}
+
+/// The stream endpoint (kept in `AppState`)
+#[derive(Default)]
+pub struct StreamServer {
+    hub: Arc<Hub>,
+    /// Port and stop flag of the running server
+    running: Mutex<Option<(u16, Arc<AtomicBool>)>>,
+}
+
+impl StreamServer {
+    /// Push a readout to the page (kept for pages that connect later)
+    pub fn publish(&self, readout: &OverlayReadout) {
+        if let Ok(json) = serde_json::to_string(readout) {
+            self.hub.publish(json);
+        }
+    }
+
+    pub fn status(&self) -> StreamStatus {
+        let port = self.running.lock_or_recover().as_ref().map(|(port, _)| *port);
+        StreamStatus {
+            running: port.is_some(),
+            port,
+            url: port.map(|port| format!("http://127.0.0.1:{}/", port)),
+        }
+    }
+
+    /// Start listening on localhost (moving to `port` if already running on another)
+    pub fn start(&self, port: u16) -> Result<StreamStatus, String> {
+        let mut running = self.running.lock_or_recover();
+        if running.as_ref().map_or(true, |(current, _)| *current != port) {
+            let listener = TcpListener::bind(("127.0.0.1", port))
+                .map_err(|e| format!("Failed to listen on port {}: {}", port, e))?;
+            listener.set_nonblocking(true)
+                .map_err(|e| format!("Failed to configure stream server: {}", e))?;
+
+            let stop = Arc::new(AtomicBool::new(false));
+            if let Some((_, previous)) = running.replace((port, stop.clone())) {
+                previous.store(true, Ordering::Relaxed);
+            }
+            let hub = self.hub.clone();
+            std::thread::spawn(move || accept_loop(listener, hub, stop));
+        }
+        drop(running);
+        Ok(self.status())
+    }
+
+    /// Stop listening (open event streams end with their next write)
+    pub fn stop(&self) {
+        if let Some((_, stop)) = self.running.lock_or_recover().take() {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+fn accept_loop(listener: TcpListener, hub: Arc<Hub>, stop: Arc<AtomicBool>) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let hub = hub.clone();
+                let stop = stop.clone();
+                std::thread::spawn(move || {
+                    if let Err(e) = handle(stream, &hub, &stop) {
+                        log::info!("Stream endpoint connection ended: {}", e);
+                    }
+                });
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => std::thread::sleep(POLL_INTERVAL),
+            Err(e) => log::warn!("Stream endpoint accept failed: {}", e),
+        }
+    }
+}
+
+/// Path of a `GET <path> HTTP/1.x` request line (query string dropped)
+fn request_path(request_line: &str) -> Option<&str> {
+    let mut parts = request_line.split_whitespace();
+    if parts.next()? != "GET" {
+        return None;
+    }
+    parts.next()?.split('?').next()
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status, content_type, body.len(), body
+    )
+}
+
+fn handle(mut stream: TcpStream, hub: &Hub, stop: &AtomicBool) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Headers are not needed; read up to the blank line so the client sees a clean close
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    match request_path(&request_line) {
+        Some("/") => respond(&mut stream, "200 OK", "text/html; charset=utf-8", PAGE),
+        Some("/readout.json") => {
+            let latest = hub.latest.lock_or_recover().clone().unwrap_or_else(|| "null".to_string());
+            respond(&mut stream, "200 OK", "application/json", &latest)
+        }
+        Some("/events") => {
+            // Same-origin only (no CORS header): other pages in the browser can't read the readouts
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\n\r\n")?;
+            let (latest, updates) = hub.subscribe();
+            if let Some(json) = latest {
+                write!(stream, "data: {}\n\n", json)?;
+            }
+            while !stop.load(Ordering::Relaxed) {
+                match updates.recv_timeout(KEEP_ALIVE) {
+                    Ok(json) => write!(stream, "data: {}\n\n", json)?,
+                    Err(RecvTimeoutError::Timeout) => write!(stream, ": keep-alive\n\n")?,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+                stream.flush()?;
+            }
+            Ok(())
+        }
+        Some(_) => respond(&mut stream, "404 Not Found", "text/plain", "Not found"),
+        None => respond(&mut stream, "405 Method Not Allowed", "text/plain", "Only GET is supported"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_hub() {
+        let settings: StreamSettings = serde_json::from_str("{}").unwrap();
+        assert!(!settings.enabled);
+        assert_eq!(settings.port(), DEFAULT_PORT);
+
+        assert_eq!(request_path("GET /events HTTP/1.1\r\n"), Some("/events"));
+        assert_eq!(request_path("GET /?theme=dark HTTP/1.1"), Some("/"));
+        assert_eq!(request_path("POST / HTTP/1.1"), None);
+
+        let hub = Hub::default();
+        let (latest, first) = hub.subscribe();
+        assert_eq!(latest, None);
+        hub.publish("{\"a\":1}".to_string());
+        assert_eq!(first.try_recv().unwrap(), "{\"a\":1}");
+
+        // Closed streams are dropped on the next publish; late subscribers get the latest
+        drop(first);
+        hub.publish("{\"a\":2}".to_string());
+        assert!(hub.subscribers.lock().unwrap().is_empty());
+        assert_eq!(hub.subscribe().0.as_deref(), Some("{\"a\":2}"));
+    }
+}
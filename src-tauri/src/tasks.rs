@@ -0,0 +1,158 @@
+//! Cancellable long-running calculations
+//!
+//! Batch commands run on a blocking worker thread and report `calc-progress`
+//! events tagged with a task id (chosen by the frontend, or generated).
+//! `cancel_calculation` flags the task; workers stop at their next item.
+
+use crate::locks::MutexExt;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Error returned by a calculation that was cancelled
+pub const CANCELLED: &str = "Calculation cancelled";
+
+/// Progress of a running calculation (sent as the `calc-progress` event)
+#[derive(Debug, Clone, Serialize)]
+pub struct CalcProgress {
+    pub task_id: String,
+    pub completed: usize,
+    pub total: usize,
+    pub percent: f64,
+}
+
+/// Cancellation flags of the calculations currently running
+#[derive(Default)]
+pub struct TaskRegistry {
+    next_id: AtomicU64,
+    running: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl TaskRegistry {
+    /// Fresh id for a task the frontend didn't name
+    pub fn next_id(&self) -> String {
+        format!("task-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    /// Track a new task; reusing a running task's id cancels the old one
+    pub fn register(&self, task_id: &str) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        if let Some(old) = self.running.lock_or_recover().insert(task_id.to_string(), flag.clone()) {
+            old.store(true, Ordering::Relaxed);
+        }
+        flag
+    }
+
+    /// Flag a running task for cancellation (false if no such task is running)
+    pub fn cancel(&self, task_id: &str) -> bool {
+        match self.running.lock_or_recover().get(task_id) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop tracking a finished task (leaves a newer task with the same id alone)
+    pub fn finish(&self, task_id: &str, flag: &Arc<AtomicBool>) {
+        let mut running = self.running.lock_or_recover();
+        if running.get(task_id).is_some_and(|current| Arc::ptr_eq(current, flag)) {
+            running.remove(task_id);
+        }
+    }
+}
+
+/// Progress counter shared by a task's worker threads
+///
+/// Reports at most once per whole percent so large batches don't flood the UI.
+pub struct TaskProgress {
+    task_id: String,
+    cancelled: Arc<AtomicBool>,
+    completed: AtomicUsize,
+    total: AtomicUsize,
+    last_percent: AtomicUsize,
+    on_progress: Box<dyn Fn(CalcProgress) + Send + Sync>,
+}
+
+impl TaskProgress {
+    pub fn new(
+        task_id: String,
+        cancelled: Arc<AtomicBool>,
+        on_progress: impl Fn(CalcProgress) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            task_id,
+            cancelled,
+            completed: AtomicUsize::new(0),
+            total: AtomicUsize::new(0),
+            last_percent: AtomicUsize::new(usize::MAX),
+            on_progress: Box::new(on_progress),
+        }
+    }
+
+    /// Set the number of items and report 0%
+    pub fn start(&self, total: usize) {
+        self.total.store(total, Ordering::Relaxed);
+        self.completed.store(0, Ordering::Relaxed);
+        self.report(0);
+    }
+
+    /// Count one finished item
+    pub fn advance(&self) {
+        let completed = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+        self.report(completed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// `Err(CANCELLED)` once the task has been cancelled
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn report(&self, completed: usize) {
+        let total = self.total.load(Ordering::Relaxed);
+        let percent = (completed.min(total) * 100).checked_div(total).unwrap_or(100);
+        if self.last_percent.swap(percent, Ordering::Relaxed) != percent {
+            (self.on_progress)(CalcProgress {
+                task_id: self.task_id.clone(),
+                completed,
+                total,
+                percent: percent as f64,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_and_cancel() {
+        let registry = TaskRegistry::default();
+        let flag = registry.register("matrix");
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        let progress = TaskProgress::new("matrix".to_string(), flag.clone(), move |p| sink.lock().unwrap().push(p.percent));
+
+        progress.start(4);
+        progress.advance();
+        progress.advance();
+        assert_eq!(*reports.lock().unwrap(), vec![0.0, 25.0, 50.0]);
+        assert!(progress.check().is_ok());
+
+        assert!(registry.cancel("matrix"));
+        assert_eq!(progress.check(), Err(CANCELLED.to_string()));
+        registry.finish("matrix", &flag);
+        assert!(!registry.cancel("matrix"));
+    }
+}
@@ -0,0 +1,216 @@
+//! Ship Lens Torpedo Strikes
+//!
+//! Time to impact for a torpedo volley (lock, then flight to the target range)
+//! and how much of it the target's defenses stop. Countermeasures spoof
+//! torpedoes first (see `Countermeasures`); the target's PDCs then shoot down
+//! survivors for as long as the volley is inside PDC range. Landed torpedoes
+//! go through the shield, armor and hull pools the same way as bombs.
+//!
+//! The data has no torpedo speeds, so the speed is an input with a typical
+//! default; PDC range and kill rate are likewise estimates the caller can adjust.
+
+use crate::bombs;
+use crate::data::{Missile, Shield, Ship};
+use crate::ttk::{Countermeasures, DamageBreakdown, ValidationErrors};
+use serde::{Deserialize, Serialize};
+
+/// Torpedo speed used when none is given (m/s)
+pub const DEFAULT_TORPEDO_SPEED: f64 = 500.0;
+
+/// Largest volley considered when counting torpedoes for a kill
+pub const MAX_TORPEDOES: i32 = 100;
+
+/// Target-side torpedo defense
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TorpedoDefense {
+    /// PDC turrets firing (counted from the target's hardpoints if None)
+    pub pdc_count: Option<i32>,
+    /// Distance at which PDCs start engaging (meters)
+    pub pdc_range: f64,
+    /// Torpedoes one PDC destroys per second of engagement
+    pub pdc_kill_rate: f64,
+    pub countermeasures: Countermeasures,
+}
+
+impl Default for TorpedoDefense {
+    fn default() -> Self {
+        Self {
+            pdc_count: None,
+            pdc_range: 1500.0,
+            pdc_kill_rate: 0.2,
+            countermeasures: Countermeasures::default(),
+        }
+    }
+}
+
+/// Expected outcome of a torpedo volley
+#[derive(Debug, Clone, Serialize)]
+pub struct TorpedoStrike {
+    pub torpedo: String,
+    pub target: String,
+    pub count: i32,
+    pub range: f64,
+    pub lock_time: f64,
+    pub flight_time: f64,
+    /// Lock plus flight (seconds)
+    pub time_to_impact: f64,
+    /// Torpedoes burn out before reaching the range
+    pub out_of_range: bool,
+    /// Torpedoes arrive before they arm
+    pub too_close: bool,
+    pub pdc_count: i32,
+    /// Seconds the volley spends inside PDC range
+    pub pdc_exposure: f64,
+    /// Expected torpedoes spoofed by countermeasures
+    pub spoofed: f64,
+    /// Expected torpedoes shot down by PDCs
+    pub intercepted: f64,
+    pub expected_hits: f64,
+    /// Fewer than one torpedo is expected to land
+    pub volley_defeated: bool,
+    /// Torpedoes that must land to destroy the target (None = more than `MAX_TORPEDOES`)
+    pub hits_to_kill: Option<i32>,
+    /// Smallest volley expected to land `hits_to_kill` (None = none within `MAX_TORPEDOES`)
+    pub torpedoes_for_kill: Option<i32>,
+}
+
+/// PDC gun turrets fitted on `ship` (sub-ports with a weapon)
+pub fn pdc_count(ship: &Ship) -> i32 {
+    ship.weapon_hardpoints.iter()
+        .filter(|hp| hp.category == "pdc")
+        .flat_map(|hp| &hp.sub_ports)
+        .filter(|p| p.default_weapon.as_deref().is_some_and(|w| !w.is_empty() && w != "empty"))
+        .count() as i32
+}
+
+/// Expected (spoofed, intercepted, landed) torpedoes of a volley of `count`
+fn volley(torpedo: &Missile, count: i32, defense: &TorpedoDefense, pdcs: i32, exposure: f64) -> (f64, f64, f64) {
+    // Each volley gets the full countermeasure stock
+    let mut countermeasures = defense.countermeasures;
+    let unspoofed = countermeasures.expected_hits(&torpedo.tracking_type, count);
+    let intercepted = (pdcs as f64 * defense.pdc_kill_rate * exposure).min(unspoofed);
+    (count as f64 - unspoofed, intercepted, unspoofed - intercepted)
+}
+
+/// Fire `count` torpedoes at `target` from `range` meters (`shield` None = unshielded)
+pub fn torpedo_strike(
+    torpedo: &Missile,
+    count: i32,
+    range: f64,
+    speed: f64,
+    target: &Ship,
+    shield: Option<&Shield>,
+    defense: &TorpedoDefense,
+) -> Result<TorpedoStrike, String> {
+    let mut errors = ValidationErrors::default();
+    if torpedo.missile_type != "torpedo" {
+        errors.push("torpedo", format!("{} is not a torpedo", torpedo.display_name));
+    }
+    errors.check_range("count", count as f64, 1.0, MAX_TORPEDOES as f64);
+    errors.check_range("range", range, 0.0, 100_000.0);
+    errors.check_range("speed", speed, 1.0, 10_000.0);
+    if let Some(pdcs) = defense.pdc_count {
+        errors.check_range("defense.pdc_count", pdcs as f64, 0.0, 100.0);
+    }
+    errors.check_range("defense.pdc_range", defense.pdc_range, 0.0, 100_000.0);
+    errors.check_range("defense.pdc_kill_rate", defense.pdc_kill_rate, 0.0, 10.0);
+    defense.countermeasures.validate(&mut errors);
+    errors.into_result().map_err(|e| e.to_string())?;
+
+    let flight_time = range / speed;
+    let out_of_range = torpedo.max_lifetime > 0.0 && flight_time > torpedo.max_lifetime;
+    let too_close = flight_time < torpedo.arm_time;
+    let pdcs = defense.pdc_count.unwrap_or_else(|| pdc_count(target));
+    let pdc_exposure = defense.pdc_range.min(range) / speed;
+
+    let damage = DamageBreakdown {
+        physical: torpedo.damage_physical,
+        energy: torpedo.damage_energy,
+        distortion: torpedo.damage_distortion,
+    };
+    let hits_to_kill = bombs::hits_to_kill(&damage, target, shield);
+
+    // A volley that never arrives armed does nothing
+    let (spoofed, intercepted, expected_hits) = if out_of_range || too_close {
+        (0.0, 0.0, 0.0)
+    } else {
+        volley(torpedo, count, defense, pdcs, pdc_exposure)
+    };
+    let torpedoes_for_kill = hits_to_kill
+        .filter(|_| !out_of_range && !too_close)
+        .and_then(|needed| {
+            (1..=MAX_TORPEDOES).find(|&n| volley(torpedo, n, defense, pdcs, pdc_exposure).2 >= needed as f64 - 1e-9)
+        });
+
+    Ok(TorpedoStrike {
+        torpedo: torpedo.display_name.clone(),
+        target: target.display_name.clone(),
+        count,
+        range,
+        lock_time: torpedo.lock_time,
+        flight_time,
+        time_to_impact: torpedo.lock_time + flight_time,
+        out_of_range,
+        too_close,
+        pdc_count: pdcs,
+        pdc_exposure,
+        spoofed,
+        intercepted,
+        expected_hits,
+        volley_defeated: expected_hits < 1.0,
+        hits_to_kill,
+        torpedoes_for_kill,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_torpedo_strike() {
+        let torpedo = Missile {
+            id: 0,
+            name: "misl_s09_cs_test".to_string(),
+            display_name: "Test Torpedo".to_string(),
+            size: 9,
+            missile_type: "torpedo".to_string(),
+            tracking_type: "CS".to_string(),
+            damage_physical: 0.0,
+            damage_energy: 100_000.0,
+            damage_distortion: 0.0,
+            explosion_min_radius: 10.0,
+            explosion_max_radius: 25.0,
+            max_lifetime: 30.0,
+            arm_time: 1.0,
+            lock_time: 7.0,
+        };
+        // Center mass pools: 0.3 x 500000 armor, 0.6 x 500000 hull = 4.5 hits
+        let target = Ship {
+            display_name: "Test Capital".to_string(),
+            hull_hp: 500_000.0,
+            armor_hp: 500_000.0,
+            armor_damage_mult_energy: 1.0,
+            armor_resist_energy: 1.0,
+            ..Default::default()
+        };
+        // Two PDCs at 0.5 kills/s over 3s in range: 3 torpedoes shot down
+        let defense = TorpedoDefense { pdc_count: Some(2), pdc_range: 1500.0, pdc_kill_rate: 0.5, ..TorpedoDefense::default() };
+
+        let strike = torpedo_strike(&torpedo, 4, 5000.0, 500.0, &target, None, &defense).unwrap();
+        assert_eq!(strike.time_to_impact, 17.0);
+        assert_eq!(strike.pdc_exposure, 3.0);
+        assert_eq!((strike.intercepted, strike.expected_hits), (3.0, 1.0));
+        assert!(!strike.volley_defeated);
+        assert_eq!(strike.hits_to_kill, Some(5));
+        assert_eq!(strike.torpedoes_for_kill, Some(8));
+
+        // Out of reach: 20000m takes 40s of a 30s lifetime
+        let far = torpedo_strike(&torpedo, 4, 20_000.0, 500.0, &target, None, &defense).unwrap();
+        assert!(far.out_of_range && far.volley_defeated);
+        assert_eq!(far.torpedoes_for_kill, None);
+
+        assert!(torpedo_strike(&torpedo, 4, 5000.0, 0.0, &target, None, &defense).is_err());
+    }
+}
@@ -7,6 +7,7 @@
 //! - Armor damage with typed resistances
 
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use crate::data::{Ship, Weapon, Shield};
 
 /// Combat scenario configuration affecting accuracy and DPS
@@ -18,10 +19,49 @@ pub struct CombatScenario {
     pub scenario_accuracy: f64,
     /// Time on target: Dogfight=0.65, Jousting=0.35, Synthetic=0.95
     pub time_on_target: f64,
-    /// Fire mode: Sustained=1.0, Burst=0.85, Staggered=0.75
+    /// Fire mode: Sustained=1.0, Burst=0.85, Staggered=0.75 (ignored when fire_schedule is set)
     pub fire_mode: f64,
     /// Power multiplier: 33%=1.0, 50%=1.07, 66%=1.13, 100%=1.2
     pub power_multiplier: f64,
+    /// Engagement range in meters (0 = ignore projectile flight time and max range)
+    #[serde(default)]
+    pub engagement_range: f64,
+    /// Rule of Two failover modeling options
+    #[serde(default)]
+    pub rule_of_two: RuleOfTwoOptions,
+    /// Defender's active shield ability (hardening)
+    #[serde(default)]
+    pub shield_hardening: ShieldHardening,
+    /// Pass-based engagement (replaces the flat time_on_target when set)
+    #[serde(default)]
+    pub passes: Option<PassModel>,
+    /// Target profile multiplier on scenario_accuracy (None = derive from the target's size)
+    #[serde(default)]
+    pub target_profile_factor: Option<f64>,
+    /// Target evasion multiplier on time_on_target (None = derive from the target's agility)
+    #[serde(default)]
+    pub evasion_factor: Option<f64>,
+    /// Armor loses its damage reduction as it is stripped (None = flat multipliers)
+    #[serde(default)]
+    pub armor_ablation: Option<ArmorAblation>,
+    /// Ballistic impact angle on armor, degrees from the surface normal (None = flat multipliers)
+    #[serde(default)]
+    pub impact_angle: Option<f64>,
+    /// Gunner accuracy per turret category (replaces mount_accuracy for those guns)
+    #[serde(default)]
+    pub gunner_profiles: GunnerProfiles,
+    /// Weapon group firing schedule (replaces the flat fire_mode when set)
+    #[serde(default)]
+    pub fire_schedule: Option<FireSchedule>,
+    /// How the defender manages their shields under fire
+    #[serde(default)]
+    pub defender: DefenderBehavior,
+    /// Shield booster consumables the defender carries (None = no boosters)
+    #[serde(default)]
+    pub shield_booster: Option<ShieldBooster>,
+    /// Health fractions below which the target's components run degraded
+    #[serde(default)]
+    pub degraded_thresholds: DegradedThresholds,
 }
 
 impl Default for CombatScenario {
@@ -32,10 +72,577 @@ impl Default for CombatScenario {
             time_on_target: 0.65,    // Dogfight
             fire_mode: 1.0,          // Sustained
             power_multiplier: 1.0,   // 33% power (no boost)
+            engagement_range: 0.0,   // Range-independent
+            rule_of_two: RuleOfTwoOptions::default(),
+            shield_hardening: ShieldHardening::default(),
+            passes: None,
+            target_profile_factor: None,
+            evasion_factor: None,
+            armor_ablation: None,
+            impact_angle: None,
+            gunner_profiles: GunnerProfiles::default(),
+            fire_schedule: None,
+            defender: DefenderBehavior::Passive,
+            shield_booster: None,
+            degraded_thresholds: DegradedThresholds::default(),
         }
     }
 }
 
+/// Target length the scenario accuracies are calibrated for (light fighter, meters)
+const REFERENCE_TARGET_LENGTH: f64 = 20.0;
+
+/// Typical hull length per size class, for ships without hitbox data (meters)
+fn typical_length(size_class: &str) -> Option<f64> {
+    match size_class {
+        "small" => Some(20.0),
+        "medium" => Some(45.0),
+        "large" => Some(110.0),
+        "capital" => Some(250.0),
+        _ => None,
+    }
+}
+
+/// How much easier a target is to hit than a light fighter
+///
+/// Scales with the square root of hull length (bigger ships present more
+/// surface but are also engaged from further out), clamped to 0.75-1.35.
+/// Unknown sizes give 1.0.
+pub fn target_profile_factor(target: &Ship) -> f64 {
+    let length = if target.hitbox_length > 0.0 {
+        Some(target.hitbox_length)
+    } else {
+        typical_length(&target.size_class)
+    };
+    length.map_or(1.0, |length| (length / REFERENCE_TARGET_LENGTH).sqrt().clamp(0.75, 1.35))
+}
+
+/// Agility classes from most to least evasive, with their time-on-target multipliers
+///
+/// Scenario time on target is calibrated against an agile light fighter.
+const AGILITY_CLASSES: [(&str, f64); 4] = [("nimble", 0.85), ("agile", 1.0), ("average", 1.1), ("sluggish", 1.25)];
+
+/// Lower bounds of the nimble/agile/average classes: turn rate (deg/s) and acceleration (g)
+const AGILITY_TURN_RATES: [f64; 3] = [70.0, 50.0, 30.0];
+const AGILITY_ACCELERATIONS: [f64; 3] = [12.0, 8.0, 5.0];
+
+/// Agility class of a ship: from its turn rates, else its thrust-to-mass
+/// acceleration, else its size class (None if all are unknown)
+pub fn agility_class(ship: &Ship) -> Option<&'static str> {
+    let rates: Vec<f64> = [ship.pitch_rate, ship.yaw_rate].into_iter().filter(|r| *r > 0.0).collect();
+    let (value, bounds) = if !rates.is_empty() {
+        (rates.iter().sum::<f64>() / rates.len() as f64, AGILITY_TURN_RATES)
+    } else if ship.mass > 0.0 && ship.main_thrust > 0.0 {
+        (ship.main_thrust / ship.mass / 9.81, AGILITY_ACCELERATIONS)
+    } else {
+        return match ship.size_class.as_str() {
+            "small" => Some("agile"),
+            "medium" => Some("average"),
+            "large" | "capital" => Some("sluggish"),
+            _ => None,
+        };
+    };
+    let class = bounds.iter().position(|bound| value >= *bound).unwrap_or(bounds.len());
+    Some(AGILITY_CLASSES[class].0)
+}
+
+/// How much more (or less) time an attacker keeps guns on `target` than on an agile fighter
+///
+/// Unknown agility gives 1.0.
+pub fn evasion_factor(target: &Ship) -> f64 {
+    agility_class(target)
+        .and_then(|class| AGILITY_CLASSES.iter().find(|(c, _)| *c == class))
+        .map_or(1.0, |(_, factor)| *factor)
+}
+
+/// Jousting passes: fire windows separated by merges with no guns on target
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PassModel {
+    /// Seconds of fire per pass
+    pub pass_duration: f64,
+    /// Seconds between passes (turn-around, shields may regen)
+    pub time_between_passes: f64,
+}
+
+impl PassModel {
+    pub fn duty_cycle(&self) -> f64 {
+        self.pass_duration / (self.pass_duration + self.time_between_passes)
+    }
+
+    fn is_firing(&self, time: f64) -> bool {
+        time % (self.pass_duration + self.time_between_passes) < self.pass_duration
+    }
+}
+
+/// Staggered fire across weapon groups
+///
+/// Each group fires for `burst` seconds, then rests for `rest` seconds while its
+/// capacitor and heat recover, so every gun's uptime is the duty cycle
+/// `burst / (burst + rest)`. Alternating groups start one after another (group
+/// `i` opens fire `i × burst` into the cycle) and fill each other's rests;
+/// groups firing together leave the whole rest without hits. Shield regen only
+/// starts during gaps at least as long as the shield's damaged regen delay.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FireSchedule {
+    /// Seconds each group fires per cycle
+    pub burst: f64,
+    /// Seconds each group holds fire per cycle
+    pub rest: f64,
+    /// Groups take turns instead of firing together
+    pub alternate: bool,
+}
+
+impl FireSchedule {
+    pub fn duty_cycle(&self) -> f64 {
+        self.burst / (self.burst + self.rest)
+    }
+
+    /// Longest stretch per cycle with no group firing
+    pub fn longest_gap(&self, groups: usize) -> f64 {
+        let covered = if self.alternate { self.burst * groups.max(1) as f64 } else { self.burst };
+        (self.burst + self.rest - covered).max(0.0)
+    }
+}
+
+/// Number of distinct weapon groups in a loadout
+pub fn weapon_group_count(weapons: &[EquippedWeapon]) -> usize {
+    let mut groups: Vec<u32> = weapons.iter().map(|w| w.group).collect();
+    groups.sort_unstable();
+    groups.dedup();
+    groups.len()
+}
+
+/// Armor ablation curve
+///
+/// Armor effectiveness (how much of its damage reduction still applies) is
+/// `min_effectiveness + (1 - min_effectiveness) × remaining^exponent`, where
+/// `remaining` is the fraction of the armor pool left. An exponent of 1 degrades
+/// linearly, above 1 front-loads the loss, below 1 holds until the armor is nearly gone.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArmorAblation {
+    /// Armor effectiveness left when the pool is empty (0.0-1.0)
+    pub min_effectiveness: f64,
+    /// Curve shape
+    pub exponent: f64,
+}
+
+impl Default for ArmorAblation {
+    fn default() -> Self {
+        Self {
+            min_effectiveness: 0.0,
+            exponent: 1.0,
+        }
+    }
+}
+
+/// How a ballistic round meets the armor plate
+///
+/// Square hits penetrate best; shallow ones skip off. Only physical damage is
+/// affected: energy and distortion ignore the angle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImpactBand {
+    /// Within 25° of the surface normal
+    Penetrating,
+    /// 25-60°, what the flat armor multipliers describe
+    Normal,
+    /// Beyond 60°, most of the round deflects
+    Glance,
+}
+
+impl ImpactBand {
+    /// Band of an impact angle in degrees from the surface normal
+    pub fn of_angle(angle: f64) -> Self {
+        if angle < 25.0 {
+            ImpactBand::Penetrating
+        } else if angle < 60.0 {
+            ImpactBand::Normal
+        } else {
+            ImpactBand::Glance
+        }
+    }
+
+    /// Multiplier on the armor's physical damage multiplier
+    pub fn physical_multiplier(self) -> f64 {
+        match self {
+            ImpactBand::Penetrating => 1.15,
+            ImpactBand::Normal => 1.0,
+            ImpactBand::Glance => 0.5,
+        }
+    }
+}
+
+/// Pool slices used to average an ablating armor damage rate
+const ABLATION_SLICES: usize = 100;
+
+impl ArmorAblation {
+    fn effectiveness(&self, remaining: f64) -> f64 {
+        let remaining = remaining.clamp(0.0, 1.0);
+        self.min_effectiveness + (1.0 - self.min_effectiveness) * remaining.powf(self.exponent)
+    }
+
+    /// Armor damage rate with `remaining` of the pool left
+    ///
+    /// `raw` is the unmitigated DPS and `full` the rate with intact armor
+    /// (see `calculate_armor_damage`); the multipliers fade toward 1.0.
+    pub fn damage_rate(&self, raw: f64, full: f64, remaining: f64) -> f64 {
+        raw + (full - raw) * self.effectiveness(remaining)
+    }
+
+    /// Constant rate that strips the whole pool in the same time as the ablating one
+    pub fn average_rate(&self, raw: f64, full: f64) -> f64 {
+        let mut seconds_per_hp = 0.0;
+        for slice in 0..ABLATION_SLICES {
+            let remaining = 1.0 - (slice as f64 + 0.5) / ABLATION_SLICES as f64;
+            let rate = self.damage_rate(raw, full, remaining);
+            if rate <= 0.0 {
+                return 0.0;
+            }
+            seconds_per_hp += 1.0 / rate;
+        }
+        ABLATION_SLICES as f64 / seconds_per_hp
+    }
+}
+
+/// Defender countermeasures against missiles
+///
+/// Decoys spoof IR seekers and noise spoofs EM/CS seekers. Each missile draws one
+/// countermeasure of its kind while the stock lasts; a missile that isn't spoofed
+/// can still be out-maneuvered with `evasion_chance`. The default (no stock, no
+/// evasion) lets every missile land.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Countermeasures {
+    /// Decoy (flare) charges available
+    pub decoys: i32,
+    /// Noise charges available
+    pub noise: i32,
+    /// Chance one decoy spoofs an IR missile
+    pub decoy_effectiveness: f64,
+    /// Chance one noise charge spoofs an EM missile
+    pub noise_effectiveness_em: f64,
+    /// Chance one noise charge spoofs a cross-section missile
+    pub noise_effectiveness_cs: f64,
+    /// Chance the defender evades a missile that wasn't spoofed
+    pub evasion_chance: f64,
+}
+
+impl Default for Countermeasures {
+    fn default() -> Self {
+        Self {
+            decoys: 0,
+            noise: 0,
+            decoy_effectiveness: 0.5,
+            noise_effectiveness_em: 0.4,
+            noise_effectiveness_cs: 0.3,
+            evasion_chance: 0.0,
+        }
+    }
+}
+
+/// Most countermeasure charges a defender can be given
+const MAX_COUNTERMEASURES: f64 = 500.0;
+
+impl Countermeasures {
+    /// Range-check counts and chances
+    pub fn validate(&self, errors: &mut ValidationErrors) {
+        errors.check_range("countermeasures.decoys", self.decoys as f64, 0.0, MAX_COUNTERMEASURES);
+        errors.check_range("countermeasures.noise", self.noise as f64, 0.0, MAX_COUNTERMEASURES);
+        errors.check_range("countermeasures.decoy_effectiveness", self.decoy_effectiveness, 0.0, 1.0);
+        errors.check_range("countermeasures.noise_effectiveness_em", self.noise_effectiveness_em, 0.0, 1.0);
+        errors.check_range("countermeasures.noise_effectiveness_cs", self.noise_effectiveness_cs, 0.0, 1.0);
+        errors.check_range("countermeasures.evasion_chance", self.evasion_chance, 0.0, 1.0);
+    }
+
+    /// Expected missiles (of `count` fired) that land, spending charges from the stock
+    pub fn expected_hits(&mut self, tracking_type: &str, count: i32) -> f64 {
+        let count = count.max(0);
+        let (stock, effectiveness) = match tracking_type.to_uppercase().as_str() {
+            "IR" => (&mut self.decoys, self.decoy_effectiveness),
+            "EM" => (&mut self.noise, self.noise_effectiveness_em),
+            "CS" => (&mut self.noise, self.noise_effectiveness_cs),
+            // Unknown seekers can't be spoofed, only evaded
+            _ => return count as f64 * (1.0 - self.evasion_chance),
+        };
+        let countered = count.min(*stock).max(0);
+        *stock -= countered;
+        let unspoofed = countered as f64 * (1.0 - effectiveness) + (count - countered) as f64;
+        unspoofed * (1.0 - self.evasion_chance)
+    }
+}
+
+/// Defender shield hardening ability (applies only while shields are up)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShieldHardening {
+    /// Fraction of the shield phase the ability is active (0.0-1.0)
+    pub uptime: f64,
+    /// Shield damage reduction while active (0.0-1.0)
+    pub damage_reduction: f64,
+}
+
+impl ShieldHardening {
+    /// Average multiplier on damage dealt to shields
+    pub fn shield_damage_factor(&self) -> f64 {
+        1.0 - self.uptime * self.damage_reduction
+    }
+}
+
+/// Most regen boost charges a defender can be given
+const MAX_REGEN_CHARGES: f64 = 20.0;
+
+/// How the defender manages their shields under fire
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum DefenderBehavior {
+    /// Shields left alone
+    #[default]
+    Passive,
+    /// Shield faces rebalanced toward the attacker
+    ///
+    /// Left alone, the face under fire drops while the others still hold HP;
+    /// shifting that capacity over counts as `hp_bonus` extra HP per phase
+    /// (a fraction of the phase HP).
+    FaceBalancing { hp_bonus: f64 },
+    /// Regen boost items used whenever a shield phase is about to drop
+    ///
+    /// Each use restores `restore` of the phase HP; a charge is ready again
+    /// `cooldown` seconds after the previous one.
+    RegenItems { charges: i32, restore: f64, cooldown: f64 },
+}
+
+impl DefenderBehavior {
+    /// Range-check the behavior parameters
+    pub fn validate(&self, errors: &mut ValidationErrors) {
+        match *self {
+            DefenderBehavior::Passive => {}
+            DefenderBehavior::FaceBalancing { hp_bonus } => {
+                errors.check_range("defender.hp_bonus", hp_bonus, 0.0, 1.0);
+            }
+            DefenderBehavior::RegenItems { charges, restore, cooldown } => {
+                errors.check_range("defender.charges", charges as f64, 0.0, MAX_REGEN_CHARGES);
+                errors.check_range("defender.restore", restore, 0.0, 1.0);
+                errors.check_range("defender.cooldown", cooldown, 0.0, 300.0);
+            }
+        }
+    }
+
+    /// Shield phases as this defender makes them hold against `shield_dps`
+    ///
+    /// Regen items are spent in phase order: a phase gets another charge each
+    /// time it is about to drop with a charge off cooldown, and the restored HP
+    /// is added to the phase.
+    pub(crate) fn defend(&self, shield: &EffectiveShield, shield_dps: f64, regen_suppressed: bool) -> EffectiveShield {
+        let mut defended = shield.clone();
+        match *self {
+            DefenderBehavior::Passive => return defended,
+            DefenderBehavior::FaceBalancing { hp_bonus } => {
+                for phase in &mut defended.phases {
+                    phase.hp *= 1.0 + hp_bonus;
+                }
+            }
+            DefenderBehavior::RegenItems { charges, restore, cooldown } => {
+                spend_charges(&mut defended, shield_dps, regen_suppressed, charges, cooldown, |hp| hp * restore);
+            }
+        }
+        defended.total_hp = defended.phases.iter().map(|p| p.hp).sum();
+        defended
+    }
+}
+
+/// Spend restore charges in phase order, each when a phase is about to drop with
+/// a charge off cooldown; `restore_of` gives the HP restored from the phase's HP
+fn spend_charges(
+    shield: &mut EffectiveShield,
+    shield_dps: f64,
+    regen_suppressed: bool,
+    charges: i32,
+    cooldown: f64,
+    restore_of: impl Fn(f64) -> f64,
+) {
+    let mut charges = charges.max(0);
+    let mut ready_at = 0.0;
+    let mut time = 0.0;
+    for phase in &mut shield.phases {
+        let net = shield_dps - if regen_suppressed { 0.0 } else { phase.regen };
+        if net <= 0.0 {
+            // Unbreakable anyway; later phases are never reached
+            break;
+        }
+        let boost = restore_of(phase.hp);
+        let mut end = time + phase.activation_delay + phase.hp / net;
+        while charges > 0 && boost > 0.0 && ready_at <= end {
+            ready_at = end + cooldown;
+            end += boost / net;
+            phase.hp += boost;
+            charges -= 1;
+        }
+        time = end;
+    }
+}
+
+/// Shield booster consumables (instant restore items)
+///
+/// The defender pops one whenever a shield phase is about to drop and one is
+/// off cooldown. Each restores `restore_hp` (at most the phase's full HP) and
+/// the next is ready `cooldown` seconds later. Works on top of the defender
+/// behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ShieldBooster {
+    /// Boosters carried
+    pub charges: i32,
+    /// Shield HP restored per use
+    pub restore_hp: f64,
+    /// Seconds before the next booster can be used
+    pub cooldown: f64,
+}
+
+impl ShieldBooster {
+    /// Range-check the booster parameters
+    pub fn validate(&self, errors: &mut ValidationErrors) {
+        errors.check_range("shield_booster.charges", self.charges as f64, 0.0, MAX_REGEN_CHARGES);
+        errors.check_range("shield_booster.restore_hp", self.restore_hp, 0.0, 1_000_000.0);
+        errors.check_range("shield_booster.cooldown", self.cooldown, 0.0, 300.0);
+    }
+
+    /// Shield phases with the boosters spent against `shield_dps`
+    pub(crate) fn apply(&self, shield: &EffectiveShield, shield_dps: f64, regen_suppressed: bool) -> EffectiveShield {
+        let mut boosted = shield.clone();
+        spend_charges(&mut boosted, shield_dps, regen_suppressed, self.charges, self.cooldown, |hp| self.restore_hp.min(hp));
+        boosted.total_hp = boosted.phases.iter().map(|p| p.hp).sum();
+        boosted
+    }
+}
+
+/// Rule of Two failover modeling options
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleOfTwoOptions {
+    /// Fraction of a standby generator's HP available once it comes online
+    pub standby_efficiency: f64,
+    /// Seconds between a phase failing and the next standby pair coming online
+    pub activation_delay: f64,
+}
+
+impl Default for RuleOfTwoOptions {
+    fn default() -> Self {
+        Self {
+            standby_efficiency: 0.8,
+            activation_delay: 1.0,
+        }
+    }
+}
+
+/// Named combat profiles (ids match the frontend's scenario dropdown values)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScenarioPreset {
+    Dogfight,
+    Jousting,
+    TurretDefense,
+    #[serde(alias = "synthetic")]
+    SyntheticMax,
+}
+
+impl ScenarioPreset {
+    pub const ALL: [ScenarioPreset; 4] = [
+        ScenarioPreset::Dogfight,
+        ScenarioPreset::Jousting,
+        ScenarioPreset::TurretDefense,
+        ScenarioPreset::SyntheticMax,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ScenarioPreset::Dogfight => "Dogfight",
+            ScenarioPreset::Jousting => "Jousting",
+            ScenarioPreset::TurretDefense => "Turret Defense",
+            ScenarioPreset::SyntheticMax => "Synthetic Max",
+        }
+    }
+
+    /// Full combat profile for this preset
+    pub fn scenario(self) -> CombatScenario {
+        match self {
+            ScenarioPreset::Dogfight => CombatScenario::default(),
+            // ~4s firing passes with 8s merges (about the flat 0.35 time on target)
+            ScenarioPreset::Jousting => CombatScenario {
+                scenario_accuracy: 0.85,
+                time_on_target: 0.35,
+                passes: Some(PassModel { pass_duration: 4.0, time_between_passes: 8.0 }),
+                ..CombatScenario::default()
+            },
+            // Manned turret tracking a target making passes on the ship
+            ScenarioPreset::TurretDefense => CombatScenario {
+                mount_accuracy: 0.70,
+                scenario_accuracy: 0.70,
+                time_on_target: 0.80,
+                ..CombatScenario::default()
+            },
+            // Upper bound: auto-gimbal, near-perfect tracking, full power
+            ScenarioPreset::SyntheticMax => CombatScenario {
+                mount_accuracy: 0.80,
+                scenario_accuracy: 0.95,
+                time_on_target: 0.95,
+                power_multiplier: 1.2,
+                ..CombatScenario::default()
+            },
+        }
+    }
+}
+
+/// Scenario preset catalog entry (for `get_scenario_presets`)
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioPresetInfo {
+    pub id: ScenarioPreset,
+    pub label: String,
+    pub scenario: CombatScenario,
+}
+
+/// All scenario presets, in display order
+pub fn scenario_presets() -> Vec<ScenarioPresetInfo> {
+    ScenarioPreset::ALL.iter()
+        .map(|&preset| ScenarioPresetInfo {
+            id: preset,
+            label: preset.label().to_string(),
+            scenario: preset.scenario(),
+        })
+        .collect()
+}
+
+/// Projectile flight time the scenario accuracies are calibrated for (seconds)
+const REFERENCE_TIME_OF_FLIGHT: f64 = 0.5;
+
+/// Accuracy factor from projectile speed and range (lead modeling)
+///
+/// Scenario accuracies assume ~0.5s flight time. Slower projectiles at longer
+/// range need more lead and miss more: factor = sqrt(reference / time_of_flight),
+/// capped at 1.0. Targets beyond the weapon's max range can't be hit at all.
+pub fn projectile_accuracy(weapon: &Weapon, range: f64) -> f64 {
+    if range <= 0.0 {
+        return 1.0;
+    }
+    if weapon.max_range > 0.0 && range > weapon.max_range {
+        return 0.0;
+    }
+    if weapon.speed <= 0.0 {
+        return 1.0;
+    }
+
+    let time_of_flight = range / weapon.speed;
+    (REFERENCE_TIME_OF_FLIGHT / time_of_flight).sqrt().min(1.0)
+}
+
+/// Shots needed to kill given a solo TTK, fire rate (RPM) and weapon count
+pub fn shots_to_kill(ttk: f64, fire_rate: f64, count: i32) -> f64 {
+    if ttk.is_finite() && fire_rate > 0.0 {
+        (ttk * fire_rate / 60.0 * count as f64).ceil()
+    } else {
+        f64::INFINITY
+    }
+}
+
 /// Target zone modifiers - determines damage distribution across ship zones
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ZoneModifiers {
@@ -61,6 +668,397 @@ impl Default for ZoneModifiers {
     }
 }
 
+impl ZoneModifiers {
+    /// Armor and hull pools of `target` under these shares (the hull pool includes thrusters and components)
+    pub fn pools(&self, target: &Ship) -> (f64, f64) {
+        let components = (target.powerplant_total_hp + target.cooler_total_hp + target.shield_gen_total_hp) as f64;
+        let hull = target.hull_hp * self.hull
+            + target.thruster_total_hp as f64 * self.thruster
+            + components * self.component;
+        (target.armor_hp * self.armor, hull)
+    }
+
+    /// Check each share is within 0.0-1.0 and that they sum to at most 1.0
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("hull", self.hull),
+            ("armor", self.armor),
+            ("thruster", self.thruster),
+            ("component", self.component),
+        ] {
+            if !value.is_finite() || !(0.0..=1.0).contains(&value) {
+                return Err(format!("Zone {} share must be between 0.0 and 1.0 (got {})", name, value));
+            }
+        }
+
+        let total = self.hull + self.armor + self.thruster + self.component;
+        // Small tolerance for UI-rounded percentages
+        if total > 1.0 + 1e-6 {
+            return Err(format!(
+                "Zone shares must sum to at most 1.0 (got {:.3}: hull {}, armor {}, thruster {}, component {})",
+                total, self.hull, self.armor, self.thruster, self.component
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Named target zones (ids match the frontend's zone dropdown values)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ZonePreset {
+    CenterMass,
+    Engines,
+    Cockpit,
+    Components,
+    Wings,
+    Turrets,
+    /// User-supplied shares
+    Custom,
+}
+
+impl ZonePreset {
+    pub const ALL: [ZonePreset; 7] = [
+        ZonePreset::CenterMass,
+        ZonePreset::Engines,
+        ZonePreset::Cockpit,
+        ZonePreset::Components,
+        ZonePreset::Wings,
+        ZonePreset::Turrets,
+        ZonePreset::Custom,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ZonePreset::CenterMass => "Center Mass",
+            ZonePreset::Engines => "Engines",
+            ZonePreset::Cockpit => "Cockpit",
+            ZonePreset::Components => "Components",
+            ZonePreset::Wings => "Wings/Extremities",
+            ZonePreset::Turrets => "Turrets",
+            ZonePreset::Custom => "Custom",
+        }
+    }
+
+    /// Id used by the frontend and `hitbox_zones.csv`
+    pub fn id(self) -> &'static str {
+        match self {
+            ZonePreset::CenterMass => "center-mass",
+            ZonePreset::Engines => "engines",
+            ZonePreset::Cockpit => "cockpit",
+            ZonePreset::Components => "components",
+            ZonePreset::Wings => "wings",
+            ZonePreset::Turrets => "turrets",
+            ZonePreset::Custom => "custom",
+        }
+    }
+
+    /// Zone shares for this preset against `ship`: its hitbox zone table, else
+    /// the generic shares (None for Custom)
+    pub fn modifiers_for(self, ship: &Ship) -> Option<ZoneModifiers> {
+        match ship.hitbox_zones.iter().find(|z| z.zone == self.id()) {
+            Some(z) => Some(ZoneModifiers { hull: z.hull, armor: z.armor, thruster: z.thruster, component: z.component }),
+            None => self.modifiers(),
+        }
+    }
+
+    /// Generic zone shares for this preset (None for Custom)
+    pub fn modifiers(self) -> Option<ZoneModifiers> {
+        let (hull, armor, thruster, component) = match self {
+            ZonePreset::CenterMass => return Some(ZoneModifiers::default()),
+            ZonePreset::Engines => (0.2, 0.1, 0.6, 0.1),
+            ZonePreset::Cockpit => (0.5, 0.2, 0.0, 0.3),
+            ZonePreset::Components => (0.3, 0.2, 0.0, 0.5),
+            ZonePreset::Wings => (0.3, 0.4, 0.2, 0.1),
+            // Turret hits mostly land on the turret itself, not the hull pools
+            ZonePreset::Turrets => (0.1, 0.1, 0.0, 0.0),
+            ZonePreset::Custom => return None,
+        };
+        Some(ZoneModifiers { hull, armor, thruster, component })
+    }
+}
+
+/// Zone preset catalog entry (for `get_zone_presets`)
+#[derive(Debug, Clone, Serialize)]
+pub struct ZonePresetInfo {
+    pub id: ZonePreset,
+    pub label: String,
+    pub modifiers: Option<ZoneModifiers>,
+    /// Shares come from the ship's hitbox zone table rather than the generic preset
+    pub ship_specific: bool,
+}
+
+/// All zone presets, in display order, with `ship`'s own shares where it has them
+pub fn zone_presets(ship: Option<&Ship>) -> Vec<ZonePresetInfo> {
+    ZonePreset::ALL.iter()
+        .map(|&preset| ZonePresetInfo {
+            id: preset,
+            label: preset.label().to_string(),
+            modifiers: match ship {
+                Some(ship) => preset.modifiers_for(ship),
+                None => preset.modifiers(),
+            },
+            ship_specific: ship.is_some_and(|s| s.hitbox_zones.iter().any(|z| z.zone == preset.id())),
+        })
+        .collect()
+}
+
+/// Highest weapon/missile count accepted per loadout entry
+pub const MAX_ITEM_COUNT: i32 = 64;
+
+/// Longest engagement range accepted (meters)
+pub const MAX_ENGAGEMENT_RANGE: f64 = 20_000.0;
+
+/// Hardpoint categories that carry ordnance rather than guns
+const ORDNANCE_CATEGORIES: [&str; 3] = ["missile", "torpedo", "bomb"];
+
+/// Hardpoint categories whose guns are brought to bear on the target
+pub const GUN_CATEGORIES: [&str; 3] = ["pilot", "manned_turret", "remote_turret"];
+
+/// Who is aiming a turret
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GunnerProfile {
+    /// Automated point defense / AI-controlled turret
+    AiPdc,
+    Average,
+    Expert,
+}
+
+impl GunnerProfile {
+    /// Hit rate of the gunner's aim (stands in for mount_accuracy)
+    pub fn accuracy(self) -> f64 {
+        match self {
+            GunnerProfile::AiPdc => 0.50,
+            GunnerProfile::Average => 0.65,
+            GunnerProfile::Expert => 0.85,
+        }
+    }
+}
+
+/// Gunner profile per turret hardpoint category (None = use the scenario mount accuracy)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GunnerProfiles {
+    pub manned_turret: Option<GunnerProfile>,
+    pub remote_turret: Option<GunnerProfile>,
+    pub pdc: Option<GunnerProfile>,
+}
+
+impl GunnerProfiles {
+    pub fn for_category(&self, category: &str) -> Option<GunnerProfile> {
+        match category {
+            "manned_turret" => self.manned_turret,
+            "remote_turret" => self.remote_turret,
+            "pdc" => self.pdc,
+            _ => None,
+        }
+    }
+}
+
+/// Mount accuracy for a hardpoint gimbal type (same values as the frontend's mount table)
+pub fn mount_accuracy(gimbal_type: &str) -> Option<f64> {
+    match gimbal_type {
+        "Fixed" => Some(0.60),
+        "Gimbal" => Some(0.75),
+        "Turret" => Some(0.70),
+        _ => None,
+    }
+}
+
+/// One invalid TTK request input
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Every invalid input found in a TTK request
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationErrors {
+    pub errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn push(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(FieldError { field: field.into(), message: message.into() });
+    }
+
+    /// Record an error unless `value` is a finite number within `min..=max`
+    pub fn check_range(&mut self, field: &str, value: f64, min: f64, max: f64) {
+        if !value.is_finite() || !(min..=max).contains(&value) {
+            self.push(field, format!("must be between {} and {} (got {})", min, max, value));
+        }
+    }
+
+    /// Record an error for each loadout count outside `0..=MAX_ITEM_COUNT`
+    pub fn check_counts(&mut self, field: &str, counts: &[i32]) {
+        for (i, &count) in counts.iter().enumerate() {
+            if !(0..=MAX_ITEM_COUNT).contains(&count) {
+                self.push(
+                    format!("{}[{}]", field, i),
+                    format!("must be between 0 and {} (got {})", MAX_ITEM_COUNT, count),
+                );
+            }
+        }
+    }
+
+    /// Record an error for each weapon larger than the attacker's biggest gun hardpoint
+    pub fn check_hardpoint_fit(&mut self, attacker: &Ship, weapons: &[EquippedWeapon]) {
+        let Some(max_size) = attacker.weapon_hardpoints.iter()
+            .filter(|hp| !ORDNANCE_CATEGORIES.contains(&hp.category.as_str()))
+            .map(|hp| hp.max_size)
+            .max()
+        else {
+            // No hardpoint data to check against
+            return;
+        };
+
+        for (i, equipped) in weapons.iter().enumerate() {
+            if equipped.weapon.size > max_size {
+                self.push(
+                    format!("weapon_names[{}]", i),
+                    format!(
+                        "S{} {} does not fit {} (largest gun hardpoint is S{})",
+                        equipped.weapon.size, equipped.weapon.display_name, attacker.display_name, max_size
+                    ),
+                );
+            }
+        }
+    }
+
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid TTK request:")?;
+        for error in &self.errors {
+            write!(f, "\n- {}: {}", error.field, error.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl CombatScenario {
+    /// Fraction of the engagement spent on target (pass duty cycle when passes are modeled)
+    pub fn effective_time_on_target(&self) -> f64 {
+        match self.passes {
+            Some(passes) => passes.duty_cycle(),
+            None => self.time_on_target,
+        }
+    }
+
+    /// Fraction of sustained DPS the fire discipline delivers (schedule duty cycle when set)
+    pub fn fire_factor(&self) -> f64 {
+        match self.fire_schedule {
+            Some(schedule) => schedule.duty_cycle(),
+            None => self.fire_mode,
+        }
+    }
+
+    /// Whether hits land often enough that a shield with `regen_delay` never starts regenerating
+    ///
+    /// Without a schedule, only sustained fire (`fire_mode` 1.0) suppresses regen.
+    pub fn regen_suppressed(&self, groups: usize, regen_delay: f64) -> bool {
+        if groups == 0 || regen_delay <= 0.0 {
+            return false;
+        }
+        match self.fire_schedule {
+            Some(schedule) => schedule.longest_gap(groups) < regen_delay,
+            None => self.fire_mode >= 1.0,
+        }
+    }
+
+    /// This scenario with `scenario_accuracy` scaled for the target's profile and
+    /// `time_on_target` for its evasion (both capped at 1.0)
+    ///
+    /// The returned scenario has factors of 1.0, so applying it twice is harmless.
+    /// Pass-based engagements keep their duty cycle.
+    pub fn for_target(&self, target: &Ship) -> CombatScenario {
+        let factor = self.target_profile_factor.unwrap_or_else(|| target_profile_factor(target));
+        let evasion = self.evasion_factor.unwrap_or_else(|| evasion_factor(target));
+        CombatScenario {
+            scenario_accuracy: (self.scenario_accuracy * factor).min(1.0),
+            time_on_target: (self.time_on_target * evasion).min(1.0),
+            target_profile_factor: Some(1.0),
+            evasion_factor: Some(1.0),
+            ..self.clone()
+        }
+    }
+
+    /// `target` with its physical armor multiplier scaled for the impact angle
+    pub fn angled_armor<'a>(&self, target: &'a Ship) -> Cow<'a, Ship> {
+        match self.impact_angle.map(ImpactBand::of_angle) {
+            Some(band) if band != ImpactBand::Normal => Cow::Owned(Ship {
+                armor_damage_mult_physical: target.armor_damage_mult_physical * band.physical_multiplier(),
+                ..target.clone()
+            }),
+            _ => Cow::Borrowed(target),
+        }
+    }
+
+    /// Mount accuracy for a gun on a hardpoint of `category` (gunner profile, else the scenario's)
+    pub fn mount_accuracy_for(&self, category: &str) -> f64 {
+        self.gunner_profiles.for_category(category)
+            .map_or(self.mount_accuracy, GunnerProfile::accuracy)
+    }
+
+    /// This scenario with the mount accuracy resolved for one hardpoint category
+    pub fn for_category(&self, category: &str) -> CombatScenario {
+        CombatScenario {
+            mount_accuracy: self.mount_accuracy_for(category),
+            ..self.clone()
+        }
+    }
+
+    /// Range-check every scenario factor
+    pub fn validate(&self, errors: &mut ValidationErrors) {
+        errors.check_range("mount_accuracy", self.mount_accuracy, 0.0, 1.0);
+        errors.check_range("scenario_accuracy", self.scenario_accuracy, 0.0, 1.0);
+        errors.check_range("time_on_target", self.time_on_target, 0.0, 1.0);
+        errors.check_range("fire_mode", self.fire_mode, 0.0, 1.0);
+        errors.check_range("power_multiplier", self.power_multiplier, 0.0, 2.0);
+        errors.check_range("engagement_range", self.engagement_range, 0.0, MAX_ENGAGEMENT_RANGE);
+        errors.check_range("rule_of_two.standby_efficiency", self.rule_of_two.standby_efficiency, 0.0, 1.0);
+        errors.check_range("rule_of_two.activation_delay", self.rule_of_two.activation_delay, 0.0, 60.0);
+        errors.check_range("shield_hardening.uptime", self.shield_hardening.uptime, 0.0, 1.0);
+        errors.check_range("shield_hardening.damage_reduction", self.shield_hardening.damage_reduction, 0.0, 1.0);
+        if let Some(factor) = self.target_profile_factor {
+            errors.check_range("target_profile_factor", factor, 0.25, 4.0);
+        }
+        if let Some(factor) = self.evasion_factor {
+            errors.check_range("evasion_factor", factor, 0.25, 4.0);
+        }
+        if let Some(passes) = self.passes {
+            errors.check_range("passes.pass_duration", passes.pass_duration, 0.5, 120.0);
+            errors.check_range("passes.time_between_passes", passes.time_between_passes, 0.0, 300.0);
+        }
+        if let Some(ablation) = self.armor_ablation {
+            errors.check_range("armor_ablation.min_effectiveness", ablation.min_effectiveness, 0.0, 1.0);
+            errors.check_range("armor_ablation.exponent", ablation.exponent, 0.1, 10.0);
+        }
+        if let Some(angle) = self.impact_angle {
+            errors.check_range("impact_angle", angle, 0.0, 90.0);
+        }
+        if let Some(schedule) = self.fire_schedule {
+            errors.check_range("fire_schedule.burst", schedule.burst, 0.1, 60.0);
+            errors.check_range("fire_schedule.rest", schedule.rest, 0.0, 60.0);
+        }
+        self.defender.validate(errors);
+        if let Some(booster) = self.shield_booster {
+            booster.validate(errors);
+        }
+        self.degraded_thresholds.validate(errors);
+    }
+}
+
 /// Damage breakdown by type
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DamageBreakdown {
@@ -73,56 +1071,495 @@ impl DamageBreakdown {
     pub fn total(&self) -> f64 {
         self.physical + self.energy + self.distortion
     }
+
+    /// Split `amount` in proportion to these rates
+    pub(crate) fn share(&self, amount: f64) -> Self {
+        let total = self.total();
+        if total <= 0.0 || amount <= 0.0 {
+            return Self::default();
+        }
+        Self {
+            physical: amount * self.physical / total,
+            energy: amount * self.energy / total,
+            distortion: amount * self.distortion / total,
+        }
+    }
+
+    /// Split a DPS value across damage types using the weapon's per-shot damage ratios
+    pub fn from_weapon_dps(weapon: &Weapon, dps: f64) -> Self {
+        let total_per_shot = weapon.damage_physical + weapon.damage_energy + weapon.damage_distortion;
+        if total_per_shot > 0.0 {
+            Self {
+                physical: dps * (weapon.damage_physical / total_per_shot),
+                energy: dps * (weapon.damage_energy / total_per_shot),
+                distortion: dps * (weapon.damage_distortion / total_per_shot),
+            }
+        } else {
+            Self::default()
+        }
+    }
+}
+
+/// Effective shield after applying Rule of Two
+#[derive(Debug, Clone)]
+pub(crate) struct EffectiveShield {
+    pub(crate) total_hp: f64,
+    /// Regen of the initially active pair
+    pub(crate) regen: f64,
+    pub(crate) failover_phases: i32,
+    /// Generator groups in the order they come online
+    pub(crate) phases: Vec<ShieldPhase>,
+}
+
+/// Generators active together during one Rule of Two phase
+#[derive(Debug, Clone)]
+pub(crate) struct ShieldPhase {
+    pub(crate) generators: i32,
+    pub(crate) hp: f64,
+    pub(crate) regen: f64,
+    /// Delay after the previous phase fails before this one is up
+    pub(crate) activation_delay: f64,
+}
+
+/// Timing of one shield phase in a TTK result (seconds from the first shot)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShieldPhaseBoundary {
+    /// 1 = initially active pair, 2+ = standby failover
+    pub phase: i32,
+    pub generators: i32,
+    pub hp: f64,
+    pub regen: f64,
+    /// When the phase's generators come online
+    pub start: f64,
+    /// When the phase's shields are depleted (infinite if they never break)
+    pub end: f64,
+}
+
+/// Per-weapon effectiveness analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponEffectiveness {
+    pub weapon_name: String,
+    pub hardpoint_label: Option<String>,  // Parent hardpoint/turret name
+    pub damage_type: String,  // "Ballistic", "Energy", "Distortion", "Mixed"
+    pub count: i32,
+    pub raw_dps: f64,
+    pub effective_dps: f64,
+    pub shield_dps: f64,      // DPS absorbed by shields
+    pub passthrough_dps: f64, // DPS bypassing shields
+    pub armor_dps: f64,       // DPS after armor resistance
+    pub hull_dps: f64,
+    pub solo_ttk: f64,        // TTK if only this weapon was equipped
+    pub shots_to_kill: f64,   // Rounds fired (all guns of this type) over the solo TTK
+    pub shield_time: f64,     // Time this weapon takes on shields (solo)
+    pub armor_time: f64,      // Time on armor
+    pub hull_time: f64,       // Time on hull
+    pub is_effective: bool,   // false if DPS < shield regen
+    pub ineffective_reason: Option<String>,  // "Shield regen exceeds damage"
+}
+
+/// Per-missile effectiveness analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissileEffectiveness {
+    pub missile_name: String,
+    pub hardpoint_label: Option<String>,  // Parent hardpoint/turret name
+    pub damage_type: String,  // Based on dominant damage type
+    pub count: i32,
+    pub total_damage: f64,    // Burst damage (not DPS)
+    pub shield_damage: f64,   // Damage to shields
+    pub passthrough_damage: f64, // Damage bypassing shields (physical)
+    pub armor_damage: f64,    // Damage after armor resistance
+    pub hull_damage: f64,
+    pub time_saved: f64,      // TTK reduction from this missile burst
+    #[serde(default)]
+    pub hit_probability: f64, // Chance each missile lands after countermeasures and evasion
+    #[serde(default)]
+    pub expected_hits: f64,   // Missiles of `count` expected to land
+    pub is_effective: bool,
+    pub ineffective_reason: Option<String>,
+}
+
+/// Kind of engagement timeline event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngagementEventKind {
+    /// A standby Rule of Two phase came online
+    ShieldPhaseOnline,
+    /// A shield phase was depleted (more phases follow)
+    ShieldPhaseDown,
+    /// The last shield phase was depleted
+    ShieldsDown,
+    ArmorDepleted,
+    /// Thrusters below their degraded threshold (reduced maneuverability)
+    ThrustersDegraded,
+    /// Power plant below its degraded threshold (reduced power output)
+    PowerPlantDegraded,
+    /// Coolers below their degraded threshold (reduced cooling)
+    CoolersDegraded,
+    /// Shield generators below their degraded threshold (reduced shield regen)
+    ShieldGeneratorsDegraded,
+    PowerPlantDestroyed,
+    CoolerDestroyed,
+    ShieldGeneratorDestroyed,
+    TargetDestroyed,
+}
+
+/// Health fraction below which each component runs degraded (0-1)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DegradedThresholds {
+    pub thrusters: f64,
+    pub power_plant: f64,
+    pub coolers: f64,
+    pub shield_generators: f64,
+}
+
+impl Default for DegradedThresholds {
+    fn default() -> Self {
+        Self { thrusters: 0.5, power_plant: 0.5, coolers: 0.5, shield_generators: 0.5 }
+    }
+}
+
+impl DegradedThresholds {
+    pub fn validate(&self, errors: &mut ValidationErrors) {
+        errors.check_range("degraded_thresholds.thrusters", self.thrusters, 0.0, 1.0);
+        errors.check_range("degraded_thresholds.power_plant", self.power_plant, 0.0, 1.0);
+        errors.check_range("degraded_thresholds.coolers", self.coolers, 0.0, 1.0);
+        errors.check_range("degraded_thresholds.shield_generators", self.shield_generators, 0.0, 1.0);
+    }
+}
+
+/// One point on the engagement timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngagementEvent {
+    /// Seconds from the first shot
+    pub time: f64,
+    pub kind: EngagementEventKind,
+    /// Shield phase number for shield events
+    pub phase: Option<i32>,
+    /// Component number (1-based) for component events on ships with several of it
+    #[serde(default)]
+    pub instance: Option<i32>,
+    pub description: String,
+}
+
+impl EngagementEvent {
+    fn new(time: f64, kind: EngagementEventKind, phase: Option<i32>, description: impl Into<String>) -> Self {
+        Self { time, kind, phase, instance: None, description: description.into() }
+    }
+
+    fn with_instance(self, instance: Option<i32>) -> Self {
+        Self { instance, ..self }
+    }
+}
+
+/// Time step for the pass simulation (seconds)
+const PASS_SIM_STEP: f64 = 0.05;
+
+/// Pass simulations still running after this long report no kill (seconds)
+const PASS_SIM_MAX_TIME: f64 = 3600.0;
+
+/// Damage rates while guns are on target (per second)
+///
+/// Armor rates are for intact armor; the hull rates double as the unmitigated
+/// DPS that ablated armor fades toward.
+struct FireRates {
+    shield: f64,
+    armor_passthrough: f64,
+    hull_passthrough: f64,
+    armor: f64,
+    hull: f64,
+    armor_ablation: Option<ArmorAblation>,
+}
+
+impl FireRates {
+    /// (armor, hull) damage rates with `remaining` of the armor pool left
+    fn armor_and_hull(&self, shield_up: bool, remaining: f64) -> (f64, f64) {
+        let (armor, hull) = if shield_up {
+            (self.armor_passthrough, self.hull_passthrough)
+        } else {
+            (self.armor, self.hull)
+        };
+        match self.armor_ablation {
+            Some(ablation) => (ablation.damage_rate(hull, armor, remaining), hull),
+            None => (armor, hull),
+        }
+    }
+}
+
+/// Outcome of a pass-based engagement
+struct PassSimulation {
+    total_ttk: f64,
+    shields_down: Option<f64>,
+    armor_depleted: Option<f64>,
+    shield_phases: Vec<ShieldPhaseBoundary>,
+}
+
+/// Step through a pass-based engagement
+///
+/// Damage is only dealt during fire windows. Between passes the current shield
+/// phase regenerates once its damaged regen delay has run out; phases that were
+/// broken stay down. Armor and hull never regenerate.
+fn simulate_passes(
+    passes: &PassModel,
+    rates: &FireRates,
+    phases: &[ShieldPhase],
+    shield: &Shield,
+    armor_hp: f64,
+    hull_hp: f64,
+    regen_suppressed: bool,
+) -> PassSimulation {
+    let mut sim = PassSimulation {
+        total_ttk: f64::INFINITY,
+        shields_down: None,
+        armor_depleted: None,
+        shield_phases: Vec::new(),
+    };
+    let mut phase_index = 0;
+    let mut phase_hp = phases.first().map_or(0.0, |p| p.hp);
+    let mut online_at = 0.0;
+    let mut last_hit = f64::NEG_INFINITY;
+    let (mut armor, mut hull) = (armor_hp, hull_hp);
+
+    if let Some(first) = phases.first() {
+        sim.shield_phases.push(ShieldPhaseBoundary {
+            phase: 1, generators: first.generators, hp: first.hp, regen: first.regen, start: 0.0, end: f64::INFINITY,
+        });
+    }
+    if hull <= 0.0 {
+        sim.total_ttk = 0.0;
+        return sim;
+    }
+
+    let mut time = 0.0;
+    while time < PASS_SIM_MAX_TIME {
+        let firing = passes.is_firing(time);
+        let shield_up = phase_index < phases.len() && time >= online_at;
+        let step_end = time + PASS_SIM_STEP;
+
+        if shield_up {
+            let phase = &phases[phase_index];
+            let regenerating = if firing { !regen_suppressed } else { time - last_hit >= shield.damaged_regen_delay };
+            if regenerating {
+                phase_hp = (phase_hp + phase.regen * PASS_SIM_STEP).min(phase.hp);
+            }
+        }
+
+        if firing {
+            last_hit = time;
+            if shield_up {
+                phase_hp -= rates.shield * PASS_SIM_STEP;
+            }
+            let remaining_armor = if armor_hp > 0.0 { armor / armor_hp } else { 0.0 };
+            let (armor_rate, hull_rate) = rates.armor_and_hull(shield_up, remaining_armor);
+            if armor > 0.0 {
+                armor -= armor_rate * PASS_SIM_STEP;
+            } else {
+                hull -= hull_rate * PASS_SIM_STEP;
+            }
+
+            if shield_up && phase_hp <= 0.0 {
+                if let Some(current) = sim.shield_phases.last_mut() {
+                    current.end = step_end;
+                }
+                phase_index += 1;
+                match phases.get(phase_index) {
+                    Some(next) => {
+                        online_at = step_end + next.activation_delay;
+                        phase_hp = next.hp;
+                        sim.shield_phases.push(ShieldPhaseBoundary {
+                            phase: phase_index as i32 + 1,
+                            generators: next.generators,
+                            hp: next.hp,
+                            regen: next.regen,
+                            start: online_at,
+                            end: f64::INFINITY,
+                        });
+                    }
+                    None => sim.shields_down = Some(step_end),
+                }
+            }
+            if armor <= 0.0 && armor_hp > 0.0 && sim.armor_depleted.is_none() {
+                sim.armor_depleted = Some(step_end);
+            }
+            if hull <= 0.0 {
+                sim.total_ttk = step_end;
+                break;
+            }
+        }
+        time = step_end;
+    }
+
+    let total_ttk = sim.total_ttk;
+    sim.shield_phases.retain(|p| p.start < total_ttk);
+    sim
+}
+
+/// Build the engagement timeline from resolved phase times
+///
+/// `hull_window` is when the hull pool starts and finishes taking damage.
+/// Components are chewed through one instance at a time (see
+/// `Ship::derive_instance_hp`): power plants first, then coolers and shield
+/// generators. Each instance degrades below its `thresholds` share of its HP and
+/// is destroyed at 0; thrusters wear evenly, so they degrade together.
+fn engagement_events(
+    shield_phases: &[ShieldPhaseBoundary],
+    shields_down: Option<f64>,
+    armor_depleted: Option<f64>,
+    hull_window: Option<(f64, f64)>,
+    target: &Ship,
+    zone: &ZoneModifiers,
+    thresholds: &DegradedThresholds,
+) -> Vec<EngagementEvent> {
+    use EngagementEventKind::*;
+    let mut events = Vec::new();
+    let kill_time = hull_window.map_or(f64::INFINITY, |(_, end)| end);
+
+    for (i, phase) in shield_phases.iter().enumerate() {
+        if i > 0 {
+            events.push(EngagementEvent::new(
+                phase.start, ShieldPhaseOnline, Some(phase.phase),
+                format!("Shield phase {} online ({} generators)", phase.phase, phase.generators),
+            ));
+        }
+        let is_last = i + 1 == shield_phases.len();
+        if phase.end.is_finite() && phase.end <= kill_time && !(is_last && shields_down.is_some()) {
+            events.push(EngagementEvent::new(
+                phase.end, ShieldPhaseDown, Some(phase.phase),
+                format!("Shield phase {} down", phase.phase),
+            ));
+        }
+    }
+    if let Some(time) = shields_down {
+        events.push(EngagementEvent::new(time, ShieldsDown, shield_phases.last().map(|p| p.phase), "Shields down"));
+    }
+    if let Some(time) = armor_depleted {
+        events.push(EngagementEvent::new(time, ArmorDepleted, None, "Armor depleted"));
+    }
+
+    if let Some((start, end)) = hull_window {
+        let at = |fraction: f64| start + (end - start) * fraction;
+        if zone.thruster > 0.0 && target.thruster_total_hp > 0 {
+            events.push(EngagementEvent::new(
+                at(1.0 - thresholds.thrusters), ThrustersDegraded, None, "Thrusters degraded (reduced maneuverability)",
+            ));
+        }
+
+        let component_hp = (target.powerplant_total_hp + target.cooler_total_hp + target.shield_gen_total_hp) as f64;
+        let components = [
+            ("Power plant", "reduced power output", PowerPlantDegraded, PowerPlantDestroyed,
+                target.powerplant_instance_hp, target.powerplant_count, thresholds.power_plant),
+            ("Cooler", "reduced cooling", CoolersDegraded, CoolerDestroyed,
+                target.cooler_instance_hp, target.cooler_count, thresholds.coolers),
+            ("Shield generator", "reduced shield regen", ShieldGeneratorsDegraded, ShieldGeneratorDestroyed,
+                target.shield_gen_instance_hp, target.shield_gen_count, thresholds.shield_generators),
+        ];
+        let mut spent = 0.0;
+        for (name, effect, degraded, destroyed, hp, count, threshold) in components {
+            if zone.component <= 0.0 || component_hp <= 0.0 || hp <= 0.0 {
+                continue;
+            }
+            let count = count.max(1);
+            for i in 1..=count {
+                let (label, instance) = if count > 1 {
+                    (format!("{} {} of {}", name, i, count), Some(i))
+                } else {
+                    (name.to_string(), None)
+                };
+                let degraded_at = at((spent + hp * (1.0 - threshold)) / component_hp);
+                events.push(EngagementEvent::new(degraded_at, degraded, None, format!("{} degraded ({})", label, effect))
+                    .with_instance(instance));
+                spent += hp;
+                // The last instance goes with the hull
+                if spent / component_hp < 1.0 - 1e-9 {
+                    events.push(EngagementEvent::new(at(spent / component_hp), destroyed, None, format!("{} destroyed", label))
+                        .with_instance(instance));
+                }
+            }
+        }
+        events.push(EngagementEvent::new(end, TargetDestroyed, None, "Target destroyed"));
+    }
+
+    events.sort_by(|a, b| a.time.total_cmp(&b.time));
+    events
+}
+
+/// Hull damage dealt between critical part checks, as a fraction of the hull pool
+const CRITICAL_CHECK_INTERVAL: f64 = 0.25;
+
+/// Most critical part checks per hull pool (bounds the curve for extreme fuse multipliers)
+const MAX_CRITICAL_CHECKS: f64 = 100.0;
+
+/// One point on the probability-of-kill-by-time curve
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct KillProbabilityPoint {
+    pub time: f64,
+    pub probability: f64,
+}
+
+/// Probability-of-kill-by-time curve and expected kill time
+///
+/// A critical part is destroyed every `CRITICAL_CHECK_INTERVAL` of the hull pool,
+/// reached sooner by the fuse penetration multiplier, and each one detonates the
+/// ship with `critical_explosion_chance`. Hull damage is assumed linear across
+/// `hull_window`; whatever survives every check dies at the end of the window.
+fn kill_probability_curve(hull_window: Option<(f64, f64)>, target: &Ship) -> (Vec<KillProbabilityPoint>, f64) {
+    let Some((start, end)) = hull_window else {
+        return (vec![], f64::INFINITY);
+    };
+    let chance = target.critical_explosion_chance.clamp(0.0, 1.0);
+    let fuse = if target.fuse_penetration_mult > 0.0 { target.fuse_penetration_mult } else { 1.0 };
+    let step = (CRITICAL_CHECK_INTERVAL / fuse).max(1.0 / MAX_CRITICAL_CHECKS);
+
+    let mut curve = Vec::new();
+    let mut survival = 1.0;
+    let mut expected = 0.0;
+    if chance > 0.0 {
+        curve.push(KillProbabilityPoint { time: start, probability: 0.0 });
+        let mut fraction = step;
+        while fraction < 1.0 {
+            let time = start + (end - start) * fraction;
+            expected += survival * chance * time;
+            survival *= 1.0 - chance;
+            curve.push(KillProbabilityPoint { time, probability: 1.0 - survival });
+            fraction += step;
+        }
+    }
+    expected += survival * end;
+    curve.push(KillProbabilityPoint { time: end, probability: 1.0 });
+    (curve, expected)
 }
 
-/// Effective shield after applying Rule of Two
-#[derive(Debug, Clone)]
-struct EffectiveShield {
-    total_hp: f64,
-    regen: f64,
-    failover_phases: i32,
+/// Effective HP one damage type removed from each layer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LayerDamage {
+    pub shield: f64,
+    pub armor: f64,
+    pub hull: f64,
 }
 
-/// Per-weapon effectiveness analysis
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WeaponEffectiveness {
-    pub weapon_name: String,
-    pub hardpoint_label: Option<String>,  // Parent hardpoint/turret name
-    pub damage_type: String,  // "Ballistic", "Energy", "Distortion", "Mixed"
-    pub count: i32,
-    pub raw_dps: f64,
-    pub effective_dps: f64,
-    pub shield_dps: f64,      // DPS absorbed by shields
-    pub passthrough_dps: f64, // DPS bypassing shields
-    pub armor_dps: f64,       // DPS after armor resistance
-    pub hull_dps: f64,
-    pub solo_ttk: f64,        // TTK if only this weapon was equipped
-    pub shield_time: f64,     // Time this weapon takes on shields (solo)
-    pub armor_time: f64,      // Time on armor
-    pub hull_time: f64,       // Time on hull
-    pub is_effective: bool,   // false if DPS < shield regen
-    pub ineffective_reason: Option<String>,  // "Shield regen exceeds damage"
+/// What each damage type contributed to the kill
+///
+/// Each layer's lost HP is split in proportion to every type's damage rate on
+/// it: absorbed shield damage, then passthrough while the shields were up and
+/// the full mix after they fell. All zero when the target survives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DamageContribution {
+    pub physical: LayerDamage,
+    pub energy: LayerDamage,
+    pub distortion: LayerDamage,
 }
 
-/// Per-missile effectiveness analysis
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MissileEffectiveness {
-    pub missile_name: String,
-    pub hardpoint_label: Option<String>,  // Parent hardpoint/turret name
-    pub damage_type: String,  // Based on dominant damage type
-    pub count: i32,
-    pub total_damage: f64,    // Burst damage (not DPS)
-    pub shield_damage: f64,   // Damage to shields
-    pub passthrough_damage: f64, // Damage bypassing shields (physical)
-    pub armor_damage: f64,    // Damage after armor resistance
-    pub hull_damage: f64,
-    pub time_saved: f64,      // TTK reduction from this missile burst
-    pub is_effective: bool,
-    pub ineffective_reason: Option<String>,
+impl DamageContribution {
+    /// Add per-type HP removed from one layer
+    fn add(&mut self, split: DamageBreakdown, layer: fn(&mut LayerDamage) -> &mut f64) {
+        *layer(&mut self.physical) += split.physical;
+        *layer(&mut self.energy) += split.energy;
+        *layer(&mut self.distortion) += split.distortion;
+    }
 }
 
 /// Complete TTK calculation result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TTKResult {
     /// Time to deplete shields (seconds)
     pub shield_time: f64,
@@ -134,6 +1571,9 @@ pub struct TTKResult {
     pub total_ttk: f64,
     /// Damage breakdown by type (after accuracy)
     pub damage_breakdown: DamageBreakdown,
+    /// Effective HP each damage type removed per layer
+    #[serde(default)]
+    pub damage_contribution: DamageContribution,
     /// Effective DPS after all modifiers
     pub effective_dps: f64,
     /// DPS applied to shields (absorbed portion)
@@ -144,6 +1584,18 @@ pub struct TTKResult {
     pub armor_damage_during_shields: f64,
     /// Number of shield failover phases (Rule of Two)
     pub shield_failover_phases: i32,
+    /// Shield phase boundaries reached before the kill (Rule of Two)
+    #[serde(default)]
+    pub shield_phases: Vec<ShieldPhaseBoundary>,
+    /// Engagement timeline (shield phases, armor depleted, components, kill), in time order
+    #[serde(default)]
+    pub events: Vec<EngagementEvent>,
+    /// Probability the target is dead by each time (critical part explosions), ending at 1.0 on the kill
+    #[serde(default)]
+    pub kill_probability: Vec<KillProbabilityPoint>,
+    /// Mean time to kill including early critical explosions (equals total_ttk without critical data)
+    #[serde(default)]
+    pub expected_ttk: f64,
     /// Can shields be depleted by energy weapons? (false = only passthrough path works)
     pub shields_breakable: bool,
     /// Per-weapon effectiveness breakdown
@@ -158,21 +1610,75 @@ pub struct EquippedWeapon {
     pub weapon: Weapon,
     pub count: i32,
     pub name_with_label: String,  // Original name from frontend (may include "HARDPOINT::weapon_name")
+    #[serde(default)]
+    pub tuning: WeaponTuning,
+    #[serde(default)]
+    pub category: String,  // Hardpoint category ("pilot", "manned_turret", ...; empty = unknown)
+    #[serde(default)]
+    pub group: u32,  // Weapon group (fire_schedule alternates between groups)
+}
+
+/// Player weapon tuning (overclocking / attenuation)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeaponTuning {
+    /// Per-shot damage multiplier
+    pub damage_multiplier: f64,
+    /// Fire rate multiplier
+    pub fire_rate_multiplier: f64,
+    /// Fraction of sustained DPS lost to extra heat (overheat/cooldown downtime, 0.0-1.0)
+    pub heat_penalty: f64,
+}
+
+impl Default for WeaponTuning {
+    fn default() -> Self {
+        Self {
+            damage_multiplier: 1.0,
+            fire_rate_multiplier: 1.0,
+            heat_penalty: 0.0,
+        }
+    }
+}
+
+impl WeaponTuning {
+    /// Range-check the tuning factors (`field` prefixes the error field names)
+    pub fn validate(&self, field: &str, errors: &mut ValidationErrors) {
+        errors.check_range(&format!("{}.damage_multiplier", field), self.damage_multiplier, 0.5, 2.0);
+        errors.check_range(&format!("{}.fire_rate_multiplier", field), self.fire_rate_multiplier, 0.5, 2.0);
+        errors.check_range(&format!("{}.heat_penalty", field), self.heat_penalty, 0.0, 0.95);
+    }
+
+    /// Weapon stats with this tuning applied
+    ///
+    /// Per-shot damage scales with `damage_multiplier`, fire rate with
+    /// `fire_rate_multiplier`, and sustained DPS with both, less the heat penalty.
+    pub fn apply(&self, weapon: &Weapon) -> Weapon {
+        let mut tuned = weapon.clone();
+        tuned.damage_physical *= self.damage_multiplier;
+        tuned.damage_energy *= self.damage_multiplier;
+        tuned.damage_distortion *= self.damage_multiplier;
+        tuned.damage_per_shot *= self.damage_multiplier;
+        tuned.fire_rate *= self.fire_rate_multiplier;
+        tuned.sustained_dps *= self.damage_multiplier * self.fire_rate_multiplier * (1.0 - self.heat_penalty);
+        tuned
+    }
 }
 
 /// Calculate total damage output from weapons with scenario modifiers
 fn sum_weapon_damage(weapons: &[EquippedWeapon], scenario: &CombatScenario) -> DamageBreakdown {
-    let accuracy = scenario.mount_accuracy
-        * scenario.scenario_accuracy
-        * scenario.time_on_target
-        * scenario.fire_mode
+    let accuracy = scenario.scenario_accuracy
+        * scenario.effective_time_on_target()
+        * scenario.fire_factor()
         * scenario.power_multiplier;
 
     let mut damage = DamageBreakdown::default();
 
     for equipped in weapons {
         let count = equipped.count as f64;
-        let weapon = &equipped.weapon;
+        let weapon = &equipped.tuning.apply(&equipped.weapon);
+        let accuracy = accuracy
+            * scenario.mount_accuracy_for(&equipped.category)
+            * projectile_accuracy(weapon, scenario.engagement_range);
 
         // Calculate DPS per damage type based on per-shot damage ratios
         // The weapon has sustained_dps (total DPS) and damage_physical/energy/distortion (per-shot)
@@ -200,7 +1706,13 @@ fn sum_weapon_damage(weapons: &[EquippedWeapon], scenario: &CombatScenario) -> D
 /// - Physical: absorb_physical (typically 0.225) absorbed, rest passes through
 /// - Energy: fully absorbed (absorb_energy = 1.0)
 /// - Distortion: fully absorbed (absorb_distortion = 1.0)
-fn calculate_shield_damage(damage: &DamageBreakdown, shield: &Shield) -> (f64, f64) {
+pub(crate) fn calculate_shield_damage(damage: &DamageBreakdown, shield: &Shield) -> (f64, f64) {
+    let (shield_damage, passthrough) = shield_damage_by_type(damage, shield);
+    (shield_damage.total(), passthrough.total())
+}
+
+/// Shield damage and passthrough per damage type (see `calculate_shield_damage`)
+pub(crate) fn shield_damage_by_type(damage: &DamageBreakdown, shield: &Shield) -> (DamageBreakdown, DamageBreakdown) {
     // Physical: partially absorbed, rest passes through to armor
     let phys_absorbed = damage.physical * shield.absorb_physical;
     let phys_passthrough = damage.physical * (1.0 - shield.absorb_physical);
@@ -218,10 +1730,10 @@ fn calculate_shield_damage(damage: &DamageBreakdown, shield: &Shield) -> (f64, f
     let dist_shield_dmg = dist_absorbed * (1.0 - shield.resist_distortion);
     let dist_passthrough = damage.distortion * (1.0 - shield.absorb_distortion);
 
-    let total_shield_dps = phys_shield_dmg + energy_shield_dmg + dist_shield_dmg;
-    let total_passthrough = phys_passthrough + energy_passthrough + dist_passthrough;
-
-    (total_shield_dps, total_passthrough)
+    (
+        DamageBreakdown { physical: phys_shield_dmg, energy: energy_shield_dmg, distortion: dist_shield_dmg },
+        DamageBreakdown { physical: phys_passthrough, energy: energy_passthrough, distortion: dist_passthrough },
+    )
 }
 
 /// Apply Rule of Two for multi-shield ships
@@ -229,41 +1741,122 @@ fn calculate_shield_damage(damage: &DamageBreakdown, shield: &Shield) -> (f64, f
 /// Rule of Two mechanics:
 /// - Only 2 shield generators can be active at once
 /// - Additional generators are on standby
-/// - When active shields fail, standby pair activates
-/// - Each failover pair operates at ~80% efficiency
-fn apply_rule_of_two(shield: &Shield, shield_count: i32) -> EffectiveShield {
-    if shield_count <= 0 {
-        return EffectiveShield {
-            total_hp: 0.0,
-            regen: 0.0,
-            failover_phases: 0,
-        };
+/// - When active shields fail, the next standby pair activates after a delay
+/// - Standby generators come online at reduced efficiency (`standby_efficiency`)
+pub(crate) fn apply_rule_of_two(shield: &Shield, shield_count: i32, options: &RuleOfTwoOptions) -> EffectiveShield {
+    let count = shield_count.max(0) as usize;
+    apply_rule_of_two_mixed(&vec![shield.clone(); count], options)
+}
+
+/// Apply Rule of Two for a mixed shield loadout (one shield per generator slot)
+///
+/// Same mechanics as `apply_rule_of_two`, but each slot may hold a different model:
+/// - The first 2 slots are the active pair (full HP and regen)
+/// - Remaining slots come online in pairs, in slot order, as each phase fails
+pub(crate) fn apply_rule_of_two_mixed(shields: &[Shield], options: &RuleOfTwoOptions) -> EffectiveShield {
+    let (active, standby) = shields.split_at(shields.len().min(2));
+
+    let mut phases = Vec::new();
+    if !active.is_empty() {
+        phases.push(ShieldPhase {
+            generators: active.len() as i32,
+            hp: active.iter().map(|s| s.max_hp).sum(),
+            regen: active.iter().map(|s| s.regen).sum(),
+            activation_delay: 0.0,
+        });
+    }
+    // An odd standby generator comes online on its own as the last phase
+    for group in standby.chunks(2) {
+        phases.push(ShieldPhase {
+            generators: group.len() as i32,
+            hp: group.iter().map(|s| s.max_hp * options.standby_efficiency).sum(),
+            regen: group.iter().map(|s| s.regen).sum(),
+            activation_delay: options.activation_delay,
+        });
     }
 
-    let active_count = shield_count.min(2);
-    let standby_count = (shield_count - 2).max(0);
+    EffectiveShield {
+        total_hp: phases.iter().map(|p| p.hp).sum(),
+        regen: phases.first().map_or(0.0, |p| p.regen),
+        failover_phases: phases.len().saturating_sub(1) as i32,
+        phases,
+    }
+}
 
-    // Active shields provide full HP and regen
-    let active_hp = shield.max_hp * active_count as f64;
-    let active_regen = shield.regen * active_count as f64;
+/// Walk the shield phases in order against a constant shield DPS
+///
+/// Each phase starts after its activation delay with full HP and its own regen
+/// (regen restarts per phase unless sustained fire suppresses it). Stops at the
+/// first phase that cannot be broken.
+fn shield_phase_timeline(phases: &[ShieldPhase], shield_dps: f64, regen_suppressed: bool) -> Vec<ShieldPhaseBoundary> {
+    let mut timeline = Vec::new();
+    let mut time = 0.0;
+
+    for (i, phase) in phases.iter().enumerate() {
+        let start = time + phase.activation_delay;
+        let regen = if regen_suppressed { 0.0 } else { phase.regen };
+        let net_shield_dps = shield_dps - regen;
+        let end = if phase.hp <= 0.0 {
+            start
+        } else if net_shield_dps > 0.0 {
+            start + phase.hp / net_shield_dps
+        } else {
+            f64::INFINITY
+        };
 
-    // Standby shields add redundancy phases
-    // Each pair of standby shields = 1 additional phase at 80% efficiency
-    let failover_phases = standby_count / 2;
-    let redundant_hp = shield.max_hp * 2.0 * failover_phases as f64 * 0.8;
+        timeline.push(ShieldPhaseBoundary {
+            phase: i as i32 + 1,
+            generators: phase.generators,
+            hp: phase.hp,
+            regen: phase.regen,
+            start,
+            end,
+        });
+        if end.is_infinite() {
+            break;
+        }
+        time = end;
+    }
+    timeline
+}
 
-    // Odd standby shield adds half a phase worth
-    let odd_standby = if standby_count % 2 == 1 {
-        shield.max_hp * 0.8
-    } else {
-        0.0
+/// Build a single representative shield from a mixed loadout
+///
+/// HP and regen follow the Rule of Two split (see `apply_rule_of_two_mixed`):
+/// `max_hp` is the loadout's total over every phase and `regen` the active
+/// pair's. Absorption and resistances are averaged weighted by each generator's
+/// effective HP, since that is the share of incoming damage it ends up soaking.
+/// Regen delays come from the slowest generator in the active pair.
+pub fn composite_shield(shields: &[Shield], options: &RuleOfTwoOptions) -> Option<Shield> {
+    let first = shields.first()?;
+    let effective = apply_rule_of_two_mixed(shields, options);
+    // Standby generators come online at reduced efficiency
+    let hp = |i: usize, s: &Shield| if i < 2 { s.max_hp } else { s.max_hp * options.standby_efficiency };
+    let weighted = |f: fn(&Shield) -> f64| -> f64 {
+        if effective.total_hp > 0.0 {
+            shields.iter().enumerate().map(|(i, s)| f(s) * hp(i, s)).sum::<f64>() / effective.total_hp
+        } else {
+            shields.iter().map(f).sum::<f64>() / shields.len() as f64
+        }
     };
-
-    EffectiveShield {
-        total_hp: active_hp + redundant_hp + odd_standby,
-        regen: active_regen,
-        failover_phases,
-    }
+    let active = &shields[..shields.len().min(2)];
+
+    Some(Shield {
+        id: 0,
+        display_name: shields.iter().map(|s| s.display_name.as_str()).collect::<Vec<_>>().join(" + "),
+        internal_name: first.internal_name.clone(),
+        size: shields.iter().map(|s| s.size).max().unwrap_or(first.size),
+        max_hp: effective.total_hp,
+        regen: effective.regen,
+        resist_physical: weighted(|s| s.resist_physical),
+        resist_energy: weighted(|s| s.resist_energy),
+        resist_distortion: weighted(|s| s.resist_distortion),
+        absorb_physical: weighted(|s| s.absorb_physical),
+        absorb_energy: weighted(|s| s.absorb_energy),
+        absorb_distortion: weighted(|s| s.absorb_distortion),
+        damaged_regen_delay: active.iter().map(|s| s.damaged_regen_delay).fold(0.0, f64::max),
+        downed_regen_delay: active.iter().map(|s| s.downed_regen_delay).fold(0.0, f64::max),
+    })
 }
 
 /// Calculate armor damage with dual-layer damage system
@@ -282,18 +1875,26 @@ fn apply_rule_of_two(shield: &Shield, shield_count: i32) -> EffectiveShield {
 /// Total effective = damage × damage_mult × resist
 /// Example: 1000 physical → 1000 × 0.75 × 0.85 = 637.5 actual armor damage
 fn calculate_armor_damage(damage: &DamageBreakdown, target: &Ship) -> f64 {
+    armor_damage_by_type(damage, target).total()
+}
+
+/// Armor damage per damage type (see `calculate_armor_damage`)
+pub(crate) fn armor_damage_by_type(damage: &DamageBreakdown, target: &Ship) -> DamageBreakdown {
     // Layer 1 × Layer 2 for each damage type
-    let phys_dmg = damage.physical
-        * target.armor_damage_mult_physical
-        * target.armor_resist_physical;
-    let energy_dmg = damage.energy
-        * target.armor_damage_mult_energy
-        * target.armor_resist_energy;
-    let dist_dmg = damage.distortion
-        * target.armor_damage_mult_distortion
-        * target.armor_resist_distortion;
+    DamageBreakdown {
+        physical: damage.physical * target.armor_damage_mult_physical * target.armor_resist_physical,
+        energy: damage.energy * target.armor_damage_mult_energy * target.armor_resist_energy,
+        distortion: damage.distortion * target.armor_damage_mult_distortion * target.armor_resist_distortion,
+    }
+}
 
-    phys_dmg + energy_dmg + dist_dmg
+/// Armor damage rate over the whole armor pool, averaged over ablation when enabled
+fn armor_damage_rate(damage: &DamageBreakdown, target: &Ship, ablation: Option<ArmorAblation>) -> f64 {
+    let full = calculate_armor_damage(damage, target);
+    match ablation {
+        Some(ablation) => ablation.average_rate(damage.total(), full),
+        None => full,
+    }
 }
 
 /// Calculate per-weapon effectiveness analysis
@@ -303,12 +1904,14 @@ fn calculate_armor_damage(damage: &DamageBreakdown, target: &Ship) -> f64 {
 /// - Armor resistance
 /// - Solo TTK (if only this weapon was equipped)
 /// - Whether the weapon can overcome shield regeneration
-pub fn calculate_weapon_effectiveness(
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn calculate_weapon_effectiveness(
     weapon: &Weapon,
     weapon_name_with_label: &str,  // May contain "HARDPOINT::weapon_name"
     count: i32,
     target: &Ship,
     shield: &Shield,
+    effective_shield: &EffectiveShield,
     scenario: &CombatScenario,
     zone: &ZoneModifiers,
 ) -> WeaponEffectiveness {
@@ -326,44 +1929,34 @@ pub fn calculate_weapon_effectiveness(
     // 2. Apply scenario modifiers (accuracy)
     let accuracy = scenario.mount_accuracy
         * scenario.scenario_accuracy
-        * scenario.time_on_target
-        * scenario.fire_mode
-        * scenario.power_multiplier;
+        * scenario.effective_time_on_target()
+        * scenario.fire_factor()
+        * scenario.power_multiplier
+        * projectile_accuracy(weapon, scenario.engagement_range);
 
     let effective_dps = raw_dps * accuracy;
 
     // 3. Calculate damage breakdown by type
-    let total_per_shot = weapon.damage_physical + weapon.damage_energy + weapon.damage_distortion;
-    let damage = if total_per_shot > 0.0 {
-        DamageBreakdown {
-            physical: effective_dps * (weapon.damage_physical / total_per_shot),
-            energy: effective_dps * (weapon.damage_energy / total_per_shot),
-            distortion: effective_dps * (weapon.damage_distortion / total_per_shot),
-        }
-    } else {
-        DamageBreakdown::default()
-    };
+    let damage = DamageBreakdown::from_weapon_dps(weapon, effective_dps);
 
-    // 4. Shield phase calculation
+    // 4. Shield phase calculation (hardening only reduces damage taken by the shield)
     let (shield_dps, passthrough_dps) = calculate_shield_damage(&damage, shield);
+    let shield_dps = shield_dps * scenario.shield_hardening.shield_damage_factor();
 
-    // 5. Apply Rule of Two for shield calculation
-    let effective_shield = apply_rule_of_two(shield, target.shield_count);
-
-    // 6. Determine if weapon can break shields
-    // Regen suppression: sustained fire prevents regen from starting
-    let regen_suppressed = scenario.fire_mode >= 1.0 && shield.damaged_regen_delay > 0.0;
+    // 5. Determine if weapon can break shields
+    // Regen suppression: sustained fire prevents regen from starting (alone, one group)
+    let regen_suppressed = scenario.regen_suppressed(1, shield.damaged_regen_delay);
     let effective_regen = if regen_suppressed { 0.0 } else { effective_shield.regen };
     let net_shield_dps = (shield_dps - effective_regen).max(0.0);
     let shields_breakable = net_shield_dps > 0.0 && effective_shield.total_hp > 0.0;
 
-    // 7. Armor phase calculation
-    let armor_dps = calculate_armor_damage(&damage, target);
+    // 6. Armor phase calculation
+    let armor_dps = armor_damage_rate(&damage, target, scenario.armor_ablation);
 
-    // 8. Hull DPS (no resistance on hull typically)
+    // 7. Hull DPS (no resistance on hull typically)
     let hull_dps = damage.total();
 
-    // 9. Calculate solo TTK and phase timelines
+    // 8. Calculate solo TTK and phase timelines
     let zone_armor_hp = target.armor_hp * zone.armor;
     let zone_hull_hp = target.hull_hp * zone.hull;
     let zone_thruster_hp = target.thruster_total_hp as f64 * zone.thruster;
@@ -393,11 +1986,11 @@ pub fn calculate_weapon_effectiveness(
         (shield_time + armor_time + hull_time, shield_time, armor_time, hull_time)
     } else if passthrough_dps > 0.0 {
         // Passthrough path: can't break shields, but can kill via passthrough
-        let armor_passthrough_dps = calculate_armor_damage(&DamageBreakdown {
+        let armor_passthrough_dps = armor_damage_rate(&DamageBreakdown {
             physical: passthrough_dps,
             energy: 0.0,
             distortion: 0.0,
-        }, target);
+        }, target, scenario.armor_ablation);
 
         let armor_time = if zone_armor_hp > 0.0 && armor_passthrough_dps > 0.0 {
             zone_armor_hp / armor_passthrough_dps
@@ -451,6 +2044,7 @@ pub fn calculate_weapon_effectiveness(
         armor_dps,
         hull_dps,
         solo_ttk,
+        shots_to_kill: shots_to_kill(solo_ttk, weapon.fire_rate, count),
         shield_time: weapon_shield_time,
         armor_time: weapon_armor_time,
         hull_time: weapon_hull_time,
@@ -464,6 +2058,7 @@ pub fn calculate_weapon_effectiveness(
 /// Analyzes missile burst damage effectiveness against a target, including:
 /// - Shield absorption and passthrough
 /// - Armor resistance
+/// - Countermeasures and evasion (damage figures are expected values over hits)
 /// - Whether missiles can contribute meaningful damage
 pub fn calculate_missile_effectiveness(
     missile: &crate::data::Missile,
@@ -471,6 +2066,7 @@ pub fn calculate_missile_effectiveness(
     count: i32,
     target: &crate::data::Ship,
     shield: &crate::data::Shield,
+    countermeasures: &mut Countermeasures,
 ) -> MissileEffectiveness {
     // Parse hardpoint label if present
     let (hardpoint_label, actual_missile_name) = if missile_name_with_label.contains("::") {
@@ -484,11 +2080,13 @@ pub fn calculate_missile_effectiveness(
     let total_per_missile = missile.damage_physical + missile.damage_energy + missile.damage_distortion;
     let total_damage = total_per_missile * count as f64;
 
-    // 2. Build damage breakdown
+    // 2. Build damage breakdown from the missiles expected to land
+    let expected_hits = countermeasures.expected_hits(&missile.tracking_type, count);
+    let hit_probability = if count > 0 { expected_hits / count as f64 } else { 0.0 };
     let damage = DamageBreakdown {
-        physical: missile.damage_physical * count as f64,
-        energy: missile.damage_energy * count as f64,
-        distortion: missile.damage_distortion * count as f64,
+        physical: missile.damage_physical * expected_hits,
+        energy: missile.damage_energy * expected_hits,
+        distortion: missile.damage_distortion * expected_hits,
     };
 
     // 3. Shield phase calculation
@@ -503,9 +2101,11 @@ pub fn calculate_missile_effectiveness(
     // 6. Determine effectiveness
     // Missiles are always somewhat effective if they have physical damage (passthrough)
     // Or if they have enough energy damage to meaningfully impact shields
-    let is_effective = total_damage > 0.0;
+    let is_effective = total_damage > 0.0 && expected_hits > 0.0;
     let ineffective_reason = if total_damage == 0.0 {
         Some("No damage".to_string())
+    } else if expected_hits <= 0.0 {
+        Some("Defeated by countermeasures".to_string())
     } else {
         None
     };
@@ -547,6 +2147,8 @@ pub fn calculate_missile_effectiveness(
         armor_damage,
         hull_damage,
         time_saved,
+        hit_probability,
+        expected_hits,
         is_effective,
         ineffective_reason,
     }
@@ -568,7 +2170,42 @@ pub fn calculate_ttk(
     scenario: &CombatScenario,
     zone: &ZoneModifiers,
 ) -> TTKResult {
-    // 1. Calculate damage breakdown by type with accuracy modifiers
+    let effective_shield = apply_rule_of_two(shield, target.shield_count, &scenario.rule_of_two);
+    calculate_ttk_with_shield(weapons, target, shield, &effective_shield, scenario, zone)
+}
+
+/// TTK calculation for a mixed shield loadout (one shield per generator slot)
+///
+/// Falls back to `calculate_ttk_no_shields` when the loadout is empty.
+pub fn calculate_ttk_mixed_shields(
+    weapons: &[EquippedWeapon],
+    target: &Ship,
+    shields: &[Shield],
+    scenario: &CombatScenario,
+    zone: &ZoneModifiers,
+) -> TTKResult {
+    match composite_shield(shields, &scenario.rule_of_two) {
+        Some(composite) => {
+            let effective_shield = apply_rule_of_two_mixed(shields, &scenario.rule_of_two);
+            calculate_ttk_with_shield(weapons, target, &composite, &effective_shield, scenario, zone)
+        }
+        None => calculate_ttk_no_shields(weapons, target, scenario),
+    }
+}
+
+/// Core TTK calculation against an already-resolved effective shield
+fn calculate_ttk_with_shield(
+    weapons: &[EquippedWeapon],
+    target: &Ship,
+    shield: &Shield,
+    effective_shield: &EffectiveShield,
+    scenario: &CombatScenario,
+    zone: &ZoneModifiers,
+) -> TTKResult {
+    // 1. Calculate damage breakdown by type with accuracy modifiers (scaled for target size)
+    let angled = scenario.angled_armor(target);
+    let target: &Ship = &angled;
+    let scenario = &scenario.for_target(target);
     let damage = sum_weapon_damage(weapons, scenario);
 
     if damage.total() <= 0.0 {
@@ -578,57 +2215,64 @@ pub fn calculate_ttk(
             hull_time: 0.0,
             total_ttk: f64::INFINITY,
             damage_breakdown: damage,
+            damage_contribution: DamageContribution::default(),
             effective_dps: 0.0,
             shield_dps: 0.0,
             passthrough_dps: 0.0,
             armor_damage_during_shields: 0.0,
             shield_failover_phases: 0,
+            shield_phases: vec![],
+            events: vec![],
+            kill_probability: vec![],
+            expected_ttk: f64::INFINITY,
             shields_breakable: false,
             weapon_breakdown: vec![],
             missile_breakdown: vec![],
         };
     }
 
-    // 2. Shield phase with absorption
+    // 2. Shield phase with absorption (defender hardening reduces the absorbed part)
     let (shield_dps, passthrough_dps) = calculate_shield_damage(&damage, shield);
-
-    // 3. Apply Rule of Two for multi-shield ships
-    let effective_shield = apply_rule_of_two(shield, target.shield_count);
-
-    // 4. Shield time calculation (time to fully deplete shields)
-    // Regen suppression: If firing continuously (sustained fire mode), constant hits
-    // prevent shield regen from ever starting (each hit resets the damaged_regen_delay timer).
-    // For sustained fire with multiple weapons, regen is effectively 0.
-    let regen_suppressed = scenario.fire_mode >= 1.0 && shield.damaged_regen_delay > 0.0 && weapons.len() > 0;
-    let effective_regen = if regen_suppressed { 0.0 } else { effective_shield.regen };
-
-    let theoretical_shield_time = if effective_shield.total_hp > 0.0 {
-        let net_shield_dps = (shield_dps - effective_regen).max(0.0);
-        if net_shield_dps > 0.0 {
-            effective_shield.total_hp / net_shield_dps
-        } else {
-            f64::INFINITY // Can't break shields if DPS <= regen
-        }
-    } else {
-        0.0 // No shields
-    };
-
-    // 5. Apply zone modifiers to effective HP
+    let shield_dps = shield_dps * scenario.shield_hardening.shield_damage_factor();
+
+    // 3. Shield time calculation (time to fully deplete shields)
+    // Regen suppression: If firing continuously (sustained fire mode, or staggered groups
+    // leaving no long gaps), constant hits prevent shield regen from ever starting
+    // (each hit resets the damaged_regen_delay timer), so regen is effectively 0.
+    let regen_suppressed = scenario.regen_suppressed(weapon_group_count(weapons), shield.damaged_regen_delay);
+    // An active defender (and any boosters) makes the phases hold longer
+    let mut defended = scenario.defender.defend(effective_shield, shield_dps, regen_suppressed);
+    if let Some(booster) = scenario.shield_booster {
+        defended = booster.apply(&defended, shield_dps, regen_suppressed);
+    }
+    let effective_shield = &defended;
+
+    // Rule of Two phases are broken one after another; infinite if one outlasts the DPS
+    let shield_timeline = shield_phase_timeline(&effective_shield.phases, shield_dps, regen_suppressed);
+    let theoretical_shield_time = shield_timeline.last().map_or(0.0, |p| p.end);
+    // Failover gaps, during which no generator is up
+    let unshielded_time: f64 = shield_timeline.iter()
+        .zip(&effective_shield.phases)
+        .map(|(_, phase)| phase.activation_delay)
+        .sum();
+
+    // 4. Apply zone modifiers to effective HP
     let zone_armor_hp = target.armor_hp * zone.armor;
     let zone_hull_hp = target.hull_hp * zone.hull;
     let zone_thruster_hp = target.thruster_total_hp as f64 * zone.thruster;
     let zone_component_hp = (target.powerplant_total_hp + target.cooler_total_hp + target.shield_gen_total_hp) as f64 * zone.component;
     let total_hull_hp = zone_hull_hp + zone_thruster_hp + zone_component_hp;
 
-    // 6. Calculate passthrough damage path
+    // 5. Calculate passthrough damage path
     // With ballistics, armor/hull can be destroyed while shields are up via passthrough
+    let passthrough_damage = DamageBreakdown {
+        physical: passthrough_dps,
+        energy: 0.0,
+        distortion: 0.0,
+    };
     let armor_passthrough_dps = if passthrough_dps > 0.0 {
         // Passthrough goes to armor first, apply armor resistances
-        calculate_armor_damage(&DamageBreakdown {
-            physical: passthrough_dps,
-            energy: 0.0,
-            distortion: 0.0,
-        }, target)
+        armor_damage_rate(&passthrough_damage, target, scenario.armor_ablation)
     } else {
         0.0
     };
@@ -654,11 +2298,26 @@ pub fn calculate_ttk(
     // Total time to kill via passthrough path (target dies while shields are still up)
     let passthrough_kill_time = time_to_destroy_armor_via_passthrough + time_to_destroy_hull_via_passthrough;
 
-    // 7. Calculate normal path (shields break, then armor, then hull)
-    // Armor damage during shield phase (passthrough from ballistics)
-    let armor_damage_during_shields = if theoretical_shield_time.is_finite() && passthrough_dps > 0.0 {
-        // Calculate how much armor passthrough damages during shield phase
-        let max_armor_damage = armor_passthrough_dps * theoretical_shield_time;
+    // 6. Calculate normal path (shields break, then armor, then hull)
+    let armor_dps = armor_damage_rate(&damage, target, scenario.armor_ablation);
+    let hull_dps = damage.total();
+
+    // Average armor/hull damage rates over the shield phase: passthrough while a
+    // phase is up, full damage during failover gaps
+    let (armor_rate_during_shields, hull_rate_during_shields) = if theoretical_shield_time > 0.0 && theoretical_shield_time.is_finite() {
+        let shielded_time = theoretical_shield_time - unshielded_time;
+        (
+            (armor_passthrough_dps * shielded_time + armor_dps * unshielded_time) / theoretical_shield_time,
+            (passthrough_dps * shielded_time + hull_dps * unshielded_time) / theoretical_shield_time,
+        )
+    } else {
+        (armor_passthrough_dps, passthrough_dps)
+    };
+
+    // Armor damage during shield phase (passthrough from ballistics, plus failover gaps)
+    let armor_damage_during_shields = if theoretical_shield_time.is_finite() && armor_rate_during_shields > 0.0 {
+        // Calculate how much armor is damaged during shield phase
+        let max_armor_damage = armor_rate_during_shields * theoretical_shield_time;
         max_armor_damage.min(zone_armor_hp) // Can't do more damage than armor HP
     } else if theoretical_shield_time.is_infinite() && passthrough_dps > 0.0 {
         // Shields never break, all armor damage happens via passthrough
@@ -668,9 +2327,9 @@ pub fn calculate_ttk(
     };
 
     // Hull damage during shield phase (if armor is destroyed before shields)
-    let hull_damage_during_shields = if theoretical_shield_time.is_finite() && passthrough_dps > 0.0 {
-        let time_armor_depleted = if armor_passthrough_dps > 0.0 && zone_armor_hp > 0.0 {
-            zone_armor_hp / armor_passthrough_dps
+    let hull_damage_during_shields = if theoretical_shield_time.is_finite() && hull_rate_during_shields > 0.0 {
+        let time_armor_depleted = if armor_rate_during_shields > 0.0 && zone_armor_hp > 0.0 {
+            zone_armor_hp / armor_rate_during_shields
         } else {
             0.0
         };
@@ -678,7 +2337,7 @@ pub fn calculate_ttk(
         if time_armor_depleted < theoretical_shield_time {
             // Armor is destroyed before shields - passthrough hits hull for remaining time
             let remaining_shield_time = theoretical_shield_time - time_armor_depleted;
-            (passthrough_dps * remaining_shield_time).min(total_hull_hp)
+            (hull_rate_during_shields * remaining_shield_time).min(total_hull_hp)
         } else {
             0.0
         }
@@ -693,7 +2352,6 @@ pub fn calculate_ttk(
     let remaining_hull = (total_hull_hp - hull_damage_during_shields).max(0.0);
 
     // Armor phase with resistances (after shields are down)
-    let armor_dps = calculate_armor_damage(&damage, target);
     let armor_time = if remaining_armor > 0.0 && armor_dps > 0.0 {
         remaining_armor / armor_dps
     } else {
@@ -701,14 +2359,13 @@ pub fn calculate_ttk(
     };
 
     // Hull phase (after armor, when shields are down)
-    let hull_dps = damage.total();
     let hull_time = if remaining_hull > 0.0 && hull_dps > 0.0 {
         remaining_hull / hull_dps
     } else {
         0.0
     };
 
-    // 8. Calculate total TTK - take the shorter path
+    // 7. Calculate total TTK - take the shorter path
     // Path A: Break shields, then destroy remaining armor/hull
     // Path B: Kill via passthrough while shields are up
 
@@ -739,21 +2396,119 @@ pub fn calculate_ttk(
         (f64::INFINITY, 0.0, 0.0)
     };
 
-    // 9. Calculate per-weapon effectiveness breakdown
+    // Only phases that came online before the target died
+    let shield_phases: Vec<ShieldPhaseBoundary> = shield_timeline.into_iter().filter(|p| p.start < total_ttk).collect();
+
+    // Event timeline for the chosen path
+    let killed_via_passthrough = passthrough_dps > 0.0 && passthrough_kill_time < shield_break_path_ttk && passthrough_kill_time.is_finite();
+    let (events, hull_window) = if killed_via_passthrough {
+        let armor_depleted = (zone_armor_hp > 0.0).then_some(time_to_destroy_armor_via_passthrough);
+        let hull_window = Some((time_to_destroy_armor_via_passthrough, total_ttk));
+        (engagement_events(&shield_phases, None, armor_depleted, hull_window, target, zone, &scenario.degraded_thresholds), hull_window)
+    } else if total_ttk.is_finite() {
+        let shields_down = (effective_shield.total_hp > 0.0).then_some(theoretical_shield_time);
+        let armor_depleted = if zone_armor_hp <= 0.0 {
+            None
+        } else if remaining_armor <= 0.0 && armor_rate_during_shields > 0.0 {
+            // Passthrough/failover damage took the armor before the shields fell
+            Some(zone_armor_hp / armor_rate_during_shields)
+        } else {
+            Some(theoretical_shield_time + armor_time)
+        };
+        let hull_window = Some((theoretical_shield_time + armor_time, total_ttk));
+        (engagement_events(&shield_phases, shields_down, armor_depleted, hull_window, target, zone, &scenario.degraded_thresholds), hull_window)
+    } else {
+        let armor_depleted = (zone_armor_hp > 0.0 && time_to_destroy_armor_via_passthrough.is_finite())
+            .then_some(time_to_destroy_armor_via_passthrough);
+        (engagement_events(&shield_phases, None, armor_depleted, None, target, zone, &scenario.degraded_thresholds), None)
+    };
+
+    // Pass-based engagements are simulated instead (shields regen between passes)
+    let (total_ttk, display_shield_time, display_armor_time, display_hull_time, shield_phases, events, hull_window) = match scenario.passes {
+        Some(passes) => {
+            // Average DPS already includes the duty cycle; undo it for the fire windows
+            let duty = passes.duty_cycle();
+            // The simulation ablates armor step by step, so it starts from the intact-armor rates
+            let rates = FireRates {
+                shield: shield_dps / duty,
+                armor_passthrough: calculate_armor_damage(&passthrough_damage, target) / duty,
+                hull_passthrough: passthrough_dps / duty,
+                armor: calculate_armor_damage(&damage, target) / duty,
+                hull: hull_dps / duty,
+                armor_ablation: scenario.armor_ablation,
+            };
+            let sim = simulate_passes(&passes, &rates, &effective_shield.phases, shield, zone_armor_hp, total_hull_hp, regen_suppressed);
+
+            let (shield_time, armor_time, hull_time) = if sim.total_ttk.is_infinite() {
+                (f64::INFINITY, 0.0, 0.0)
+            } else {
+                let shield_time = sim.shields_down.unwrap_or(0.0);
+                let armor_end = sim.armor_depleted.unwrap_or(0.0).max(shield_time);
+                (shield_time, armor_end - shield_time, sim.total_ttk - armor_end)
+            };
+            let hull_window = sim.total_ttk.is_finite()
+                .then(|| (sim.armor_depleted.unwrap_or(0.0).max(sim.shields_down.unwrap_or(0.0)), sim.total_ttk));
+            let events = engagement_events(&sim.shield_phases, sim.shields_down, sim.armor_depleted, hull_window, target, zone, &scenario.degraded_thresholds);
+            (sim.total_ttk, shield_time, armor_time, hull_time, sim.shield_phases, events, hull_window)
+        }
+        None => (total_ttk, display_shield_time, display_armor_time, display_hull_time, shield_phases, events, hull_window),
+    };
+    let (kill_probability, expected_ttk) = kill_probability_curve(hull_window, target);
+
+    // What each damage type took off each layer
+    let mut damage_contribution = DamageContribution::default();
+    if total_ttk.is_finite() {
+        let (shield_by_type, passthrough_by_type) = shield_damage_by_type(&damage, shield);
+        let shield_removed: f64 = shield_phases.iter()
+            .map(|phase| {
+                if phase.end <= total_ttk {
+                    phase.hp
+                } else if phase.end.is_finite() && phase.start < total_ttk {
+                    phase.hp * (total_ttk - phase.start) / (phase.end - phase.start)
+                } else {
+                    0.0
+                }
+            })
+            .sum();
+        // Armor and hull lost while the shields were up came from the passthrough,
+        // plus the full mix during failover gaps
+        let (armor_shielded, hull_shielded, gap_weight) = if killed_via_passthrough {
+            (zone_armor_hp, total_hull_hp, 0.0)
+        } else {
+            let shielded_time = theoretical_shield_time - unshielded_time;
+            (armor_damage_during_shields, hull_damage_during_shields, unshielded_time / shielded_time.max(f64::EPSILON))
+        };
+        let armor_by_type = armor_damage_by_type(&damage, target);
+        let during_shields = |passthrough: &DamageBreakdown, full: &DamageBreakdown| DamageBreakdown {
+            physical: passthrough.physical + full.physical * gap_weight,
+            energy: passthrough.energy + full.energy * gap_weight,
+            distortion: passthrough.distortion + full.distortion * gap_weight,
+        };
+        let armor_passthrough_by_type = armor_damage_by_type(&passthrough_by_type, target);
+        damage_contribution.add(shield_by_type.share(shield_removed), |l| &mut l.shield);
+        damage_contribution.add(during_shields(&armor_passthrough_by_type, &armor_by_type).share(armor_shielded), |l| &mut l.armor);
+        damage_contribution.add(armor_by_type.share(zone_armor_hp - armor_shielded), |l| &mut l.armor);
+        damage_contribution.add(during_shields(&passthrough_by_type, &damage).share(hull_shielded), |l| &mut l.hull);
+        damage_contribution.add(damage.share(total_hull_hp - hull_shielded), |l| &mut l.hull);
+    }
+
+    // 8. Calculate per-weapon effectiveness breakdown
     // Group weapons by name_with_label (preserves hardpoint grouping from frontend)
     use std::collections::HashMap;
-    let mut weapon_groups: HashMap<String, (Weapon, i32)> = HashMap::new();
+    let mut weapon_groups: HashMap<String, (Weapon, i32, &str)> = HashMap::new();
 
     for equipped in weapons {
         weapon_groups.entry(equipped.name_with_label.clone())
-            .and_modify(|(_, count)| *count += equipped.count)
-            .or_insert((equipped.weapon.clone(), equipped.count));
+            .and_modify(|(_, count, _)| *count += equipped.count)
+            .or_insert((equipped.tuning.apply(&equipped.weapon), equipped.count, &equipped.category));
     }
 
     let mut weapon_breakdown: Vec<WeaponEffectiveness> = weapon_groups
         .iter()
-        .map(|(name_with_label, (weapon, count))| {
-            calculate_weapon_effectiveness(weapon, name_with_label, *count, target, shield, scenario, zone)
+        .map(|(name_with_label, (weapon, count, category))| {
+            calculate_weapon_effectiveness(
+                weapon, name_with_label, *count, target, shield, effective_shield, &scenario.for_category(category), zone,
+            )
         })
         .collect();
 
@@ -782,11 +2537,16 @@ pub fn calculate_ttk(
         hull_time: display_hull_time,
         total_ttk,
         damage_breakdown: damage,
+        damage_contribution,
         effective_dps: hull_dps,
         shield_dps,
         passthrough_dps,
         armor_damage_during_shields,
         shield_failover_phases: effective_shield.failover_phases,
+        shield_phases,
+        events,
+        kill_probability,
+        expected_ttk,
         shields_breakable,
         weapon_breakdown,
         missile_breakdown: vec![],  // No missiles passed to this function yet
@@ -799,6 +2559,9 @@ pub fn calculate_ttk_no_shields(
     target: &Ship,
     scenario: &CombatScenario,
 ) -> TTKResult {
+    let angled = scenario.angled_armor(target);
+    let target: &Ship = &angled;
+    let scenario = &scenario.for_target(target);
     let damage = sum_weapon_damage(weapons, scenario);
 
     if damage.total() <= 0.0 {
@@ -808,18 +2571,23 @@ pub fn calculate_ttk_no_shields(
             hull_time: 0.0,
             total_ttk: f64::INFINITY,
             damage_breakdown: damage,
+            damage_contribution: DamageContribution::default(),
             effective_dps: 0.0,
             shield_dps: 0.0,
             passthrough_dps: 0.0,
             armor_damage_during_shields: 0.0,
             shield_failover_phases: 0,
+            shield_phases: vec![],
+            events: vec![],
+            kill_probability: vec![],
+            expected_ttk: f64::INFINITY,
             shields_breakable: true,
             weapon_breakdown: vec![],
             missile_breakdown: vec![],
         };
     }
 
-    let armor_dps = calculate_armor_damage(&damage, target);
+    let armor_dps = armor_damage_rate(&damage, target, scenario.armor_ablation);
     let armor_time = if target.armor_hp > 0.0 && armor_dps > 0.0 {
         target.armor_hp / armor_dps
     } else {
@@ -833,17 +2601,32 @@ pub fn calculate_ttk_no_shields(
         0.0
     };
 
+    let total_ttk = armor_time + hull_time;
+    let armor_depleted = (target.armor_hp > 0.0).then_some(armor_time);
+    // No zone targeting here, so components use the center-mass split
+    let events = engagement_events(&[], None, armor_depleted, Some((armor_time, total_ttk)), target, &ZoneModifiers::default(), &scenario.degraded_thresholds);
+    let (kill_probability, expected_ttk) = kill_probability_curve(Some((armor_time, total_ttk)), target);
+
+    let mut damage_contribution = DamageContribution::default();
+    damage_contribution.add(armor_damage_by_type(&damage, target).share(target.armor_hp), |l| &mut l.armor);
+    damage_contribution.add(damage.share(target.hull_hp), |l| &mut l.hull);
+
     TTKResult {
         shield_time: 0.0,
         armor_time,
         hull_time,
-        total_ttk: armor_time + hull_time,
+        total_ttk,
         damage_breakdown: damage.clone(),
+        damage_contribution,
         effective_dps: hull_dps,
         shield_dps: 0.0,
         passthrough_dps: hull_dps, // All damage goes to armor/hull (same as effective_dps)
         armor_damage_during_shields: 0.0,
         shield_failover_phases: 0,
+        shield_phases: vec![],
+        events,
+        kill_probability,
+        expected_ttk,
         shields_breakable: true,
         weapon_breakdown: vec![],
         missile_breakdown: vec![],
@@ -851,11 +2634,13 @@ pub fn calculate_ttk_no_shields(
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
-    fn make_test_weapon(phys: f64, energy: f64, dist: f64) -> Weapon {
+    /// S3 test gun doing `phys`/`energy`/`dist` DPS (shared with other modules' tests)
+    pub(crate) fn make_test_weapon(phys: f64, energy: f64, dist: f64) -> Weapon {
         Weapon {
+            id: 0,
             display_name: "Test Weapon".to_string(),
             filename: "test_weapon".to_string(),
             size: 3,
@@ -871,11 +2656,19 @@ mod tests {
             weapon_type: "gun".to_string(),
             restricted_to: vec![],
             ship_exclusive: false,
+            manufacturer: String::new(),
+            manufacturer_code: String::new(),
+            fire_rate: 600.0,
+            damage_per_shot: (phys + energy + dist) / 10.0,
+            speed: 1000.0,
+            max_range: 2000.0,
+            heat_per_shot: 0.0,
         }
     }
 
     fn make_test_shield() -> Shield {
         Shield {
+            id: 0,
             display_name: "Test Shield".to_string(),
             internal_name: "test_shield".to_string(),
             size: 2,
@@ -887,11 +2680,13 @@ mod tests {
             absorb_physical: 0.225,   // Only 22.5% absorbed
             absorb_energy: 1.0,       // Fully absorbed
             absorb_distortion: 1.0,   // Fully absorbed
+            damaged_regen_delay: 5.0,
+            downed_regen_delay: 10.0,
         }
     }
 
     fn make_test_ship() -> Ship {
-        Ship {
+        let mut ship = Ship {
             filename: "test_ship".to_string(),
             display_name: "Test Ship".to_string(),
             hull_hp: 5000.0,
@@ -919,7 +2714,10 @@ mod tests {
             shield_count: 2,
             default_shield_ref: "".to_string(),
             weapon_hardpoints: vec![],
-        }
+            ..Default::default()
+        };
+        ship.derive_instance_hp();
+        ship
     }
 
     #[test]
@@ -962,23 +2760,70 @@ mod tests {
     #[test]
     fn test_rule_of_two() {
         let shield = make_test_shield();
+        let options = RuleOfTwoOptions::default();
 
         // 2 shields = no failover
-        let eff2 = apply_rule_of_two(&shield, 2);
+        let eff2 = apply_rule_of_two(&shield, 2, &options);
         assert_eq!(eff2.failover_phases, 0);
         assert!((eff2.total_hp - 20000.0).abs() < 0.1);
 
         // 4 shields = 1 failover phase
-        let eff4 = apply_rule_of_two(&shield, 4);
+        let eff4 = apply_rule_of_two(&shield, 4, &options);
         assert_eq!(eff4.failover_phases, 1);
         // 2 active + 2 standby at 80% = 20000 + 16000 = 36000
         assert!((eff4.total_hp - 36000.0).abs() < 0.1);
 
         // 6 shields = 2 failover phases
-        let eff6 = apply_rule_of_two(&shield, 6);
+        let eff6 = apply_rule_of_two(&shield, 6, &options);
         assert_eq!(eff6.failover_phases, 2);
         // 2 active + 4 standby at 80% = 20000 + 32000 = 52000
         assert!((eff6.total_hp - 52000.0).abs() < 0.1);
+
+        // Phases come up one after another, each with its own regen
+        let timeline = shield_phase_timeline(&eff4.phases, 3000.0, false);
+        assert_eq!(timeline.len(), 2);
+        assert!((timeline[0].end - 10.0).abs() < 1e-9); // 20000 / (3000 - 1000)
+        assert!((timeline[1].start - 11.0).abs() < 1e-9); // 1s activation delay
+        assert!((timeline[1].end - 19.0).abs() < 1e-9); // 16000 / (3000 - 1000)
+
+        // Regen outpacing DPS stops the timeline at the unbreakable phase
+        let stalled = shield_phase_timeline(&eff4.phases, 800.0, false);
+        assert_eq!(stalled.len(), 1);
+        assert!(stalled[0].end.is_infinite());
+    }
+
+    #[test]
+    fn test_rule_of_two_mixed() {
+        let shield = make_test_shield();
+        let options = RuleOfTwoOptions::default();
+        let mut big = make_test_shield();
+        big.max_hp = 20000.0;
+        big.regen = 1000.0;
+        big.absorb_physical = 0.45;
+
+        // Identical loadout matches the single-model path
+        let same = apply_rule_of_two_mixed(&vec![shield.clone(); 4], &options);
+        assert_eq!(same.failover_phases, 1);
+        assert!((same.total_hp - 36000.0).abs() < 0.1);
+
+        // Big pair active, small pair on standby: 40000 + 2 * 10000 * 0.8 = 56000
+        let mixed = vec![big.clone(), big.clone(), shield.clone(), shield.clone()];
+        let eff = apply_rule_of_two_mixed(&mixed, &options);
+        assert_eq!(eff.failover_phases, 1);
+        assert!((eff.total_hp - 56000.0).abs() < 0.1);
+        assert!((eff.regen - 2000.0).abs() < 0.1);
+
+        // HP-weighted absorption: (20000 * 0.45 + 10000 * 0.225) / 30000 = 0.375
+        let composite = composite_shield(&[big.clone(), shield.clone()], &options).unwrap();
+        assert!((composite.absorb_physical - 0.375).abs() < 1e-9);
+        // HP and regen are summed over the generators, not averaged
+        assert!((composite.max_hp - 30000.0).abs() < 0.1);
+        assert!((composite.regen - (big.regen + shield.regen)).abs() < 0.1);
+        // The composite of a mixed loadout matches its Rule of Two split
+        let composite = composite_shield(&mixed, &options).unwrap();
+        assert!((composite.max_hp - eff.total_hp).abs() < 0.1);
+        assert!((composite.regen - eff.regen).abs() < 0.1);
+        assert!(composite_shield(&[], &options).is_none());
     }
 
     #[test]
@@ -1007,7 +2852,7 @@ mod tests {
     #[test]
     fn test_full_ttk_calculation() {
         let weapon = make_test_weapon(500.0, 500.0, 0.0);
-        let equipped = vec![EquippedWeapon { weapon, count: 2 }];
+        let equipped = vec![EquippedWeapon { weapon, count: 2, name_with_label: "Test Weapon".to_string(), tuning: WeaponTuning::default(), category: String::new(), group: 0 }];
         let target = make_test_ship();
         let shield = make_test_shield();
         let scenario = CombatScenario {
@@ -1016,6 +2861,19 @@ mod tests {
             time_on_target: 1.0,
             fire_mode: 1.0,
             power_multiplier: 1.0,
+            engagement_range: 0.0,
+            rule_of_two: RuleOfTwoOptions::default(),
+            shield_hardening: ShieldHardening::default(),
+            passes: None,
+            target_profile_factor: None,
+            evasion_factor: None,
+            armor_ablation: None,
+            impact_angle: None,
+            gunner_profiles: GunnerProfiles::default(),
+            fire_schedule: None,
+            defender: DefenderBehavior::Passive,
+            shield_booster: None,
+            degraded_thresholds: DegradedThresholds::default(),
         };
         let zone = ZoneModifiers::default(); // Center mass
 
@@ -1033,12 +2891,102 @@ mod tests {
 
         // Passthrough should be present (ballistic component)
         assert!(result.passthrough_dps > 0.0);
+
+        // Timeline is ordered and ends with the kill
+        assert!(result.events.windows(2).all(|w| w[0].time <= w[1].time));
+        let last = result.events.last().unwrap();
+        assert_eq!(last.kind, EngagementEventKind::TargetDestroyed);
+        assert!((last.time - result.total_ttk).abs() < 1e-9);
+
+        // Hardening only slows the shield phase; passthrough is unchanged
+        let hardened_scenario = CombatScenario {
+            shield_hardening: ShieldHardening { uptime: 0.5, damage_reduction: 0.6 },
+            ..scenario
+        };
+        let hardened = calculate_ttk(&equipped, &target, &shield, &hardened_scenario, &zone);
+        assert!((hardened.shield_dps - result.shield_dps * 0.7).abs() < 1e-9);
+        assert!((hardened.passthrough_dps - result.passthrough_dps).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_damage_contribution() {
+        // Energy + distortion, no physical: nothing passes through the shields
+        let weapon = make_test_weapon(0.0, 500.0, 500.0);
+        let equipped = vec![EquippedWeapon { weapon, count: 2, name_with_label: "Test Weapon".to_string(), tuning: WeaponTuning::default(), category: String::new(), group: 0 }];
+        let target = make_test_ship();
+        let zone = ZoneModifiers::default();
+        let scenario = CombatScenario::default();
+        let result = calculate_ttk(&equipped, &target, &make_test_shield(), &scenario, &zone);
+        assert!(result.total_ttk.is_finite());
+
+        let c = result.damage_contribution;
+        let layer_total = |f: fn(&LayerDamage) -> f64| f(&c.physical) + f(&c.energy) + f(&c.distortion);
+        let shield_hp: f64 = result.shield_phases.iter().map(|p| p.hp).sum();
+        let (armor_hp, hull_hp) = zone.pools(&target);
+        assert!((layer_total(|l| l.shield) - shield_hp).abs() < 1e-6);
+        assert!((layer_total(|l| l.armor) - armor_hp).abs() < 1e-6);
+        assert!((layer_total(|l| l.hull) - hull_hp).abs() < 1e-6);
+
+        // Distortion is mostly resisted by the shield; no physical damage at all
+        assert!(c.distortion.shield < c.energy.shield * 0.2);
+        assert_eq!(c.physical, LayerDamage::default());
+
+        // A target that survives gets an empty table
+        let no_guns = calculate_ttk(&[], &target, &make_test_shield(), &scenario, &zone);
+        assert_eq!(no_guns.damage_contribution, DamageContribution::default());
+    }
+
+    #[test]
+    fn test_zone_validation() {
+        for preset in ZonePreset::ALL {
+            if let Some(zone) = preset.modifiers() {
+                assert!(zone.validate().is_ok(), "{:?} preset should be valid", preset);
+            }
+        }
+
+        let over = ZoneModifiers { hull: 0.6, armor: 0.4, thruster: 0.1, component: 0.0 };
+        assert!(over.validate().unwrap_err().contains("sum to at most 1.0"));
+
+        let negative = ZoneModifiers { hull: -0.1, armor: 0.3, thruster: 0.0, component: 0.0 };
+        assert!(negative.validate().is_err());
+    }
+
+    #[test]
+    fn test_request_validation() {
+        for preset in ScenarioPreset::ALL {
+            let mut errors = ValidationErrors::default();
+            preset.scenario().validate(&mut errors);
+            assert!(errors.errors.is_empty(), "{:?} preset should be valid", preset);
+        }
+
+        let mut errors = ValidationErrors::default();
+        CombatScenario { mount_accuracy: -0.5, ..CombatScenario::default() }.validate(&mut errors);
+        errors.check_counts("weapon_counts", &[2, 10_000]);
+        let fields: Vec<&str> = errors.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["mount_accuracy", "weapon_counts[1]"]);
+        assert!(errors.to_string().contains("- weapon_counts[1]: must be between 0 and 64"));
+    }
+
+    #[test]
+    fn test_ship_zone_table() {
+        let mut target = make_test_ship();
+        target.hitbox_zones = vec![crate::data::HitboxZone { zone: "engines".to_string(), hull: 0.1, armor: 0.1, thruster: 0.8, component: 0.0 }];
+
+        assert_eq!(ZonePreset::Engines.modifiers_for(&target).unwrap().thruster, 0.8);
+        // Zones without a row fall back to the generic shares
+        assert_eq!(ZonePreset::Cockpit.modifiers_for(&target).unwrap().component, 0.3);
+        assert!(ZonePreset::Custom.modifiers_for(&target).is_none());
+
+        let presets = zone_presets(Some(&target));
+        let specific: Vec<_> = presets.iter().filter(|p| p.ship_specific).map(|p| p.id).collect();
+        assert_eq!(specific, [ZonePreset::Engines]);
+        assert_eq!(zone_presets(None)[1].modifiers.as_ref().unwrap().thruster, 0.6);
     }
 
     #[test]
     fn test_zone_modifiers_affect_ttk() {
         let weapon = make_test_weapon(1000.0, 0.0, 0.0);
-        let equipped = vec![EquippedWeapon { weapon, count: 1 }];
+        let equipped = vec![EquippedWeapon { weapon, count: 1, name_with_label: "Test Weapon".to_string(), tuning: WeaponTuning::default(), category: String::new(), group: 0 }];
         let target = make_test_ship();
         let shield = make_test_shield();
         let scenario = CombatScenario {
@@ -1047,6 +2995,19 @@ mod tests {
             time_on_target: 1.0,
             fire_mode: 1.0,
             power_multiplier: 1.0,
+            engagement_range: 0.0,
+            rule_of_two: RuleOfTwoOptions::default(),
+            shield_hardening: ShieldHardening::default(),
+            passes: None,
+            target_profile_factor: None,
+            evasion_factor: None,
+            armor_ablation: None,
+            impact_angle: None,
+            gunner_profiles: GunnerProfiles::default(),
+            fire_schedule: None,
+            defender: DefenderBehavior::Passive,
+            shield_booster: None,
+            degraded_thresholds: DegradedThresholds::default(),
         };
 
         // Center mass (default: 60% hull, 30% armor)
@@ -1085,4 +3046,385 @@ mod tests {
             "Targeting engines should be faster: engines={}, center={}",
             result_engines.total_ttk, result_center.total_ttk);
     }
+
+    #[test]
+    fn test_pass_model_regen_between_passes() {
+        let weapon = make_test_weapon(0.0, 1000.0, 0.0);
+        let equipped = vec![EquippedWeapon { weapon, count: 2, name_with_label: "Test Weapon".to_string(), tuning: WeaponTuning::default(), category: String::new(), group: 0 }];
+        let target = make_test_ship();
+        let shield = make_test_shield();
+        let zone = ZoneModifiers::default();
+
+        // Same average time on target, with and without merges between passes
+        let flat = CombatScenario { time_on_target: 1.0 / 3.0, ..CombatScenario::default() };
+        let passes = CombatScenario {
+            passes: Some(PassModel { pass_duration: 4.0, time_between_passes: 8.0 }),
+            ..CombatScenario::default()
+        };
+        let flat_result = calculate_ttk(&equipped, &target, &shield, &flat, &zone);
+        let pass_result = calculate_ttk(&equipped, &target, &shield, &passes, &zone);
+
+        assert!((pass_result.effective_dps - flat_result.effective_dps).abs() < 1e-9);
+        assert!(pass_result.total_ttk.is_finite());
+        // Shields recover during each merge, so the kill takes longer
+        assert!(pass_result.total_ttk > flat_result.total_ttk);
+        assert_eq!(pass_result.events.last().unwrap().kind, EngagementEventKind::TargetDestroyed);
+    }
+
+    #[test]
+    fn test_fire_schedule() {
+        let gun = |group: u32| EquippedWeapon {
+            weapon: make_test_weapon(0.0, 1000.0, 0.0),
+            count: 1,
+            name_with_label: format!("G{}::Test Weapon", group),
+            tuning: WeaponTuning::default(),
+            category: String::new(),
+            group,
+        };
+        let equipped = vec![gun(0), gun(1)];
+        assert_eq!(weapon_group_count(&equipped), 2);
+        let target = make_test_ship();
+        let shield = make_test_shield();
+        let zone = ZoneModifiers::default();
+
+        // Each group fires 2s of every 8s; alternating leaves 4s gaps, together 6s
+        let schedule = FireSchedule { burst: 2.0, rest: 6.0, alternate: true };
+        assert_eq!(schedule.duty_cycle(), 0.25);
+        assert_eq!(schedule.longest_gap(2), 4.0);
+        assert_eq!(FireSchedule { alternate: false, ..schedule }.longest_gap(2), 6.0);
+
+        let scenario = |schedule: FireSchedule| CombatScenario {
+            mount_accuracy: 1.0,
+            scenario_accuracy: 1.0,
+            time_on_target: 1.0,
+            target_profile_factor: Some(1.0),
+            fire_schedule: Some(schedule),
+            ..CombatScenario::default()
+        };
+        let alternating = calculate_ttk(&equipped, &target, &shield, &scenario(schedule), &zone);
+        let together = calculate_ttk(&equipped, &target, &shield, &scenario(FireSchedule { alternate: false, ..schedule }), &zone);
+
+        // Same uptime, but only alternating keeps hits landing within the 5s regen delay
+        assert!((alternating.effective_dps - 500.0).abs() < 1e-9);
+        assert!((together.effective_dps - alternating.effective_dps).abs() < 1e-9);
+        assert!(alternating.total_ttk.is_finite());
+        assert!(together.total_ttk.is_infinite());
+    }
+
+    #[test]
+    fn test_defender_behavior() {
+        let weapon = make_test_weapon(0.0, 1000.0, 0.0);
+        let equipped = vec![EquippedWeapon { weapon, count: 2, name_with_label: "Test Weapon".to_string(), tuning: WeaponTuning::default(), category: String::new(), group: 0 }];
+        let target = make_test_ship();
+        let shield = make_test_shield();
+        let zone = ZoneModifiers::default();
+        let run = |defender: DefenderBehavior| {
+            let scenario = CombatScenario { defender, ..CombatScenario::default() };
+            calculate_ttk(&equipped, &target, &shield, &scenario, &zone)
+        };
+        let passive = run(DefenderBehavior::Passive);
+
+        // Balanced faces: 25% more HP per phase, so the shield phase lasts 25% longer
+        let balanced = run(DefenderBehavior::FaceBalancing { hp_bonus: 0.25 });
+        assert!((balanced.shield_time / passive.shield_time - 1.25).abs() < 1e-9);
+
+        // 20000 HP pair against 950 shield DPS: each 50% boost buys ~10.5s, so a
+        // 5s cooldown spends every charge and a 60s one only the first
+        let quick = run(DefenderBehavior::RegenItems { charges: 3, restore: 0.5, cooldown: 5.0 });
+        let slow = run(DefenderBehavior::RegenItems { charges: 3, restore: 0.5, cooldown: 60.0 });
+        assert!((quick.shield_time / passive.shield_time - 2.5).abs() < 1e-9);
+        assert!((slow.shield_time / passive.shield_time - 1.5).abs() < 1e-9);
+
+        let mut errors = ValidationErrors::default();
+        DefenderBehavior::RegenItems { charges: -1, restore: 0.5, cooldown: 5.0 }.validate(&mut errors);
+        assert_eq!(errors.errors[0].field, "defender.charges");
+    }
+
+    #[test]
+    fn test_shield_booster() {
+        let weapon = make_test_weapon(0.0, 1000.0, 0.0);
+        let equipped = vec![EquippedWeapon { weapon, count: 2, name_with_label: "Test Weapon".to_string(), tuning: WeaponTuning::default(), category: String::new(), group: 0 }];
+        let target = make_test_ship();
+        let shield = make_test_shield();
+        let zone = ZoneModifiers::default();
+        let run = |shield_booster: Option<ShieldBooster>| {
+            let scenario = CombatScenario { shield_booster, ..CombatScenario::default() };
+            calculate_ttk(&equipped, &target, &shield, &scenario, &zone)
+        };
+        let passive = run(None);
+
+        // 20000 HP pair: two 5000 HP boosters add half again
+        let boosted = run(Some(ShieldBooster { charges: 2, restore_hp: 5000.0, cooldown: 5.0 }));
+        assert!((boosted.shield_time / passive.shield_time - 1.5).abs() < 1e-9);
+        // A booster never restores more than the phase's full HP
+        let oversized = run(Some(ShieldBooster { charges: 1, restore_hp: 1_000_000.0, cooldown: 5.0 }));
+        assert!((oversized.shield_time / passive.shield_time - 2.0).abs() < 1e-9);
+
+        let mut errors = ValidationErrors::default();
+        ShieldBooster { charges: 2, restore_hp: 5000.0, cooldown: -1.0 }.validate(&mut errors);
+        assert_eq!(errors.errors[0].field, "shield_booster.cooldown");
+    }
+
+    #[test]
+    fn test_gunner_profiles() {
+        let scenario = CombatScenario {
+            gunner_profiles: GunnerProfiles { manned_turret: Some(GunnerProfile::Expert), ..GunnerProfiles::default() },
+            ..CombatScenario::default()
+        };
+        assert_eq!(scenario.mount_accuracy_for("manned_turret"), 0.85);
+        assert_eq!(scenario.mount_accuracy_for("remote_turret"), scenario.mount_accuracy);
+        assert_eq!(scenario.mount_accuracy_for("pilot"), scenario.mount_accuracy);
+
+        // Applied per weapon: only the turret gun gains accuracy
+        let gun = |category: &str| EquippedWeapon {
+            weapon: make_test_weapon(0.0, 1000.0, 0.0),
+            count: 1,
+            name_with_label: format!("{}::Test Weapon", category),
+            tuning: WeaponTuning::default(),
+            category: category.to_string(),
+            group: 0,
+        };
+        let pilot = sum_weapon_damage(&[gun("pilot")], &scenario).total();
+        let turret = sum_weapon_damage(&[gun("manned_turret")], &scenario).total();
+        assert!((turret / pilot - 0.85 / 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_countermeasures() {
+        // No stock or evasion: every missile lands
+        assert_eq!(Countermeasures::default().expected_hits("IR", 4), 4.0);
+
+        let mut countermeasures = Countermeasures { decoys: 2, noise: 1, evasion_chance: 0.1, ..Countermeasures::default() };
+        // Two of four IR missiles draw a decoy (50% spoof), then the stock is gone
+        assert!((countermeasures.expected_hits("IR", 4) - 3.0 * 0.9).abs() < 1e-9);
+        assert_eq!(countermeasures.decoys, 0);
+        assert!((countermeasures.expected_hits("IR", 1) - 0.9).abs() < 1e-9);
+        // Noise is shared by EM and CS seekers
+        assert!((countermeasures.expected_hits("EM", 1) - 0.6 * 0.9).abs() < 1e-9);
+        assert!((countermeasures.expected_hits("CS", 1) - 0.9).abs() < 1e-9);
+
+        let missile = crate::data::Missile {
+            id: 0,
+            name: "test_missile".to_string(),
+            display_name: "Test Missile".to_string(),
+            size: 3,
+            missile_type: "missile".to_string(),
+            tracking_type: "IR".to_string(),
+            damage_physical: 2000.0,
+            damage_energy: 0.0,
+            damage_distortion: 0.0,
+            explosion_min_radius: 1.0,
+            explosion_max_radius: 5.0,
+            max_lifetime: 10.0,
+            arm_time: 0.5,
+            lock_time: 2.0,
+        };
+        let mut countermeasures = Countermeasures { decoys: 10, ..Countermeasures::default() };
+        let result = calculate_missile_effectiveness(&missile, "Test Missile", 2, &make_test_ship(), &make_test_shield(), &mut countermeasures);
+        assert_eq!(result.hit_probability, 0.5);
+        assert_eq!(result.total_damage, 4000.0);
+        assert_eq!(result.hull_damage, 2000.0);
+    }
+
+    #[test]
+    fn test_armor_ablation() {
+        let ablation = ArmorAblation::default();
+        // Intact armor gives the full reduction, stripped armor none
+        assert_eq!(ablation.damage_rate(1000.0, 600.0, 1.0), 600.0);
+        assert_eq!(ablation.damage_rate(1000.0, 600.0, 0.0), 1000.0);
+        let average = ablation.average_rate(1000.0, 600.0);
+        assert!(average > 600.0 && average < 1000.0);
+
+        let weapon = make_test_weapon(1000.0, 0.0, 0.0);
+        let equipped = vec![EquippedWeapon { weapon, count: 1, name_with_label: "Test Weapon".to_string(), tuning: WeaponTuning::default(), category: String::new(), group: 0 }];
+        let target = make_test_ship();
+        let flat = calculate_ttk_no_shields(&equipped, &target, &CombatScenario::default());
+        let ablating = CombatScenario { armor_ablation: Some(ablation), ..CombatScenario::default() };
+        let ablated = calculate_ttk_no_shields(&equipped, &target, &ablating);
+        assert!(ablated.armor_time < flat.armor_time);
+        assert_eq!(ablated.hull_time, flat.hull_time);
+
+        // The pass simulation ablates too
+        let passes = Some(PassModel { pass_duration: 4.0, time_between_passes: 8.0 });
+        let shield = make_test_shield();
+        let zone = ZoneModifiers::default();
+        let flat = calculate_ttk(&equipped, &target, &shield, &CombatScenario { passes, ..CombatScenario::default() }, &zone);
+        let ablated = calculate_ttk(&equipped, &target, &shield, &CombatScenario { passes, ..ablating }, &zone);
+        assert!(ablated.total_ttk < flat.total_ttk);
+    }
+
+    #[test]
+    fn test_target_profile_factor() {
+        let mut target = make_test_ship();
+        assert_eq!(target_profile_factor(&target), 1.0);
+
+        target.size_class = "capital".to_string();
+        assert_eq!(target_profile_factor(&target), 1.35);
+
+        // Hitbox data wins over the size class; small targets are harder to hit
+        target.hitbox_length = 12.0;
+        let factor = target_profile_factor(&target);
+        assert!((0.75..1.0).contains(&factor));
+
+        let scenario = CombatScenario::default().for_target(&target);
+        assert!((scenario.scenario_accuracy - 0.75 * factor).abs() < 1e-9);
+        // Explicit factor overrides the derived one, and re-applying is a no-op
+        let fixed = CombatScenario { target_profile_factor: Some(1.0), ..CombatScenario::default() };
+        assert_eq!(fixed.for_target(&target).scenario_accuracy, 0.75);
+        assert_eq!(scenario.for_target(&target).scenario_accuracy, scenario.scenario_accuracy);
+    }
+
+    #[test]
+    fn test_impact_angle() {
+        let ballistic = vec![EquippedWeapon { weapon: make_test_weapon(1000.0, 0.0, 0.0), count: 1, name_with_label: "Test Weapon".to_string(), tuning: WeaponTuning::default(), category: String::new(), group: 0 }];
+        let laser = vec![EquippedWeapon { weapon: make_test_weapon(0.0, 1000.0, 0.0), ..ballistic[0].clone() }];
+        let target = make_test_ship();
+        let at = |angle: Option<f64>| CombatScenario { impact_angle: angle, ..CombatScenario::default() };
+
+        assert_eq!(ImpactBand::of_angle(10.0), ImpactBand::Penetrating);
+        assert_eq!(ImpactBand::of_angle(45.0), ImpactBand::Normal);
+        assert_eq!(ImpactBand::of_angle(75.0), ImpactBand::Glance);
+
+        let flat = calculate_ttk_no_shields(&ballistic, &target, &at(None));
+        assert_eq!(calculate_ttk_no_shields(&ballistic, &target, &at(Some(45.0))).armor_time, flat.armor_time);
+        assert!(calculate_ttk_no_shields(&ballistic, &target, &at(Some(80.0))).armor_time > flat.armor_time);
+        assert!(calculate_ttk_no_shields(&ballistic, &target, &at(Some(0.0))).armor_time < flat.armor_time);
+        // Energy ignores the angle
+        assert_eq!(
+            calculate_ttk_no_shields(&laser, &target, &at(Some(80.0))).armor_time,
+            calculate_ttk_no_shields(&laser, &target, &at(None)).armor_time,
+        );
+
+        let mut errors = ValidationErrors::default();
+        at(Some(95.0)).validate(&mut errors);
+        assert_eq!(errors.errors[0].field, "impact_angle");
+    }
+
+    #[test]
+    fn test_evasion_factor() {
+        let mut target = make_test_ship();
+        assert_eq!(agility_class(&target), None);
+        assert_eq!(evasion_factor(&target), 1.0);
+
+        target.size_class = "large".to_string();
+        assert_eq!(agility_class(&target), Some("sluggish"));
+
+        // Thrust-to-mass beats the size class, turn rates beat both
+        target.mass = 50_000.0;
+        target.main_thrust = 50_000.0 * 9.81 * 9.0;
+        assert_eq!(agility_class(&target), Some("agile"));
+        target.pitch_rate = 80.0;
+        target.yaw_rate = 70.0;
+        assert_eq!(agility_class(&target), Some("nimble"));
+
+        let scenario = CombatScenario::default().for_target(&target);
+        assert!((scenario.time_on_target - 0.65 * 0.85).abs() < 1e-9);
+        assert_eq!(scenario.for_target(&target).time_on_target, scenario.time_on_target);
+        let fixed = CombatScenario { evasion_factor: Some(1.25), target_profile_factor: Some(1.0), ..CombatScenario::default() };
+        assert!((fixed.for_target(&target).time_on_target - 0.8125).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_component_degraded_events() {
+        // Test ship: 900 HP thrusters; 500 power plant, 300 coolers, 400 shield generators
+        let target = make_test_ship();
+        let thresholds = DegradedThresholds::default();
+        let events = engagement_events(&[], None, None, Some((10.0, 22.0)), &target, &ZoneModifiers::default(), &thresholds);
+        let at = |kind: EngagementEventKind| events.iter().find(|e| e.kind == kind).map_or(f64::NAN, |e| e.time);
+        assert!((at(EngagementEventKind::ThrustersDegraded) - 16.0).abs() < 1e-9);
+        assert!((at(EngagementEventKind::PowerPlantDegraded) - 12.5).abs() < 1e-9);
+        assert!((at(EngagementEventKind::CoolersDegraded) - 16.5).abs() < 1e-9);
+        assert!((at(EngagementEventKind::ShieldGeneratorsDegraded) - 20.0).abs() < 1e-9);
+        // Degrades before it is destroyed
+        assert!((at(EngagementEventKind::PowerPlantDestroyed) - 15.0).abs() < 1e-9);
+        assert!((at(EngagementEventKind::CoolerDestroyed) - 18.0).abs() < 1e-9);
+        // The last generator goes with the hull
+        assert!(at(EngagementEventKind::ShieldGeneratorDestroyed).is_nan());
+
+        // Two 250 HP power plants each degrade and go down in turn
+        let mut twin = make_test_ship();
+        twin.powerplant_count = 2;
+        twin.derive_instance_hp();
+        let events = engagement_events(&[], None, None, Some((10.0, 22.0)), &twin, &ZoneModifiers::default(), &thresholds);
+        let times = |kind: EngagementEventKind| -> Vec<f64> {
+            events.iter().filter(|e| e.kind == kind).map(|e| e.time).collect()
+        };
+        let close = |a: Vec<f64>, b: [f64; 2]| a.len() == 2 && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-9);
+        assert!(close(times(EngagementEventKind::PowerPlantDegraded), [11.25, 13.75]));
+        assert!(close(times(EngagementEventKind::PowerPlantDestroyed), [12.5, 15.0]));
+        let second = events.iter().filter(|e| e.kind == EngagementEventKind::PowerPlantDestroyed).nth(1).unwrap();
+        assert_eq!(second.instance, Some(2));
+        assert_eq!(second.description, "Power plant 2 of 2 destroyed");
+
+        // A lower threshold holds out longer
+        let custom = DegradedThresholds { power_plant: 0.25, ..DegradedThresholds::default() };
+        let events = engagement_events(&[], None, None, Some((10.0, 22.0)), &target, &ZoneModifiers::default(), &custom);
+        let degraded = events.iter().find(|e| e.kind == EngagementEventKind::PowerPlantDegraded).unwrap();
+        assert!((degraded.time - 13.75).abs() < 1e-9);
+
+        // Shots kept off the thrusters never degrade them
+        let no_thrusters = ZoneModifiers { thruster: 0.0, ..ZoneModifiers::default() };
+        let events = engagement_events(&[], None, None, Some((10.0, 22.0)), &target, &no_thrusters, &thresholds);
+        assert!(events.iter().all(|e| e.kind != EngagementEventKind::ThrustersDegraded));
+    }
+
+    #[test]
+    fn test_kill_probability_curve() {
+        let mut target = make_test_ship();
+        // No critical data: certain kill at the end of the hull window
+        let (curve, expected) = kill_probability_curve(Some((10.0, 20.0)), &target);
+        assert_eq!(curve.len(), 1);
+        assert_eq!(expected, 20.0);
+
+        // Checks at 25/50/75% of the hull pool, each a 20% detonation chance
+        target.critical_explosion_chance = 0.2;
+        let (curve, expected) = kill_probability_curve(Some((10.0, 20.0)), &target);
+        assert_eq!(curve.len(), 5);
+        assert!((curve[3].probability - (1.0 - 0.8f64.powi(3))).abs() < 1e-9);
+        assert!(expected < 20.0 && expected > 10.0);
+        assert!(curve.windows(2).all(|w| w[0].probability <= w[1].probability));
+
+        // Fuse penetration reaches critical parts sooner
+        target.fuse_penetration_mult = 2.0;
+        let (_, faster) = kill_probability_curve(Some((10.0, 20.0)), &target);
+        assert!(faster < expected);
+        assert!(kill_probability_curve(None, &target).1.is_infinite());
+
+        // Absurd multipliers don't blow up the curve
+        target.fuse_penetration_mult = 1e6;
+        assert!(kill_probability_curve(Some((10.0, 20.0)), &target).0.len() <= MAX_CRITICAL_CHECKS as usize + 2);
+    }
+
+    #[test]
+    fn test_weapon_tuning() {
+        let weapon = make_test_weapon(500.0, 500.0, 0.0);
+        let tuning = WeaponTuning { damage_multiplier: 1.2, fire_rate_multiplier: 0.9, heat_penalty: 0.1 };
+        let tuned = tuning.apply(&weapon);
+        assert!((tuned.damage_physical - 600.0).abs() < 1e-9);
+        assert!((tuned.sustained_dps - weapon.sustained_dps * 1.2 * 0.9 * 0.9).abs() < 1e-9);
+
+        let mut errors = ValidationErrors::default();
+        WeaponTuning { heat_penalty: 1.0, ..tuning }.validate("weapon_tuning[0]", &mut errors);
+        assert_eq!(errors.errors[0].field, "weapon_tuning[0].heat_penalty");
+    }
+
+    #[test]
+    fn test_projectile_accuracy_and_shots_to_kill() {
+        // 1000 m/s, 2000 m max range
+        let weapon = make_test_weapon(1000.0, 0.0, 0.0);
+
+        // Range disabled or within reference flight time: no penalty
+        assert_eq!(projectile_accuracy(&weapon, 0.0), 1.0);
+        assert_eq!(projectile_accuracy(&weapon, 400.0), 1.0);
+        // 2.0s flight time: sqrt(0.5 / 2.0) = 0.5
+        let mut slow = weapon.clone();
+        slow.speed = 500.0;
+        assert!((projectile_accuracy(&slow, 1000.0) - 0.5).abs() < 1e-9);
+        // Beyond max range: no hits
+        assert_eq!(projectile_accuracy(&weapon, 2500.0), 0.0);
+
+        // 10s at 600 RPM with 2 guns = 200 shots
+        assert_eq!(shots_to_kill(10.0, 600.0, 2), 200.0);
+        assert!(shots_to_kill(f64::INFINITY, 600.0, 2).is_infinite());
+    }
 }
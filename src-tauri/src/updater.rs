@@ -0,0 +1,210 @@
+//! Ship Lens Linux Updater
+//!
+//! The Tauri updater has no Linux package entry, so Linux installs update here.
+//! - Flatpak: the sandbox can't install packages, so `flatpak update` is run on the host
+//! - Native: the .rpm/.deb/.pkg.tar.zst is downloaded from GitHub releases into the
+//!   app's private cache dir, checked against its published SHA-256 and installed
+//!   with the system package manager via pkexec (resuming interrupted downloads)
+
+use crate::channel;
+use crate::download::{self, DownloadProgress};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const RELEASES_URL: &str = "https://github.com/CapCeph/ship-lens/releases/download";
+
+/// Present in every Flatpak sandbox (keyfile with the app id under `[Application]`)
+const FLATPAK_INFO: &str = "/.flatpak-info";
+
+/// Flatpak app id when running inside a Flatpak sandbox
+pub fn flatpak_app_id() -> Option<String> {
+    if let Ok(id) = std::env::var("FLATPAK_ID") {
+        if !id.is_empty() {
+            return Some(id);
+        }
+    }
+    parse_flatpak_info(&fs::read_to_string(FLATPAK_INFO).ok()?)
+}
+
+/// App id (`name=`) from the `[Application]` group of a `.flatpak-info` keyfile
+fn parse_flatpak_info(info: &str) -> Option<String> {
+    let mut in_application = false;
+    for line in info.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_application = line == "[Application]";
+        } else if in_application {
+            if let Some(name) = line.strip_prefix("name=") {
+                return Some(name.trim().to_string()).filter(|n| !n.is_empty());
+            }
+        }
+    }
+    None
+}
+
+/// Detect Linux package manager type
+pub fn detect_package_manager() -> Option<&'static str> {
+    // Check for DNF (Fedora, RHEL 8+)
+    if PathBuf::from("/usr/bin/dnf").exists() {
+        return Some("dnf");
+    }
+    // Check for APT (Debian, Ubuntu)
+    if PathBuf::from("/usr/bin/apt").exists() {
+        return Some("apt");
+    }
+    // Check for pacman (Arch, Manjaro, EndeavourOS)
+    if PathBuf::from("/usr/bin/pacman").exists() {
+        return Some("pacman");
+    }
+    // Fallback: check for rpm vs dpkg
+    if PathBuf::from("/usr/bin/rpm").exists() {
+        return Some("rpm");
+    }
+    if PathBuf::from("/usr/bin/dpkg").exists() {
+        return Some("dpkg");
+    }
+    None
+}
+
+/// Update to `version` by whichever route matches how the app was installed
+///
+/// Packages download into `download_dir` (a directory only this user can write);
+/// `on_progress` is called while they download (see `download`).
+pub fn install_update(version: &str, download_dir: &Path, on_progress: impl Fn(&DownloadProgress)) -> Result<String, String> {
+    match flatpak_app_id() {
+        Some(app_id) => update_flatpak(&app_id),
+        None => install_package(version, download_dir, on_progress),
+    }
+}
+
+/// Update a Flatpak install through the host's `flatpak` (needs `--talk-name=org.freedesktop.Flatpak`)
+fn update_flatpak(app_id: &str) -> Result<String, String> {
+    let manual = format!(
+        "Ship Lens is installed as a Flatpak; update it from your software center or run `flatpak update {}`",
+        app_id
+    );
+
+    log::info!("Running flatpak update for {} on the host", app_id);
+    let output = Command::new("flatpak-spawn")
+        .args(["--host", "flatpak", "update", "-y", "--noninteractive", app_id])
+        .output()
+        .map_err(|e| format!("{} (could not reach the host: {})", manual, e))?;
+
+    if output.status.success() {
+        Ok("Update installed successfully. Please restart the application.".to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        log::error!("Flatpak update failed: {}", stderr.trim());
+        Err(format!("{}\n{}", manual, stderr.trim()))
+    }
+}
+
+/// Package owning the running executable when pacman installed it, and whether it
+/// is foreign (built from the AUR rather than a sync repository)
+fn pacman_owner() -> Option<(String, bool)> {
+    let exe = std::env::current_exe().ok()?;
+    let output = Command::new("pacman").arg("-Qqo").arg(&exe).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let package = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let foreign = Command::new("pacman")
+        .args(["-Qqm", &package])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    Some((package, foreign))
+}
+
+/// Release asset (file extension, file name) for a package manager
+fn package_asset(pkg_manager: &str, version: &str) -> Option<(&'static str, String)> {
+    match pkg_manager {
+        "dnf" | "rpm" => Some(("rpm", format!("Ship.Lens-{}-1.x86_64.rpm", version))),
+        "apt" | "dpkg" => Some(("deb", format!("Ship.Lens_{}_amd64.deb", version))),
+        "pacman" => Some(("pkg.tar.zst", format!("ship-lens-{}-1-x86_64.pkg.tar.zst", version))),
+        _ => None,
+    }
+}
+
+/// Download the release package (resumably), verify it and install it via pkexec
+fn install_package(version: &str, download_dir: &Path, on_progress: impl Fn(&DownloadProgress)) -> Result<String, String> {
+    channel::check_version(version)?;
+    let pkg_manager = detect_package_manager()
+        .ok_or_else(|| "Could not detect package manager".to_string())?;
+
+    // AUR builds are rebuilt from the PKGBUILD; replacing them with the release package
+    // would leave the AUR helper out of sync
+    if pkg_manager == "pacman" {
+        if let Some((package, true)) = pacman_owner() {
+            return Err(format!(
+                "Ship Lens was installed from the AUR ({0}); update it with your AUR helper, e.g. `yay -Syu {0}`",
+                package
+            ));
+        }
+    }
+
+    // Determine package type and URL based on package manager
+    let (pkg_type, filename) = package_asset(pkg_manager, version)
+        .ok_or_else(|| format!("Unsupported package manager: {}", pkg_manager))?;
+    let pkg_url = format!("{}/v{}/{}", RELEASES_URL, version, filename);
+
+    // Download into the private cache dir: a shared temp dir would let another
+    // user plant the file that gets installed as root
+    fs::create_dir_all(download_dir)
+        .map_err(|e| format!("Failed to create {}: {}", download_dir.display(), e))?;
+    let pkg_path = download_dir.join(format!("ship-lens-{}.{}", version, pkg_type));
+
+    log::info!("Downloading {} to {:?}", pkg_url, pkg_path);
+
+    download::download_verified(&pkg_url, &pkg_path, on_progress)?;
+
+    log::info!("Downloaded and verified package {:?}, installing via pkexec...", pkg_path);
+
+    // Install command for the package manager (run directly, without a shell)
+    let install_args: &[&str] = match pkg_manager {
+        "dnf" => &["dnf", "install", "-y"],
+        "apt" => &["apt", "install", "-y"],
+        "rpm" => &["rpm", "-U"],
+        "dpkg" => &["dpkg", "-i"],
+        "pacman" => &["pacman", "-U", "--noconfirm"],
+        _ => return Err("Unknown package manager".to_string()),
+    };
+
+    // Run via pkexec for privilege elevation
+    let output = Command::new("pkexec")
+        .args(install_args)
+        .arg(&pkg_path)
+        .output()
+        .map_err(|e| format!("Failed to run pkexec: {}", e))?;
+
+    // Clean up temp file
+    let _ = fs::remove_file(&pkg_path);
+
+    if output.status.success() {
+        Ok("Update installed successfully. Please restart the application.".to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        log::error!("Package install failed: {}", stderr.trim());
+        Err(format!("Install failed:\n{}\n{}", stdout, stderr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flatpak_info() {
+        let info = "[Application]\nname=com.shiplens.app\nruntime=runtime/org.gnome.Platform/x86_64/47\n\n[Instance]\nname=other\n";
+        assert_eq!(parse_flatpak_info(info), Some("com.shiplens.app".to_string()));
+        assert_eq!(parse_flatpak_info("[Instance]\nname=other\n"), None);
+    }
+
+    #[test]
+    fn test_package_asset() {
+        assert_eq!(package_asset("dnf", "0.1.24"), Some(("rpm", "Ship.Lens-0.1.24-1.x86_64.rpm".to_string())));
+        assert_eq!(package_asset("pacman", "0.1.24").unwrap().1, "ship-lens-0.1.24-1-x86_64.pkg.tar.zst");
+        assert_eq!(package_asset("zypper", "0.1.24"), None);
+    }
+}
@@ -0,0 +1,115 @@
+//! Ship Lens What-If Deltas
+//!
+//! A small change to a ship TTK request (one weapon swapped, another shield,
+//! another zone or scenario) and how much it moves the result, for the
+//! "+/- seconds" badges next to loadout pickers. The base request (with any
+//! saved loadout resolved the same way as the TTK call that showed it) is
+//! usually already in the result cache, so only the changed one is computed.
+
+use crate::ttk::{ScenarioPreset, TTKResult, ZonePreset};
+use crate::ShipTtkRequest;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Change applied on top of a base request (unset fields keep the base's)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TtkChange {
+    /// Port -> weapon overrides, merged into `attacker_loadout`
+    pub weapons: HashMap<String, String>,
+    /// Target shield internal name
+    pub shield_name: Option<String>,
+    pub zone_preset: Option<ZonePreset>,
+    pub scenario_preset: Option<ScenarioPreset>,
+}
+
+impl TtkChange {
+    /// `base` with this change applied
+    pub fn apply(&self, base: &ShipTtkRequest) -> Result<ShipTtkRequest, String> {
+        if self.weapons.is_empty() && self.shield_name.is_none() && self.zone_preset.is_none() && self.scenario_preset.is_none() {
+            return Err("No change to compare".to_string());
+        }
+        let mut changed = base.clone();
+        if !self.weapons.is_empty() {
+            let mut loadout = base.attacker_loadout.clone().unwrap_or_default();
+            loadout.extend(self.weapons.clone());
+            changed.attacker_loadout = Some(loadout);
+        }
+        if let Some(shield) = &self.shield_name {
+            changed.shield_name = Some(shield.clone());
+        }
+        changed.zone_preset = self.zone_preset.or(base.zone_preset);
+        changed.scenario_preset = self.scenario_preset.or(base.scenario_preset);
+        Ok(changed)
+    }
+}
+
+/// Base and changed results with their difference (changed minus base)
+#[derive(Debug, Clone, Serialize)]
+pub struct TtkDelta {
+    pub base: TTKResult,
+    pub changed: TTKResult,
+    /// Seconds gained (negative = faster kill); None unless both kill
+    pub delta_ttk: Option<f64>,
+    /// `delta_ttk` as a percentage of the base TTK
+    pub delta_pct: Option<f64>,
+    pub delta_shield_time: Option<f64>,
+    pub delta_dps: f64,
+    /// The change turns a kill into none (true) or none into a kill (false)
+    pub kill_lost: Option<bool>,
+}
+
+/// Difference between a base and a changed result
+pub fn delta(base: TTKResult, changed: TTKResult) -> TtkDelta {
+    let diff = |a: f64, b: f64| (a.is_finite() && b.is_finite()).then(|| b - a);
+    let delta_ttk = diff(base.total_ttk, changed.total_ttk);
+    let kill_lost = match (base.total_ttk.is_finite(), changed.total_ttk.is_finite()) {
+        (true, false) => Some(true),
+        (false, true) => Some(false),
+        _ => None,
+    };
+    TtkDelta {
+        delta_ttk,
+        delta_pct: delta_ttk.filter(|_| base.total_ttk > 0.0).map(|d| d / base.total_ttk * 100.0),
+        delta_shield_time: diff(base.shield_time, changed.shield_time),
+        delta_dps: changed.effective_dps - base.effective_dps,
+        kill_lost,
+        base,
+        changed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ttk_delta() {
+        let base = ShipTtkRequest {
+            attacker: "Aegis Gladius".to_string(),
+            attacker_loadout: Some(HashMap::from([("hardpoint_nose".to_string(), "CF-337".to_string())])),
+            target: "Anvil Arrow".to_string(),
+            zone_preset: Some(ZonePreset::Engines),
+            ..Default::default()
+        };
+        let change = TtkChange {
+            weapons: HashMap::from([("hardpoint_wing".to_string(), "Attrition-3".to_string())]),
+            shield_name: Some("shld_fr66".to_string()),
+            ..Default::default()
+        };
+        let changed = change.apply(&base).unwrap();
+        let loadout = changed.attacker_loadout.unwrap();
+        assert_eq!((loadout["hardpoint_nose"].as_str(), loadout["hardpoint_wing"].as_str()), ("CF-337", "Attrition-3"));
+        assert_eq!(changed.shield_name.as_deref(), Some("shld_fr66"));
+        assert_eq!(changed.zone_preset, Some(ZonePreset::Engines));
+        assert!(TtkChange::default().apply(&base).is_err());
+
+        let result = |total_ttk: f64, effective_dps: f64| TTKResult { total_ttk, shield_time: 2.0, effective_dps, ..Default::default() };
+        let faster = delta(result(10.0, 500.0), result(8.0, 600.0));
+        assert_eq!((faster.delta_ttk, faster.delta_pct, faster.delta_dps), (Some(-2.0), Some(-20.0), 100.0));
+        assert_eq!(faster.kill_lost, None);
+
+        let lost = delta(result(10.0, 500.0), result(f64::INFINITY, 0.0));
+        assert_eq!((lost.delta_ttk, lost.kill_lost), (None, Some(true)));
+    }
+}
@@ -1,7 +1,7 @@
 import "./style.css";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { getVersion } from "@tauri-apps/api/app";
-import { check } from "@tauri-apps/plugin-updater";
 import { relaunch } from "@tauri-apps/plugin-process";
 
 // Type definitions matching Rust structs
@@ -57,6 +57,12 @@ interface Ship {
   max_shield_size: number;
   shield_count: number;
   default_shield_ref: string;
+  default_shield: string | null;
+  manufacturer: string;
+  manufacturer_code: string;
+  size_class: string;  // "small", "medium", "large", "capital" ("" = unknown)
+  role: string;
+  crew: number;
   weapon_hardpoints: WeaponHardpoint[];
 }
 
@@ -69,6 +75,8 @@ interface Weapon {
   power_consumption: number;
   weapon_type: string;  // "gun", "missile", "torpedo", "bomb"
   ship_exclusive?: boolean;  // True if weapon can only be used on specific ships (e.g., Vanduul weapons)
+  manufacturer: string;
+  manufacturer_code: string;
   // 4.5 damage breakdown
   damage_physical: number;
   damage_energy: number;
@@ -152,6 +160,20 @@ interface MissileEffectiveness {
   is_effective: boolean;
   ineffective_reason: string | null;
   time_saved: number;
+  hit_probability: number;
+  expected_hits: number;
+}
+
+interface LayerDamage {
+  shield: number;
+  armor: number;
+  hull: number;
+}
+
+interface DamageContribution {
+  physical: LayerDamage;
+  energy: LayerDamage;
+  distortion: LayerDamage;
 }
 
 interface TTKResult {
@@ -160,6 +182,7 @@ interface TTKResult {
   hull_time: number;
   total_ttk: number;
   damage_breakdown: DamageBreakdown;
+  damage_contribution?: DamageContribution;
   effective_dps: number;
   shield_dps: number;
   passthrough_dps: number;
@@ -258,17 +281,23 @@ const FIRE_MODE_DPS_MOD: Record<string, number> = {
   "staggered": 0.75,
 };
 
-const SCENARIO_MODIFIERS: Record<string, { accuracy: number; tot: number }> = {
-  "dogfight": { accuracy: 0.75, tot: 0.65 },
-  "synthetic": { accuracy: 0.95, tot: 0.95 },
-  "jousting": { accuracy: 0.85, tot: 0.35 },
-  "custom": { accuracy: 0.8, tot: 0.8 },
-};
+// Staggered fire: two weapon groups taking turns (same 75% uptime per gun,
+// but the other group keeps hitting while one recovers)
+const STAGGERED_SCHEDULE = { burst: 3.0, rest: 1.0, alternate: true };
+
+// Scenario preset catalog (get_scenario_presets), in dropdown order
+interface ScenarioPresetInfo {
+  id: string;
+  label: string;
+  scenario: { scenario_accuracy: number; time_on_target: number };
+}
+let scenarioPresets: ScenarioPresetInfo[] = [];
 
 const ZONE_MODIFIERS: Record<string, { hull: number; armor: number; thruster: number; component: number }> = {
   "center-mass": { hull: 0.6, armor: 0.3, thruster: 0.05, component: 0.05 },
   "engines": { hull: 0.2, armor: 0.1, thruster: 0.6, component: 0.1 },
   "cockpit": { hull: 0.5, armor: 0.2, thruster: 0.0, component: 0.3 },
+  "components": { hull: 0.3, armor: 0.2, thruster: 0.0, component: 0.5 },
   "wings": { hull: 0.3, armor: 0.4, thruster: 0.2, component: 0.1 },
   "turrets": { hull: 0.1, armor: 0.1, thruster: 0.0, component: 0.0 },
 };
@@ -1477,6 +1506,8 @@ let allShields: Shield[] = [];
 let allMissiles: Missile[] = [];
 let currentAttackerShip: Ship | null = null;
 let currentTargetShip: Ship | null = null;
+// Last calculate_ttk_v2 request (CalcRequest in lib.rs), pinned to the overlay on demand
+let lastCalcRequest: { kind: "loadout"; request: Record<string, unknown> } | null = null;
 
 const maxValues = { hull: 100000, armor: 50000, shield: 50000 };
 
@@ -1659,9 +1690,8 @@ function animateTextValue(element: HTMLElement, newText: string) {
 // Calculate effective accuracy based on scenario, mount type, and time on target
 // This represents the percentage of raw DPS that actually lands on target
 function getHitRate(): number {
-  const scenario = scenarioDropdown.getValue() || "dogfight";
   const mountType = mountTypeDropdown.getValue() || "Gimballed";
-  const scenarioMod = SCENARIO_MODIFIERS[scenario] || SCENARIO_MODIFIERS["dogfight"];
+  const scenarioMod = getScenarioModifiers();
   const mountAccuracy = MOUNT_TYPE_ACCURACY[mountType] || 0.75;
   // Include time on target in the displayed accuracy
   return Math.min(scenarioMod.accuracy * mountAccuracy * scenarioMod.tot, 1);
@@ -1669,9 +1699,7 @@ function getHitRate(): number {
 
 // Get time-on-target from scenario (separate from hit rate)
 function getTimeOnTarget(): number {
-  const scenario = scenarioDropdown.getValue() || "dogfight";
-  const scenarioMod = SCENARIO_MODIFIERS[scenario] || SCENARIO_MODIFIERS["dogfight"];
-  return scenarioMod.tot;
+  return getScenarioModifiers().tot;
 }
 
 // Get fire mode DPS modifier
@@ -1730,7 +1758,7 @@ function updateTimeline(shieldTime: number, armorTime: number, hullTime: number,
 
 async function loadShips() {
   try {
-    const shipNames: string[] = await invoke("get_ships");
+    const shipNames: string[] = await invoke("get_filtered_ships");
     const shipOptions = shipNames.map(name => ({ value: name, label: name }));
     attackerShipDropdown.setOptions(shipOptions);
     targetShipDropdown.setOptions(shipOptions);
@@ -1783,10 +1811,69 @@ function updateShieldOptions() {
   if (currentValue && !validShields.find(s => s.display_name === currentValue)) shieldDropdown.setValue("");
 }
 
+interface LoadProgress {
+  stage: string;
+  completed: number;
+  total: number;
+}
+
+interface DataStatus {
+  state: "loading" | "ready" | "failed";
+  error?: string;
+}
+
+// Game data loads in the background; resolve once the backend reports it ready
+async function waitForData(): Promise<boolean> {
+  return new Promise(async (resolve) => {
+    await listen<LoadProgress>("data-loading", (event) => {
+      const { completed, total } = event.payload;
+      statsInfoEl.textContent = total > 0 ? `Loading data... (${completed}/${total})` : "Loading data...";
+    });
+    await listen("data-ready", () => resolve(true));
+    await listen<string>("data-failed", (event) => {
+      console.error("Data load failed:", event.payload);
+      statsInfoEl.textContent = "Failed to load data";
+      resolve(false);
+    });
+
+    // The load may have finished before the listeners were registered
+    const status: DataStatus = await invoke("get_data_status");
+    if (status.state === "ready") {
+      resolve(true);
+    } else if (status.state === "failed") {
+      console.error("Data load failed:", status.error);
+      statsInfoEl.textContent = "Failed to load data";
+      resolve(false);
+    } else {
+      statsInfoEl.textContent = "Loading data...";
+    }
+  });
+}
+
+interface LoadIssue {
+  file: string;
+  message: string;
+}
+
+interface LoadReport {
+  from_cache: boolean;
+  errors: LoadIssue[];
+  warnings: LoadIssue[];
+}
+
 async function loadStats() {
   try {
     const stats: Stats = await invoke("get_stats");
     statsInfoEl.textContent = `${stats.ship_count} ships | ${stats.weapon_count} weapons | ${stats.shield_count} shields`;
+
+    // Surface data file problems so an incomplete data set isn't silent
+    const report: LoadReport = await invoke("get_load_report");
+    const issues = [...report.errors, ...report.warnings];
+    if (issues.length > 0) {
+      issues.forEach(i => console.warn(`Data load issue in ${i.file}: ${i.message}`));
+      statsInfoEl.textContent += ` | ${issues.length} data issue${issues.length === 1 ? "" : "s"}`;
+      statsInfoEl.title = issues.map(i => `${i.file}: ${i.message}`).join("\n");
+    }
   } catch (e) {
     console.error("Failed to load stats:", e);
     statsInfoEl.textContent = "Failed to load data";
@@ -1843,9 +1930,9 @@ async function updateTargetShip(name: string) {
       updateShieldOptions();
 
       // Auto-select default shield if ship has one
-      if (currentTargetShip.default_shield_ref) {
+      if (currentTargetShip.default_shield) {
         const defaultShield = allShields.find(s =>
-          s.internal_name.toLowerCase() === currentTargetShip!.default_shield_ref.toLowerCase()
+          s.internal_name === currentTargetShip!.default_shield
         );
         if (defaultShield) {
           shieldDropdown.setValue(defaultShield.display_name);
@@ -1870,6 +1957,36 @@ function updateLoadoutSummary() {
   animateTextValue(powerDrawEl, powerDraw > 0 ? `${formatNumber(Math.round(powerDraw))} pwr/s` : "--");
 }
 
+// Effective HP each damage type removed from shield, armor and hull
+function updateDamageContribution(contribution?: DamageContribution) {
+  const container = document.getElementById('damage-contribution');
+  if (!container) return;
+
+  const types: [string, LayerDamage | undefined][] = [
+    ['Physical', contribution?.physical],
+    ['Energy', contribution?.energy],
+    ['Distortion', contribution?.distortion],
+  ];
+  const used = types.filter(([, layers]) => layers && layers.shield + layers.armor + layers.hull > 0);
+  if (used.length === 0) {
+    container.innerHTML = '';
+    container.style.display = 'none';
+    return;
+  }
+
+  const cell = (hp: number) => `<td>${hp > 0 ? formatNumber(Math.round(hp)) : '—'}</td>`;
+  container.style.display = 'block';
+  container.innerHTML = `
+    <div class="section-title">DAMAGE BY TYPE</div>
+    <table class="damage-contribution-table">
+      <thead><tr><th></th><th>Shield</th><th>Armor</th><th>Hull</th></tr></thead>
+      <tbody>
+        ${used.map(([name, l]) => `<tr><th>${name}</th>${cell(l!.shield)}${cell(l!.armor)}${cell(l!.hull)}</tr>`).join('')}
+      </tbody>
+    </table>
+  `;
+}
+
 function updateWeaponBreakdown(
   weaponBreakdown: WeaponEffectiveness[],
   missileBreakdown: MissileEffectiveness[],
@@ -2105,7 +2222,7 @@ async function calculateTTK() {
 
   // Build weapon names and counts grouped by hardpoint
   const hardpointGroups = weaponSlotManager?.getEnabledWeaponsGroupedByHardpoint() || [];
-  const weaponEntries: { hardpointLabel: string; weaponName: string; count: number }[] = [];
+  const weaponEntries: { hardpointLabel: string; weaponName: string; count: number; category: string }[] = [];
   const missileEntries: { hardpointLabel: string; missileName: string; count: number }[] = [];
 
   for (const group of hardpointGroups) {
@@ -2117,6 +2234,7 @@ async function calculateTTK() {
         hardpointLabel: group.hardpointLabel,
         weaponName: group.weaponName,
         count: group.count,
+        category: group.category,
       });
     } else if (isMissile) {
       missileEntries.push({
@@ -2130,6 +2248,7 @@ async function calculateTTK() {
   // Prepare arrays for backend call - include hardpoint label in weapon name
   const weaponNames = weaponEntries.map(e => `${e.hardpointLabel}::${e.weaponName}`);
   const weaponCounts = weaponEntries.map(e => e.count);
+  const weaponCategories = weaponEntries.map(e => e.category);
   const missileNames = missileEntries.map(e => `${e.hardpointLabel}::${e.missileName}`);
   const missileCounts = missileEntries.map(e => e.count);
 
@@ -2147,10 +2266,13 @@ async function calculateTTK() {
   const shieldData = allShields.find(s => s.display_name === selectedShieldDisplayName);
   const selectedShieldName = shieldData?.internal_name || null;
 
-  // Get scenario modifiers
+  // Get scenario modifiers (accuracy and time on target come from the backend's scenario preset)
   const mountAccuracy = getMountAccuracy();
-  const scenarioMod = getScenarioModifiers();
+  const scenarioPreset = scenarioDropdown.getValue() || "dogfight";
   const fireModeMod = getFireModeMod();
+  const staggered = (fireModeDropdown?.getValue() || "sustained") === "staggered";
+  const fireSchedule = staggered ? STAGGERED_SCHEDULE : null;
+  const weaponGroups = staggered ? weaponNames.map((_, i) => i % 2) : null;
   const powerMult = getPowerMultiplier();
   const zoneMod = getZoneModifiers();
 
@@ -2164,16 +2286,45 @@ async function calculateTTK() {
       targetShip: currentTargetShip.display_name,
       shieldName: selectedShieldName || null,
       mountAccuracy,
-      scenarioAccuracy: scenarioMod.accuracy,
-      timeOnTarget: scenarioMod.tot,
+      scenarioPreset,
       fireMode: fireModeMod,
       powerMultiplier: powerMult,
       zoneHull: zoneMod.hull,
       zoneArmor: zoneMod.armor,
       zoneThruster: zoneMod.thruster,
       zoneComponent: zoneMod.component,
+      zonePreset: targetZoneDropdown.getValue() || "center-mass",
+      attackerShip: currentAttackerShip?.display_name ?? null,
+      weaponCategories,
+      weaponGroups,
+      fireSchedule,
     });
 
+    lastCalcRequest = {
+      kind: "loadout",
+      request: {
+        weapon_names: weaponNames,
+        weapon_counts: weaponCounts,
+        missile_names: missileNames,
+        missile_counts: missileCounts,
+        target_ship: currentTargetShip.display_name,
+        shield_name: selectedShieldName || null,
+        mount_accuracy: mountAccuracy,
+        scenario_preset: scenarioPreset,
+        fire_mode: fireModeMod,
+        power_multiplier: powerMult,
+        zone_hull: zoneMod.hull,
+        zone_armor: zoneMod.armor,
+        zone_thruster: zoneMod.thruster,
+        zone_component: zoneMod.component,
+        zone_preset: targetZoneDropdown.getValue() || "center-mass",
+        attacker_ship: currentAttackerShip?.display_name ?? null,
+        weapon_categories: weaponCategories,
+        weapon_groups: weaponGroups,
+        fire_schedule: fireSchedule,
+      },
+    };
+
     // Update shield HP display (uses Rule of Two now)
     const shieldCount = currentTargetShip.shield_count || 1;
     const activeShields = Math.min(shieldCount, 2);
@@ -2185,7 +2336,9 @@ async function calculateTTK() {
       const regenRate = shieldData.regen || 0;
       const regenDelay = shieldData.damaged_regen_delay || 0;
       const fireMode = fireModeDropdown?.getValue() || "sustained";
-      const regenSuppressed = fireMode === "sustained" && regenDelay > 0;
+      // Staggered: a lone group leaves its rest as a gap in fire
+      const fireGap = fireMode === "staggered" && weaponNames.length < 2 ? STAGGERED_SCHEDULE.rest : 0;
+      const regenSuppressed = (fireMode === "sustained" || fireMode === "staggered") && regenDelay > fireGap;
       const regenStatus = regenSuppressed ? "(suppressed)" : "";
       shieldDetailEl.textContent = `${activeShields}/${shieldCount} Shields • ${formatNumber(Math.round(regenRate))} HP/s ${regenStatus}`;
     } else {
@@ -2205,6 +2358,7 @@ async function calculateTTK() {
       result.missile_breakdown || [],
       result.shields_breakable
     );
+    updateDamageContribution(result.damage_contribution);
 
   } catch (e) {
     console.error("TTK calculation failed:", e);
@@ -2212,6 +2366,7 @@ async function calculateTTK() {
     calculateTTKLegacy();
     // Still update weapon breakdown to show disabled state correctly
     updateWeaponBreakdown([], [], true);
+    updateDamageContribution();
   }
 }
 
@@ -2228,10 +2383,11 @@ function getMountAccuracy(): number {
   return accuracyMap[mountType] || 0.75;
 }
 
-// Helper to get scenario modifiers
+// Helper to get scenario modifiers (from the selected preset, else the first)
 function getScenarioModifiers(): { accuracy: number; tot: number } {
-  const scenario = scenarioDropdown?.getValue() || "dogfight";
-  return SCENARIO_MODIFIERS[scenario] || SCENARIO_MODIFIERS["dogfight"];
+  const id = scenarioDropdown?.getValue() || "dogfight";
+  const scenario = (scenarioPresets.find(p => p.id === id) ?? scenarioPresets[0])?.scenario;
+  return { accuracy: scenario?.scenario_accuracy ?? 1, tot: scenario?.time_on_target ?? 1 };
 }
 
 // Legacy local calculation as fallback
@@ -2284,15 +2440,15 @@ function calculateTTKLegacy() {
   updateTimeline(shieldTime, armorTime, hullTime);
 }
 
-function initStaticDropdowns() {
-  scenarioDropdown.setOptions([
-    { value: "dogfight", label: "Dogfight" },
-    { value: "synthetic", label: "Synthetic Test" },
-    { value: "jousting", label: "Jousting" },
-    { value: "custom", label: "Custom" },
-  ]);
-  scenarioDropdown.setValue("dogfight");
+async function loadScenarioPresets() {
+  try {
+    scenarioPresets = await invoke("get_scenario_presets");
+    scenarioDropdown.setOptions(scenarioPresets.map(p => ({ value: p.id, label: p.label })));
+    scenarioDropdown.setValue(scenarioPresets[0]?.id ?? "dogfight");
+  } catch (e) { console.error("Failed to load scenario presets:", e); }
+}
 
+function initStaticDropdowns() {
   mountTypeDropdown.setOptions([
     { value: "Fixed", label: "Fixed" },
     { value: "Gimballed", label: "Gimballed" },
@@ -2312,6 +2468,7 @@ function initStaticDropdowns() {
     { value: "center-mass", label: "Center Mass" },
     { value: "engines", label: "Engines" },
     { value: "cockpit", label: "Cockpit" },
+    { value: "components", label: "Components" },
     { value: "wings", label: "Wings/Extremities" },
     { value: "turrets", label: "Turrets" },
   ]);
@@ -2431,30 +2588,65 @@ function initAboutModal() {
   });
 }
 
-// Check GitHub releases API for latest version (fallback for Linux)
-async function checkGitHubRelease(currentVersion: string): Promise<{ version: string } | null> {
-  try {
-    const response = await fetch("https://api.github.com/repos/CapCeph/ship-lens/releases/latest");
-    if (!response.ok) return null;
-
-    const release = await response.json();
-    const latestVersion = release.tag_name?.replace(/^v/, "") || "";
-
-    // Simple version comparison (works for semver x.y.z)
-    if (latestVersion && latestVersion !== currentVersion) {
-      // Compare versions
-      const current = currentVersion.split(".").map(Number);
-      const latest = latestVersion.split(".").map(Number);
-
-      for (let i = 0; i < 3; i++) {
-        if ((latest[i] || 0) > (current[i] || 0)) {
-          return { version: latestVersion };
-        } else if ((latest[i] || 0) < (current[i] || 0)) {
-          return null;
-        }
+// Overlay button: pin the current matchup and show/hide the always-on-top overlay
+function initOverlayButton() {
+  const overlayBtn = document.getElementById("overlay-btn");
+  overlayBtn?.addEventListener("click", async () => {
+    try {
+      if (lastCalcRequest) {
+        await invoke("pin_matchup", { request: lastCalcRequest });
       }
+      await invoke("toggle_overlay");
+    } catch (e) {
+      console.error("Overlay failed:", e);
     }
-    return null;
+  });
+}
+
+// Share button: copy the current result as a Discord-ready message
+function initShareButton() {
+  const shareBtn = document.getElementById("share-btn");
+  shareBtn?.addEventListener("click", async () => {
+    if (!lastCalcRequest) return;
+    try {
+      const message = await invoke<string>("format_for_discord", { request: lastCalcRequest });
+      await navigator.clipboard.writeText(message);
+      shareBtn.title = "Copied!";
+      setTimeout(() => { shareBtn.title = "Copy result for Discord"; }, 2000);
+    } catch (e) {
+      console.error("Copy for Discord failed:", e);
+    }
+  });
+}
+
+// Stream overlay setting: local endpoint OBS browser sources can point at
+async function initStreamToggle() {
+  const toggle = document.getElementById("stream-toggle") as HTMLInputElement | null;
+  const urlEl = document.getElementById("stream-url");
+  if (!toggle || !urlEl) return;
+
+  const show = (status: { running: boolean; url: string | null }) => {
+    toggle.checked = status.running;
+    urlEl.textContent = status.url ?? "";
+  };
+  show(await invoke("get_stream_status"));
+
+  toggle.addEventListener("change", async () => {
+    try {
+      show(await invoke("set_stream_enabled", { enabled: toggle.checked }));
+    } catch (e) {
+      toggle.checked = false;
+      urlEl.textContent = String(e);
+    }
+  });
+}
+
+// Check GitHub releases API for latest version (fallback for Linux)
+async function checkGitHubRelease(_currentVersion: string): Promise<{ version: string } | null> {
+  try {
+    // Backend compares against this build and honors the selected update channel
+    const version = await invoke<string | null>("check_linux_update");
+    return version ? { version } : null;
   } catch (e) {
     console.warn("GitHub release check failed:", e);
     return null;
@@ -2480,8 +2672,18 @@ async function checkForUpdates(manual: boolean = false) {
   }
 
   try {
-    // Try Tauri's built-in updater first (works for Windows)
-    const update = await check();
+    // Offline mode: make no network calls at all
+    const network = await invoke<{ offline: boolean }>("get_network_status");
+    if (network.offline) {
+      if (manual && statusEl) {
+        statusEl.textContent = "Offline mode is on";
+        statusEl.className = "update-status";
+      }
+      return;
+    }
+
+    // Try Tauri's built-in updater first (works for Windows and macOS), on the selected update channel
+    const update = await invoke<AppUpdate | null>("check_app_update");
     if (update) {
       console.log(`Update available: ${update.version}`);
       if (manual && statusEl) {
@@ -2606,7 +2808,23 @@ function showUpdateModalLinux(version: string) {
         updateProgress.textContent = "Downloading package... (this may take a moment)";
       }
 
-      const result = await invoke<string>("install_linux_update", { version });
+      const unlisten = await listen<{ downloaded: number; total: number | null; percent: number | null }>(
+        "update-download-progress",
+        (event) => {
+          if (!updateProgress) return;
+          const { downloaded, percent } = event.payload;
+          const mb = (downloaded / (1024 * 1024)).toFixed(1);
+          updateProgress.textContent = percent !== null
+            ? `Downloading package... ${percent.toFixed(0)}% (${mb} MB)`
+            : `Downloading package... ${mb} MB`;
+        },
+      );
+      let result: string;
+      try {
+        result = await invoke<string>("install_linux_update", { version });
+      } finally {
+        unlisten();
+      }
       console.log("Linux update result:", result);
 
       if (updateProgress) {
@@ -2640,7 +2858,14 @@ function initUpdateCheckButton() {
   checkBtn?.addEventListener("click", () => checkForUpdates(true));
 }
 
-function showUpdateModal(update: Awaited<ReturnType<typeof check>>) {
+interface AppUpdate {
+  version: string;
+  current_version: string;
+  notes: string | null;
+  channel: string;
+}
+
+function showUpdateModal(update: AppUpdate | null) {
   if (!update) return;
 
   const updateModal = document.getElementById("update-modal");
@@ -2734,19 +2959,22 @@ function showUpdateModal(update: Awaited<ReturnType<typeof check>>) {
         return;
       }
 
-      // Windows/Mac: Use Tauri's built-in updater
-      await update.downloadAndInstall((event) => {
-        if (event.event === "Started" && updateProgress) {
-          const contentLength = event.data.contentLength;
-          updateProgress.textContent = contentLength
-            ? `Downloading: 0 / ${Math.round(contentLength / 1024)} KB`
-            : "Downloading...";
-        } else if (event.event === "Progress" && updateProgress) {
-          updateProgress.textContent = `Downloading: ${Math.round(event.data.chunkLength / 1024)} KB received`;
-        } else if (event.event === "Finished" && updateProgress) {
-          updateProgress.textContent = "Download complete. Installing...";
-        }
-      });
+      // Windows/Mac: Use Tauri's built-in updater (on the selected update channel)
+      const unlisten = await listen<{ downloaded: number; total: number | null }>(
+        "update-download-progress",
+        (event) => {
+          if (!updateProgress) return;
+          const { downloaded, total } = event.payload;
+          updateProgress.textContent = total
+            ? `Downloading: ${Math.round(downloaded / 1024)} / ${Math.round(total / 1024)} KB`
+            : `Downloading: ${Math.round(downloaded / 1024)} KB received`;
+        },
+      );
+      try {
+        await invoke("install_app_update");
+      } finally {
+        unlisten();
+      }
 
       // Relaunch the app
       if (updateProgress) {
@@ -2998,8 +3226,12 @@ async function init() {
   targetShipDropdown.enableFleetPresets();
 
   initStaticDropdowns();
+  await loadScenarioPresets();
   initSettings();
   initAboutModal();
+  initOverlayButton();
+  initStreamToggle();
+  initShareButton();
   initUpdateCheckButton();
   initSavePresetModal();
   initFleetManagementModal();
@@ -3030,6 +3262,10 @@ async function init() {
   });
 
   // Load data
+  if (!(await waitForData())) {
+    checkForUpdates();
+    return;
+  }
   await loadWeapons();
   await loadShields();
   await loadMissiles();
@@ -3071,7 +3307,8 @@ async function restoreSavedSettings() {
   }
 
   // Restore combat settings first (these don't trigger async operations)
-  if (saved.scenario) scenarioDropdown.setValue(saved.scenario);
+  // Settings from older versions may name a scenario the backend no longer offers
+  if (scenarioPresets.some(p => p.id === saved.scenario)) scenarioDropdown.setValue(saved.scenario);
   if (saved.mountType) mountTypeDropdown.setValue(saved.mountType);
   if (saved.fireMode) fireModeDropdown.setValue(saved.fireMode);
   if (saved.targetZone) targetZoneDropdown.setValue(saved.targetZone);
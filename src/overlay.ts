@@ -0,0 +1,52 @@
+import "./style.css";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+// Matches OverlayReadout in overlay.rs
+interface OverlayReadout {
+  attacker: string | null;
+  target: string;
+  shield: string | null;
+  summary: {
+    total_ttk: number;
+    expected_ttk: number;
+    effective_dps: number;
+    shield_time: number;
+    armor_time: number;
+    hull_time: number;
+    shields_breakable: boolean;
+  };
+}
+
+const matchupEl = document.getElementById("overlay-matchup")!;
+const ttkEl = document.getElementById("overlay-ttk")!;
+const phasesEl = document.getElementById("overlay-phases")!;
+
+function formatSeconds(seconds: number | null): string {
+  if (seconds === null || !isFinite(seconds) || seconds < 0) return "∞";
+  return seconds.toFixed(1);
+}
+
+async function refresh() {
+  try {
+    const readout = await invoke<OverlayReadout | null>("get_overlay_readout");
+    if (!readout) {
+      matchupEl.textContent = "NO MATCHUP PINNED";
+      ttkEl.textContent = "--";
+      phasesEl.textContent = "";
+      return;
+    }
+    const { summary } = readout;
+    matchupEl.textContent = readout.attacker ? `${readout.attacker} → ${readout.target}` : readout.target;
+    ttkEl.textContent = formatSeconds(summary.total_ttk);
+    phasesEl.textContent = `SHD ${formatSeconds(summary.shield_time)}s · ARM ${formatSeconds(summary.armor_time)}s · HULL ${formatSeconds(summary.hull_time)}s`;
+  } catch (e) {
+    // Data may still be loading; the data-ready event triggers another refresh
+    console.error("Overlay refresh failed:", e);
+    ttkEl.textContent = "--";
+  }
+}
+
+listen("overlay-pin-changed", refresh);
+listen("data-ready", refresh);
+refresh();